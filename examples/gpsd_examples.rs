@@ -0,0 +1,65 @@
+//! Extracts the example sentences of the gpsd NMEA documentation into the
+//! table of `tests/data/gpsd_examples.txt`, used by the conformance test.
+//!
+//! Run it on a checkout of the gpsd documentation:
+//!
+//! ```text
+//! cargo run --example gpsd_examples -- gpsd/www/NMEA.adoc
+//! ```
+//!
+//! Only the sentences known to the crate are printed, with a fixed checksum
+//! since many documented checksums are made up.
+
+use std::{collections::BTreeSet, env, fs};
+
+use nmea::parse_nmea_sentence;
+
+fn main() {
+    let path = env::args()
+        .nth(1)
+        .expect("usage: gpsd_examples <path to NMEA.adoc>");
+    let doc = fs::read_to_string(path).unwrap();
+
+    let mut rows = BTreeSet::new();
+    for line in doc.lines() {
+        for (start, _) in line.match_indices('$') {
+            let Some(candidate) = sentence_at(&line[start..]) else {
+                continue;
+            };
+            let Ok(sentence) = parse_nmea_sentence(candidate) else {
+                continue;
+            };
+            let fixed = format!(
+                "${}{},{}*{:02X}",
+                sentence.talker_id,
+                sentence.message_id.as_str(),
+                sentence.data,
+                sentence.calc_checksum()
+            );
+            let row = if sentence.checksum == sentence.calc_checksum() {
+                format!("{} {}", sentence.message_id.as_str(), fixed)
+            } else {
+                format!(
+                    "{} {} # documented as *{:02X}",
+                    sentence.message_id.as_str(),
+                    fixed,
+                    sentence.checksum
+                )
+            };
+            rows.insert(row);
+        }
+    }
+
+    for row in rows {
+        println!("{}", row);
+    }
+}
+
+/// Returns the sentence at the start of `text`, up to its checksum.
+fn sentence_at(text: &str) -> Option<&str> {
+    let end = text.find('*')? + 3;
+    let sentence = text.get(..end)?;
+    let checksum = &sentence[end - 2..];
+    (checksum.chars().all(|c| c.is_ascii_hexdigit()) && !sentence.contains(char::is_whitespace))
+        .then_some(sentence)
+}
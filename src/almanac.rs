@@ -0,0 +1,322 @@
+//! Visibility of the GPS satellites predicted from the ALM sentences.
+//!
+//! The almanac of each satellite is kept by [`Nmea`] as it is received.
+//! With the position and time of the fix,
+//! [`Nmea::predicted_satellites()`] computes the azimuth and elevation of
+//! the satellites above a mask angle and tells whether the GSV sentences
+//! report them: healthy satellites high in the sky which are not tracked
+//! point to an obstructed or faulty antenna.
+//!
+//! The almanac positions are accurate to a few kilometers, a fraction of a
+//! degree seen from the ground, for weeks.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use heapless::Vec;
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use crate::{
+    sentences::{alm::AlmanacOrbit, AlmData, GnssType},
+    Float, Nmea,
+};
+
+/// Number of GPS satellites of the almanac.
+pub const ALMANAC_LEN: usize = 32;
+
+/// Earth gravitational constant of WGS 84, in m³/s².
+const MU: f64 = 3.986_005e14;
+
+/// Earth rotation rate of WGS 84, in radians per second.
+const EARTH_ROTATION_RATE: f64 = 7.292_115_146_7e-5;
+
+const SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+const ECCENTRICITY_SQUARED: f64 = 0.006_694_379_990_14;
+
+const SECONDS_PER_WEEK: f64 = 604_800.0;
+
+/// A satellite expected above the mask angle, see
+/// [`Nmea::predicted_satellites()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredictedSatellite {
+    pub prn: u8,
+    /// Azimuth in `[0, 360)` degrees True
+    pub azimuth: f32,
+    /// Elevation in degrees
+    pub elevation: f32,
+    /// The satellite is in the GSV sentences
+    pub in_view: bool,
+}
+
+/// The almanac of the GPS satellites, by PRN.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Almanac {
+    orbits: [Option<AlmanacOrbit>; ALMANAC_LEN],
+}
+
+impl Almanac {
+    /// Stores the almanac of a satellite, ignoring incomplete ones.
+    pub fn update(&mut self, alm: &AlmData) {
+        let Some(orbit) = alm.orbit() else {
+            return;
+        };
+        if let Some(slot) = self.orbits.get_mut(usize::from(orbit.prn).wrapping_sub(1)) {
+            *slot = Some(orbit);
+        }
+    }
+
+    /// Returns the almanac of a satellite.
+    pub fn get(&self, prn: u8) -> Option<&AlmanacOrbit> {
+        self.orbits.get(usize::from(prn).wrapping_sub(1))?.as_ref()
+    }
+
+    /// Returns the almanacs received.
+    pub fn iter(&self) -> impl Iterator<Item = &AlmanacOrbit> {
+        self.orbits.iter().flatten()
+    }
+
+    /// Returns the healthy satellites above `mask_angle` degrees of
+    /// elevation from a position at a GPS time, not marked
+    /// [`in_view`](PredictedSatellite::in_view).
+    pub fn predict(
+        &self,
+        latitude: Float,
+        longitude: Float,
+        altitude: f32,
+        gps_time: NaiveDateTime,
+        mask_angle: f32,
+    ) -> Vec<PredictedSatellite, ALMANAC_LEN> {
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let (latitude, longitude) = (
+            (latitude as f64).to_radians(),
+            (longitude as f64).to_radians(),
+        );
+        let receiver = ecef(latitude, longitude, f64::from(altitude));
+        let (sin_lat, cos_lat) = (latitude.sin(), latitude.cos());
+        let (sin_lon, cos_lon) = (longitude.sin(), longitude.cos());
+        let mut predicted = Vec::new();
+        for orbit in self.iter().filter(|orbit| orbit.healthy) {
+            let satellite = orbit.ecef_position(gps_time);
+            let [dx, dy, dz] = [0, 1, 2].map(|axis| satellite[axis] - receiver[axis]);
+            let east = -sin_lon * dx + cos_lon * dy;
+            let north = -sin_lat * cos_lon * dx - sin_lat * sin_lon * dy + cos_lat * dz;
+            let up = cos_lat * cos_lon * dx + cos_lat * sin_lon * dy + sin_lat * dz;
+            let elevation = up.atan2(east.hypot(north)).to_degrees() as f32;
+            if elevation < mask_angle {
+                continue;
+            }
+            let azimuth = east.atan2(north).to_degrees() as f32;
+            // at most one satellite per slot
+            let _ = predicted.push(PredictedSatellite {
+                prn: orbit.prn,
+                azimuth: if azimuth < 0. {
+                    azimuth + 360.
+                } else {
+                    azimuth
+                },
+                elevation,
+                in_view: false,
+            });
+        }
+        predicted
+    }
+}
+
+/// Returns the Earth-centered, Earth-fixed coordinates of a geodetic
+/// position.
+fn ecef(latitude: f64, longitude: f64, height: f64) -> [f64; 3] {
+    let sin_lat = latitude.sin();
+    let prime_vertical = SEMI_MAJOR_AXIS / (1.0 - ECCENTRICITY_SQUARED * sin_lat * sin_lat).sqrt();
+    [
+        (prime_vertical + height) * latitude.cos() * longitude.cos(),
+        (prime_vertical + height) * latitude.cos() * longitude.sin(),
+        (prime_vertical * (1.0 - ECCENTRICITY_SQUARED) + height) * sin_lat,
+    ]
+}
+
+/// Returns the GPS week and the seconds in the week of a GPS time.
+fn gps_week(gps_time: NaiveDateTime) -> (i64, f64) {
+    let epoch = NaiveDate::from_ymd_opt(1980, 1, 6)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .unwrap_or_default();
+    let milliseconds = gps_time.signed_duration_since(epoch).num_milliseconds();
+    let week_milliseconds = SECONDS_PER_WEEK as i64 * 1000;
+    (
+        milliseconds.div_euclid(week_milliseconds),
+        milliseconds.rem_euclid(week_milliseconds) as f64 / 1000.0,
+    )
+}
+
+impl AlmanacOrbit {
+    /// Returns the Earth-centered, Earth-fixed coordinates of the satellite
+    /// in meters at a GPS time, with the algorithm of IS-GPS-200.
+    ///
+    /// The almanac week is compared modulo 1024 weeks, so 10-bit weeks work
+    /// with almanacs less than ten years old.
+    pub fn ecef_position(&self, gps_time: NaiveDateTime) -> [f64; 3] {
+        let (week, seconds) = gps_week(gps_time);
+        let weeks = (week - i64::from(self.week) + 512).rem_euclid(1024) - 512;
+        let elapsed = weeks as f64 * SECONDS_PER_WEEK + seconds - self.reference_time;
+
+        let mean_motion = (MU / self.semi_major_axis.powi(3)).sqrt();
+        let mean_anomaly = self.mean_anomaly + mean_motion * elapsed;
+        let mut eccentric_anomaly = mean_anomaly;
+        for _ in 0..10 {
+            eccentric_anomaly = mean_anomaly + self.eccentricity * eccentric_anomaly.sin();
+        }
+        let true_anomaly = ((1.0 - self.eccentricity * self.eccentricity).sqrt()
+            * eccentric_anomaly.sin())
+        .atan2(eccentric_anomaly.cos() - self.eccentricity);
+        let latitude_argument = true_anomaly + self.argument_of_perigee;
+        let radius = self.semi_major_axis * (1.0 - self.eccentricity * eccentric_anomaly.cos());
+        let (x, y) = (
+            radius * latitude_argument.cos(),
+            radius * latitude_argument.sin(),
+        );
+        let node = self.longitude_of_ascension_node
+            + (self.rate_of_right_ascension - EARTH_ROTATION_RATE) * elapsed
+            - EARTH_ROTATION_RATE * self.reference_time;
+        let (sin_node, cos_node) = (node.sin(), node.cos());
+        let cos_inclination = self.inclination.cos();
+        [
+            x * cos_node - y * cos_inclination * sin_node,
+            x * sin_node + y * cos_inclination * cos_node,
+            y * self.inclination.sin(),
+        ]
+    }
+}
+
+impl Nmea {
+    /// Returns the almanac of the ALM sentences.
+    pub fn almanac(&self) -> &Almanac {
+        &self.almanac
+    }
+
+    /// Returns the healthy GPS satellites expected above `mask_angle`
+    /// degrees of elevation at the position and time of the fix, and
+    /// whether the GSV sentences report them, see the
+    /// [module documentation](crate::almanac).
+    ///
+    /// Empty without a fix with a position and a date, or without almanac.
+    pub fn predicted_satellites(&self, mask_angle: f32) -> Vec<PredictedSatellite, ALMANAC_LEN> {
+        let (Some(latitude), Some(longitude), Some(gps_time)) =
+            (self.latitude, self.longitude, self.fix_gps_time())
+        else {
+            return Vec::new();
+        };
+        let in_view = self.satellites();
+        let mut predicted = self.almanac.predict(
+            latitude,
+            longitude,
+            self.altitude.unwrap_or(0.),
+            gps_time,
+            mask_angle,
+        );
+        for satellite in predicted.iter_mut() {
+            satellite.in_view = in_view.iter().any(|sat| {
+                sat.gnss_type() == GnssType::Gps && sat.prn() == u32::from(satellite.prn)
+            });
+        }
+        predicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::{parse_str, ParseResult};
+
+    const ALM: &str =
+        "$GPALM,31,1,02,1617,00,50F6,0F,FD98,FD39,A10CF3,81389B,423632,BD913C,148,001*3C";
+
+    fn almanac() -> Almanac {
+        let Ok(ParseResult::ALM(alm)) = parse_str(ALM) else {
+            panic!("{}", ALM);
+        };
+        let mut almanac = Almanac::default();
+        almanac.update(&alm);
+        almanac
+    }
+
+    /// The GPS time at the reference time of the almanac
+    fn reference_time() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(1980, 1, 6)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            + Duration::weeks(1617)
+            + Duration::seconds(61_440)
+    }
+
+    #[test]
+    fn test_orbit_position() {
+        let almanac = almanac();
+        let orbit = almanac.get(2).unwrap();
+        assert!(almanac.get(3).is_none());
+        assert!(almanac.get(0).is_none());
+        let time = reference_time();
+        assert_eq!(gps_week(time), (1617, 61_440.));
+        for hours in [0, 3, 6, 12] {
+            let [x, y, z] = orbit.ecef_position(time + Duration::hours(hours));
+            let radius = (x * x + y * y + z * z).sqrt();
+            assert!((26_000e3..27_100e3).contains(&radius), "{}", radius);
+            // within the inclination
+            assert!((z / radius).asin().to_degrees().abs() < 54.);
+        }
+        // 10-bit weeks
+        let mut ten_bits = *orbit;
+        ten_bits.week %= 1024;
+        assert_eq!(ten_bits.ecef_position(time), orbit.ecef_position(time));
+    }
+
+    #[test]
+    fn test_predict() {
+        let almanac = almanac();
+        let time = reference_time() + Duration::hours(1);
+        let [x, y, z] = almanac.get(2).unwrap().ecef_position(time);
+        // under the satellite, and on the other side of the Earth
+        let latitude = z.atan2(x.hypot(y)).to_degrees();
+        let longitude = y.atan2(x).to_degrees();
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let (latitude, longitude) = (latitude as Float, longitude as Float);
+        let predicted = almanac.predict(latitude, longitude, 0., time, 10.);
+        assert_eq!(predicted.len(), 1);
+        assert_eq!(predicted[0].prn, 2);
+        assert!(predicted[0].elevation > 89., "{:?}", predicted);
+        let antipode = if longitude > 0. {
+            longitude - 180.
+        } else {
+            longitude + 180.
+        };
+        assert!(almanac
+            .predict(-latitude, antipode, 0., time, 0.)
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ALM", feature = "RMC", feature = "GSV"))]
+    fn test_predicted_satellites() {
+        let mut nmea = Nmea::default();
+        nmea.parse(ALM).unwrap();
+        assert_eq!(nmea.almanac().iter().count(), 1);
+        assert!(nmea.predicted_satellites(0.).is_empty());
+
+        // under the satellite, an hour after the reference time
+        nmea.parse("$GPRMC,180342.00,A,4546.2676,N,04724.7598,W,0.0,0.0,020111,,,A*43")
+            .unwrap();
+        nmea.parse("$GPGSV,1,1,01,05,40,000,40*4D").unwrap();
+        let predicted = nmea.predicted_satellites(10.);
+        assert_eq!(predicted.len(), 1);
+        assert!(predicted[0].elevation > 89.);
+        assert!(!predicted[0].in_view);
+
+        nmea.parse("$GPGSV,1,1,01,02,89,000,40*4F").unwrap();
+        assert!(nmea.predicted_satellites(10.)[0].in_view);
+    }
+}
@@ -0,0 +1,99 @@
+//! Helpers for angles in degrees, like courses, headings and bearings, that
+//! wrap around at 0/360.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::float::FloatCore;
+
+/// Wraps an angle to `[0, 360)` degrees.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::angle::normalize;
+///
+/// assert_eq!(normalize(360.), 0.);
+/// assert_eq!(normalize(-90.), 270.);
+/// assert_eq!(normalize(725.), 5.);
+/// ```
+pub fn normalize(degrees: f32) -> f32 {
+    let wrapped = degrees % 360.;
+    let wrapped = if wrapped < 0. {
+        wrapped + 360.
+    } else {
+        wrapped
+    };
+    // tiny negative angles round up to 360
+    if wrapped >= 360. {
+        0.
+    } else {
+        wrapped
+    }
+}
+
+/// Returns the signed angle from `from` to `to` in `(-180, 180]` degrees,
+/// positive clockwise.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::angle::difference;
+///
+/// assert_eq!(difference(350., 10.), 20.);
+/// assert_eq!(difference(10., 350.), -20.);
+/// assert_eq!(difference(0., 180.), 180.);
+/// ```
+pub fn difference(from: f32, to: f32) -> f32 {
+    let difference = normalize(to - from);
+    if difference > 180. {
+        difference - 360.
+    } else {
+        difference
+    }
+}
+
+/// Interpolates between two angles along the shorter arc, `t` is `0` for
+/// `from` and `1` for `to`.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::angle::interpolate;
+///
+/// assert_eq!(interpolate(350., 10., 0.5), 0.);
+/// assert_eq!(interpolate(10., 350., 0.25), 5.);
+/// ```
+pub fn interpolate(from: f32, to: f32, t: f32) -> f32 {
+    normalize(from + difference(from, to) * t)
+}
+
+/// Returns the smallest absolute angle between two angles in `[0, 180]`
+/// degrees.
+pub fn distance(a: f32, b: f32) -> f32 {
+    difference(a, b).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(0.), 0.);
+        assert_eq!(normalize(359.5), 359.5);
+        assert_eq!(normalize(-360.), 0.);
+        assert_eq!(normalize(-1e-9), 0.);
+        assert_relative_eq!(normalize(-0.5), 359.5);
+    }
+
+    #[test]
+    fn test_difference() {
+        assert_eq!(difference(90., 90.), 0.);
+        assert_eq!(difference(180., 0.), 180.);
+        assert_relative_eq!(difference(359.5, 0.3), 0.8, epsilon = 1e-4);
+        assert_relative_eq!(distance(0.3, 359.5), 0.8, epsilon = 1e-4);
+        assert_eq!(interpolate(90., 270., 0.5), 180.);
+    }
+}
@@ -0,0 +1,60 @@
+//! Time source for the time-based features of [`Nmea`](crate::Nmea).
+
+use core::time::Duration;
+
+/// A monotonic time source.
+///
+/// The returned time is measured from an arbitrary, fixed point, e.g. the
+/// boot of the device. On `no_std` targets implement it on top of a
+/// hardware timer; with `std` use [`StdClock`].
+///
+/// Any `fn() -> Duration` is a clock as well.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+/// use nmea::Nmea;
+///
+/// fn uptime() -> Duration {
+///     // read the hardware timer here
+///     Duration::from_millis(1234)
+/// }
+///
+/// let mut nmea = Nmea::default();
+/// nmea.set_clock(&(uptime as fn() -> Duration));
+/// ```
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Duration;
+}
+
+impl<F: Fn() -> Duration> Clock for F {
+    fn now(&self) -> Duration {
+        self()
+    }
+}
+
+/// [`Clock`] backed by [`std::time::Instant`], counting from its first use.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdClock;
+
+#[cfg(feature = "std")]
+impl Clock for StdClock {
+    fn now(&self) -> Duration {
+        static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        START.get_or_init(std::time::Instant::now).elapsed()
+    }
+}
+
+/// A `'static` clock reference stored in the parser state.
+#[derive(Clone, Copy)]
+pub(crate) struct ClockRef(pub(crate) &'static (dyn Clock + Sync));
+
+impl core::fmt::Debug for ClockRef {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Clock")
+    }
+}
@@ -0,0 +1,257 @@
+//! Consistency of the positions of the sentences of a fix.
+//!
+//! A receiver reports the same position in the GGA, RMC, GNS and GLL
+//! sentences of a fix. A buggy multiplexer interleaving the sentences of two
+//! receivers mixes their positions within the epoch, which the
+//! [`PositionCheck`] detects from the spread of the positions of the same
+//! fix time, see [`Nmea::set_position_check()`].
+
+use chrono::NaiveTime;
+use heapless::Vec;
+
+use crate::{geo, Float, Nmea, SentenceMask, SentenceType};
+
+/// Number of sentence types reporting the position of a fix.
+const POSITIONS_LEN: usize = 4;
+
+/// Maximum spread of the positions of a fix, see
+/// [`Nmea::set_position_check()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionCheck {
+    /// Maximum distance in meters between the positions of the same fix
+    /// time.
+    ///
+    /// The positions are compared as parsed, so it has to allow for the
+    /// rounding of the sentences with fewer decimals, about 1 m with 3
+    /// decimals of minutes.
+    pub max_spread: f32,
+}
+
+impl Default for PositionCheck {
+    fn default() -> Self {
+        Self { max_spread: 10. }
+    }
+}
+
+/// The positions of the sentences of a fix, see
+/// [`Nmea::position_spread()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionSpread {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub fix_time: NaiveTime,
+    /// Largest distance in meters between two positions of the fix
+    pub spread: f32,
+    /// The sentence types which reported a position
+    pub sentences: SentenceMask,
+    /// The spread exceeds [`PositionCheck::max_spread`]
+    pub inconsistent: bool,
+}
+
+/// Counters of the [`PositionCheck`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PositionCheckStats {
+    /// Fixes with the positions of at least two sentences
+    pub checked: u32,
+    /// Fixes whose positions spread more than the maximum
+    pub inconsistent: u32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PositionConsistency {
+    check: Option<PositionCheck>,
+    /// Fix time of the positions
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    fix_time: Option<NaiveTime>,
+    positions: Vec<(SentenceType, (Float, Float)), POSITIONS_LEN>,
+    spread: Option<PositionSpread>,
+    stats: PositionCheckStats,
+}
+
+impl PositionConsistency {
+    /// The state after a reset, keeping the check.
+    pub(crate) fn cleared(&self) -> Self {
+        Self {
+            check: self.check,
+            ..Self::default()
+        }
+    }
+}
+
+impl Nmea {
+    /// Sets the maximum spread of the positions of a fix, `None` to disable
+    /// the check, see [`PositionCheck`].
+    ///
+    /// The valid positions of GGA, RMC, GNS and GLL with the same fix time
+    /// are compared, before the [jump filter](Self::set_jump_filter). A
+    /// second sentence of the same type in a fix, as sent by a second
+    /// receiver, is compared with the first one as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{Nmea, PositionCheck};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_position_check(Some(PositionCheck::default()));
+    /// # #[cfg(all(feature = "GGA", feature = "GLL"))]
+    /// # {
+    /// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///     .unwrap();
+    /// // the GLL of another receiver, 1 km to the North
+    /// nmea.parse("$GPGLL,5322.2198,N,00630.3372,W,092750.000,A,A*46").unwrap();
+    /// let spread = nmea.position_spread().unwrap();
+    /// assert!(spread.inconsistent);
+    /// assert_eq!(nmea.fix().position_consistent, Some(false));
+    /// assert_eq!(nmea.position_check_stats().inconsistent, 1);
+    /// # }
+    /// ```
+    pub fn set_position_check(&mut self, check: Option<PositionCheck>) {
+        self.consistency = PositionConsistency {
+            check,
+            stats: self.consistency.stats,
+            ..PositionConsistency::default()
+        };
+    }
+
+    /// Returns the spread of the positions of the current fix, `None`
+    /// without check or before the second position of the fix.
+    pub fn position_spread(&self) -> Option<PositionSpread> {
+        self.consistency
+            .spread
+            .filter(|spread| Some(spread.fix_time) == self.fix_time)
+    }
+
+    /// Returns the counters of the position check.
+    pub fn position_check_stats(&self) -> PositionCheckStats {
+        self.consistency.stats
+    }
+
+    /// Compares the position of a sentence which was just stored with the
+    /// other positions of the fix.
+    pub(crate) fn check_consistency(&mut self, sentence_type: SentenceType) {
+        let state = &mut self.consistency;
+        let Some(check) = state.check else {
+            return;
+        };
+        if !self.fix_type.is_some_and(|fix_type| fix_type.is_valid()) {
+            return;
+        }
+        let (Some(fix_time), Some(latitude), Some(longitude)) =
+            (self.fix_time, self.latitude, self.longitude)
+        else {
+            return;
+        };
+        if state.fix_time != Some(fix_time) {
+            state.fix_time = Some(fix_time);
+            state.positions.clear();
+            state.spread = None;
+        }
+        let position = (latitude, longitude);
+        let mut spread = state.spread.map_or(0., |spread| spread.spread);
+        let mut sentences = SentenceMask::from_types(&[sentence_type]);
+        let compared = !state.positions.is_empty();
+        for (other_type, other) in state.positions.iter() {
+            #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+            let distance = geo::distance(*other, position) as f32;
+            spread = spread.max(distance);
+            sentences.insert(*other_type);
+        }
+        match state
+            .positions
+            .iter_mut()
+            .find(|(other_type, _)| *other_type == sentence_type)
+        {
+            Some(entry) => entry.1 = position,
+            // at most one position per sentence type
+            None => {
+                let _ = state.positions.push((sentence_type, position));
+            }
+        }
+        if !compared {
+            return;
+        }
+        let was_inconsistent = state.spread.is_some_and(|spread| spread.inconsistent);
+        if state.spread.is_none() {
+            state.stats.checked = state.stats.checked.saturating_add(1);
+        }
+        let inconsistent = spread > check.max_spread;
+        if inconsistent && !was_inconsistent {
+            state.stats.inconsistent = state.stats.inconsistent.saturating_add(1);
+        }
+        state.spread = Some(PositionSpread {
+            fix_time,
+            spread,
+            sentences,
+            inconsistent,
+        });
+    }
+}
+
+#[cfg(all(test, feature = "GGA", feature = "RMC", feature = "GLL"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_check() {
+        let mut nmea = Nmea::default();
+        nmea.set_position_check(Some(PositionCheck::default()));
+        let parse = |nmea: &mut Nmea, sentence: &str| {
+            let checksum = sentence.bytes().fold(0, |checksum, byte| checksum ^ byte);
+            nmea.parse(&format!("${}*{:02X}", sentence, checksum))
+                .unwrap();
+        };
+        parse(
+            &mut nmea,
+            "GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,",
+        );
+        assert_eq!(nmea.position_spread(), None);
+        assert_eq!(nmea.fix().position_consistent, None);
+        // the RMC rounds to fewer decimals
+        parse(
+            &mut nmea,
+            "GPRMC,092750.000,A,5321.680,N,00630.337,W,0.02,31.66,280511,,,A",
+        );
+        parse(&mut nmea, "GPGLL,5321.6802,N,00630.3372,W,092750.000,A,A");
+        let spread = nmea.position_spread().unwrap();
+        assert!(spread.spread < 1.);
+        assert!(!spread.inconsistent);
+        assert!(spread.sentences.contains(&SentenceType::RMC));
+        assert!(spread.sentences.contains(&SentenceType::GLL));
+        assert_eq!(nmea.fix().position_consistent, Some(true));
+
+        // a second receiver 100 m to the East sends its GGA in the same fix
+        parse(
+            &mut nmea,
+            "GPGGA,092750.000,5321.6802,N,00630.2467,W,1,8,1.03,61.7,M,55.2,M,,",
+        );
+        let spread = nmea.position_spread().unwrap();
+        assert!((spread.spread - 100.).abs() < 1.);
+        assert!(spread.inconsistent);
+        parse(&mut nmea, "GPGLL,5321.6802,N,00630.2467,W,092750.000,A,A");
+        assert_eq!(nmea.fix().position_consistent, Some(false));
+        assert_eq!(
+            nmea.position_check_stats(),
+            PositionCheckStats {
+                checked: 1,
+                inconsistent: 1,
+            }
+        );
+
+        // an invalid fix is not compared
+        parse(&mut nmea, "GPGLL,5322.6802,N,00630.3372,W,092751.000,V,N");
+        parse(&mut nmea, "GPGLL,5321.6802,N,00630.3372,W,092752.000,A,A");
+        assert_eq!(nmea.position_spread(), None);
+
+        nmea.reset();
+        assert_eq!(nmea.position_check_stats().checked, 0);
+        assert!(nmea.consistency.check.is_some());
+    }
+}
@@ -0,0 +1,223 @@
+//! Course over ground filtering and RMC/VTG consistency checks.
+//!
+//! The course reported by a receiver is derived from its velocity and
+//! becomes noise when the receiver is slow or stationary. A [`CourseFilter`]
+//! installed with [`Nmea::set_course_filter()`](crate::Nmea::set_course_filter)
+//! is applied to every course from RMC and VTG before
+//! [`Nmea::true_course`](crate::Nmea::true_course) is updated.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::float::FloatCore;
+
+use crate::angle;
+
+/// Filter applied to the course over ground before it is stored.
+///
+/// Any `fn(Option<f32>, Option<f32>, Option<f32>) -> Option<f32>` taking
+/// the same arguments as [`filter()`](Self::filter) is a filter as well.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{LowSpeedCourseFreeze, Nmea};
+///
+/// static FREEZE: LowSpeedCourseFreeze = LowSpeedCourseFreeze::new(0.5);
+///
+/// let mut nmea = Nmea::default();
+/// nmea.set_course_filter(&FREEZE);
+/// # #[cfg(feature = "VTG")]
+/// # {
+/// nmea.parse("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48").unwrap();
+/// nmea.parse("$GPVTG,270.0,T,,M,000.1,N,000.2,K*66").unwrap();
+/// assert_eq!(nmea.true_course, Some(54.7));
+/// # }
+/// ```
+pub trait CourseFilter {
+    /// Returns the course to store, in degrees True.
+    ///
+    /// `previous` is the last course returned by the filter, `course` and
+    /// `speed_over_ground` (knots) are the values from the sentence.
+    fn filter(
+        &self,
+        previous: Option<f32>,
+        course: Option<f32>,
+        speed_over_ground: Option<f32>,
+    ) -> Option<f32>;
+}
+
+impl<F: Fn(Option<f32>, Option<f32>, Option<f32>) -> Option<f32>> CourseFilter for F {
+    fn filter(
+        &self,
+        previous: Option<f32>,
+        course: Option<f32>,
+        speed_over_ground: Option<f32>,
+    ) -> Option<f32> {
+        self(previous, course, speed_over_ground)
+    }
+}
+
+/// Keeps the previous course, or clears it, while the speed over ground is
+/// below a threshold.
+///
+/// It is a [`CourseFilter`], and a plain value which can be set without a
+/// `'static` reference with
+/// [`Nmea::set_course_speed_gate()`](crate::Nmea::set_course_speed_gate).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LowSpeedCourseFreeze {
+    /// Minimum speed over ground in knots for a course to be accepted
+    pub min_speed: f32,
+    /// The course below the minimum speed, or without speed
+    pub below: LowSpeedCourse,
+}
+
+impl LowSpeedCourseFreeze {
+    /// Holds the last valid course below `min_speed`.
+    pub const fn new(min_speed: f32) -> Self {
+        Self {
+            min_speed,
+            below: LowSpeedCourse::Hold,
+        }
+    }
+
+    /// Clears the course below `min_speed`.
+    pub const fn clearing(min_speed: f32) -> Self {
+        Self {
+            min_speed,
+            below: LowSpeedCourse::Clear,
+        }
+    }
+}
+
+impl CourseFilter for LowSpeedCourseFreeze {
+    fn filter(
+        &self,
+        previous: Option<f32>,
+        course: Option<f32>,
+        speed_over_ground: Option<f32>,
+    ) -> Option<f32> {
+        match (speed_over_ground, self.below) {
+            (Some(speed), _) if speed >= self.min_speed => course,
+            (_, LowSpeedCourse::Hold) => previous,
+            (_, LowSpeedCourse::Clear) => None,
+        }
+    }
+}
+
+/// What happens to the course over ground below the speed of a
+/// [`LowSpeedCourseFreeze`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LowSpeedCourse {
+    /// The last valid course is kept
+    #[default]
+    Hold,
+    /// The course is unknown
+    Clear,
+}
+
+/// A `'static` course filter reference stored in the parser state.
+#[derive(Clone, Copy)]
+pub(crate) struct CourseFilterRef(pub(crate) &'static (dyn CourseFilter + Sync));
+
+impl core::fmt::Debug for CourseFilterRef {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("CourseFilter")
+    }
+}
+
+/// Tolerances for the comparison of course and speed reported by RMC and
+/// VTG in the same epoch, see
+/// [`Nmea::set_course_check()`](crate::Nmea::set_course_check).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CourseCheck {
+    /// Maximum course difference in degrees
+    pub max_course_difference: f32,
+    /// Maximum speed difference in knots
+    pub max_speed_difference: f32,
+}
+
+impl Default for CourseCheck {
+    fn default() -> Self {
+        Self {
+            max_course_difference: 1.0,
+            max_speed_difference: 0.1,
+        }
+    }
+}
+
+impl CourseCheck {
+    pub(crate) fn compare(
+        &self,
+        rmc: (Option<f32>, Option<f32>),
+        vtg: (Option<f32>, Option<f32>),
+    ) -> Option<CourseDiscrepancy> {
+        let difference = CourseDiscrepancy {
+            course: rmc.0.zip(vtg.0).map(|(rmc, vtg)| angle::distance(rmc, vtg)),
+            speed: rmc.1.zip(vtg.1).map(|(rmc, vtg)| (rmc - vtg).abs()),
+        };
+        let course_exceeded = difference
+            .course
+            .is_some_and(|course| course > self.max_course_difference);
+        let speed_exceeded = difference
+            .speed
+            .is_some_and(|speed| speed > self.max_speed_difference);
+        (course_exceeded || speed_exceeded).then_some(difference)
+    }
+}
+
+/// Difference between the RMC and VTG values of an epoch.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CourseDiscrepancy {
+    /// Absolute course difference in degrees, `None` if either course is
+    /// missing
+    pub course: Option<f32>,
+    /// Absolute speed difference in knots, `None` if either speed is
+    /// missing
+    pub speed: Option<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_course_check() {
+        let check = CourseCheck::default();
+        assert_eq!(
+            check.compare((Some(359.5), Some(5.0)), (Some(0.3), Some(5.05))),
+            None
+        );
+        let discrepancy = check
+            .compare((Some(350.0), Some(5.0)), (Some(10.0), None))
+            .unwrap();
+        assert_relative_eq!(discrepancy.course.unwrap(), 20.0);
+        assert_eq!(discrepancy.speed, None);
+        assert_eq!(check.compare((None, None), (Some(10.0), Some(1.0))), None);
+    }
+
+    #[test]
+    fn test_low_speed_freeze() {
+        let freeze = LowSpeedCourseFreeze::new(0.5);
+        assert_eq!(freeze.filter(None, Some(10.0), Some(1.0)), Some(10.0));
+        assert_eq!(freeze.filter(Some(10.0), Some(90.0), Some(0.1)), Some(10.0));
+        assert_eq!(freeze.filter(Some(10.0), Some(90.0), None), Some(10.0));
+    }
+
+    #[test]
+    fn test_low_speed_clear() {
+        let clear = LowSpeedCourseFreeze::clearing(0.5);
+        assert_eq!(clear.filter(Some(10.0), Some(90.0), Some(1.0)), Some(90.0));
+        assert_eq!(clear.filter(Some(10.0), Some(90.0), Some(0.1)), None);
+        assert_eq!(clear.filter(Some(10.0), Some(90.0), None), None);
+    }
+}
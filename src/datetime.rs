@@ -0,0 +1,125 @@
+//! Dates of the fix times.
+//!
+//! Most sentences only carry the time of day of the fix. The date of RMC and
+//! ZDA sentences is carried over to the following fix times, and advanced
+//! when the time of day wraps around midnight, see
+//! [`Nmea::fix_datetime()`].
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::Nmea;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct DateTracker {
+    /// The latest date and time
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    last: Option<NaiveDateTime>,
+}
+
+impl DateTracker {
+    /// Returns the date and time of a time of day, with the date of the
+    /// sentence if it has one, or else the date of the latest time.
+    ///
+    /// Times of day more than 12 hours before the latest one are on the
+    /// next day, and more than 12 hours after it on the previous day.
+    pub(crate) fn resolve(
+        &self,
+        time: NaiveTime,
+        date: Option<NaiveDate>,
+    ) -> Option<NaiveDateTime> {
+        if let Some(date) = date {
+            return Some(date.and_time(time));
+        }
+        let last = self.last?;
+        let half_day = Duration::hours(12);
+        let date = if last.time() - time > half_day {
+            last.date().succ_opt()?
+        } else if time - last.time() > half_day {
+            last.date().pred_opt()?
+        } else {
+            last.date()
+        };
+        Some(date.and_time(time))
+    }
+
+    /// Resolves the date and time of a time of day and keeps the latest.
+    pub(crate) fn update(
+        &mut self,
+        time: NaiveTime,
+        date: Option<NaiveDate>,
+    ) -> Option<NaiveDateTime> {
+        let datetime = self.resolve(time, date)?;
+        if self.last.map_or(true, |last| datetime > last) || date.is_some() {
+            self.last = Some(datetime);
+        }
+        Some(datetime)
+    }
+}
+
+impl Nmea {
+    /// Returns the date and time of the fix.
+    ///
+    /// The date is the one of the RMC sentence of the fix, or else the date
+    /// of the last RMC or ZDA sentence, advanced at midnight. Unlike
+    /// [`fix_timestamp()`](Self::fix_timestamp), it orders the fixes of
+    /// logs spanning midnight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(all(feature = "RMC", feature = "GGA"))]
+    /// # {
+    /// nmea.parse("$GPRMC,235959.00,A,5321.6802,N,00630.3372,W,0.02,31.66,280511,,,A*7B")
+    ///     .unwrap();
+    /// nmea.parse("$GPGGA,000001.00,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*4E")
+    ///     .unwrap();
+    /// let date = NaiveDate::from_ymd_opt(2011, 5, 29).unwrap();
+    /// assert_eq!(nmea.fix_datetime(), date.and_hms_opt(0, 0, 1));
+    /// # }
+    /// ```
+    pub fn fix_datetime(&self) -> Option<NaiveDateTime> {
+        self.fix_datetime
+    }
+
+    /// Updates the date and time of the fix after its time was set.
+    pub(crate) fn update_fix_datetime(&mut self, date: Option<NaiveDate>) {
+        self.fix_datetime = self
+            .fix_time
+            .and_then(|time| self.date_tracker.update(time, date));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_date_tracker() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        let mut tracker = DateTracker::default();
+        assert_eq!(tracker.update(time(23, 58), None), None);
+        assert_eq!(
+            tracker.update(time(23, 59), Some(date)),
+            Some(date.and_time(time(23, 59)))
+        );
+
+        let next_day = date.succ_opt().unwrap();
+        assert_eq!(
+            tracker.update(time(0, 1), None),
+            Some(next_day.and_time(time(0, 1)))
+        );
+        // a late sentence of the previous day
+        assert_eq!(
+            tracker.update(time(23, 59), None),
+            Some(date.and_time(time(23, 59)))
+        );
+        assert_eq!(tracker.last, Some(next_day.and_time(time(0, 1))));
+    }
+}
@@ -0,0 +1,379 @@
+//! Datum of the positions stored by [`Nmea`], and their transformation to
+//! another datum.
+//!
+//! Receivers send their positions in WGS 84 unless a DTM sentence names
+//! another local datum. A [`DatumTransform`] set with
+//! [`Nmea::set_datum_transform()`] converts the positions of GGA, RMC, GNS
+//! and GLL before they are stored, when they are in its source datum. The
+//! positions in another datum are stored as received, and
+//! [`Fix::datum`](crate::Fix::datum) tells which datum the stored position
+//! is in, so positions of different datums are not mixed unknowingly.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use crate::{
+    sentences::{Datum, DtmData},
+    Float, Nmea,
+};
+
+/// Semi-major axis of the GRS 80 ellipsoid, in meters, which only differs
+/// from WGS 84 by 0.1 mm.
+const SEMI_MAJOR_AXIS: f64 = 6_378_137.0;
+
+/// First eccentricity squared of the GRS 80 ellipsoid.
+const ECCENTRICITY_SQUARED: f64 = 0.006_694_380_022_90;
+
+/// Radians per milliarcsecond.
+const MAS: f64 = core::f64::consts::PI / (180.0 * 3_600_000.0);
+
+/// A position converted by a [`DatumTransform`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub latitude: Float,
+    pub longitude: Float,
+    /// Altitude in meters, `None` for the sentences without one
+    pub altitude: Option<f32>,
+}
+
+/// A conversion of the positions from one datum to another.
+///
+/// The built-in transformations are approximations at the 2025.0 epoch,
+/// accurate to a few decimeters, for navigation and mapping. Survey work
+/// needs a [`DatumTransform::new()`] with a complete transformation, e.g.
+/// with the velocity model of the area.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{datum::DatumTransform, sentences::Datum, Nmea};
+///
+/// let mut nmea = Nmea::default();
+/// nmea.set_datum_transform(Some(DatumTransform::wgs84_to_etrs89()));
+/// # #[cfg(feature = "GGA")]
+/// # {
+/// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+///     .unwrap();
+/// let fix = nmea.fix();
+/// assert_eq!(fix.datum, Some(Datum::Etrs89));
+/// // moved by less than a meter
+/// assert!((fix.latitude.unwrap() - (53. + 21.6802 / 60.)).abs() < 1e-5);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DatumTransform {
+    source: Datum,
+    target: Datum,
+    transform: fn(Position) -> Position,
+}
+
+impl DatumTransform {
+    /// A transformation of the positions in `source` to `target`.
+    pub const fn new(source: Datum, target: Datum, transform: fn(Position) -> Position) -> Self {
+        Self {
+            source,
+            target,
+            transform,
+        }
+    }
+
+    /// WGS 84 to ETRS 89, for Europe.
+    pub const fn wgs84_to_etrs89() -> Self {
+        Self::new(Datum::Wgs84, Datum::Etrs89, |position| {
+            ETRS89.shift(position, 1.0)
+        })
+    }
+
+    /// ETRS 89 to WGS 84, for Europe.
+    pub const fn etrs89_to_wgs84() -> Self {
+        Self::new(Datum::Etrs89, Datum::Wgs84, |position| {
+            ETRS89.shift(position, -1.0)
+        })
+    }
+
+    /// WGS 84 to NAD 83 (2011), for North America.
+    pub const fn wgs84_to_nad83() -> Self {
+        Self::new(Datum::Wgs84, Datum::Nad83, |position| {
+            NAD83.shift(position, 1.0)
+        })
+    }
+
+    /// NAD 83 (2011) to WGS 84, for North America.
+    pub const fn nad83_to_wgs84() -> Self {
+        Self::new(Datum::Nad83, Datum::Wgs84, |position| {
+            NAD83.shift(position, -1.0)
+        })
+    }
+
+    /// Returns the datum of the positions which are converted.
+    pub fn source(&self) -> Datum {
+        self.source
+    }
+
+    /// Returns the datum of the converted positions.
+    pub fn target(&self) -> Datum {
+        self.target
+    }
+
+    /// Converts a position.
+    pub fn apply(&self, position: Position) -> Position {
+        (self.transform)(position)
+    }
+}
+
+/// A seven-parameter Helmert transformation, from the ITRF, within a few
+/// centimeters of WGS 84, to a plate-fixed datum. The rotations follow the
+/// convention of the EUREF technical note 1.
+struct Helmert {
+    /// Translation in meters
+    translation: [f64; 3],
+    /// Rotation around the X, Y and Z axes in milliarcseconds
+    rotation: [f64; 3],
+    /// Scale difference in parts per billion
+    scale: f64,
+}
+
+/// ITRF2014 to ETRF2014, the rotation rates of the EUREF technical note 1
+/// over 1989.0 to 2025.0.
+const ETRS89: Helmert = Helmert {
+    translation: [0.0, 0.0, 0.0],
+    rotation: [3.06, 19.116, -27.72],
+    scale: 0.0,
+};
+
+/// ITRF2008 to NAD 83 (2011), the NGS parameters of 1997.0 and their rates
+/// carried to 2025.0.
+const NAD83: Helmert = Helmert {
+    translation: [1.01555, -1.92011, -0.56407],
+    rotation: [-27.78143, 11.78187, -10.16211],
+    scale: -1.14124,
+};
+
+impl Helmert {
+    /// Applies the transformation, or its inverse with a `sign` of `-1`.
+    ///
+    /// The shift of a few meters at most is computed in Earth-centered
+    /// coordinates and applied to the geodetic coordinates through the
+    /// local north, east and up directions. The altitude is used as the
+    /// ellipsoidal height, which changes the shift by less than a
+    /// micrometer.
+    fn shift(&self, position: Position, sign: f64) -> Position {
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let (latitude, longitude) = (
+            (position.latitude as f64).to_radians(),
+            (position.longitude as f64).to_radians(),
+        );
+        let height = f64::from(position.altitude.unwrap_or(0.0));
+        let (sin_lat, cos_lat) = (latitude.sin(), latitude.cos());
+        let (sin_lon, cos_lon) = (longitude.sin(), longitude.cos());
+        let w = (1.0 - ECCENTRICITY_SQUARED * sin_lat * sin_lat).sqrt();
+        let prime_vertical = SEMI_MAJOR_AXIS / w;
+        let meridian = SEMI_MAJOR_AXIS * (1.0 - ECCENTRICITY_SQUARED) / (w * w * w);
+
+        let x = (prime_vertical + height) * cos_lat * cos_lon;
+        let y = (prime_vertical + height) * cos_lat * sin_lon;
+        let z = (prime_vertical * (1.0 - ECCENTRICITY_SQUARED) + height) * sin_lat;
+        let [tx, ty, tz] = self.translation;
+        let [rx, ry, rz] = self.rotation.map(|rotation| rotation * MAS);
+        let scale = self.scale * 1e-9;
+        let dx = sign * (tx + scale * x - rz * y + ry * z);
+        let dy = sign * (ty + rz * x + scale * y - rx * z);
+        let dz = sign * (tz - ry * x + rx * y + scale * z);
+
+        let north = -sin_lat * cos_lon * dx - sin_lat * sin_lon * dy + cos_lat * dz;
+        let east = -sin_lon * dx + cos_lon * dy;
+        let up = cos_lat * cos_lon * dx + cos_lat * sin_lon * dy + sin_lat * dz;
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let (latitude, longitude) = (
+            (north / (meridian + height)).to_degrees() as Float,
+            (east / ((prime_vertical + height) * cos_lat)).to_degrees() as Float,
+        );
+        Position {
+            latitude: position.latitude + latitude,
+            longitude: position.longitude + longitude,
+            altitude: position.altitude.map(|altitude| altitude + up as f32),
+        }
+    }
+}
+
+/// The datum of the DTM sentences and of the stored position.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct DatumState {
+    /// The local datum of the last DTM sentence
+    local: Option<Datum>,
+    /// The datum of the stored position
+    position: Option<Datum>,
+    transform: Option<DatumTransform>,
+}
+
+impl DatumState {
+    /// The state of a new epoch, without position.
+    pub(crate) fn restarted(&self) -> Self {
+        Self {
+            position: None,
+            ..*self
+        }
+    }
+
+    /// The state after a reset, keeping the transform.
+    pub(crate) fn cleared(&self) -> Self {
+        Self {
+            transform: self.transform,
+            ..Self::default()
+        }
+    }
+}
+
+impl Nmea {
+    /// Sets the transformation of the positions of GGA, RMC, GNS and GLL
+    /// before they are stored, see the [module documentation](crate::datum).
+    ///
+    /// The positions are converted when their datum, the local datum of the
+    /// last DTM sentence or WGS 84 without one, is the source datum of the
+    /// transformation. The others are stored unchanged.
+    pub fn set_datum_transform(&mut self, transform: Option<DatumTransform>) {
+        self.datum.transform = transform;
+    }
+
+    /// Returns the transformation of the positions.
+    pub fn datum_transform(&self) -> Option<&DatumTransform> {
+        self.datum.transform.as_ref()
+    }
+
+    /// Returns the local datum of the last DTM sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{sentences::Datum, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "DTM")]
+    /// # {
+    /// nmea.parse("$GPDTM,W72,,0.00,S,0.01,W,-2.8,W84*4F").unwrap();
+    /// assert_eq!(nmea.local_datum(), Some(Datum::Wgs72));
+    /// # }
+    /// ```
+    pub fn local_datum(&self) -> Option<Datum> {
+        self.datum.local
+    }
+
+    /// Returns the datum of the stored position: the target datum of the
+    /// [transformation](Self::set_datum_transform) if it was applied, else
+    /// the local datum of the DTM sentences, `None` without any.
+    pub fn position_datum(&self) -> Option<Datum> {
+        self.datum.position
+    }
+
+    pub(crate) fn merge_dtm_data(&mut self, dtm: &DtmData) {
+        if dtm.datum.is_some() {
+            self.datum.local = dtm.datum;
+        }
+    }
+
+    /// Converts the position which was just stored, and its altitude if the
+    /// sentence has one.
+    pub(crate) fn transform_position(&mut self, with_altitude: bool) {
+        self.datum.position = self.datum.local;
+        let Some(transform) = self.datum.transform else {
+            return;
+        };
+        if self.datum.local.unwrap_or(Datum::Wgs84) != transform.source {
+            return;
+        }
+        let (Some(latitude), Some(longitude)) = (self.latitude, self.longitude) else {
+            return;
+        };
+        let position = transform.apply(Position {
+            latitude,
+            longitude,
+            altitude: self.altitude.filter(|_| with_altitude),
+        });
+        self.latitude = Some(position.latitude);
+        self.longitude = Some(position.longitude);
+        if with_altitude {
+            self.altitude = position.altitude;
+        }
+        self.datum.position = Some(transform.target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(feature = "f32-only"))]
+    fn test_builtin_transforms() {
+        /// Meters per degree of latitude.
+        const METERS_PER_DEGREE: f64 = 111_200.0;
+
+        fn distance(a: Position, b: Position) -> f64 {
+            let north = (a.latitude - b.latitude) * METERS_PER_DEGREE;
+            let east =
+                (a.longitude - b.longitude) * METERS_PER_DEGREE * a.latitude.to_radians().cos();
+            (north * north + east * east).sqrt()
+        }
+
+        let paris = Position {
+            latitude: 48.85,
+            longitude: 2.35,
+            altitude: Some(35.0),
+        };
+        let etrs89 = DatumTransform::wgs84_to_etrs89().apply(paris);
+        // about 0.9 m in 2025, to the south west
+        let shift = distance(paris, etrs89);
+        assert!((0.6..1.2).contains(&shift), "{}", shift);
+        assert!(etrs89.latitude < paris.latitude);
+        let back = DatumTransform::etrs89_to_wgs84().apply(etrs89);
+        assert!(distance(paris, back) < 1e-3);
+
+        let denver = Position {
+            latitude: 39.74,
+            longitude: -104.99,
+            altitude: None,
+        };
+        let nad83 = DatumTransform::wgs84_to_nad83().apply(denver);
+        let shift = distance(denver, nad83);
+        assert!((0.5..2.5).contains(&shift), "{}", shift);
+        assert_eq!(nad83.altitude, None);
+        let back = DatumTransform::nad83_to_wgs84().apply(nad83);
+        assert!(distance(denver, back) < 1e-3);
+    }
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "DTM"))]
+    fn test_nmea_datum() {
+        let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+        let mut nmea = Nmea::default();
+        nmea.parse(gga).unwrap();
+        assert_eq!(nmea.position_datum(), None);
+        let latitude = nmea.latitude().unwrap();
+
+        // a custom transformation
+        nmea.set_datum_transform(Some(DatumTransform::new(
+            Datum::Wgs84,
+            Datum::Other(arrayvec::ArrayString::from("TST").unwrap()),
+            |position| Position {
+                latitude: position.latitude + 1.0,
+                ..position
+            },
+        )));
+        nmea.parse(gga).unwrap();
+        assert_eq!(nmea.latitude(), Some(latitude + 1.0));
+        assert_eq!(nmea.fix().datum.unwrap().code(), "TST");
+
+        // positions of another datum are not converted
+        nmea.parse("$GPDTM,W72,,0.00,S,0.01,W,-2.8,W84*4F").unwrap();
+        nmea.parse(gga).unwrap();
+        assert_eq!(nmea.latitude(), Some(latitude));
+        assert_eq!(nmea.position_datum(), Some(Datum::Wgs72));
+
+        nmea.reset();
+        assert_eq!(nmea.local_datum(), None);
+        assert!(nmea.datum_transform().is_some());
+    }
+}
@@ -0,0 +1,201 @@
+//! Decimation of high-rate sentences.
+//!
+//! Receivers configured for 10 or 25 Hz send GSV and GST at the full rate,
+//! which can take most of the time of a small MCU that only needs the
+//! satellites once per second. With [`Nmea::set_decimation()`] only every
+//! n-th sentence of a type is parsed and merged, the others are just framed
+//! to read their type and skipped.
+
+use heapless::Vec;
+
+use crate::{parse::NmeaSentence, sentence_table::SentenceTable, Nmea, SentenceType, TalkerId};
+
+/// Number of sentence types with a decimation.
+pub const DECIMATION_LEN: usize = 8;
+
+/// Number of talkers counted separately per sentence type.
+const DECIMATION_TALKERS: usize = 8;
+
+/// The count of the sentences of a talker.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TalkerCount {
+    talker: Option<TalkerId>,
+    /// Sentences (or GSV groups) seen since the last parsed one
+    count: u16,
+    /// Whether the current GSV group is skipped
+    skipping: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Decimator {
+    n: u16,
+    /// The counts per talker, e.g. the GSV of GPS and GLONASS are both kept
+    /// with the same decimation
+    talkers: Vec<TalkerCount, DECIMATION_TALKERS>,
+}
+
+impl Decimator {
+    fn new(n: u16) -> Self {
+        Self {
+            n,
+            talkers: Vec::new(),
+        }
+    }
+
+    fn skip(&mut self, sentence: &NmeaSentence) -> bool {
+        let talker = sentence.talker();
+        let index = match self.talkers.iter().position(|count| count.talker == talker) {
+            Some(index) => index,
+            None => {
+                let count = TalkerCount {
+                    talker,
+                    count: 0,
+                    skipping: false,
+                };
+                if self.talkers.push(count).is_err() {
+                    // too many talkers, the others are not decimated
+                    return false;
+                }
+                self.talkers.len() - 1
+            }
+        };
+        let state = &mut self.talkers[index];
+        // decimate GSV by groups, a group is only useful as a whole
+        if sentence.message_id == SentenceType::GSV && !is_group_start(sentence.data) {
+            return state.skipping;
+        }
+        state.skipping = state.count != 0;
+        state.count = (state.count + 1) % self.n;
+        state.skipping
+    }
+}
+
+/// Returns `true` for the first sentence of a GSV group, whose sentence
+/// number is the second field.
+fn is_group_start(data: &str) -> bool {
+    data.split(',').nth(1) == Some("1")
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Decimation {
+    decimators: SentenceTable<Decimator, DECIMATION_LEN>,
+}
+
+impl Decimation {
+    /// Returns `true` if the sentence should be skipped.
+    pub(crate) fn skip(&mut self, sentence: &NmeaSentence) -> bool {
+        self.decimators
+            .get_mut(sentence.message_id)
+            .is_some_and(|decimator| decimator.skip(sentence))
+    }
+}
+
+impl Nmea {
+    /// Parses only every `n`-th sentence of a type, starting with the next
+    /// one, e.g. `n = 25` for 1 Hz satellite info from a 25 Hz GSV stream.
+    ///
+    /// The sentences are counted per talker, so with e.g. `GPGSV` and
+    /// `GLGSV` both constellations are kept. GSV is decimated by groups of
+    /// sentences, so the satellites in view stay complete. Skipped sentences are only framed, without checksum
+    /// check, and return their type from [`parse()`](Self::parse).
+    ///
+    /// An `n` of 0 or 1 parses all sentences of the type again. Returns the
+    /// sentence type back if [`DECIMATION_LEN`] types are already decimated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{Nmea, SentenceType};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_decimation(SentenceType::GGA, 2).unwrap();
+    /// # #[cfg(feature = "GGA")]
+    /// # {
+    /// let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+    /// nmea.parse(gga).unwrap();
+    /// nmea.clear_fix();
+    /// // the second GGA is skipped
+    /// assert_eq!(nmea.parse(gga), Ok(SentenceType::GGA));
+    /// assert_eq!(nmea.fix_satellites(), None);
+    /// # }
+    /// ```
+    pub fn set_decimation(
+        &mut self,
+        sentence_type: SentenceType,
+        n: u16,
+    ) -> Result<(), SentenceType> {
+        let decimators = &mut self.decimation.decimators;
+        if n <= 1 {
+            decimators.remove(sentence_type);
+            return Ok(());
+        }
+        decimators
+            .insert(sentence_type, Decimator::new(n))
+            .map_err(|_| sentence_type)
+    }
+
+    /// Returns the decimation of a sentence type, 1 if all its sentences
+    /// are parsed.
+    pub fn decimation(&self, sentence_type: SentenceType) -> u16 {
+        self.decimation
+            .decimators
+            .get(sentence_type)
+            .map_or(1, |decimator| decimator.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::parse_nmea_sentence;
+
+    use super::*;
+
+    #[test]
+    fn test_decimation() {
+        let mut decimation = Decimation::default();
+        for (sentence_type, n) in [(SentenceType::GST, 3), (SentenceType::GSV, 2)] {
+            decimation
+                .decimators
+                .insert(sentence_type, Decimator::new(n))
+                .unwrap();
+        }
+        let mut skip = |sentence| decimation.skip(&parse_nmea_sentence(sentence).unwrap());
+
+        let gst = "$GPGST,182141.000,15.5,15.3,7.2,21.8,0.9,0.5,0.8*54";
+        let skipped: [bool; 6] = core::array::from_fn(|_| skip(gst));
+        assert_eq!(skipped, [false, true, true, false, true, true]);
+
+        let gsv = [
+            "$GPGSV,2,1,08,01,,,42,02,,,37,03,,,33,04,,,42*70",
+            "$GPGSV,2,2,08,05,,,42,06,,,37,07,,,33,08,,,42*7C",
+        ];
+        let skipped: [bool; 6] = core::array::from_fn(|i| skip(gsv[i % 2]));
+        assert_eq!(skipped, [false, false, true, true, false, false]);
+
+        assert!(!skip("$GPHDT,274.07,T*03"));
+    }
+
+    #[test]
+    fn test_decimation_per_talker() {
+        let mut decimation = Decimation::default();
+        decimation
+            .decimators
+            .insert(SentenceType::GSV, Decimator::new(2))
+            .unwrap();
+        let mut skip = |sentence| decimation.skip(&parse_nmea_sentence(sentence).unwrap());
+
+        // the GPS and GLONASS groups of the same epochs
+        let gsv = [
+            "$GPGSV,1,1,04,01,,,42,02,,,37,03,,,33,04,,,42*7D",
+            "$GLGSV,1,1,04,65,,,42,66,,,37,67,,,33,68,,,42*69",
+        ];
+        let skipped: [bool; 6] = core::array::from_fn(|i| skip(gsv[i % 2]));
+        assert_eq!(skipped, [false, false, true, true, false, false]);
+    }
+}
@@ -0,0 +1,255 @@
+//! Receiver detection from TXT boot banners and the parsing quirks of
+//! known receivers.
+
+use crate::{Error, Nmea, NmeaSentence, SatIdPolicy};
+
+/// Receiver families with known quirks.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Device {
+    UBlox,
+    SiRF,
+    JacksonLabs,
+    Garmin,
+    Quectel,
+    Telit,
+}
+
+/// Lowercase banner fragments identifying the receivers.
+const BANNERS: [(&str, Device); 6] = [
+    ("u-blox", Device::UBlox),
+    ("sirf", Device::SiRF),
+    ("jackson labs technologies", Device::JacksonLabs),
+    ("garmin", Device::Garmin),
+    ("quectel", Device::Quectel),
+    ("telit", Device::Telit),
+];
+
+impl Device {
+    /// Identifies the receiver from the text of a TXT sentence, e.g. the
+    /// `u-blox AG - www.u-blox.com` boot banner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Device;
+    ///
+    /// assert_eq!(Device::from_banner("u-blox AG - www.u-blox.com"), Some(Device::UBlox));
+    /// assert_eq!(Device::from_banner("ANTSTATUS=OK"), None);
+    /// ```
+    pub fn from_banner(text: &str) -> Option<Self> {
+        BANNERS
+            .iter()
+            .find(|(banner, _)| {
+                text.as_bytes()
+                    .windows(banner.len())
+                    .any(|window| window.eq_ignore_ascii_case(banner.as_bytes()))
+            })
+            .map(|(_, device)| *device)
+    }
+
+    /// Returns the parsing options working around the receiver's quirks.
+    pub fn quirks(&self) -> Quirks {
+        match self {
+            // sends GSA sentences with the wrong number of fields
+            Device::SiRF => Quirks {
+                lenient_gsa: true,
+                ..Quirks::default()
+            },
+            // send NMEA PRNs and satellite IDs in conflicting ways
+            Device::JacksonLabs | Device::Quectel | Device::Telit => Quirks {
+                sat_id_policy: SatIdPolicy::MIXED,
+                ..Quirks::default()
+            },
+            // sometimes merges two sentences into an over-long one with a
+            // valid checksum
+            Device::Garmin => Quirks {
+                strict_length: true,
+                ..Quirks::default()
+            },
+            Device::UBlox => Quirks::default(),
+        }
+    }
+}
+
+/// Parsing options for receivers that deviate from the standard.
+///
+/// The default is strictly standard parsing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Quirks {
+    /// Interpretation of the satellite numbers of GSV sentences
+    pub sat_id_policy: SatIdPolicy,
+    /// Ignore GSA sentences, which are unreliable, and their errors
+    pub ignore_gsa: bool,
+    /// Accept GSA sentences with missing fields, see
+    /// [`parse_gsa_lenient()`](crate::sentences::gsa::parse_gsa_lenient)
+    pub lenient_gsa: bool,
+    /// Accept MTW sentences without a unit letter, as Celsius, see
+    /// [`parse_mtw_lenient()`](crate::sentences::mtw::parse_mtw_lenient)
+    pub lenient_mtw: bool,
+    /// Handling of satellites reported by both `GN` and constellation
+    /// specific GSV sentences
+    pub gsv_talkers: GsvTalkerPolicy,
+    /// Reject sentences longer than the 82 characters of NMEA 0183, line
+    /// ending included, with [`Error::SentenceLength`] instead of only
+    /// those longer than [`SENTENCE_MAX_LEN`](crate::SENTENCE_MAX_LEN)
+    pub strict_length: bool,
+}
+
+/// Maximum length of a sentence of NMEA 0183 without its line ending.
+const STANDARD_SENTENCE_MAX_LEN: usize = 80;
+
+impl Quirks {
+    /// Checks the length of a sentence with [`strict_length`](Self::strict_length).
+    pub(crate) fn check_length<'a>(&self, sentence: &NmeaSentence) -> Result<(), Error<'a>> {
+        // `$`, the address, `,`, the fields and the checksum
+        let len = 1 + sentence.talker_id.len() + 3 + 1 + sentence.data.len() + 3;
+        if self.strict_length && len > STANDARD_SENTENCE_MAX_LEN {
+            return Err(Error::SentenceLength(len));
+        }
+        Ok(())
+    }
+}
+
+/// Handling of receivers which send the same satellites in `GNGSV` and in
+/// constellation specific GSV sentences like `GPGSV`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GsvTalkerPolicy {
+    /// Keep the satellites of all GSV sentences.
+    #[default]
+    KeepAll,
+    /// Drop the satellites of `GN` sentences for constellations with GSV
+    /// sentences of their own talker in this or the last fix cycle, see
+    /// [`Nmea::gsv_talker_duplicates()`].
+    PreferSpecific,
+}
+
+impl Nmea {
+    /// Returns the receiver identified by a TXT boot banner, or set with
+    /// [`set_device()`](Self::set_device).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{Device, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "TXT")]
+    /// # {
+    /// nmea.parse("$GNTXT,01,01,02,u-blox AG - www.u-blox.com*4E").unwrap();
+    /// assert_eq!(nmea.detected_device(), Some(Device::UBlox));
+    /// # }
+    /// ```
+    pub fn detected_device(&self) -> Option<Device> {
+        self.device
+    }
+
+    /// Sets the receiver and its [`Quirks`], disabling the detection from
+    /// TXT sentences. `None` enables the detection again, keeping the
+    /// current quirks until a receiver is detected.
+    pub fn set_device(&mut self, device: Option<Device>) {
+        self.device_override = device.is_some();
+        if let Some(device) = device {
+            self.device = Some(device);
+            self.quirks = device.quirks();
+        }
+    }
+
+    /// Returns the active parsing options.
+    pub fn quirks(&self) -> Quirks {
+        self.quirks
+    }
+
+    /// Sets the parsing options, until a receiver is detected.
+    pub fn set_quirks(&mut self, quirks: Quirks) {
+        self.quirks = quirks;
+    }
+
+    pub(crate) fn detect_device(&mut self, text: &str) {
+        if self.device_override {
+            return;
+        }
+        if let Some(device) = Device::from_banner(text) {
+            if self.device != Some(device) {
+                self.device = Some(device);
+                self.quirks = device.quirks();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_banner() {
+        assert_eq!(
+            Device::from_banner("SiRF Binary Protocol"),
+            Some(Device::SiRF)
+        );
+        assert_eq!(
+            Device::from_banner("Jackson Labs Technologies, Inc."),
+            Some(Device::JacksonLabs)
+        );
+        assert_eq!(Device::from_banner("JLT"), None);
+        assert_eq!(Device::from_banner(""), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "TXT", feature = "GSA"))]
+    fn test_quirks() {
+        let mut nmea = Nmea::default();
+        nmea.set_device(Some(Device::Quectel));
+        nmea.parse("$GNTXT,01,01,02,u-blox AG - www.u-blox.com*4E")
+            .unwrap();
+        assert_eq!(nmea.detected_device(), Some(Device::Quectel));
+        assert_eq!(nmea.quirks().sat_id_policy, SatIdPolicy::Auto);
+
+        nmea.set_device(None);
+        nmea.parse("$GPTXT,01,01,02,SiRF*63").unwrap();
+        assert_eq!(nmea.detected_device(), Some(Device::SiRF));
+        assert!(nmea.quirks().lenient_gsa);
+        // wrong number of fields
+        nmea.parse("$GPGSA,A,3,1.8,1.1,1.4*3E").unwrap();
+        assert_eq!(nmea.pdop, Some(1.8));
+
+        nmea.set_quirks(Quirks {
+            ignore_gsa: true,
+            ..Quirks::default()
+        });
+        nmea.parse("$GPGSA,A,3,,,,,,,,*30").unwrap();
+        assert_eq!(nmea.pdop, Some(1.8));
+    }
+
+    #[test]
+    #[cfg(feature = "MTW")]
+    fn test_lenient_mtw() {
+        let mut nmea = Nmea::default();
+        assert!(nmea.parse("$INMTW,17.9,*58").is_err());
+        nmea.set_quirks(Quirks {
+            lenient_mtw: true,
+            ..Quirks::default()
+        });
+        assert_eq!(nmea.parse("$INMTW,17.9,*58"), Ok(crate::SentenceType::MTW));
+        assert_eq!(nmea.water_temperature().unwrap().value, 17.9);
+    }
+
+    #[test]
+    #[cfg(feature = "GGA")]
+    fn test_strict_length() {
+        let long = "$GPGGA,224749.00,3333.4268304,N,11153.3538273,W,2,19,0.6,406.110,M,-26.294,M,1.2,0138*7D";
+        let mut nmea = Nmea::default();
+        assert!(nmea.parse(long).is_ok());
+        nmea.set_device(Some(Device::Garmin));
+        assert_eq!(nmea.parse(long), Err(Error::SentenceLength(88)));
+        assert!(nmea
+            .parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+            .is_ok());
+    }
+}
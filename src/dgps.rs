@@ -0,0 +1,203 @@
+//! Monitoring of differential (DGPS and RTK) corrections.
+
+use core::{mem, time::Duration};
+
+/// Reason for a [`DgpsWatchdog`] alarm.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DgpsAlarm {
+    /// The corrections are older than the configured maximum age, in seconds.
+    AgeExceeded { age: f32 },
+    /// The differential reference station has changed.
+    StationChanged { previous: u16, current: u16 },
+    /// A fix was received without corrections after corrections were used.
+    CorrectionsLost,
+}
+
+/// Watchdog for the age of differential corrections reported by GGA and
+/// GNS sentences.
+///
+/// Install it with [`Nmea::set_dgps_watchdog()`](crate::Nmea::set_dgps_watchdog).
+/// The reported age is checked on every sentence. With a
+/// [`Clock`](crate::Clock) set on the parser, the time since the last
+/// sentence is added as well, so [`Nmea::check_dgps_watchdog()`](crate::Nmea::check_dgps_watchdog)
+/// also raises an alarm when the sentences stop arriving.
+///
+/// The callback is only invoked when an alarm is raised, not on every check
+/// while it is active.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{DgpsAlarm, DgpsWatchdog, Nmea};
+///
+/// fn on_alarm(alarm: DgpsAlarm) {
+///     println!("DGPS: {:?}", alarm);
+/// }
+///
+/// let mut nmea = Nmea::default();
+/// nmea.set_dgps_watchdog(DgpsWatchdog::new(10.0).on_alarm(on_alarm));
+/// # #[cfg(feature = "GGA")]
+/// # {
+/// nmea.parse("$GPGGA,172814.0,3723.46587704,N,12202.26957864,W,2,6,1.2,18.893,M,-25.669,M,12.0,0031*7E")
+///     .unwrap();
+/// assert_eq!(nmea.dgps_alarm(), Some(DgpsAlarm::AgeExceeded { age: 12.0 }));
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone)]
+pub struct DgpsWatchdog {
+    max_age: f32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    callback: Option<fn(DgpsAlarm)>,
+    station_id: Option<u16>,
+    age: Option<f32>,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    received_at: Option<Duration>,
+    alarm: Option<DgpsAlarm>,
+}
+
+impl DgpsWatchdog {
+    /// Creates a watchdog for corrections older than `max_age` seconds.
+    pub fn new(max_age: f32) -> Self {
+        Self {
+            max_age,
+            callback: None,
+            station_id: None,
+            age: None,
+            received_at: None,
+            alarm: None,
+        }
+    }
+
+    /// Sets a function to call when an alarm is raised.
+    pub fn on_alarm(mut self, callback: fn(DgpsAlarm)) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    /// Returns the maximum age of corrections in seconds.
+    pub fn max_age(&self) -> f32 {
+        self.max_age
+    }
+
+    /// Returns the active alarm, if any.
+    pub fn alarm(&self) -> Option<DgpsAlarm> {
+        self.alarm
+    }
+
+    /// Returns the last reported differential reference station ID.
+    pub fn station_id(&self) -> Option<u16> {
+        self.station_id
+    }
+
+    /// Returns a watchdog with the same configuration and no history.
+    pub(crate) fn cleared(&self) -> Self {
+        Self {
+            callback: self.callback,
+            ..Self::new(self.max_age)
+        }
+    }
+
+    /// Updates the watchdog with the fields of a received sentence.
+    pub(crate) fn update(
+        &mut self,
+        age: Option<f32>,
+        station_id: Option<u16>,
+        now: Option<Duration>,
+    ) {
+        let Some(age) = age else {
+            if self.age.take().is_some() {
+                self.raise(Some(DgpsAlarm::CorrectionsLost));
+            }
+            return;
+        };
+        self.age = Some(age);
+        self.received_at = now;
+
+        let previous = self.station_id;
+        self.station_id = station_id.or(previous);
+        let alarm = match (previous, station_id) {
+            (Some(previous), Some(current)) if previous != current => {
+                Some(DgpsAlarm::StationChanged { previous, current })
+            }
+            _ if age > self.max_age => Some(DgpsAlarm::AgeExceeded { age }),
+            _ => None,
+        };
+        self.raise(alarm);
+    }
+
+    /// Checks the age of the last corrections at the time `now`.
+    pub(crate) fn check(&mut self, now: Duration) -> Option<DgpsAlarm> {
+        if let (Some(age), Some(received_at)) = (self.age, self.received_at) {
+            let age = age + now.saturating_sub(received_at).as_secs_f32();
+            if age > self.max_age {
+                self.raise(Some(DgpsAlarm::AgeExceeded { age }));
+            }
+        }
+        self.alarm
+    }
+
+    fn raise(&mut self, alarm: Option<DgpsAlarm>) {
+        let is_new = match (&self.alarm, &alarm) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(old), Some(new)) => mem::discriminant(old) != mem::discriminant(new),
+        };
+        self.alarm = alarm;
+        if let (true, Some(callback), Some(alarm)) = (is_new, self.callback, alarm) {
+            callback(alarm);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    static CALLS: AtomicU32 = AtomicU32::new(0);
+
+    fn count(_: DgpsAlarm) {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_dgps_watchdog() {
+        let mut watchdog = DgpsWatchdog::new(5.0).on_alarm(count);
+        let secs = Duration::from_secs;
+
+        watchdog.update(Some(1.0), Some(31), Some(secs(100)));
+        assert_eq!(watchdog.alarm(), None);
+        assert_eq!(watchdog.check(secs(103)), None);
+
+        assert_eq!(
+            watchdog.check(secs(106)),
+            Some(DgpsAlarm::AgeExceeded { age: 7.0 })
+        );
+        // still active, the callback is not invoked again
+        watchdog.check(secs(107));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        watchdog.update(Some(2.0), Some(31), Some(secs(108)));
+        assert_eq!(watchdog.alarm(), None);
+
+        watchdog.update(Some(2.0), Some(42), Some(secs(109)));
+        assert_eq!(
+            watchdog.alarm(),
+            Some(DgpsAlarm::StationChanged {
+                previous: 31,
+                current: 42
+            })
+        );
+        assert_eq!(watchdog.station_id(), Some(42));
+
+        watchdog.update(None, None, None);
+        assert_eq!(watchdog.alarm(), Some(DgpsAlarm::CorrectionsLost));
+        assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,202 @@
+//! Formatting of positions, courses and speeds the way navigation displays
+//! show them, without allocation.
+//!
+//! The values are rounded once, so that e.g. 59.9996 minutes are shown as
+//! the next degree instead of `60.000'`. The precision of the formatter sets
+//! the number of decimals, of the minutes for the positions, at most 6 for
+//! the positions and the courses.
+//!
+//! # Examples
+//!
+//! ```
+//! use nmea::display::{Cog, LatLon, Sog};
+//!
+//! let position = LatLon::new(48.1173, 11.522067);
+//! assert_eq!(format!("{}", position), "48°07.038'N 011°31.324'E");
+//! assert_eq!(format!("{:.1}", position), "48°07.0'N 011°31.3'E");
+//! assert_eq!(format!("{}", Cog(54.7)), "COG 054.7°T");
+//! assert_eq!(format!("{}", Sog(0.5)), "SOG 0.5 kn");
+//! ```
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::float::FloatCore;
+
+use core::fmt;
+
+use crate::{angle, Fix, Float};
+
+/// Default number of decimals of the minutes of a position.
+const MINUTES_PRECISION: usize = 3;
+/// Default number of decimals of a course or a speed.
+const PRECISION: usize = 1;
+/// Maximum number of decimals of the minutes of a position or of a course,
+/// beyond the precision of the values.
+const MAX_PRECISION: usize = 6;
+
+/// A latitude in degrees, formatted as `48°07.038'N`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Latitude(pub Float);
+
+/// A longitude in degrees, formatted as `011°31.324'E`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Longitude(pub Float);
+
+/// A position in degrees, formatted as `48°07.038'N 011°31.324'E`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatLon {
+    pub latitude: Float,
+    pub longitude: Float,
+}
+
+impl LatLon {
+    /// Returns a position of a latitude and a longitude in degrees.
+    pub fn new(latitude: Float, longitude: Float) -> Self {
+        Self {
+            latitude,
+            longitude,
+        }
+    }
+}
+
+/// A course over ground in degrees True, formatted as `COG 054.7°T`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cog(pub f32);
+
+/// A speed over ground in knots, formatted as `SOG 0.5 kn`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sog(pub f32);
+
+/// Writes degrees and minutes, with `degree_digits` digits for the degrees
+/// and the hemisphere letters for positive and negative angles.
+fn write_degrees_minutes(
+    f: &mut fmt::Formatter<'_>,
+    degrees: Float,
+    degree_digits: usize,
+    hemispheres: (char, char),
+) -> fmt::Result {
+    let precision = f
+        .precision()
+        .unwrap_or(MINUTES_PRECISION)
+        .min(MAX_PRECISION);
+    let scale = 10_u64.pow(precision as u32);
+    // in units of the last decimal of the minutes
+    let total = (degrees.abs() * 60. * scale as Float).round() as u64;
+    let (whole_degrees, minutes) = (total / (60 * scale), total % (60 * scale));
+    let hemisphere = if degrees < 0. && total > 0 {
+        hemispheres.1
+    } else {
+        hemispheres.0
+    };
+    write!(
+        f,
+        "{:0width$}°{:02}",
+        whole_degrees,
+        minutes / scale,
+        width = degree_digits
+    )?;
+    if precision > 0 {
+        write!(f, ".{:0precision$}", minutes % scale, precision = precision)?;
+    }
+    write!(f, "'{}", hemisphere)
+}
+
+impl fmt::Display for Latitude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_degrees_minutes(f, self.0, 2, ('N', 'S'))
+    }
+}
+
+impl fmt::Display for Longitude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_degrees_minutes(f, self.0, 3, ('E', 'W'))
+    }
+}
+
+impl fmt::Display for LatLon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&Latitude(self.latitude), f)?;
+        f.write_str(" ")?;
+        fmt::Display::fmt(&Longitude(self.longitude), f)
+    }
+}
+
+impl fmt::Display for Cog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(PRECISION).min(MAX_PRECISION);
+        let scale = 10_u32.pow(precision as u32);
+        // 359.96 is shown as 000.0
+        let total = (angle::normalize(self.0) * scale as f32).round() as u32 % (360 * scale);
+        write!(f, "COG {:03}", total / scale)?;
+        if precision > 0 {
+            write!(f, ".{:0precision$}", total % scale, precision = precision)?;
+        }
+        f.write_str("°T")
+    }
+}
+
+impl fmt::Display for Sog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(PRECISION);
+        write!(f, "SOG {:.precision$} kn", self.0, precision = precision)
+    }
+}
+
+impl Fix {
+    /// Returns the position for display, see [`LatLon`].
+    pub fn display_position(&self) -> Option<LatLon> {
+        Some(LatLon::new(self.latitude?, self.longitude?))
+    }
+
+    /// Returns the course over ground for display, see [`Cog`].
+    pub fn display_course(&self) -> Option<Cog> {
+        self.true_course.map(Cog)
+    }
+
+    /// Returns the speed over ground for display, see [`Sog`].
+    pub fn display_speed(&self) -> Option<Sog> {
+        self.speed_over_ground.map(Sog)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", LatLon::new(-33.856784, -151.2153)),
+            "33°51.407'S 151°12.918'W"
+        );
+        // rounded up to the next degree
+        assert_eq!(format!("{}", Latitude(47.99999999)), "48°00.000'N");
+        assert_eq!(format!("{:.0}", Longitude(-0.9999)), "001°00'W");
+        assert_eq!(format!("{}", Latitude(-0.0000001)), "00°00.000'N");
+
+        assert_eq!(format!("{}", Cog(359.96)), "COG 000.0°T");
+        assert_eq!(format!("{}", Cog(-5.)), "COG 355.0°T");
+        assert_eq!(format!("{:.0}", Cog(9.6)), "COG 010°T");
+        assert_eq!(format!("{:.2}", Sog(12.345)), "SOG 12.35 kn");
+
+        // the precision is clamped
+        assert_eq!(format!("{:.9}", Cog(54.5)), "COG 054.500000°T");
+        assert_eq!(
+            format!("{:.20}", LatLon::new(0.5, -1.5)),
+            "00°30.000000'N 001°30.000000'W"
+        );
+
+        let fix = Fix {
+            latitude: Some(48.1173),
+            longitude: Some(11.522067),
+            true_course: Some(54.7),
+            ..Fix::default()
+        };
+        assert_eq!(
+            format!("{}", fix.display_position().unwrap()),
+            "48°07.038'N 011°31.324'E"
+        );
+        assert_eq!(format!("{}", fix.display_course().unwrap()), "COG 054.7°T");
+        assert_eq!(fix.display_speed(), None);
+    }
+}
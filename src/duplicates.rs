@@ -0,0 +1,122 @@
+//! Suppression of duplicated sentences.
+//!
+//! Some NMEA splitters and multiplexers forward the same sentence twice,
+//! e.g. when a receiver is connected to two of their inputs. Once enabled
+//! with [`Nmea::set_duplicate_suppression()`], a sentence identical to the
+//! last one of its type in the same epoch is skipped and counted.
+
+use crate::{sentence_table::SentenceTable, Nmea, NmeaSentence};
+use chrono::NaiveTime;
+
+/// Number of sentence types whose last sentence is remembered.
+pub const DUPLICATES_LEN: usize = 16;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DuplicateFilter {
+    pub(crate) enabled: bool,
+    /// Fix time of the remembered sentences
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    epoch: Option<NaiveTime>,
+    /// Hash of the last sentence per type
+    last: SentenceTable<u32, DUPLICATES_LEN>,
+    dropped: u32,
+}
+
+impl DuplicateFilter {
+    /// Returns `true` if the sentence repeats the last one of its type in
+    /// the epoch of `fix_time`.
+    pub(crate) fn is_duplicate(
+        &mut self,
+        sentence: &NmeaSentence,
+        fix_time: Option<NaiveTime>,
+    ) -> bool {
+        if self.epoch != fix_time {
+            self.epoch = fix_time;
+            self.last.clear();
+        }
+        let sentence_type = sentence.message_id;
+        let hash = fnv1a(
+            sentence
+                .talker_id
+                .bytes()
+                .chain(sentence.data.bytes())
+                .chain([sentence.checksum]),
+        );
+        if self.last.get(sentence_type) == Some(&hash) {
+            self.dropped = self.dropped.saturating_add(1);
+            return true;
+        }
+        // only the first types are checked
+        let _ = self.last.insert(sentence_type, hash);
+        false
+    }
+}
+
+/// 32-bit FNV-1a hash.
+fn fnv1a(bytes: impl Iterator<Item = u8>) -> u32 {
+    bytes.fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+impl Nmea {
+    /// Skips sentences identical to the last sentence of their type with
+    /// the same fix time, as sent by some splitters and multiplexers.
+    ///
+    /// Skipped sentences return their type from [`parse()`](Self::parse)
+    /// and are counted in [`duplicates_dropped()`](Self::duplicates_dropped).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{Nmea, SentenceType};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_duplicate_suppression(true);
+    /// # #[cfg(feature = "GSV")]
+    /// # {
+    /// let gsv = "$GPGSV,1,1,01,01,,,42*7F";
+    /// assert_eq!(nmea.parse(gsv), Ok(SentenceType::GSV));
+    /// assert_eq!(nmea.parse(gsv), Ok(SentenceType::GSV));
+    /// assert_eq!(nmea.duplicates_dropped(), 1);
+    /// # }
+    /// ```
+    pub fn set_duplicate_suppression(&mut self, enabled: bool) {
+        self.duplicates.enabled = enabled;
+    }
+
+    /// Returns the number of sentences skipped as duplicates, see
+    /// [`set_duplicate_suppression()`](Self::set_duplicate_suppression).
+    pub fn duplicates_dropped(&self) -> u32 {
+        self.duplicates.dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_nmea_sentence;
+
+    #[test]
+    fn test_duplicate_filter() {
+        let mut filter = DuplicateFilter::default();
+        let t1 = NaiveTime::from_hms_opt(9, 27, 50);
+        let t2 = NaiveTime::from_hms_opt(9, 27, 51);
+        let gsa = parse_nmea_sentence("$GPGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1,1.4*3E").unwrap();
+        let hdt = parse_nmea_sentence("$GPHDT,274.07,T*03").unwrap();
+
+        assert!(!filter.is_duplicate(&gsa, t1));
+        assert!(!filter.is_duplicate(&hdt, t1));
+        assert!(filter.is_duplicate(&gsa, t1));
+        // from another talker
+        let gngsa =
+            parse_nmea_sentence("$GNGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1,1.4*20").unwrap();
+        assert!(!filter.is_duplicate(&gngsa, t1));
+        // the same sentence in the next epoch
+        assert!(!filter.is_duplicate(&gsa, t2));
+        assert!(!filter.is_duplicate(&hdt, t2));
+        assert_eq!(filter.dropped, 1);
+    }
+}
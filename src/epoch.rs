@@ -0,0 +1,61 @@
+//! Epochs counted from the fix times, to expire the values of sources which
+//! stopped reporting under [`Nmea::parse()`](crate::Nmea::parse), which only
+//! starts a new cycle with the [clock](crate::CycleSource::Clock).
+
+use chrono::NaiveTime;
+
+/// Number of fix time changes a value is kept after being reported.
+///
+/// A value of an epoch may come before its fix, e.g. HDT before GGA and RMC,
+/// and is then reported with the fix time of the previous epoch.
+const MAX_AGE: u32 = 1;
+
+/// Counter of the fix time changes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct FixEpochs {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    fix_time: Option<NaiveTime>,
+    count: u32,
+}
+
+impl FixEpochs {
+    /// Counts a change of the fix time and returns the current epoch.
+    pub(crate) fn advance(&mut self, fix_time: Option<NaiveTime>) -> u32 {
+        if fix_time.is_some() && fix_time != self.fix_time {
+            self.fix_time = fix_time;
+            self.count = self.count.wrapping_add(1);
+        }
+        self.count
+    }
+
+    /// Returns the current epoch.
+    pub(crate) fn current(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns `true` if a value reported in `epoch` is still current.
+    pub(crate) fn is_recent(&self, epoch: u32) -> bool {
+        self.count.wrapping_sub(epoch) <= MAX_AGE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fix_epochs() {
+        let mut epochs = FixEpochs::default();
+        let time = |second| NaiveTime::from_hms_opt(12, 0, second);
+        assert_eq!(epochs.advance(None), 0);
+        assert_eq!(epochs.advance(time(0)), 1);
+        assert_eq!(epochs.advance(None), 1);
+        assert_eq!(epochs.advance(time(0)), 1);
+        assert_eq!(epochs.advance(time(1)), 2);
+        assert!(epochs.is_recent(1));
+        assert_eq!(epochs.advance(time(2)), 3);
+        assert!(!epochs.is_recent(1));
+    }
+}
@@ -1,9 +1,10 @@
 use core::fmt;
 
-use crate::{sentences::GnssType, SentenceType};
+use crate::{metadata::NmeaVersion, sentences::GnssType, SentenceType};
 
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Error<'a> {
     /// The provided input was not a proper UTF-8 string
     Utf8Decoding,
@@ -40,6 +41,14 @@ pub enum Error<'a> {
     /// The current sentences is parsable but the feature has been disabled.
     // TODO: Add sentences and data?!
     DisabledSentence,
+    /// An optional field of the sentence is present although the target
+    /// revision of the standard does not define it, or it is missing although
+    /// the revision requires it.
+    VersionMismatch {
+        sentence_type: SentenceType,
+        field: &'static str,
+        version: NmeaVersion,
+    },
 }
 
 impl<'a> From<nom::Err<nom::error::Error<&'a str>>> for Error<'a> {
@@ -103,6 +112,15 @@ impl fmt::Display for Error<'_> {
             Error::DisabledSentence => {
                 write!(f, "Sentence is parsable but it's feature is disabled",)
             }
+            Error::VersionMismatch {
+                sentence_type,
+                field,
+                version,
+            } => write!(
+                f,
+                "The {} field of the {} sentence does not match NMEA {}",
+                field, sentence_type, version
+            ),
         }
     }
 }
@@ -110,3 +128,497 @@ impl fmt::Display for Error<'_> {
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std::error::Error for Error<'_> {}
+
+/// How to recover from an [`Error`], see [`Error::recovery_hint()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryHint {
+    /// The sentence was garbled on the link, e.g. a checksum mismatch: the
+    /// next sentences are likely fine, frequent errors point to the link.
+    Transient,
+    /// The sentence is valid but this build or configuration cannot handle
+    /// it, e.g. a disabled feature: the same sentences will always fail.
+    Configuration,
+    /// The sentence violates its format: only this sentence is lost, but
+    /// the talker will likely send more of them.
+    Fatal,
+}
+
+impl Error<'_> {
+    /// Classifies the error for generic retry and alarm logic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_str, RecoveryHint};
+    ///
+    /// let error = parse_str("$GPHDT,274.07,T*00").unwrap_err();
+    /// assert_eq!(error.recovery_hint(), RecoveryHint::Transient);
+    /// ```
+    pub fn recovery_hint(&self) -> RecoveryHint {
+        self.error_code().recovery_hint()
+    }
+
+    /// The [`ErrorCode`] of the error, also without the `compact-errors`
+    /// feature.
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Error::Utf8Decoding => ErrorCode::Utf8Decoding,
+            Error::ASCII => ErrorCode::ASCII,
+            Error::ChecksumMismatch { .. } => ErrorCode::ChecksumMismatch,
+            Error::WrongSentenceHeader { .. } => ErrorCode::WrongSentenceHeader,
+            Error::UnknownGnssType(_) => ErrorCode::UnknownGnssType,
+            Error::ParsingError(_) => ErrorCode::ParsingError,
+            Error::SentenceLength(_) => ErrorCode::SentenceLength,
+            Error::ParameterLength { .. } => ErrorCode::ParameterLength,
+            Error::Unsupported(_) => ErrorCode::Unsupported,
+            Error::Unknown(_) => ErrorCode::Unknown,
+            Error::EmptyNavConfig => ErrorCode::EmptyNavConfig,
+            Error::UnknownTalkerId { .. } => ErrorCode::UnknownTalkerId,
+            Error::DisabledSentence => ErrorCode::DisabledSentence,
+            Error::VersionMismatch { .. } => ErrorCode::VersionMismatch,
+        }
+    }
+}
+
+/// Maximum length of the input copied into an [`OwnedError`].
+pub const OWNED_ERROR_INPUT_LEN: usize = 32;
+
+/// Input copied into an [`OwnedError`], truncated to
+/// [`OWNED_ERROR_INPUT_LEN`] bytes.
+pub type ErrorInput = heapless::String<OWNED_ERROR_INPUT_LEN>;
+
+/// An [`Error`] which does not borrow the parsed input, so it can be kept
+/// after the input buffer is reused, see [`Error::into_owned()`].
+///
+/// Borrowed input is copied and truncated to [`OWNED_ERROR_INPUT_LEN`]
+/// bytes.
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum OwnedError {
+    Utf8Decoding,
+    ASCII,
+    ChecksumMismatch {
+        calculated: u8,
+        found: u8,
+    },
+    WrongSentenceHeader {
+        expected: SentenceType,
+        found: SentenceType,
+    },
+    UnknownGnssType(ErrorInput),
+    ParsingError(
+        #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+        nom::Err<nom::error::Error<ErrorInput>>,
+    ),
+    SentenceLength(usize),
+    ParameterLength {
+        max_length: usize,
+        parameter_length: usize,
+    },
+    Unsupported(SentenceType),
+    Unknown(ErrorInput),
+    EmptyNavConfig,
+    UnknownTalkerId {
+        expected: ErrorInput,
+        found: ErrorInput,
+    },
+    DisabledSentence,
+    VersionMismatch {
+        sentence_type: SentenceType,
+        field: &'static str,
+        version: NmeaVersion,
+    },
+}
+
+fn to_owned_input(input: &str) -> ErrorInput {
+    let mut owned = ErrorInput::new();
+    for c in input.chars() {
+        if owned.push(c).is_err() {
+            break;
+        }
+    }
+    owned
+}
+
+fn borrow_nom_error(error: &nom::error::Error<ErrorInput>) -> nom::error::Error<&str> {
+    nom::error::Error::new(error.input.as_str(), error.code)
+}
+
+impl Error<'_> {
+    /// Copies the borrowed input of the error, see [`OwnedError`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_str, OwnedError};
+    ///
+    /// let error = {
+    ///     let buffer = String::from("$XXGSV,1,1,00*00");
+    ///     parse_str(&buffer).unwrap_err().into_owned()
+    /// };
+    /// assert!(matches!(error, OwnedError::ChecksumMismatch { .. }));
+    /// ```
+    pub fn into_owned(self) -> OwnedError {
+        match self {
+            Error::Utf8Decoding => OwnedError::Utf8Decoding,
+            Error::ASCII => OwnedError::ASCII,
+            Error::ChecksumMismatch { calculated, found } => {
+                OwnedError::ChecksumMismatch { calculated, found }
+            }
+            Error::WrongSentenceHeader { expected, found } => {
+                OwnedError::WrongSentenceHeader { expected, found }
+            }
+            Error::UnknownGnssType(found) => OwnedError::UnknownGnssType(to_owned_input(found)),
+            Error::ParsingError(error) => OwnedError::ParsingError(
+                error.map(|error| nom::error::Error::new(to_owned_input(error.input), error.code)),
+            ),
+            Error::SentenceLength(length) => OwnedError::SentenceLength(length),
+            Error::ParameterLength {
+                max_length,
+                parameter_length,
+            } => OwnedError::ParameterLength {
+                max_length,
+                parameter_length,
+            },
+            Error::Unsupported(sentence_type) => OwnedError::Unsupported(sentence_type),
+            Error::Unknown(sentence_type) => OwnedError::Unknown(to_owned_input(sentence_type)),
+            Error::EmptyNavConfig => OwnedError::EmptyNavConfig,
+            Error::UnknownTalkerId { expected, found } => OwnedError::UnknownTalkerId {
+                expected: to_owned_input(expected),
+                found: to_owned_input(found),
+            },
+            Error::DisabledSentence => OwnedError::DisabledSentence,
+            Error::VersionMismatch {
+                sentence_type,
+                field,
+                version,
+            } => OwnedError::VersionMismatch {
+                sentence_type,
+                field,
+                version,
+            },
+        }
+    }
+}
+
+impl OwnedError {
+    /// Returns the error borrowing the copied input.
+    pub fn as_error(&self) -> Error<'_> {
+        match self {
+            OwnedError::Utf8Decoding => Error::Utf8Decoding,
+            OwnedError::ASCII => Error::ASCII,
+            OwnedError::ChecksumMismatch { calculated, found } => Error::ChecksumMismatch {
+                calculated: *calculated,
+                found: *found,
+            },
+            OwnedError::WrongSentenceHeader { expected, found } => Error::WrongSentenceHeader {
+                expected: *expected,
+                found: *found,
+            },
+            OwnedError::UnknownGnssType(found) => Error::UnknownGnssType(found),
+            OwnedError::ParsingError(error) => Error::ParsingError(match error {
+                nom::Err::Incomplete(needed) => nom::Err::Incomplete(*needed),
+                nom::Err::Error(error) => nom::Err::Error(borrow_nom_error(error)),
+                nom::Err::Failure(error) => nom::Err::Failure(borrow_nom_error(error)),
+            }),
+            OwnedError::SentenceLength(length) => Error::SentenceLength(*length),
+            OwnedError::ParameterLength {
+                max_length,
+                parameter_length,
+            } => Error::ParameterLength {
+                max_length: *max_length,
+                parameter_length: *parameter_length,
+            },
+            OwnedError::Unsupported(sentence_type) => Error::Unsupported(*sentence_type),
+            OwnedError::Unknown(sentence_type) => Error::Unknown(sentence_type),
+            OwnedError::EmptyNavConfig => Error::EmptyNavConfig,
+            OwnedError::UnknownTalkerId { expected, found } => {
+                Error::UnknownTalkerId { expected, found }
+            }
+            OwnedError::DisabledSentence => Error::DisabledSentence,
+            OwnedError::VersionMismatch {
+                sentence_type,
+                field,
+                version,
+            } => Error::VersionMismatch {
+                sentence_type: *sentence_type,
+                field,
+                version: *version,
+            },
+        }
+    }
+}
+
+impl OwnedError {
+    /// Classifies the error, see [`Error::recovery_hint()`].
+    pub fn recovery_hint(&self) -> RecoveryHint {
+        self.as_error().recovery_hint()
+    }
+}
+
+impl From<Error<'_>> for OwnedError {
+    fn from(error: Error<'_>) -> Self {
+        error.into_owned()
+    }
+}
+
+impl fmt::Display for OwnedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_error(), f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for OwnedError {}
+
+/// An [`Error`] as a single byte, see [`Error::code()`].
+///
+/// The code drops the borrowed input, the nom error and the messages, so it
+/// is small and can be stored or sent as is over a telemetry link. The
+/// values are stable, new errors get new values.
+///
+/// The parsers still return the full [`Error`], with its messages in
+/// [`Display`](fmt::Display): the feature does not reduce the code size.
+// Always compiled for `Error::recovery_hint()`, only exported with the
+// `compact-errors` feature.
+#[allow(clippy::upper_case_acronyms)] // the names of the `Error` variants
+#[cfg_attr(docsrs, doc(cfg(feature = "compact-errors")))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// [`Error::Utf8Decoding`]
+    Utf8Decoding = 1,
+    /// [`Error::ASCII`]
+    ASCII = 2,
+    /// [`Error::ChecksumMismatch`]
+    ChecksumMismatch = 3,
+    /// [`Error::WrongSentenceHeader`]
+    WrongSentenceHeader = 4,
+    /// [`Error::UnknownGnssType`]
+    UnknownGnssType = 5,
+    /// [`Error::ParsingError`]
+    ParsingError = 6,
+    /// [`Error::SentenceLength`]
+    SentenceLength = 7,
+    /// [`Error::ParameterLength`]
+    ParameterLength = 8,
+    /// [`Error::Unsupported`]
+    Unsupported = 9,
+    /// [`Error::Unknown`]
+    Unknown = 10,
+    /// [`Error::EmptyNavConfig`]
+    EmptyNavConfig = 11,
+    /// [`Error::UnknownTalkerId`]
+    UnknownTalkerId = 12,
+    /// [`Error::DisabledSentence`]
+    DisabledSentence = 13,
+    /// [`Error::VersionMismatch`]
+    VersionMismatch = 14,
+}
+
+impl ErrorCode {
+    #[cfg(feature = "compact-errors")]
+    const ALL: [ErrorCode; 14] = [
+        ErrorCode::Utf8Decoding,
+        ErrorCode::ASCII,
+        ErrorCode::ChecksumMismatch,
+        ErrorCode::WrongSentenceHeader,
+        ErrorCode::UnknownGnssType,
+        ErrorCode::ParsingError,
+        ErrorCode::SentenceLength,
+        ErrorCode::ParameterLength,
+        ErrorCode::Unsupported,
+        ErrorCode::Unknown,
+        ErrorCode::EmptyNavConfig,
+        ErrorCode::UnknownTalkerId,
+        ErrorCode::DisabledSentence,
+        ErrorCode::VersionMismatch,
+    ];
+
+    /// Classifies the error, see [`Error::recovery_hint()`].
+    pub fn recovery_hint(self) -> RecoveryHint {
+        match self {
+            ErrorCode::Utf8Decoding
+            | ErrorCode::ASCII
+            | ErrorCode::ChecksumMismatch
+            | ErrorCode::SentenceLength => RecoveryHint::Transient,
+            ErrorCode::Unsupported
+            | ErrorCode::Unknown
+            | ErrorCode::EmptyNavConfig
+            | ErrorCode::DisabledSentence
+            | ErrorCode::VersionMismatch => RecoveryHint::Configuration,
+            ErrorCode::WrongSentenceHeader
+            | ErrorCode::UnknownGnssType
+            | ErrorCode::ParsingError
+            | ErrorCode::ParameterLength
+            | ErrorCode::UnknownTalkerId => RecoveryHint::Fatal,
+        }
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl Error<'_> {
+    /// Returns the error as a single byte, see [`ErrorCode`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_str, ErrorCode};
+    ///
+    /// let code = parse_str("$GPHDT,274.07,T*00").unwrap_err().code();
+    /// assert_eq!(code, ErrorCode::ChecksumMismatch);
+    /// assert_eq!(ErrorCode::try_from(u8::from(code)), Ok(code));
+    /// ```
+    pub fn code(&self) -> ErrorCode {
+        self.error_code()
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl OwnedError {
+    /// Returns the error as a single byte, see [`ErrorCode`].
+    pub fn code(&self) -> ErrorCode {
+        self.as_error().code()
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl From<Error<'_>> for ErrorCode {
+    fn from(error: Error<'_>) -> Self {
+        error.code()
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl From<ErrorCode> for u8 {
+    fn from(code: ErrorCode) -> Self {
+        code as u8
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl TryFrom<u8> for ErrorCode {
+    /// The unknown value
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        ErrorCode::ALL
+            .into_iter()
+            .find(|code| *code as u8 == value)
+            .ok_or(value)
+    }
+}
+
+/// The messages are left out of `no_std` builds to save their space.
+#[cfg(all(feature = "compact-errors", feature = "std"))]
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ErrorCode::Utf8Decoding => "invalid UTF-8",
+            ErrorCode::ASCII => "non-ASCII characters",
+            ErrorCode::ChecksumMismatch => "checksum mismatch",
+            ErrorCode::WrongSentenceHeader => "wrong sentence header",
+            ErrorCode::UnknownGnssType => "unknown GNSS type",
+            ErrorCode::ParsingError => "invalid sentence format",
+            ErrorCode::SentenceLength => "sentence too long",
+            ErrorCode::ParameterLength => "parameter too long",
+            ErrorCode::Unsupported => "unsupported sentence",
+            ErrorCode::Unknown => "unknown sentence",
+            ErrorCode::EmptyNavConfig => "empty navigation configuration",
+            ErrorCode::UnknownTalkerId => "unknown talker ID",
+            ErrorCode::DisabledSentence => "disabled sentence",
+            ErrorCode::VersionMismatch => "NMEA version mismatch",
+        };
+        write!(f, "E{:02} {}", *self as u8, message)
+    }
+}
+
+#[cfg(all(feature = "compact-errors", feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for ErrorCode {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_owned() {
+        let input = "0123456789012345678901234567890123456789";
+        let error = Error::ParsingError(nom::Err::Failure(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Char,
+        )));
+        let owned = error.into_owned();
+        let OwnedError::ParsingError(nom::Err::Failure(ref inner)) = owned else {
+            panic!("unexpected error {:?}", owned);
+        };
+        assert_eq!(inner.input.as_str(), &input[..OWNED_ERROR_INPUT_LEN]);
+        assert_eq!(owned.as_error().into_owned(), owned);
+
+        let owned = Error::UnknownTalkerId {
+            expected: "GP",
+            found: "XX",
+        }
+        .into_owned();
+        assert_eq!(
+            owned.as_error(),
+            Error::UnknownTalkerId {
+                expected: "GP",
+                found: "XX",
+            }
+        );
+    }
+
+    #[test]
+    fn test_recovery_hint() {
+        assert_eq!(Error::ASCII.recovery_hint(), RecoveryHint::Transient);
+        assert_eq!(
+            Error::DisabledSentence.recovery_hint(),
+            RecoveryHint::Configuration
+        );
+        let error = Error::ParsingError(nom::Err::Failure(nom::error::Error::new(
+            "x",
+            nom::error::ErrorKind::Char,
+        )));
+        assert_eq!(error.recovery_hint(), RecoveryHint::Fatal);
+        assert_eq!(error.into_owned().recovery_hint(), RecoveryHint::Fatal);
+    }
+
+    #[test]
+    #[cfg(feature = "compact-errors")]
+    fn test_error_code() {
+        assert_eq!(core::mem::size_of::<ErrorCode>(), 1);
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::try_from(u8::from(code)), Ok(code));
+        }
+        assert_eq!(ErrorCode::try_from(0), Err(0));
+        assert_eq!(ErrorCode::try_from(15), Err(15));
+
+        let error = Error::UnknownTalkerId {
+            expected: "GP",
+            found: "XX",
+        };
+        assert_eq!(error.code(), ErrorCode::UnknownTalkerId);
+        assert_eq!(error.code().recovery_hint(), error.recovery_hint());
+        assert_eq!(error.into_owned().code(), ErrorCode::UnknownTalkerId);
+        assert_eq!(
+            ErrorCode::from(Error::ChecksumMismatch {
+                calculated: 1,
+                found: 2
+            }),
+            ErrorCode::ChecksumMismatch
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            ErrorCode::ChecksumMismatch.to_string(),
+            "E03 checksum mismatch"
+        );
+    }
+}
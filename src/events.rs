@@ -0,0 +1,73 @@
+//! Events detected while the fixes are stored, see
+//! [`Nmea::pop_fix_event()`].
+
+use chrono::NaiveTime;
+use heapless::Vec;
+
+use crate::{Nmea, PositionJump};
+
+/// Number of events kept until they are popped.
+pub const FIX_EVENTS_LEN: usize = 8;
+
+/// Something which happened to the fix.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum FixEvent {
+    /// The position moved faster than the [`JumpFilter`](crate::JumpFilter)
+    /// allows
+    PositionJump(PositionJump),
+    /// The position entered a [`Geofence`](crate::Geofence)
+    GeofenceEnter {
+        id: u16,
+        #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+        fix_time: Option<NaiveTime>,
+    },
+    /// The position exited a [`Geofence`](crate::Geofence)
+    GeofenceExit {
+        id: u16,
+        #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+        fix_time: Option<NaiveTime>,
+    },
+}
+
+/// The events not popped yet, oldest first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct FixEvents {
+    events: Vec<FixEvent, FIX_EVENTS_LEN>,
+    /// Events dropped because the queue was full
+    dropped: u32,
+}
+
+impl FixEvents {
+    /// Queues an event, dropping the oldest one if the queue is full.
+    pub(crate) fn push(&mut self, event: FixEvent) {
+        if self.events.is_full() {
+            self.events.remove(0);
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        let _ = self.events.push(event);
+    }
+}
+
+impl Nmea {
+    /// Returns the oldest event which was not popped yet.
+    ///
+    /// The last [`FIX_EVENTS_LEN`] events are kept, so they should be popped
+    /// after each sentence or epoch.
+    pub fn pop_fix_event(&mut self) -> Option<FixEvent> {
+        if self.fix_events.events.is_empty() {
+            None
+        } else {
+            Some(self.fix_events.events.remove(0))
+        }
+    }
+
+    /// Returns the number of events dropped before they were popped.
+    pub fn dropped_fix_events(&self) -> u32 {
+        self.fix_events.dropped
+    }
+}
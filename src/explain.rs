@@ -0,0 +1,412 @@
+//! Field-by-field breakdown of a sentence for humans.
+
+use std::{
+    fmt,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{parse::parse_nmea_sentence, parse_str, SentenceType};
+
+/// Expected syntax of a field, used to flag invalid values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    /// `hhmmss` with optional fractional seconds
+    Time,
+    /// `ddmmyy`
+    Date,
+    /// `ddmm.mmm`
+    Latitude,
+    /// `dddmm.mmm`
+    Longitude,
+    Float,
+    Int,
+    /// Single hex digit
+    Hex,
+    /// One of the given characters
+    Char(&'static str),
+    /// One or more of the given characters
+    Chars(&'static str),
+    Text,
+}
+
+const FAA_MODES: &str = "ACDEFMNPRSU";
+
+type Spec = (&'static str, Kind);
+
+fn specs(sentence_type: SentenceType, field_count: usize) -> Vec<Spec> {
+    use Kind::*;
+
+    let specs: &[Spec] = match sentence_type {
+        SentenceType::DPT => &[
+            ("Water depth relative to transducer, meters", Float),
+            ("Offset from transducer, meters", Float),
+            ("Maximum range scale in use, meters", Float),
+        ],
+        SentenceType::GGA => &[
+            ("UTC time", Time),
+            ("Latitude", Latitude),
+            ("N/S indicator", Char("NS")),
+            ("Longitude", Longitude),
+            ("E/W indicator", Char("EW")),
+            ("GPS quality indicator", Int),
+            ("Satellites in use", Int),
+            ("HDOP", Float),
+            ("Altitude above MSL", Float),
+            ("Altitude units", Char("M")),
+            ("Geoidal separation", Float),
+            ("Separation units", Char("M")),
+            ("Age of differential GPS data, seconds", Float),
+            ("Differential reference station ID", Int),
+        ],
+        SentenceType::GLL => &[
+            ("Latitude", Latitude),
+            ("N/S indicator", Char("NS")),
+            ("Longitude", Longitude),
+            ("E/W indicator", Char("EW")),
+            ("UTC time", Time),
+            ("Status", Char("AV")),
+            ("FAA mode indicator", Char(FAA_MODES)),
+        ],
+        SentenceType::GNS => &[
+            ("UTC time", Time),
+            ("Latitude", Latitude),
+            ("N/S indicator", Char("NS")),
+            ("Longitude", Longitude),
+            ("E/W indicator", Char("EW")),
+            ("Mode indicator per constellation", Chars(FAA_MODES)),
+            ("Satellites in use", Int),
+            ("HDOP", Float),
+            ("Orthometric height, meters", Float),
+            ("Geoidal separation, meters", Float),
+            ("Age of differential data, seconds", Float),
+            ("Differential reference station ID", Int),
+            ("Navigational status", Char("SCUV")),
+        ],
+        SentenceType::GSA => &[
+            ("Selection mode", Char("AM")),
+            ("Fix mode", Char("123")),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("Satellite ID", Int),
+            ("PDOP", Float),
+            ("HDOP", Float),
+            ("VDOP", Float),
+            ("System ID", Hex),
+        ],
+        SentenceType::GST => &[
+            ("UTC time", Time),
+            ("RMS of the pseudorange residuals", Float),
+            ("Error ellipse semi-major axis 1-sigma, meters", Float),
+            ("Error ellipse semi-minor axis 1-sigma, meters", Float),
+            ("Error ellipse orientation, degrees True", Float),
+            ("Latitude 1-sigma error, meters", Float),
+            ("Longitude 1-sigma error, meters", Float),
+            ("Height 1-sigma error, meters", Float),
+        ],
+        SentenceType::GSV => {
+            let mut specs = Vec::from([
+                ("Number of sentences", Int),
+                ("Sentence number", Int),
+                ("Satellites in view", Int),
+            ]);
+            let sat_fields = field_count.saturating_sub(specs.len());
+            for _ in 0..sat_fields / 4 {
+                specs.extend([
+                    ("Satellite ID", Int),
+                    ("Elevation, degrees", Int),
+                    ("Azimuth, degrees True", Int),
+                    ("SNR, dB", Int),
+                ]);
+            }
+            if sat_fields % 4 == 1 {
+                specs.push(("Signal ID", Hex));
+            }
+            return specs;
+        }
+        SentenceType::HDT => &[("Heading, degrees", Float), ("True", Char("T"))],
+        SentenceType::MWV => &[
+            ("Wind angle, degrees", Float),
+            ("Reference, Relative or True", Char("RT")),
+            ("Wind speed", Float),
+            ("Wind speed units", Char("KMNS")),
+            ("Status", Char("AV")),
+        ],
+        SentenceType::RMC => &[
+            ("UTC time", Time),
+            ("Status", Char("AV")),
+            ("Latitude", Latitude),
+            ("N/S indicator", Char("NS")),
+            ("Longitude", Longitude),
+            ("E/W indicator", Char("EW")),
+            ("Speed over ground, knots", Float),
+            ("Course over ground, degrees True", Float),
+            ("Date", Date),
+            ("Magnetic variation, degrees", Float),
+            ("Magnetic variation direction", Char("EW")),
+            ("FAA mode indicator", Char(FAA_MODES)),
+            ("Navigational status", Char("SCUV")),
+        ],
+        SentenceType::TXT => &[
+            ("Total number of sentences", Int),
+            ("Sentence number", Int),
+            ("Text identifier", Int),
+            ("Text", Text),
+        ],
+        SentenceType::VTG => &[
+            ("Course over ground, degrees True", Float),
+            ("True", Char("T")),
+            ("Course over ground, degrees Magnetic", Float),
+            ("Magnetic", Char("M")),
+            ("Speed over ground, knots", Float),
+            ("Knots", Char("N")),
+            ("Speed over ground, km/h", Float),
+            ("Kilometers per hour", Char("K")),
+            ("FAA mode indicator", Char(FAA_MODES)),
+        ],
+        SentenceType::ZDA => &[
+            ("UTC time", Time),
+            ("Day", Int),
+            ("Month", Int),
+            ("Year", Int),
+            ("Local zone hours", Int),
+            ("Local zone minutes", Int),
+        ],
+        _ => &[],
+    };
+    specs.to_vec()
+}
+
+fn is_digits(value: &str) -> bool {
+    !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Checks the two-digit groups of `hhmmss` or `ddmmyy` against `max`.
+fn check_pairs(value: &str, max: [u32; 3]) -> bool {
+    value.len() == 6
+        && is_digits(value)
+        && (0..3).all(|i| {
+            value[i * 2..i * 2 + 2]
+                .parse::<u32>()
+                .is_ok_and(|v| v <= max[i])
+        })
+}
+
+fn check_angle(value: &str, degree_digits: usize, max_degrees: f64) -> bool {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, "0"));
+    if whole.len() != degree_digits + 2 || !is_digits(whole) || !is_digits(fraction) {
+        return false;
+    }
+    let minutes: f64 = value[degree_digits..].parse().unwrap_or(f64::MAX);
+    let degrees: f64 = whole[..degree_digits].parse().unwrap_or(f64::MAX);
+    minutes < 60. && degrees + minutes / 60. <= max_degrees
+}
+
+impl Kind {
+    fn is_valid(self, value: &str) -> bool {
+        if value.is_empty() {
+            return true;
+        }
+        match self {
+            Kind::Time => {
+                let (hms, fraction) = value.split_once('.').unwrap_or((value, "0"));
+                check_pairs(hms, [23, 59, 60]) && is_digits(fraction)
+            }
+            Kind::Date => check_pairs(value, [31, 12, 99]) && !value.starts_with("00"),
+            Kind::Latitude => check_angle(value, 2, 90.),
+            Kind::Longitude => check_angle(value, 3, 180.),
+            Kind::Float => value.parse::<f64>().is_ok() && !value.contains(['e', 'E']),
+            Kind::Int => value.parse::<i64>().is_ok(),
+            Kind::Hex => value.len() == 1 && value.chars().all(|c| c.is_ascii_hexdigit()),
+            Kind::Char(allowed) => value.len() == 1 && allowed.contains(value),
+            Kind::Chars(allowed) => value.chars().all(|c| allowed.contains(c)),
+            Kind::Text => true,
+        }
+    }
+}
+
+/// One field of an [`Explanation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExplainedField {
+    /// 1-based field number
+    pub number: usize,
+    /// Field name, `None` for fields this crate has no description of
+    pub name: Option<&'static str>,
+    pub value: String,
+    /// `false` if the value does not match the expected syntax or range
+    pub valid: bool,
+}
+
+/// Human-readable breakdown of a sentence, see [`explain()`].
+///
+/// The [`Display`](fmt::Display) implementation renders one line per field
+/// and marks invalid fields with `!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub talker_id: Option<String>,
+    pub sentence_type: Option<SentenceType>,
+    pub title: Option<&'static str>,
+    pub fields: Vec<ExplainedField>,
+    /// Checksum in the sentence
+    pub checksum: Option<u8>,
+    /// Checksum calculated over the sentence
+    pub calculated_checksum: Option<u8>,
+    /// Parsing error of the whole sentence, if any
+    pub error: Option<String>,
+}
+
+impl Explanation {
+    /// Returns `true` if the sentence parses and all fields are valid.
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none() && self.fields.iter().all(|field| field.valid)
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.talker_id, self.sentence_type) {
+            (Some(talker_id), Some(sentence_type)) => {
+                write!(f, "{}{}", talker_id, sentence_type.as_str())?;
+                if let Some(title) = self.title {
+                    write!(f, " - {}", title)?;
+                }
+                writeln!(f)?;
+            }
+            _ => writeln!(f, "unrecognized sentence")?,
+        }
+        for field in &self.fields {
+            writeln!(
+                f,
+                "{} {:>2} {:<46} {}",
+                if field.valid { ' ' } else { '!' },
+                field.number,
+                field.name.unwrap_or("?"),
+                field.value
+            )?;
+        }
+        if let (Some(checksum), Some(calculated)) = (self.checksum, self.calculated_checksum) {
+            if checksum == calculated {
+                writeln!(f, "  checksum {:02X} ok", checksum)?;
+            } else {
+                writeln!(
+                    f,
+                    "! checksum {:02X}, calculated {:02X}",
+                    checksum, calculated
+                )?;
+            }
+        }
+        if let Some(error) = &self.error {
+            writeln!(f, "! {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a sentence and breaks it down field by field.
+///
+/// Unlike [`parse_str()`] this never fails: fields that cannot be parsed
+/// are flagged as invalid and the parsing error is kept in
+/// [`Explanation::error`].
+///
+/// # Examples
+///
+/// ```
+/// let explanation = nmea::explain("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76");
+/// assert!(explanation.fields.iter().all(|field| field.valid));
+/// assert_eq!(explanation.fields[0].name, Some("UTC time"));
+/// println!("{}", explanation);
+/// ```
+pub fn explain(sentence: &str) -> Explanation {
+    let mut explanation = Explanation {
+        talker_id: None,
+        sentence_type: None,
+        title: None,
+        fields: Vec::new(),
+        checksum: None,
+        calculated_checksum: None,
+        error: None,
+    };
+    let nmea_sentence = match parse_nmea_sentence(sentence) {
+        Ok(nmea_sentence) => nmea_sentence,
+        Err(err) => {
+            explanation.error = Some(err.to_string());
+            return explanation;
+        }
+    };
+    explanation.talker_id = Some(nmea_sentence.talker_id.into());
+    explanation.sentence_type = Some(nmea_sentence.message_id);
+    explanation.title = Some(nmea_sentence.message_id.metadata().name);
+    explanation.checksum = Some(nmea_sentence.checksum);
+    explanation.calculated_checksum = Some(nmea_sentence.calc_checksum());
+
+    let values: Vec<&str> = nmea_sentence.data.split(',').collect();
+    let specs = specs(nmea_sentence.message_id, values.len());
+    explanation.fields = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let spec = specs.get(index);
+            ExplainedField {
+                number: index + 1,
+                name: spec.map(|spec| spec.0),
+                value: (*value).into(),
+                valid: spec.map_or(true, |spec| spec.1.is_valid(value)),
+            }
+        })
+        .collect();
+    explanation.error = parse_str(sentence).err().map(|err| err.to_string());
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_invalid_fields() {
+        // bad minutes in the latitude, bad N/S and a wrong checksum
+        let explanation =
+            explain("$GPRMC,225446.33,A,4961.45,X,12311.12,W,000.5,054.7,191194,020.3,E*00");
+        assert_eq!(explanation.sentence_type, Some(SentenceType::RMC));
+        let invalid: Vec<_> = explanation
+            .fields
+            .iter()
+            .filter(|field| !field.valid)
+            .map(|field| field.name.unwrap())
+            .collect();
+        assert_eq!(invalid, ["Latitude", "N/S indicator"]);
+        assert!(explanation.error.is_some());
+        assert!(!explanation.is_valid());
+
+        let text = explanation.to_string();
+        assert!(text.starts_with("GPRMC - Recommended Minimum Navigation Information\n"));
+        assert!(text.contains("!  3 Latitude"));
+        assert!(text.contains("! checksum 00, calculated"));
+    }
+
+    #[test]
+    #[cfg(feature = "GSV")]
+    fn test_explain_gsv_signal_id() {
+        let explanation = explain("$GPGSV,1,1,02,10,63,137,37,05,59,290,35,8*6E");
+        assert!(explanation.is_valid(), "{}", explanation);
+        assert_eq!(explanation.fields.len(), 12);
+        assert_eq!(explanation.fields[11].name, Some("Signal ID"));
+    }
+
+    #[test]
+    fn test_explain_garbage() {
+        let explanation = explain("hello");
+        assert_eq!(explanation.sentence_type, None);
+        assert!(explanation.error.is_some());
+        assert!(explanation.to_string().starts_with("unrecognized sentence"));
+    }
+}
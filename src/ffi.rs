@@ -0,0 +1,350 @@
+//! C-compatible interface to the [`Nmea`] parser.
+//!
+//! The functions in this module use the C calling convention and only
+//! `#[repr(C)]` types, so a C header can be generated with `cbindgen`.
+//! No allocation is performed: the caller provides the storage for the
+//! parser, which makes the interface usable on `no_std` targets.
+//!
+//! The storage has to hold [`nmea_parser_size()`] bytes, about 16 KiB with
+//! the default features, aligned to [`nmea_parser_align()`]. It can be a
+//! static buffer on targets without an allocator, as long as it is large
+//! enough for the features of the build:
+//!
+//! ```c
+//! size_t align = nmea_parser_align();
+//! size_t size = (nmea_parser_size() + align - 1) / align * align;
+//! uint8_t *storage = aligned_alloc(align, size);
+//!
+//! NmeaParser *parser = nmea_parser_new(storage, size);
+//! if (parser == NULL) {
+//!     /* out of memory */
+//!     return -1;
+//! }
+//! const char *line = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+//! if (nmea_parse_sentence(parser, line, strlen(line)) == NMEA_STATUS_OK) {
+//!     NmeaFix fix;
+//!     nmea_get_fix(parser, &fix);
+//! }
+//! ```
+//!
+//! Missing floating point values are reported as `NaN` and missing integers
+//! as `-1`.
+#![allow(unsafe_code)]
+
+use core::{ffi::c_char, mem, ptr, slice};
+
+use chrono::{Datelike, Timelike};
+
+use crate::{sentences::FixType, Error, Nmea};
+
+/// Opaque parser handle.
+pub struct NmeaParser(Nmea);
+
+/// Result of [`nmea_parse_sentence()`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NmeaStatus {
+    /// The sentence was parsed and stored.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = -1,
+    /// The sentence is not valid UTF-8 or ASCII.
+    Encoding = -2,
+    /// The checksum of the sentence does not match.
+    ChecksumMismatch = -3,
+    /// The sentence could not be parsed.
+    ParsingError = -4,
+    /// The sentence is valid but not stored by the parser.
+    Unsupported = -5,
+    /// The sentence type is disabled by crate features.
+    DisabledSentence = -6,
+}
+
+impl From<&Error<'_>> for NmeaStatus {
+    fn from(error: &Error<'_>) -> Self {
+        match error {
+            Error::Utf8Decoding | Error::ASCII => NmeaStatus::Encoding,
+            Error::ChecksumMismatch { .. } => NmeaStatus::ChecksumMismatch,
+            Error::Unsupported(_) | Error::Unknown(_) => NmeaStatus::Unsupported,
+            Error::DisabledSentence => NmeaStatus::DisabledSentence,
+            _ => NmeaStatus::ParsingError,
+        }
+    }
+}
+
+/// Fix type, see [`FixType`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NmeaFixType {
+    /// No fix type has been received yet.
+    None = -1,
+    Invalid = 0,
+    Gps = 1,
+    DGps = 2,
+    Pps = 3,
+    Rtk = 4,
+    FloatRtk = 5,
+    Estimated = 6,
+    Manual = 7,
+    Simulation = 8,
+}
+
+impl From<Option<FixType>> for NmeaFixType {
+    fn from(fix_type: Option<FixType>) -> Self {
+        match fix_type {
+            None => NmeaFixType::None,
+            Some(FixType::Invalid) => NmeaFixType::Invalid,
+            Some(FixType::Gps) => NmeaFixType::Gps,
+            Some(FixType::DGps) => NmeaFixType::DGps,
+            Some(FixType::Pps) => NmeaFixType::Pps,
+            Some(FixType::Rtk) => NmeaFixType::Rtk,
+            Some(FixType::FloatRtk) => NmeaFixType::FloatRtk,
+            Some(FixType::Estimated) => NmeaFixType::Estimated,
+            Some(FixType::Manual) => NmeaFixType::Manual,
+            Some(FixType::Simulation) => NmeaFixType::Simulation,
+        }
+    }
+}
+
+/// The last fix, see [`crate::Fix`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NmeaFix {
+    pub has_time: bool,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+    pub has_date: bool,
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+    pub fix_type: NmeaFixType,
+    /// Degrees, `NaN` if unknown
+    pub latitude: f64,
+    /// Degrees, `NaN` if unknown
+    pub longitude: f64,
+    /// MSL altitude in meters, `NaN` if unknown
+    pub altitude: f32,
+    /// Knots, `NaN` if unknown
+    pub speed_over_ground: f32,
+    /// Degrees, `NaN` if unknown
+    pub true_course: f32,
+    /// `-1` if unknown
+    pub fix_satellites: i32,
+    pub hdop: f32,
+    pub vdop: f32,
+    pub pdop: f32,
+    /// Meters, `NaN` if unknown
+    pub geoid_separation: f32,
+}
+
+/// A satellite in view, see [`crate::Satellite`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NmeaSatellite {
+    /// Discriminant of [`crate::sentences::GnssType`]
+    pub gnss_type: u8,
+    pub prn: u32,
+    /// Degrees, `NaN` if unknown
+    pub elevation: f32,
+    /// Degrees, `NaN` if unknown
+    pub azimuth: f32,
+    /// dB-Hz, `NaN` if unknown
+    pub snr: f32,
+}
+
+/// Returns the number of bytes needed to store a parser.
+#[no_mangle]
+pub extern "C" fn nmea_parser_size() -> usize {
+    mem::size_of::<NmeaParser>()
+}
+
+/// Returns the required alignment of the parser storage.
+#[no_mangle]
+pub extern "C" fn nmea_parser_align() -> usize {
+    mem::align_of::<NmeaParser>()
+}
+
+/// Initializes a new parser in `storage`.
+///
+/// Returns null if `storage` is null, smaller than [`nmea_parser_size()`]
+/// or not aligned to [`nmea_parser_align()`].
+///
+/// # Safety
+///
+/// `storage` must be null or valid for writes of `size` bytes for as long
+/// as the returned parser is used.
+#[no_mangle]
+pub unsafe extern "C" fn nmea_parser_new(storage: *mut u8, size: usize) -> *mut NmeaParser {
+    let parser = storage.cast::<NmeaParser>();
+    if storage.is_null() || size < nmea_parser_size() || storage as usize % nmea_parser_align() != 0
+    {
+        return ptr::null_mut();
+    }
+    parser.write(NmeaParser(Nmea::default()));
+    parser
+}
+
+/// Parses a sentence of `len` bytes and stores its data in `parser`.
+///
+/// # Safety
+///
+/// `parser` must be null or returned by [`nmea_parser_new()`] and
+/// `sentence` must be null or valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nmea_parse_sentence(
+    parser: *mut NmeaParser,
+    sentence: *const c_char,
+    len: usize,
+) -> NmeaStatus {
+    let (Some(parser), false) = (parser.as_mut(), sentence.is_null()) else {
+        return NmeaStatus::NullPointer;
+    };
+    let bytes = slice::from_raw_parts(sentence.cast::<u8>(), len);
+    let Ok(sentence) = core::str::from_utf8(bytes) else {
+        return NmeaStatus::Encoding;
+    };
+    match parser.0.parse(sentence) {
+        Ok(_) => NmeaStatus::Ok,
+        Err(error) => (&error).into(),
+    }
+}
+
+/// Copies the last fix into `fix`.
+///
+/// Returns `false` if any of the pointers is null.
+///
+/// # Safety
+///
+/// `parser` must be null or returned by [`nmea_parser_new()`] and `fix`
+/// must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn nmea_get_fix(parser: *const NmeaParser, fix: *mut NmeaFix) -> bool {
+    let Some(parser) = parser.as_ref() else {
+        return false;
+    };
+    if fix.is_null() {
+        return false;
+    }
+    let f = parser.0.fix();
+    fix.write(NmeaFix {
+        has_time: f.fix_time.is_some(),
+        hour: f.fix_time.map_or(0, |t| t.hour() as u8),
+        minute: f.fix_time.map_or(0, |t| t.minute() as u8),
+        second: f.fix_time.map_or(0, |t| t.second() as u8),
+        nanosecond: f.fix_time.map_or(0, |t| t.nanosecond()),
+        has_date: f.fix_date.is_some(),
+        year: f.fix_date.map_or(0, |d| d.year()),
+        month: f.fix_date.map_or(0, |d| d.month() as u8),
+        day: f.fix_date.map_or(0, |d| d.day() as u8),
+        fix_type: f.fix_type.into(),
+        latitude: f.latitude.map_or(f64::NAN, f64::from),
+        longitude: f.longitude.map_or(f64::NAN, f64::from),
+        altitude: f.altitude_msl.unwrap_or(f32::NAN),
+        speed_over_ground: f.speed_over_ground.unwrap_or(f32::NAN),
+        true_course: f.true_course.unwrap_or(f32::NAN),
+        fix_satellites: f.fix_satellites.map_or(-1, |n| n as i32),
+        hdop: f.hdop.unwrap_or(f32::NAN),
+        vdop: f.vdop.unwrap_or(f32::NAN),
+        pdop: f.pdop.unwrap_or(f32::NAN),
+        geoid_separation: f.geoid_separation.unwrap_or(f32::NAN),
+    });
+    true
+}
+
+/// Copies up to `capacity` satellites in view into `satellites`.
+///
+/// Returns the number of satellites written.
+///
+/// # Safety
+///
+/// `parser` must be null or returned by [`nmea_parser_new()`] and
+/// `satellites` must be null or valid for writes of `capacity` elements.
+#[no_mangle]
+pub unsafe extern "C" fn nmea_get_satellites(
+    parser: *const NmeaParser,
+    satellites: *mut NmeaSatellite,
+    capacity: usize,
+) -> usize {
+    let Some(parser) = parser.as_ref() else {
+        return 0;
+    };
+    if satellites.is_null() {
+        return 0;
+    }
+    let mut count = 0;
+    for sat in parser.0.satellites().iter().take(capacity) {
+        satellites.add(count).write(NmeaSatellite {
+            gnss_type: sat.gnss_type() as u8,
+            prn: sat.prn(),
+            elevation: sat.elevation().unwrap_or(f32::NAN),
+            azimuth: sat.azimuth().unwrap_or(f32::NAN),
+            snr: sat.snr().unwrap_or(f32::NAN),
+        });
+        count += 1;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+
+    use super::*;
+
+    #[test]
+    fn test_null_pointers() {
+        unsafe {
+            assert!(nmea_parser_new(ptr::null_mut(), 0).is_null());
+            assert_eq!(
+                nmea_parse_sentence(ptr::null_mut(), ptr::null(), 0),
+                NmeaStatus::NullPointer
+            );
+            assert!(!nmea_get_fix(ptr::null(), ptr::null_mut()));
+            assert_eq!(nmea_get_satellites(ptr::null(), ptr::null_mut(), 1), 0);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "GSV"))]
+    fn test_parse_and_get_fix() {
+        let mut storage = MaybeUninit::<NmeaParser>::uninit();
+        unsafe {
+            let parser = nmea_parser_new(storage.as_mut_ptr().cast(), nmea_parser_size());
+            assert!(!parser.is_null());
+
+            let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+            assert_eq!(
+                nmea_parse_sentence(parser, gga.as_ptr().cast(), gga.len()),
+                NmeaStatus::Ok
+            );
+            let gsv = "$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70";
+            assert_eq!(
+                nmea_parse_sentence(parser, gsv.as_ptr().cast(), gsv.len()),
+                NmeaStatus::Ok
+            );
+            let bad = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*77";
+            assert_eq!(
+                nmea_parse_sentence(parser, bad.as_ptr().cast(), bad.len()),
+                NmeaStatus::ChecksumMismatch
+            );
+
+            let mut fix = MaybeUninit::<NmeaFix>::uninit();
+            assert!(nmea_get_fix(parser, fix.as_mut_ptr()));
+            let fix = fix.assume_init();
+            assert!(fix.has_time);
+            assert_eq!((fix.hour, fix.minute, fix.second), (9, 27, 50));
+            assert!(!fix.has_date);
+            assert_eq!(fix.fix_type, NmeaFixType::Gps);
+            assert_eq!(fix.fix_satellites, 8);
+            assert_eq!(fix.altitude, 61.7);
+            assert!(fix.speed_over_ground.is_nan());
+
+            let mut sats = [MaybeUninit::<NmeaSatellite>::uninit(); 8];
+            let n = nmea_get_satellites(parser, sats.as_mut_ptr().cast(), sats.len());
+            assert_eq!(n, 4);
+            assert_eq!(sats[0].assume_init().prn, 5);
+        }
+    }
+}
@@ -0,0 +1,493 @@
+//! A small constant velocity Kalman filter smoothing the fixes of [`Nmea`](crate::Nmea).
+//!
+//! [`PositionFilter`] is fed with [`Fix`] snapshots: the position, with the
+//! standard deviations of a [`GstData`] when available or else the HDOP,
+//! and the speed and course over ground. It estimates the position and the
+//! velocity with their uncertainty, for receivers whose fixes jump around
+//! by a few meters, without a full inertial navigation crate.
+//!
+//! The north and east axes are filtered separately, in meters around the
+//! estimated position, so the correlation of the error ellipse is dropped.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use chrono::NaiveTime;
+
+use crate::{
+    sentences::{FixType, GstData},
+    Fix, Float,
+};
+
+const EARTH_RADIUS: Float = 6_371_008.8;
+
+/// Standard deviation of the velocity before the first speed, in m/s.
+const INITIAL_VELOCITY_SD: f32 = 50.;
+
+#[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+fn meters(meters: Float) -> f32 {
+    meters as f32
+}
+
+/// Position and velocity estimated by a [`PositionFilter`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FilterEstimate {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub time: NaiveTime,
+    pub latitude: Float,
+    pub longitude: Float,
+    /// Velocity towards the north in m/s
+    pub velocity_north: f32,
+    /// Velocity towards the east in m/s
+    pub velocity_east: f32,
+    /// Standard deviation of the horizontal position in meters
+    pub position_sd: f32,
+    /// Standard deviation of the horizontal velocity in m/s
+    pub velocity_sd: f32,
+}
+
+impl FilterEstimate {
+    /// Returns the speed over ground in m/s.
+    pub fn speed(&self) -> f32 {
+        self.velocity_north.hypot(self.velocity_east)
+    }
+
+    /// Returns the course over ground in `[0, 360)` degrees True.
+    pub fn course(&self) -> f32 {
+        crate::angle::normalize(self.velocity_east.atan2(self.velocity_north).to_degrees())
+    }
+}
+
+/// The velocity and covariance of one axis, in meters around the
+/// estimated position.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Axis {
+    velocity: f32,
+    /// Position variance
+    pp: f32,
+    /// Position and velocity covariance
+    pv: f32,
+    /// Velocity variance
+    vv: f32,
+}
+
+impl Axis {
+    fn new(variance: f32, velocity: Option<(f32, f32)>) -> Self {
+        let (velocity, vv) = velocity.unwrap_or((0., INITIAL_VELOCITY_SD * INITIAL_VELOCITY_SD));
+        Self {
+            velocity,
+            pp: variance,
+            pv: 0.,
+            vv,
+        }
+    }
+
+    /// Moves the state `dt` seconds ahead with a white noise acceleration
+    /// of variance `q` and returns the position change.
+    fn predict(&mut self, dt: f32, q: f32) -> f32 {
+        let dt2 = dt * dt;
+        self.pp += 2. * dt * self.pv + dt2 * self.vv + q * dt2 * dt2 / 4.;
+        self.pv += dt * self.vv + q * dt2 * dt / 2.;
+        self.vv += q * dt2;
+        self.velocity * dt
+    }
+
+    /// Corrects the state with a position `offset` from the estimate of
+    /// variance `r`, and returns the position correction.
+    fn correct_position(&mut self, offset: f32, r: f32) -> f32 {
+        let s = self.pp + r;
+        let (kp, kv) = (self.pp / s, self.pv / s);
+        self.velocity += kv * offset;
+        // (I - KH)P, with the factors of r / s, which keep it positive
+        self.vv -= kv * self.pv;
+        self.pp *= r / s;
+        self.pv *= r / s;
+        kp * offset
+    }
+
+    /// Corrects the state with a velocity of variance `r`, and returns the
+    /// position correction.
+    fn correct_velocity(&mut self, velocity: f32, r: f32) -> f32 {
+        let s = self.vv + r;
+        let (kp, kv) = (self.pv / s, self.vv / s);
+        let innovation = velocity - self.velocity;
+        self.velocity += kv * innovation;
+        self.pp -= kp * self.pv;
+        self.pv *= r / s;
+        self.vv *= r / s;
+        kp * innovation
+    }
+}
+
+/// Constant velocity filter of the fixes, see the [module documentation](self).
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{filter::PositionFilter, Nmea};
+///
+/// let mut nmea = Nmea::default();
+/// let mut filter = PositionFilter::new().acceleration_sd(0.5);
+/// # #[cfg(feature = "RMC")]
+/// # {
+/// for sentence in [
+///     "$GPRMC,225446.00,A,4916.450,N,12311.120,W,000.0,054.7,191194,020.3,E*43",
+///     "$GPRMC,225447.00,A,4916.451,N,12311.119,W,000.0,054.7,191194,020.3,E*49",
+/// ] {
+///     nmea.parse(sentence).unwrap();
+///     filter.update(&nmea.fix(), None);
+/// }
+/// let estimate = filter.estimate().unwrap();
+/// assert!(estimate.speed() < 0.5);
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionFilter {
+    acceleration_sd: f32,
+    uere: f32,
+    speed_sd: f32,
+    max_gap: f32,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    time: Option<NaiveTime>,
+    latitude: Float,
+    longitude: Float,
+    north: Axis,
+    east: Axis,
+}
+
+impl Default for PositionFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PositionFilter {
+    /// A filter with an acceleration of 1 m/s², an UERE of 5 m, a speed
+    /// error of 0.2 m/s and a maximum gap of 10 s.
+    pub const fn new() -> Self {
+        Self {
+            acceleration_sd: 1.,
+            uere: 5.,
+            speed_sd: 0.2,
+            max_gap: 10.,
+            time: None,
+            latitude: 0.,
+            longitude: 0.,
+            north: Axis {
+                velocity: 0.,
+                pp: 0.,
+                pv: 0.,
+                vv: 0.,
+            },
+            east: Axis {
+                velocity: 0.,
+                pp: 0.,
+                pv: 0.,
+                vv: 0.,
+            },
+        }
+    }
+
+    /// Sets the standard deviation of the accelerations of the vehicle, in
+    /// m/s². Lower values smooth more, but lag behind maneuvers.
+    pub const fn acceleration_sd(mut self, acceleration_sd: f32) -> Self {
+        self.acceleration_sd = acceleration_sd;
+        self
+    }
+
+    /// Sets the User Equivalent Range Error in meters, the position
+    /// standard deviation per unit of HDOP used without GST.
+    pub const fn uere(mut self, uere: f32) -> Self {
+        self.uere = uere;
+        self
+    }
+
+    /// Sets the standard deviation of the speed over ground, in m/s.
+    pub const fn speed_sd(mut self, speed_sd: f32) -> Self {
+        self.speed_sd = speed_sd;
+        self
+    }
+
+    /// Sets the longest time between fixes, in seconds, after which the
+    /// filter starts over.
+    pub const fn max_gap(mut self, max_gap: f32) -> Self {
+        self.max_gap = max_gap;
+        self
+    }
+
+    /// Forgets the estimate.
+    pub fn reset(&mut self) {
+        self.time = None;
+    }
+
+    /// Returns the current estimate, `None` before the first fix.
+    pub fn estimate(&self) -> Option<FilterEstimate> {
+        Some(FilterEstimate {
+            time: self.time?,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            velocity_north: self.north.velocity,
+            velocity_east: self.east.velocity,
+            position_sd: (self.north.pp + self.east.pp).sqrt(),
+            velocity_sd: (self.north.vv + self.east.vv).sqrt(),
+        })
+    }
+
+    /// Updates the estimate with a fix, and the GST sentence of the same
+    /// epoch if any, and returns it.
+    ///
+    /// Fixes without a valid position or time are ignored. Fixes older than
+    /// the estimate, or after a gap longer than the
+    /// [maximum](Self::max_gap), restart the filter.
+    pub fn update(&mut self, fix: &Fix, gst: Option<&GstData>) -> Option<FilterEstimate> {
+        let valid = !matches!(fix.fix_type, None | Some(FixType::Invalid));
+        let (Some(time), Some(latitude), Some(longitude), true) =
+            (fix.fix_time, fix.latitude, fix.longitude, valid)
+        else {
+            return self.estimate();
+        };
+        let (north_variance, east_variance) = match gst.and_then(GstData::horizontal_covariance) {
+            Some([[north, _], [_, east]]) => (north, east),
+            None => {
+                let sd = fix.hdop.unwrap_or(2.) * self.uere;
+                (sd * sd, sd * sd)
+            }
+        };
+        let velocity = match (fix.speed_over_ground, fix.true_course) {
+            (Some(knots), Some(course)) => {
+                let speed = knots * 1852. / 3600.;
+                let (sin, cos) = course.to_radians().sin_cos();
+                Some((speed * cos, speed * sin))
+            }
+            _ => None,
+        };
+        let velocity_variance = self.speed_sd * self.speed_sd;
+
+        let dt = self.time.map(|last| {
+            let mut seconds = (time - last).num_milliseconds() as f32 / 1000.;
+            // across midnight
+            if seconds < -43_200. {
+                seconds += 86_400.;
+            }
+            seconds
+        });
+        match dt {
+            Some(dt) if (0. ..=self.max_gap).contains(&dt) => {}
+            _ => {
+                self.time = Some(time);
+                self.latitude = latitude;
+                self.longitude = longitude;
+                let variance = |velocity| (velocity, velocity_variance);
+                self.north = Axis::new(north_variance, velocity.map(|(north, _)| variance(north)));
+                self.east = Axis::new(east_variance, velocity.map(|(_, east)| variance(east)));
+                return self.estimate();
+            }
+        }
+        let dt = dt.unwrap_or_default();
+        self.time = Some(time);
+        let q = self.acceleration_sd * self.acceleration_sd;
+        let mut north = self.north.predict(dt, q);
+        let mut east = self.east.predict(dt, q);
+
+        // the offsets of the measurement from the predicted position
+        let radius_east = EARTH_RADIUS * self.latitude.to_radians().cos();
+        let predicted_latitude = self.latitude + (Float::from(north) / EARTH_RADIUS).to_degrees();
+        let predicted_longitude = self.longitude + (Float::from(east) / radius_east).to_degrees();
+        let north_offset = meters((latitude - predicted_latitude).to_radians() * EARTH_RADIUS);
+        let east_offset =
+            meters(longitude_difference(predicted_longitude, longitude).to_radians() * radius_east);
+        north += self.north.correct_position(north_offset, north_variance);
+        east += self.east.correct_position(east_offset, east_variance);
+        if let Some((velocity_north, velocity_east)) = velocity {
+            north += self
+                .north
+                .correct_velocity(velocity_north, velocity_variance);
+            east += self.east.correct_velocity(velocity_east, velocity_variance);
+        }
+
+        self.latitude += (Float::from(north) / EARTH_RADIUS).to_degrees();
+        self.longitude =
+            wrap_longitude(self.longitude + (Float::from(east) / radius_east).to_degrees());
+        self.estimate()
+    }
+}
+
+/// Returns the difference from one longitude to another in `(-180, 180]`
+/// degrees, so a track crossing the antimeridian moves by a few meters and
+/// not around the earth.
+fn longitude_difference(from: Float, to: Float) -> Float {
+    let difference = (to - from) % 360.;
+    if difference > 180. {
+        difference - 360.
+    } else if difference <= -180. {
+        difference + 360.
+    } else {
+        difference
+    }
+}
+
+/// Wraps a longitude to `(-180, 180]` degrees.
+fn wrap_longitude(longitude: Float) -> Float {
+    longitude_difference(0., longitude)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    /// Tolerance of the positions in meters, coordinates are rounded to
+    /// about 0.5 m with `f32-only`.
+    const POSITION_EPSILON: Float = if cfg!(feature = "f32-only") { 2. } else { 0.5 };
+
+    /// A fix `north` and `east` meters away from 48° N 2° E.
+    fn fix(seconds: u32, north: Float, east: Float) -> Fix {
+        let latitude: Float = 48.;
+        Fix {
+            fix_time: NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0),
+            fix_type: Some(FixType::Gps),
+            latitude: Some(latitude + (north / EARTH_RADIUS).to_degrees()),
+            longitude: Some(
+                2. + (east / (EARTH_RADIUS * latitude.to_radians().cos())).to_degrees(),
+            ),
+            hdop: Some(1.),
+            ..Fix::default()
+        }
+    }
+
+    /// Returns the offset of an estimate from 48° N 2° E in meters.
+    fn offset(estimate: &FilterEstimate) -> (Float, Float) {
+        let latitude: Float = 48.;
+        (
+            (estimate.latitude - latitude).to_radians() * EARTH_RADIUS,
+            (estimate.longitude - 2.).to_radians() * EARTH_RADIUS * latitude.to_radians().cos(),
+        )
+    }
+
+    #[test]
+    fn test_stationary() {
+        let mut filter = PositionFilter::new().acceleration_sd(0.1);
+        let first = filter.update(&fix(0, 5., -5.), None).unwrap();
+        // the HDOP times the UERE on both axes
+        assert_relative_eq!(first.position_sd, 50_f32.sqrt(), epsilon = 1e-4);
+        assert_eq!(first.velocity_sd, 50. * 2_f32.sqrt());
+
+        // fixes jumping 5 m around the position
+        let mut estimate = first;
+        for second in 1..60 {
+            let sign = if second % 2 == 0 { 1. } else { -1. };
+            estimate = filter
+                .update(&fix(second, 5. * sign, -5. * sign), None)
+                .unwrap();
+        }
+        let (north, east) = offset(&estimate);
+        assert!(
+            north.abs() < POSITION_EPSILON && east.abs() < POSITION_EPSILON,
+            "{} {}",
+            north,
+            east
+        );
+        assert!(estimate.position_sd < first.position_sd / 2.);
+        assert!(estimate.speed() < 0.5);
+    }
+
+    #[test]
+    fn test_moving() {
+        let mut filter = PositionFilter::new();
+        // 10 m/s towards the north east, with the speed and course
+        let speed = 10. * 3600. / 1852.;
+        let mut estimate = None;
+        for second in 0..20 {
+            let distance = second as Float * 10. / Float::from(2_u8).sqrt();
+            let fix = Fix {
+                speed_over_ground: Some(speed),
+                true_course: Some(45.),
+                ..fix(second, distance, distance)
+            };
+            estimate = filter.update(&fix, None);
+        }
+        let estimate = estimate.unwrap();
+        assert_relative_eq!(estimate.speed(), 10., epsilon = 1e-2);
+        assert_relative_eq!(estimate.course(), 45., epsilon = 1e-1);
+        let (north, east) = offset(&estimate);
+        assert_relative_eq!(north, 134.35, epsilon = POSITION_EPSILON);
+        assert_relative_eq!(east, 134.35, epsilon = POSITION_EPSILON);
+    }
+
+    #[test]
+    fn test_gst_and_restart() {
+        let gst = GstData {
+            ellipse_semi_major_sd: Some(0.4),
+            ellipse_semi_minor_sd: Some(0.3),
+            err_ellipse_orientation: Some(0.),
+            ..GstData::default()
+        };
+        let mut filter = PositionFilter::new();
+        let estimate = filter.update(&fix(0, 0., 0.), Some(&gst)).unwrap();
+        assert_relative_eq!(estimate.position_sd, 0.5, epsilon = 1e-6);
+
+        // invalid fixes are ignored
+        let invalid = Fix {
+            fix_type: Some(FixType::Invalid),
+            ..fix(1, 100., 100.)
+        };
+        assert_eq!(filter.update(&invalid, None), Some(estimate));
+
+        // after a gap, the filter starts over at the fix
+        let estimate = filter.update(&fix(11, 100., 0.), None).unwrap();
+        assert_relative_eq!(offset(&estimate).0, 100., epsilon = POSITION_EPSILON);
+        assert_eq!(estimate.velocity_north, 0.);
+        filter.reset();
+        assert_eq!(filter.estimate(), None);
+    }
+
+    #[test]
+    fn test_antimeridian() {
+        let mut filter = PositionFilter::new();
+        // 10 m/s towards the east on the equator, crossing 180° after 10 s
+        let step = (10. / EARTH_RADIUS).to_degrees();
+        let mut estimate = None;
+        for second in 0..20 {
+            let longitude = wrap_longitude(180. + (second as Float - 10.) * step);
+            let fix = Fix {
+                fix_time: NaiveTime::from_num_seconds_from_midnight_opt(second, 0),
+                fix_type: Some(FixType::Gps),
+                latitude: Some(0.),
+                longitude: Some(longitude),
+                hdop: Some(1.),
+                speed_over_ground: Some(10. * 3600. / 1852.),
+                true_course: Some(90.),
+                ..Fix::default()
+            };
+            estimate = filter.update(&fix, None);
+        }
+        let estimate = estimate.unwrap();
+        assert_relative_eq!(estimate.speed(), 10., epsilon = 1e-2);
+        assert_relative_eq!(estimate.course(), 90., epsilon = 1e-1);
+        let east = (estimate.longitude + 180.).to_radians() * EARTH_RADIUS;
+        // an `f32` longitude near 180° is only precise to about 2 m
+        let epsilon = if cfg!(feature = "f32-only") {
+            5.
+        } else {
+            POSITION_EPSILON
+        };
+        assert_relative_eq!(east, 90., epsilon = epsilon);
+    }
+
+    #[test]
+    fn test_longitude_difference() {
+        assert_eq!(longitude_difference(179., -179.), 2.);
+        assert_eq!(longitude_difference(-179., 179.), -2.);
+        assert_eq!(longitude_difference(10., 20.), 10.);
+        assert_eq!(wrap_longitude(-180.), 180.);
+        assert_eq!(wrap_longitude(190.), -170.);
+    }
+}
@@ -0,0 +1,86 @@
+//! A copyable snapshot of the navigation state kept by [`Nmea`].
+
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::{
+    sentences::{Datum, FixType},
+    Float, Nmea, SignalCounts,
+};
+
+/// Position, velocity and quality values of the last fix.
+///
+/// Unlike [`Nmea`] itself, a `Fix` is small and `Copy`, so it can be handed
+/// over to other tasks or threads without holding on to the parser.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::Nmea;
+///
+/// let mut nmea = Nmea::default();
+/// # #[cfg(feature = "GGA")]
+/// # {
+/// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+///     .unwrap();
+/// let fix = nmea.fix();
+/// assert_eq!(fix.fix_satellites, Some(8));
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Fix {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub fix_time: Option<NaiveTime>,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub fix_date: Option<NaiveDate>,
+    pub fix_type: Option<FixType>,
+    pub latitude: Option<Float>,
+    pub longitude: Option<Float>,
+    /// Datum of the position, `None` if not reported by a DTM sentence, see
+    /// [`Nmea::position_datum()`]
+    pub datum: Option<Datum>,
+    /// Altitude above mean sea level in meters
+    pub altitude_msl: Option<f32>,
+    /// Height above the WGS-84 ellipsoid in meters
+    pub altitude_ellipsoidal: Option<f32>,
+    pub speed_over_ground: Option<f32>,
+    pub true_course: Option<f32>,
+    pub fix_satellites: Option<u32>,
+    pub hdop: Option<f32>,
+    pub vdop: Option<f32>,
+    pub pdop: Option<f32>,
+    /// Geoid separation in meters
+    pub geoid_separation: Option<f32>,
+    /// Satellites tracked per band over all constellations, see
+    /// [`Nmea::total_signal_counts()`]
+    pub signal_counts: SignalCounts,
+    /// Whether the positions of the sentences of the fix agree, `None` if
+    /// not checked, see [`Nmea::set_position_check()`]
+    pub position_consistent: Option<bool>,
+}
+
+impl Nmea {
+    /// Returns a snapshot of the current fix.
+    pub fn fix(&self) -> Fix {
+        Fix {
+            fix_time: self.fix_time,
+            fix_date: self.fix_date,
+            fix_type: self.fix_type,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            datum: self.position_datum(),
+            altitude_msl: self.altitude,
+            altitude_ellipsoidal: self.altitude_ellipsoidal(),
+            speed_over_ground: self.speed_over_ground,
+            true_course: self.true_course,
+            fix_satellites: self.num_of_fix_satellites,
+            hdop: self.hdop,
+            vdop: self.vdop,
+            pdop: self.pdop,
+            geoid_separation: self.geoid_separation,
+            signal_counts: self.total_signal_counts(),
+            position_consistent: self.position_spread().map(|spread| !spread.inconsistent),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Distances between positions and times between fixes, for the checks of
+//! the fixes.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use chrono::{NaiveDateTime, NaiveTime};
+
+use crate::Float;
+
+/// Mean radius of the Earth in meters
+pub(crate) const EARTH_RADIUS: Float = 6_371_008.8;
+
+/// Meters per second in a knot
+pub(crate) const METERS_PER_SECOND_PER_KNOT: f32 = 1852. / 3600.;
+
+/// Returns the great circle distance in meters between two positions given
+/// as latitude and longitude in degrees, with the haversine formula.
+pub(crate) fn distance(from: (Float, Float), to: (Float, Float)) -> Float {
+    let (from_latitude, to_latitude) = (from.0.to_radians(), to.0.to_radians());
+    let half_latitude = (to_latitude - from_latitude) / 2.;
+    let half_longitude = (to.1 - from.1).to_radians() / 2.;
+    let haversine = half_latitude.sin().powi(2)
+        + from_latitude.cos() * to_latitude.cos() * half_longitude.sin().powi(2);
+    2. * EARTH_RADIUS * haversine.sqrt().min(1.).asin()
+}
+
+/// Returns the seconds between two fix times, using their dates if both are
+/// known, else through midnight if the time goes more than 12 hours back.
+pub(crate) fn elapsed(
+    from: (NaiveTime, Option<NaiveDateTime>),
+    to: (NaiveTime, Option<NaiveDateTime>),
+) -> f32 {
+    let milliseconds = match (from.1, to.1) {
+        (Some(from), Some(to)) => (to - from).num_milliseconds(),
+        _ => {
+            let milliseconds = (to.0 - from.0).num_milliseconds();
+            if milliseconds < -43_200_000 {
+                milliseconds + 86_400_000
+            } else {
+                milliseconds
+            }
+        }
+    };
+    milliseconds as f32 / 1000.
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        // a minute of latitude is about a nautical mile
+        assert_relative_eq!(
+            distance((45., 10.), (45. + 1. / 60., 10.)),
+            1853.25,
+            epsilon = 1.
+        );
+        assert_relative_eq!(distance((0., 179.9), (0., -179.9)), 22_239., epsilon = 1.);
+        assert_eq!(distance((48.1, 11.5), (48.1, 11.5)), 0.);
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let time = |h, m, s| NaiveTime::from_hms_opt(h, m, s).unwrap();
+        assert_eq!(elapsed((time(12, 0, 0), None), (time(12, 0, 1), None)), 1.);
+        assert_eq!(elapsed((time(12, 0, 1), None), (time(12, 0, 0), None)), -1.);
+        // through midnight without date
+        assert_eq!(elapsed((time(23, 59, 59), None), (time(0, 0, 1), None)), 2.);
+        // with dates, a day later
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(
+            elapsed(
+                (time(12, 0, 0), Some(date.and_time(time(12, 0, 0)))),
+                (
+                    time(12, 0, 0),
+                    date.succ_opt().map(|date| date.and_time(time(12, 0, 0)))
+                )
+            ),
+            86_400.
+        );
+    }
+}
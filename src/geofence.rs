@@ -0,0 +1,366 @@
+//! Geofences checked on each new position, see [`Nmea::set_geofence()`].
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use heapless::Vec;
+
+use crate::{geo, FixEvent, Float, Nmea};
+
+/// Number of geofences of a [`Nmea`].
+pub const GEOFENCES_LEN: usize = 8;
+
+/// Number of vertices of a polygon [`Geofence`].
+pub const GEOFENCE_VERTICES_LEN: usize = 8;
+
+/// An area, with positions as latitude and longitude in degrees.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geofence {
+    /// The positions closer to the center than the radius in meters
+    Circle { center: (Float, Float), radius: f32 },
+    /// The positions inside a convex polygon, see [`Geofence::polygon()`]
+    Polygon(Vec<(Float, Float), GEOFENCE_VERTICES_LEN>),
+}
+
+impl Geofence {
+    /// Returns a circle of a radius in meters.
+    pub fn circle(latitude: Float, longitude: Float, radius: f32) -> Self {
+        Geofence::Circle {
+            center: (latitude, longitude),
+            radius,
+        }
+    }
+
+    /// Returns a convex polygon, `None` if it has less than 3 or more than
+    /// [`GEOFENCE_VERTICES_LEN`] vertices or is not convex.
+    ///
+    /// The vertices may be clockwise or counterclockwise. The edges are
+    /// straight in latitude and longitude, so they should be short enough
+    /// for the curvature of the Earth not to matter, but they may cross the
+    /// antimeridian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Geofence;
+    ///
+    /// let square = [(48., 11.), (48., 12.), (49., 12.), (49., 11.)];
+    /// assert!(Geofence::polygon(&square).unwrap().contains(48.5, 11.5));
+    /// // a bow tie
+    /// assert_eq!(Geofence::polygon(&[(48., 11.), (49., 12.), (48., 12.), (49., 11.)]), None);
+    /// ```
+    pub fn polygon(vertices: &[(Float, Float)]) -> Option<Self> {
+        if vertices.len() < 3 {
+            return None;
+        }
+        let vertices = Vec::from_slice(vertices).ok()?;
+        let origin = vertices[0];
+        let mut sign = 0.;
+        let mut turning = 0.;
+        for (i, vertex) in vertices.iter().enumerate() {
+            let next = vertices[(i + 1) % vertices.len()];
+            let after = vertices[(i + 2) % vertices.len()];
+            let (a, b, c) = (
+                planar(origin, *vertex),
+                planar(origin, next),
+                planar(origin, after),
+            );
+            let (edge, next_edge) = ((b.0 - a.0, b.1 - a.1), (c.0 - b.0, c.1 - b.1));
+            let turn = cross(edge, next_edge);
+            if turn == 0. || turn * sign < 0. {
+                return None;
+            }
+            sign = turn;
+            turning += turn.atan2(edge.0 * next_edge.0 + edge.1 * next_edge.1);
+        }
+        // a star turns the same way at each vertex, but by 4π or more
+        // instead of 2π
+        if turning.abs() > 10. {
+            return None;
+        }
+        Some(Geofence::Polygon(vertices))
+    }
+
+    /// Returns whether a position is inside the area, including its border.
+    pub fn contains(&self, latitude: Float, longitude: Float) -> bool {
+        match self {
+            Geofence::Circle { center, radius } => {
+                #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+                let radius = *radius as Float;
+                geo::distance(*center, (latitude, longitude)) <= radius
+            }
+            Geofence::Polygon(vertices) => {
+                let mut sign = 0.;
+                for (i, vertex) in vertices.iter().enumerate() {
+                    let next = vertices[(i + 1) % vertices.len()];
+                    let side = cross(
+                        planar((latitude, longitude), *vertex),
+                        planar((latitude, longitude), next),
+                    );
+                    if side * sign < 0. {
+                        return false;
+                    }
+                    if side != 0. {
+                        sign = side;
+                    }
+                }
+                true
+            }
+        }
+    }
+}
+
+/// Returns a position in degrees of latitude East and North of an origin,
+/// on the plane tangent at the origin.
+fn planar(origin: (Float, Float), position: (Float, Float)) -> (Float, Float) {
+    let mut longitude = position.1 - origin.1;
+    if longitude > 180. {
+        longitude -= 360.;
+    } else if longitude < -180. {
+        longitude += 360.;
+    }
+    (
+        longitude * origin.0.to_radians().cos(),
+        position.0 - origin.0,
+    )
+}
+
+fn cross(a: (Float, Float), b: (Float, Float)) -> Float {
+    a.0 * b.1 - a.1 * b.0
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+struct Fence {
+    id: u16,
+    geofence: Geofence,
+    /// Whether the last position was inside, `None` before the first one
+    inside: Option<bool>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct Geofences {
+    fences: Vec<Fence, GEOFENCES_LEN>,
+}
+
+impl Geofences {
+    /// The geofences after a reset, forgetting the positions.
+    pub(crate) fn cleared(&self) -> Self {
+        let mut geofences = self.clone();
+        for fence in geofences.fences.iter_mut() {
+            fence.inside = None;
+        }
+        geofences
+    }
+}
+
+impl Nmea {
+    /// Sets or replaces a geofence.
+    ///
+    /// Each valid fix of GGA, RMC, GNS or GLL is checked against the
+    /// geofences and queues a [`FixEvent::GeofenceEnter`] or
+    /// [`FixEvent::GeofenceExit`] when it crosses them. The first fix inside
+    /// a geofence enters it, the first one outside doesn't exit it.
+    ///
+    /// Returns the ID back if there are already [`GEOFENCES_LEN`]
+    /// geofences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{FixEvent, Geofence, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_geofence(1, Geofence::circle(53.36, -6.5, 1000.)).unwrap();
+    /// # #[cfg(feature = "GGA")]
+    /// # {
+    /// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///     .unwrap();
+    /// assert!(matches!(
+    ///     nmea.pop_fix_event(),
+    ///     Some(FixEvent::GeofenceEnter { id: 1, .. })
+    /// ));
+    /// assert_eq!(nmea.inside_geofence(1), Some(true));
+    /// # }
+    /// ```
+    pub fn set_geofence(&mut self, id: u16, geofence: Geofence) -> Result<(), u16> {
+        let fence = Fence {
+            id,
+            geofence,
+            inside: None,
+        };
+        let fences = &mut self.geofences.fences;
+        match fences.iter_mut().find(|fence| fence.id == id) {
+            Some(existing) => {
+                *existing = fence;
+                Ok(())
+            }
+            None => fences.push(fence).map_err(|fence| fence.id),
+        }
+    }
+
+    /// Removes a geofence.
+    pub fn remove_geofence(&mut self, id: u16) {
+        self.geofences.fences.retain(|fence| fence.id != id);
+    }
+
+    /// Returns the IDs and the geofences.
+    pub fn geofences(&self) -> impl Iterator<Item = (u16, &Geofence)> + '_ {
+        self.geofences
+            .fences
+            .iter()
+            .map(|fence| (fence.id, &fence.geofence))
+    }
+
+    /// Returns whether the last valid position was inside a geofence,
+    /// `None` if there is no such geofence or position.
+    pub fn inside_geofence(&self, id: u16) -> Option<bool> {
+        self.geofences
+            .fences
+            .iter()
+            .find(|fence| fence.id == id)?
+            .inside
+    }
+
+    /// Checks the position which was just stored against the geofences.
+    pub(crate) fn check_geofences(&mut self) {
+        if !self.fix_type.is_some_and(|fix_type| fix_type.is_valid()) {
+            return;
+        }
+        let (Some(latitude), Some(longitude)) = (self.latitude, self.longitude) else {
+            return;
+        };
+        for fence in self.geofences.fences.iter_mut() {
+            let inside = fence.geofence.contains(latitude, longitude);
+            let event = match (fence.inside, inside) {
+                (Some(false) | None, true) => FixEvent::GeofenceEnter {
+                    id: fence.id,
+                    fix_time: self.fix_time,
+                },
+                (Some(true), false) => FixEvent::GeofenceExit {
+                    id: fence.id,
+                    fix_time: self.fix_time,
+                },
+                _ => {
+                    fence.inside = Some(inside);
+                    continue;
+                }
+            };
+            fence.inside = Some(inside);
+            self.fix_events.push(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geofence_contains() {
+        let circle = Geofence::circle(48., 11., 1000.);
+        assert!(circle.contains(48.008, 11.));
+        assert!(!circle.contains(48.01, 11.));
+
+        // counterclockwise triangle across the antimeridian
+        let triangle = Geofence::polygon(&[(-1., 179.), (1., -179.), (1., 179.)]).unwrap();
+        assert!(triangle.contains(0.5, 179.5));
+        assert!(triangle.contains(0.9, -179.9));
+        assert!(!triangle.contains(-0.5, 179.8));
+        assert!(!triangle.contains(0., 178.));
+        // on a vertex
+        assert!(triangle.contains(1., 179.));
+
+        assert_eq!(Geofence::polygon(&[(0., 0.), (1., 1.)]), None);
+        // collinear vertices
+        assert_eq!(Geofence::polygon(&[(0., 0.), (1., 1.), (2., 2.)]), None);
+        // a pentagram
+        assert_eq!(
+            Geofence::polygon(&[
+                (0., 1.),
+                (0.588, -0.809),
+                (-0.951, 0.309),
+                (0.951, 0.309),
+                (-0.588, -0.809)
+            ]),
+            None
+        );
+        // concave
+        assert_eq!(
+            Geofence::polygon(&[(0., 0.), (0., 2.), (2., 2.), (1., 1.), (2., 0.)]),
+            None
+        );
+        assert_eq!(
+            Geofence::polygon(&[(0., 0.); GEOFENCE_VERTICES_LEN + 1]),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "GLL")]
+    fn test_geofence_events() {
+        let mut nmea = Nmea::default();
+        let square = [(49., 11.), (49., 12.), (50., 12.), (50., 11.)];
+        nmea.set_geofence(7, Geofence::polygon(&square).unwrap())
+            .unwrap();
+        for id in 0..GEOFENCES_LEN as u16 - 1 {
+            nmea.set_geofence(id, Geofence::circle(0., 0., 1.)).unwrap();
+        }
+        assert_eq!(
+            nmea.set_geofence(100, Geofence::circle(0., 0., 1.)),
+            Err(100)
+        );
+        // replaced
+        nmea.set_geofence(0, Geofence::circle(49.5, 11.5, 1.))
+            .unwrap();
+        assert_eq!(nmea.geofences().count(), GEOFENCES_LEN);
+
+        // outside at first, no event
+        nmea.parse("$GPGLL,4830.000,N,01130.000,E,120000.00,A,A*66")
+            .unwrap();
+        assert_eq!(nmea.pop_fix_event(), None);
+        assert_eq!(nmea.inside_geofence(7), Some(false));
+        nmea.parse("$GPGLL,4930.000,N,01130.000,E,120001.00,A,A*66")
+            .unwrap();
+        assert_eq!(
+            nmea.pop_fix_event(),
+            Some(FixEvent::GeofenceEnter {
+                id: 7,
+                fix_time: chrono::NaiveTime::from_hms_opt(12, 0, 1)
+            })
+        );
+        // the replaced circle, at its center
+        assert!(matches!(
+            nmea.pop_fix_event(),
+            Some(FixEvent::GeofenceEnter { id: 0, .. })
+        ));
+        assert_eq!(nmea.pop_fix_event(), None);
+        // an invalid fix is ignored
+        nmea.parse("$GPGLL,4830.000,N,01130.000,E,120002.00,V,N*7C")
+            .unwrap();
+        assert_eq!(nmea.pop_fix_event(), None);
+        nmea.parse("$GPGLL,4830.000,N,01130.000,E,120003.00,A,A*65")
+            .unwrap();
+        assert!(matches!(
+            nmea.pop_fix_event(),
+            Some(FixEvent::GeofenceExit { id: 7, .. })
+        ));
+        assert!(matches!(
+            nmea.pop_fix_event(),
+            Some(FixEvent::GeofenceExit { id: 0, .. })
+        ));
+
+        nmea.remove_geofence(7);
+        assert_eq!(nmea.inside_geofence(7), None);
+        nmea.reset();
+        assert_eq!(nmea.geofences().count(), GEOFENCES_LEN - 1);
+        // the positions are forgotten
+        assert_eq!(nmea.inside_geofence(0), None);
+    }
+}
@@ -0,0 +1,104 @@
+//! Cross-check of the satellites of GSV groups against their declared
+//! number of satellites in view.
+//!
+//! A corrupted GSV sentence whose fields shift by one usually still parses,
+//! but the number of satellites of its group no longer matches the
+//! satellites in view of the header. The check is made once a group is
+//! complete, see [`Nmea::gsv_count_stats()`].
+
+use crate::Nmea;
+
+/// Counts of the GSV groups checked against their declared satellites in
+/// view, see [`Nmea::gsv_count_stats()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GsvCountStats {
+    /// Number of complete GSV groups
+    pub groups: u32,
+    /// Number of complete groups whose satellites do not match their
+    /// satellites in view
+    pub mismatches: u32,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct GsvCountCheck {
+    pub(crate) reject: bool,
+    stats: GsvCountStats,
+}
+
+impl GsvCountCheck {
+    pub(crate) fn record(&mut self, consistent: bool) {
+        self.stats.groups = self.stats.groups.saturating_add(1);
+        if !consistent {
+            self.stats.mismatches = self.stats.mismatches.saturating_add(1);
+        }
+    }
+}
+
+impl Nmea {
+    /// Drops the complete GSV groups whose number of satellites does not
+    /// match their declared satellites in view, keeping the satellites of
+    /// the previous group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_reject_inconsistent_gsv(true);
+    /// # #[cfg(feature = "GSV")]
+    /// # {
+    /// // 3 satellites in view, but only 2 reported
+    /// nmea.parse("$GPGSV,1,1,03,01,40,083,46,02,17,308,41*7C").unwrap();
+    /// assert!(nmea.satellites().is_empty());
+    /// assert_eq!(nmea.gsv_count_stats().mismatches, 1);
+    /// # }
+    /// ```
+    pub fn set_reject_inconsistent_gsv(&mut self, reject: bool) {
+        self.gsv_check.reject = reject;
+    }
+
+    /// Returns the number of complete GSV groups and of the groups whose
+    /// satellites do not match their declared satellites in view.
+    pub fn gsv_count_stats(&self) -> GsvCountStats {
+        self.gsv_check.stats
+    }
+}
+
+#[cfg(all(test, feature = "GSV"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gsv_count_check() {
+        let mut nmea = Nmea::default();
+        nmea.parse("$GPGSV,1,1,02,01,40,083,46,02,17,308,41*7D")
+            .unwrap();
+        nmea.parse("$GPGSV,1,1,03,01,40,083,46,02,17,308,41*7C")
+            .unwrap();
+        // flagged, but kept
+        assert_eq!(nmea.satellites().len(), 2);
+        assert_eq!(
+            nmea.gsv_count_stats(),
+            GsvCountStats {
+                groups: 2,
+                mismatches: 1
+            }
+        );
+
+        nmea.set_reject_inconsistent_gsv(true);
+        nmea.parse("$GPGSV,1,1,03,01,40,083,46,02,17,308,41*7C")
+            .unwrap();
+        assert_eq!(nmea.gsv_count_stats().mismatches, 2);
+        // the satellites of the last consistent group
+        assert_eq!(nmea.satellites().len(), 2);
+        nmea.clear_satellites();
+        nmea.parse("$GPGSV,1,1,03,01,40,083,46,02,17,308,41*7C")
+            .unwrap();
+        assert!(nmea.satellites().is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+//! Selection of [`Nmea::heading`] among the heading sources of an epoch.
+//!
+//! A vessel may report its heading with HDT from a gyro compass, with VHW
+//! from a speed log, and the course over ground of RMC and VTG approximates
+//! it when under way. The heading of [`Nmea`] is the value of the first
+//! source in the [priority](Nmea::set_heading_priority) which reported one
+//! in the current epoch.
+//!
+//! The epochs follow the fix times, a heading expires once the fix time
+//! changed twice since it was reported, so that the next source takes over
+//! when the preferred one stops. The heading of an epoch may come before
+//! its fix, e.g. HDT before GGA and RMC, and is still used in the epoch.
+
+use chrono::NaiveTime;
+use heapless::Vec;
+
+use crate::{epoch::FixEpochs, Nmea};
+
+/// A source of [`Nmea::heading`].
+///
+/// HDG, with its magnetic sensor heading, is not parsed by the crate.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingSource {
+    /// The true heading of HDT
+    Hdt,
+    /// The true heading of VHW
+    Vhw,
+    /// The course over ground of RMC and VTG
+    Cog,
+}
+
+impl HeadingSource {
+    pub const COUNT: usize = 3;
+}
+
+/// The heading sources of an epoch, by priority.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HeadingSelector {
+    priority: Vec<HeadingSource, { HeadingSource::COUNT }>,
+    /// The last heading of each source, with the epoch it was reported in
+    values: [Option<(f32, u32)>; HeadingSource::COUNT],
+    source: Option<HeadingSource>,
+    epochs: FixEpochs,
+}
+
+impl Default for HeadingSelector {
+    fn default() -> Self {
+        Self {
+            priority: Vec::from_slice(&[HeadingSource::Hdt]).unwrap_or_default(),
+            values: [None; HeadingSource::COUNT],
+            source: None,
+            epochs: FixEpochs::default(),
+        }
+    }
+}
+
+impl HeadingSelector {
+    /// The selector of a new epoch, with the same priority.
+    pub(crate) fn restarted(&self) -> Self {
+        Self {
+            priority: self.priority.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Records the heading of a source at the current fix time and returns
+    /// the selected heading.
+    pub(crate) fn update(
+        &mut self,
+        source: HeadingSource,
+        heading: Option<f32>,
+        fix_time: Option<NaiveTime>,
+    ) -> Option<f32> {
+        let epoch = self.epochs.advance(fix_time);
+        self.values[source as usize] = heading.map(|heading| (heading, epoch));
+        let selected = self.priority.iter().find_map(|&source| {
+            let (heading, reported) = self.values[source as usize]?;
+            self.epochs.is_recent(reported).then_some((source, heading))
+        });
+        self.source = selected.map(|(source, _)| source);
+        selected.map(|(_, heading)| heading)
+    }
+}
+
+impl Nmea {
+    /// Sets the heading sources, from the most to the least preferred.
+    /// Sources not in the list are ignored, and repeated ones are only used
+    /// once.
+    ///
+    /// The default is HDT only.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{HeadingSource, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_heading_priority(&[HeadingSource::Hdt, HeadingSource::Vhw, HeadingSource::Cog]);
+    /// # #[cfg(all(feature = "VTG", feature = "VHW"))]
+    /// # {
+    /// nmea.parse("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48").unwrap();
+    /// assert_eq!(nmea.heading, Some(54.7));
+    /// assert_eq!(nmea.heading_source(), Some(HeadingSource::Cog));
+    ///
+    /// nmea.parse("$GPVHW,100.5,T,105.5,M,10.5,N,19.4,K*4F").unwrap();
+    /// assert_eq!(nmea.heading, Some(100.5));
+    /// assert_eq!(nmea.heading_source(), Some(HeadingSource::Vhw));
+    /// # }
+    /// ```
+    pub fn set_heading_priority(&mut self, priority: &[HeadingSource]) {
+        let mut sources = Vec::new();
+        for &source in priority {
+            if !sources.contains(&source) {
+                // there are no more sources than the capacity
+                let _ = sources.push(source);
+            }
+        }
+        self.heading_selector = HeadingSelector {
+            priority: sources,
+            ..HeadingSelector::default()
+        };
+        self.heading = None;
+    }
+
+    /// Returns the heading sources, from the most to the least preferred.
+    pub fn heading_priority(&self) -> &[HeadingSource] {
+        &self.heading_selector.priority
+    }
+
+    /// Returns the source of the current [`heading`](Self::heading).
+    pub fn heading_source(&self) -> Option<HeadingSource> {
+        self.heading_selector.source
+    }
+
+    pub(crate) fn update_heading(&mut self, source: HeadingSource, heading: Option<f32>) {
+        self.heading = self.heading_selector.update(source, heading, self.fix_time);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heading_selector() {
+        let mut selector = HeadingSelector::default();
+        assert_eq!(selector.update(HeadingSource::Cog, Some(10.), None), None);
+        assert_eq!(
+            selector.update(HeadingSource::Hdt, Some(20.), None),
+            Some(20.)
+        );
+
+        selector.priority =
+            Vec::from_slice(&[HeadingSource::Vhw, HeadingSource::Hdt, HeadingSource::Cog]).unwrap();
+        assert_eq!(selector.update(HeadingSource::Vhw, None, None), Some(20.));
+        assert_eq!(selector.source, Some(HeadingSource::Hdt));
+        assert_eq!(
+            selector.update(HeadingSource::Vhw, Some(30.), None),
+            Some(30.)
+        );
+        assert_eq!(selector.source, Some(HeadingSource::Vhw));
+
+        let mut selector = selector.restarted();
+        assert_eq!(
+            selector.update(HeadingSource::Cog, Some(10.), None),
+            Some(10.)
+        );
+        assert_eq!(selector.source, Some(HeadingSource::Cog));
+
+        // the HDT of each epoch comes before its RMC, and then stops
+        let time = |second| NaiveTime::from_hms_opt(12, 0, second);
+        selector.priority = Vec::from_slice(&[HeadingSource::Hdt, HeadingSource::Cog]).unwrap();
+        assert_eq!(
+            selector.update(HeadingSource::Hdt, Some(20.), time(0)),
+            Some(20.)
+        );
+        assert_eq!(
+            selector.update(HeadingSource::Cog, Some(11.), time(1)),
+            Some(20.)
+        );
+        assert_eq!(
+            selector.update(HeadingSource::Hdt, Some(21.), time(1)),
+            Some(21.)
+        );
+        assert_eq!(
+            selector.update(HeadingSource::Cog, Some(12.), time(2)),
+            Some(21.)
+        );
+        assert_eq!(
+            selector.update(HeadingSource::Cog, Some(13.), time(3)),
+            Some(13.)
+        );
+        assert_eq!(selector.source, Some(HeadingSource::Cog));
+    }
+
+    #[test]
+    #[cfg(all(feature = "HDT", feature = "RMC"))]
+    fn test_heading_fallback() {
+        let mut nmea = Nmea::default();
+        nmea.set_heading_priority(&[HeadingSource::Hdt, HeadingSource::Cog]);
+        nmea.parse("$GPHDT,274.07,T*03").unwrap();
+        assert_eq!(nmea.heading, Some(274.07));
+        let rmc = [
+            "$GPRMC,225446.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*46",
+            "$GPRMC,225447.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*47",
+        ];
+        for rmc in rmc {
+            nmea.parse(rmc).unwrap();
+        }
+        assert_eq!(nmea.heading, Some(54.7));
+        assert_eq!(nmea.heading_source(), Some(HeadingSource::Cog));
+    }
+}
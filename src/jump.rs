@@ -0,0 +1,305 @@
+//! Detection of the position jumps between consecutive fixes.
+//!
+//! Multipath and receiver glitches occasionally move the position by
+//! hundreds of meters for a single fix. The [`JumpFilter`] computes the
+//! speed implied by the distance from the previous fix and flags the fixes
+//! faster than physically possible with a [`FixEvent::PositionJump`], and
+//! optionally keeps the previous position instead.
+
+use chrono::{NaiveDateTime, NaiveTime};
+
+use crate::{geo, FixEvent, Float, Nmea};
+
+/// Maximum speed between consecutive fixes, see
+/// [`Nmea::set_jump_filter()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JumpFilter {
+    /// Maximum speed in knots
+    pub max_speed: f32,
+    /// Keep the previous position instead of a jumped one
+    pub reject: bool,
+    /// Number of consecutive rejected fixes after which the position is
+    /// accepted, so the filter recovers from a real jump, e.g. when the
+    /// receiver was moved while off
+    pub max_rejections: u8,
+}
+
+impl JumpFilter {
+    /// The filter for vessels, rejecting fixes faster than 500 knots.
+    pub const MARINE: Self = Self {
+        max_speed: 500.,
+        reject: true,
+        max_rejections: 5,
+    };
+}
+
+impl Default for JumpFilter {
+    fn default() -> Self {
+        Self::MARINE
+    }
+}
+
+/// A position jump, see [`FixEvent::PositionJump`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionJump {
+    /// Time of the jumped fix
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub fix_time: NaiveTime,
+    /// Distance from the previous position in meters
+    pub distance: f32,
+    /// Time since the previous position in seconds
+    pub elapsed: f32,
+    /// Implied speed in knots
+    pub speed: f32,
+    /// The previous position was kept
+    pub rejected: bool,
+}
+
+/// Counters of the [`JumpFilter`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JumpStats {
+    /// Fixes compared with the previous one
+    pub checked: u32,
+    /// Fixes faster than the maximum speed
+    pub jumps: u32,
+    /// Fixes whose position was rejected
+    pub rejected: u32,
+}
+
+/// The last accepted position.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Reference {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    fix_time: NaiveTime,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    fix_datetime: Option<NaiveDateTime>,
+    latitude: Float,
+    longitude: Float,
+    altitude: Option<f32>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct JumpState {
+    filter: Option<JumpFilter>,
+    reference: Option<Reference>,
+    /// Time of the last checked fix and whether it was rejected, for the
+    /// other sentences of the same fix
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    checked: Option<(NaiveTime, bool)>,
+    /// Consecutive rejected fixes
+    rejections: u8,
+    stats: JumpStats,
+}
+
+impl JumpState {
+    /// The state after a reset, keeping the filter.
+    pub(crate) fn cleared(&self) -> Self {
+        Self {
+            filter: self.filter,
+            ..Self::default()
+        }
+    }
+}
+
+impl Nmea {
+    /// Sets the maximum speed between consecutive fixes, `None` to disable
+    /// the check, see [`JumpFilter`].
+    ///
+    /// The positions of GGA, RMC, GNS and GLL are compared with the last
+    /// accepted position of a previous fix time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{FixEvent, JumpFilter, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_jump_filter(Some(JumpFilter::MARINE));
+    /// # #[cfg(feature = "GGA")]
+    /// # {
+    /// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///     .unwrap();
+    /// // a degree of latitude in a second
+    /// nmea.parse("$GPGGA,092751.000,5421.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*70")
+    ///     .unwrap();
+    /// assert!(matches!(
+    ///     nmea.pop_fix_event(),
+    ///     Some(FixEvent::PositionJump(jump)) if jump.rejected
+    /// ));
+    /// assert_eq!(nmea.latitude().map(|latitude| latitude.floor()), Some(53.));
+    /// assert_eq!(nmea.jump_stats().rejected, 1);
+    /// # }
+    /// ```
+    pub fn set_jump_filter(&mut self, filter: Option<JumpFilter>) {
+        self.jump.filter = filter;
+        self.jump.reference = None;
+        self.jump.checked = None;
+        self.jump.rejections = 0;
+    }
+
+    /// Returns the maximum speed between consecutive fixes.
+    pub fn jump_filter(&self) -> Option<JumpFilter> {
+        self.jump.filter
+    }
+
+    /// Returns the counters of the [`JumpFilter`].
+    pub fn jump_stats(&self) -> JumpStats {
+        self.jump.stats
+    }
+
+    /// Compares the position which was just stored with the previous one,
+    /// restoring the previous one if the jump is rejected.
+    pub(crate) fn check_jump(&mut self) {
+        let Some(filter) = self.jump.filter else {
+            return;
+        };
+        let (Some(fix_time), Some(latitude), Some(longitude)) =
+            (self.fix_time, self.latitude, self.longitude)
+        else {
+            return;
+        };
+        let current = Reference {
+            fix_time,
+            fix_datetime: self.fix_datetime,
+            latitude,
+            longitude,
+            altitude: self.altitude,
+        };
+        let Some(reference) = self.jump.reference else {
+            self.jump.reference = Some(current);
+            return;
+        };
+        if let Some((time, rejected)) = self.jump.checked {
+            if time == fix_time {
+                // another sentence of the same fix
+                if rejected {
+                    self.latitude = Some(reference.latitude);
+                    self.longitude = Some(reference.longitude);
+                    self.altitude = reference.altitude;
+                }
+                return;
+            }
+        }
+        let elapsed = geo::elapsed(
+            (reference.fix_time, reference.fix_datetime),
+            (fix_time, self.fix_datetime),
+        );
+        if elapsed == 0. {
+            return;
+        }
+        if elapsed < 0. {
+            // the time went back, e.g. a replayed log
+            self.jump.reference = Some(current);
+            return;
+        }
+        self.jump.stats.checked = self.jump.stats.checked.saturating_add(1);
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let distance = geo::distance(
+            (reference.latitude, reference.longitude),
+            (latitude, longitude),
+        ) as f32;
+        let speed = distance / elapsed / geo::METERS_PER_SECOND_PER_KNOT;
+        if speed <= filter.max_speed {
+            self.jump.checked = Some((fix_time, false));
+            self.jump.reference = Some(current);
+            self.jump.rejections = 0;
+            return;
+        }
+        let rejected = filter.reject && self.jump.rejections < filter.max_rejections;
+        self.jump.checked = Some((fix_time, rejected));
+        self.jump.stats.jumps = self.jump.stats.jumps.saturating_add(1);
+        if rejected {
+            self.jump.stats.rejected = self.jump.stats.rejected.saturating_add(1);
+            self.jump.rejections += 1;
+            self.latitude = Some(reference.latitude);
+            self.longitude = Some(reference.longitude);
+            self.altitude = reference.altitude;
+        } else {
+            self.jump.reference = Some(current);
+            self.jump.rejections = 0;
+        }
+        self.fix_events.push(FixEvent::PositionJump(PositionJump {
+            fix_time,
+            distance,
+            elapsed,
+            speed,
+            rejected,
+        }));
+    }
+}
+
+#[cfg(all(test, feature = "GGA", feature = "RMC"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jump_filter() {
+        let mut nmea = Nmea::default();
+        nmea.set_jump_filter(Some(JumpFilter {
+            max_rejections: 2,
+            ..JumpFilter::MARINE
+        }));
+        let rmc = |time: &str, latitude: &str| {
+            let body = format!(
+                "GPRMC,{},A,{},N,01131.000,E,022.4,084.4,230394,,,A",
+                time, latitude
+            );
+            let checksum = body.bytes().fold(0, |checksum, byte| checksum ^ byte);
+            format!("${}*{:02X}", body, checksum)
+        };
+        let parse = |nmea: &mut Nmea, time, latitude| {
+            nmea.parse(&rmc(time, latitude)).unwrap();
+            (nmea.latitude().unwrap(), nmea.pop_fix_event())
+        };
+        assert_eq!(parse(&mut nmea, "125400", "4807.000").1, None);
+        // 11 m in a second, about 21 knots
+        assert_eq!(parse(&mut nmea, "125401", "4807.006").1, None);
+        // 1.1 km in a second, rejected twice then accepted
+        let (latitude, event) = parse(&mut nmea, "125402", "4807.600");
+        assert!((latitude - (48. + 7.006 / 60.)).abs() < 1e-6);
+        let Some(FixEvent::PositionJump(jump)) = event else {
+            panic!("{:?}", event);
+        };
+        assert!((jump.distance - 1100.).abs() < 5., "{:?}", jump);
+        assert!((jump.speed - 2140.).abs() < 10., "{:?}", jump);
+        assert_eq!(jump.elapsed, 1.);
+        assert!(jump.rejected);
+        // the GGA of the same fix is rejected too, without another event
+        nmea.parse("$GPGGA,125402,4807.600,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47")
+            .unwrap();
+        assert!((nmea.latitude().unwrap() - (48. + 7.006 / 60.)).abs() < 1e-6);
+        assert_eq!(nmea.pop_fix_event(), None);
+        assert!(matches!(
+            parse(&mut nmea, "125403", "4807.606").1,
+            Some(FixEvent::PositionJump(PositionJump { rejected: true, .. }))
+        ));
+        let (latitude, event) = parse(&mut nmea, "125404", "4807.612");
+        assert!((latitude - (48. + 7.612 / 60.)).abs() < 1e-6);
+        assert!(matches!(
+            event,
+            Some(FixEvent::PositionJump(PositionJump {
+                rejected: false,
+                ..
+            }))
+        ));
+        assert_eq!(parse(&mut nmea, "125405", "4807.618").1, None);
+        assert_eq!(
+            nmea.jump_stats(),
+            JumpStats {
+                checked: 5,
+                jumps: 3,
+                rejected: 2
+            }
+        );
+    }
+}
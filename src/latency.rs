@@ -0,0 +1,175 @@
+//! Skew between the receipt of sentences, measured with the user's
+//! [`Clock`](crate::Clock), and the UTC time they carry.
+//!
+//! With a clock counting UTC, e.g. from the UNIX epoch, the skew is the
+//! latency of the serial path. With a monotonic clock it is a constant
+//! offset plus the latency, its variation still shows buffering.
+
+use crate::{sentence_table::SentenceTable, Nmea, ParseResult, SentenceType};
+use chrono::{NaiveTime, Timelike};
+
+/// Number of sentence types with a separate latency.
+const LATENCY_SENTENCES: usize = 8;
+
+const SECONDS_PER_DAY: f64 = 86_400.;
+
+/// Latency statistics, in seconds.
+///
+/// The smoothing follows the round-trip time estimation of TCP (RFC 6298).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Latency {
+    /// Skew of the last sentence
+    pub last: f64,
+    /// Exponentially smoothed skew
+    pub smoothed: f64,
+    /// Smoothed deviation of the skew from `smoothed`
+    pub jitter: f64,
+    /// Number of sentences measured
+    pub samples: u32,
+}
+
+impl Latency {
+    fn new(skew: f64) -> Self {
+        Self {
+            last: skew,
+            smoothed: skew,
+            jitter: skew.abs() / 2.,
+            samples: 1,
+        }
+    }
+
+    fn update(&mut self, skew: f64) {
+        self.jitter += ((skew - self.smoothed).abs() - self.jitter) / 4.;
+        self.smoothed += (skew - self.smoothed) / 8.;
+        self.last = skew;
+        self.samples = self.samples.saturating_add(1);
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct LatencyTable {
+    sentences: SentenceTable<Latency, LATENCY_SENTENCES>,
+    total: Option<Latency>,
+}
+
+impl LatencyTable {
+    fn update(&mut self, sentence_type: SentenceType, skew: f64) {
+        match self.total.as_mut() {
+            Some(total) => total.update(skew),
+            None => self.total = Some(Latency::new(skew)),
+        }
+        self.sentences.update_or_insert(
+            sentence_type,
+            |latency| latency.update(skew),
+            || Latency::new(skew),
+        );
+    }
+}
+
+/// Returns the UTC time of day carried by a sentence.
+fn sentence_time(parse_result: &ParseResult) -> Option<NaiveTime> {
+    match parse_result {
+        ParseResult::BWC(bwc) => bwc.fix_time,
+        ParseResult::GBS(gbs) => gbs.time,
+        ParseResult::GGA(gga) => gga.fix_time,
+        ParseResult::GLL(gll) => Some(gll.fix_time),
+        ParseResult::GNS(gns) => gns.fix_time,
+        ParseResult::GST(gst) => gst.time,
+        ParseResult::RMC(rmc) => rmc.fix_time,
+        ParseResult::ZDA(zda) => zda.utc_time,
+        _ => None,
+    }
+}
+
+/// Returns the skew in `[-12, 12)` hours between the receipt time, in
+/// seconds, and a UTC time of day.
+fn skew(received: f64, time: NaiveTime) -> f64 {
+    let time = f64::from(time.num_seconds_from_midnight()) + f64::from(time.nanosecond()) * 1e-9;
+    let skew = (received - time) % SECONDS_PER_DAY;
+    if skew >= SECONDS_PER_DAY / 2. {
+        skew - SECONDS_PER_DAY
+    } else if skew < -SECONDS_PER_DAY / 2. {
+        skew + SECONDS_PER_DAY
+    } else {
+        skew
+    }
+}
+
+impl Nmea {
+    /// Returns the skew between the receipt of all sentences carrying a UTC
+    /// time and that time, if a [`Clock`](crate::Clock) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use nmea::Nmea;
+    ///
+    /// // 09:27:50.25 UTC
+    /// fn utc() -> Duration {
+    ///     Duration::from_millis(1_700_000_000_000 / 86_400_000 * 86_400_000 + 34_070_250)
+    /// }
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_clock(&(utc as fn() -> Duration));
+    /// # #[cfg(feature = "GGA")]
+    /// # {
+    /// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///     .unwrap();
+    /// assert!((nmea.latency().unwrap().last - 0.25).abs() < 1e-3);
+    /// # }
+    /// ```
+    pub fn latency(&self) -> Option<Latency> {
+        self.latency.total
+    }
+
+    /// Returns the skew of one sentence type, see [`latency()`](Self::latency).
+    ///
+    /// Only the first 8 sentence types received are tracked.
+    pub fn sentence_latency(&self, sentence_type: SentenceType) -> Option<Latency> {
+        self.latency.sentences.get(sentence_type).copied()
+    }
+
+    pub(crate) fn update_latency(&mut self, parse_result: &ParseResult) {
+        let (Some(now), Some(time)) = (self.now(), sentence_time(parse_result)) else {
+            return;
+        };
+        self.latency
+            .update(parse_result.into(), skew(now.as_secs_f64(), time));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_skew() {
+        let time = NaiveTime::from_hms_milli_opt(23, 59, 59, 500).unwrap();
+        // received just after midnight
+        assert_relative_eq!(skew(SECONDS_PER_DAY * 3. + 0.25, time), 0.75);
+        assert_relative_eq!(skew(86_399., time), -0.5);
+    }
+
+    #[test]
+    fn test_latency_table() {
+        let mut table = LatencyTable::default();
+        table.update(SentenceType::GGA, 0.5);
+        table.update(SentenceType::RMC, 0.1);
+        table.update(SentenceType::GGA, 1.3);
+
+        let total = table.total.unwrap();
+        assert_eq!(total.samples, 3);
+        assert_relative_eq!(total.last, 1.3);
+        let gga = table.sentences.get(SentenceType::GGA).unwrap();
+        assert_eq!(gga.samples, 2);
+        assert_relative_eq!(gga.smoothed, 0.6);
+        assert_relative_eq!(gga.jitter, 0.3875);
+    }
+}
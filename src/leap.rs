@@ -0,0 +1,181 @@
+//! Leap second offset between UTC and GPS time, for conversions of fix
+//! times to GPS time and TAI.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+
+use crate::Nmea;
+
+/// GPS - UTC offset in seconds since 2017-01-01, used until a receiver
+/// reports it.
+pub const DEFAULT_GPS_UTC_OFFSET: i8 = 18;
+
+/// TAI - GPS offset in seconds, fixed since the GPS epoch.
+const TAI_GPS_OFFSET: i64 = 19;
+
+/// Leap second state: the GPS - UTC offset and an announced leap second.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use nmea::LeapSecondInfo;
+///
+/// let mut info = LeapSecondInfo::default();
+/// info.announce(NaiveDate::from_ymd_opt(2030, 1, 1).unwrap(), 1);
+///
+/// let utc = NaiveDate::from_ymd_opt(2029, 12, 31).unwrap().and_hms_opt(12, 0, 0).unwrap();
+/// assert_eq!(info.gps_time(utc), utc + chrono::Duration::seconds(18));
+/// let utc = NaiveDate::from_ymd_opt(2030, 1, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+/// assert_eq!(info.gps_time(utc), utc + chrono::Duration::seconds(19));
+/// assert_eq!(info.tai(utc), utc + chrono::Duration::seconds(38));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LeapSecondInfo {
+    /// GPS - UTC offset in seconds reported by the receiver or set by the
+    /// user
+    pub offset: Option<i8>,
+    /// Date of the next leap second, from its first second on, and the
+    /// change of the offset
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub pending: Option<(NaiveDate, i8)>,
+}
+
+impl LeapSecondInfo {
+    /// Announces a leap second taking effect at the start of `date`, with
+    /// `change` of `1` for an inserted second.
+    pub fn announce(&mut self, date: NaiveDate, change: i8) {
+        self.pending = Some((date, change));
+    }
+
+    /// Returns the GPS - UTC offset in seconds at a UTC time, using
+    /// [`DEFAULT_GPS_UTC_OFFSET`] if it is unknown.
+    pub fn offset_at(&self, utc: NaiveDateTime) -> i8 {
+        let offset = self.offset.unwrap_or(DEFAULT_GPS_UTC_OFFSET);
+        match self.pending {
+            Some((date, change)) if utc.date() >= date => offset.saturating_add(change),
+            _ => offset,
+        }
+    }
+
+    /// Converts a UTC time to GPS time.
+    pub fn gps_time(&self, utc: NaiveDateTime) -> NaiveDateTime {
+        utc + Duration::seconds(self.offset_at(utc).into())
+    }
+
+    /// Converts a UTC time to TAI.
+    pub fn tai(&self, utc: NaiveDateTime) -> NaiveDateTime {
+        self.gps_time(utc) + Duration::seconds(TAI_GPS_OFFSET)
+    }
+
+    /// Reads the offset from TXT sentences with `LEAP` followed by the
+    /// number of seconds, e.g. `LEAP SECONDS: 18`.
+    ///
+    /// An offset marked as pending, e.g. `LEAPSEC 19 (pending)`, is
+    /// announced for the next possible leap second after `today`, the start
+    /// of January or July, and ignored while the date is unknown.
+    pub(crate) fn update_from_text(&mut self, text: &str, today: Option<NaiveDate>) {
+        let Some(start) = text
+            .as_bytes()
+            .windows(4)
+            .position(|window| window.eq_ignore_ascii_case(b"LEAP"))
+        else {
+            return;
+        };
+        let rest = &text[start + 4..];
+        let Some(digits) = rest.find(|c: char| c.is_ascii_digit()) else {
+            return;
+        };
+        let end = rest[digits..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest.len(), |end| digits + end);
+        let Ok(offset) = rest[digits..end].parse::<i8>() else {
+            return;
+        };
+        if !contains_ignore_case(rest, "pending") {
+            // a passed announcement is now part of the offset
+            self.pending = None;
+            self.offset = Some(offset);
+            return;
+        }
+        let change = offset.saturating_sub(self.offset.unwrap_or(DEFAULT_GPS_UTC_OFFSET));
+        if let (Some(today), true) = (today, change != 0) {
+            let date = if today.month() <= 6 {
+                NaiveDate::from_ymd_opt(today.year(), 7, 1)
+            } else {
+                NaiveDate::from_ymd_opt(today.year() + 1, 1, 1)
+            };
+            if let Some(date) = date {
+                self.announce(date, change);
+            }
+        }
+    }
+}
+
+fn contains_ignore_case(text: &str, pattern: &str) -> bool {
+    text.as_bytes()
+        .windows(pattern.len())
+        .any(|window| window.eq_ignore_ascii_case(pattern.as_bytes()))
+}
+
+impl Nmea {
+    /// Returns the leap second state.
+    pub fn leap_second_info(&self) -> &LeapSecondInfo {
+        &self.leap_seconds
+    }
+
+    /// Sets the leap second state, e.g. from a proprietary sentence.
+    pub fn set_leap_second_info(&mut self, info: LeapSecondInfo) {
+        self.leap_seconds = info;
+    }
+
+    /// Returns the fix date and time in GPS time.
+    pub fn fix_gps_time(&self) -> Option<NaiveDateTime> {
+        Some(self.leap_seconds.gps_time(self.fix_datetime()?))
+    }
+
+    /// Returns the fix date and time in TAI.
+    pub fn fix_tai(&self) -> Option<NaiveDateTime> {
+        Some(self.leap_seconds.tai(self.fix_datetime()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_from_text() {
+        let mut info = LeapSecondInfo::default();
+        let today = NaiveDate::from_ymd_opt(2030, 8, 14);
+        info.update_from_text("ANTSTATUS=OK", today);
+        assert_eq!(info.offset, None);
+        info.update_from_text("UTC leap seconds: 18", today);
+        assert_eq!(info.offset, Some(18));
+        info.update_from_text("leap", today);
+        assert_eq!(info.offset, Some(18));
+
+        info.update_from_text("LEAPSEC 19 (pending)", None);
+        assert_eq!(info.pending, None);
+        info.update_from_text("LEAPSEC 19 (pending)", today);
+        assert_eq!(info.offset, Some(18));
+        let new_year = NaiveDate::from_ymd_opt(2031, 1, 1).unwrap();
+        assert_eq!(info.pending, Some((new_year, 1)));
+        let utc = new_year.and_hms_opt(0, 0, 0).unwrap();
+        assert_eq!(info.offset_at(utc - Duration::seconds(1)), 18);
+        assert_eq!(info.offset_at(utc), 19);
+
+        info.update_from_text("LEAPSEC 19", NaiveDate::from_ymd_opt(2031, 1, 2));
+        assert_eq!((info.offset, info.pending), (Some(19), None));
+    }
+
+    #[test]
+    fn test_offset_at_overflow() {
+        let info = LeapSecondInfo {
+            offset: Some(i8::MAX),
+            pending: Some((NaiveDate::MIN, 1)),
+        };
+        assert_eq!(info.offset_at(NaiveDateTime::MAX), i8::MAX);
+    }
+}
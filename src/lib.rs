@@ -4,12 +4,24 @@
 //! to preserve state between receiving new NMEA sentences
 //! (large size, not recommended for embedded platforms).
 //!
+//! With the `std` feature, [`SharedNmea`] wraps the parser for use from
+//! several threads, e.g. a serial-reading thread and a UI thread.
+//!
 //! For embedded platforms, i.e. `no_std`, use [`parse_str()`] or [`parse_bytes()`]
 //! to parse sentences without preserving state.
 //!
 //! Units used: **celsius**, **degrees**, **knots**, **meters** for altitude.
 //!
-//! Check the feature flags below for all the supported sentences.
+//! Coordinates, depths and other values that need more than `f32` precision
+//! are [`Float`], which is `f64` unless the `f32-only` feature is enabled.
+//!
+//! Check the feature flags below for all the supported sentences. The
+//! `profile-*` features enable curated sets of them, e.g. `profile-gnss-min`
+//! for a minimal GNSS receiver on embedded targets:
+//!
+//! ```toml
+//! nmea = { version = "0.7", default-features = false, features = ["profile-gnss-min"] }
+//! ```
 //!
 //! ## Crate features
 #![cfg_attr(feature = "features-docs", doc = document_features::document_features!())]
@@ -29,20 +41,162 @@
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(unsafe_code, rustdoc::broken_intra_doc_links)]
 
+#[cfg(feature = "almanac")]
+#[cfg_attr(docsrs, doc(cfg(feature = "almanac")))]
+pub mod almanac;
+mod clock;
+mod consistency;
+mod course;
+mod datetime;
+mod decimation;
+mod device;
+mod dgps;
+mod duplicates;
+mod epoch;
 mod error;
+mod events;
+#[cfg(feature = "std")]
+mod explain;
+#[cfg(feature = "ffi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ffi")))]
+pub mod ffi;
+#[cfg(feature = "filter")]
+#[cfg_attr(docsrs, doc(cfg(feature = "filter")))]
+pub mod filter;
+mod fix;
+mod geo;
+mod geofence;
+mod gsv_check;
+mod heading;
+mod jump;
+mod latency;
+mod leap;
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod mock;
+mod motion;
+mod nav_config;
+mod odometer;
 pub(crate) mod parse;
 mod parser;
+mod pps;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub mod python;
+mod rtk;
+mod sat_id;
+#[cfg(feature = "sat-rates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sat-rates")))]
+pub mod sat_rates;
+mod schedule;
+#[cfg(feature = "selftest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "selftest")))]
+pub mod selftest;
+mod sentence_table;
+#[cfg(feature = "std")]
+mod shared;
+mod signal;
+mod sink;
+#[cfg(feature = "snapshot")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+pub mod snapshot;
+#[cfg(feature = "snr-history")]
+#[cfg_attr(docsrs, doc(cfg(feature = "snr-history")))]
+pub mod snr;
+mod spans;
+mod tag_block;
+mod time_systems;
+#[cfg(feature = "parse-timing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parse-timing")))]
+pub mod timing;
+mod unsupported;
+mod updates;
+#[cfg(feature = "utm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "utm")))]
+pub mod utm;
+mod velocity;
+#[cfg(feature = "wasm-bindgen")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm-bindgen")))]
+pub mod wasm;
 
+pub mod angle;
+pub mod datum;
+pub mod display;
+pub mod metadata;
+pub mod rewrite;
 pub mod sentences;
+pub mod source;
+pub mod stream;
+pub mod weather;
+pub mod wind;
 
 #[doc(inline)]
 pub use parser::*;
 
-pub use error::Error;
+#[cfg(feature = "compact-errors")]
+#[cfg_attr(docsrs, doc(cfg(feature = "compact-errors")))]
+pub use error::ErrorCode;
+pub use error::{Error, ErrorInput, OwnedError, RecoveryHint, OWNED_ERROR_INPUT_LEN};
+
+pub use clock::*;
+pub use consistency::{PositionCheck, PositionCheckStats, PositionSpread};
+pub use course::{
+    CourseCheck, CourseDiscrepancy, CourseFilter, LowSpeedCourse, LowSpeedCourseFreeze,
+};
+pub use decimation::DECIMATION_LEN;
+pub use device::{Device, GsvTalkerPolicy, Quirks};
+pub use dgps::{DgpsAlarm, DgpsWatchdog};
+pub use duplicates::DUPLICATES_LEN;
+pub use events::{FixEvent, FIX_EVENTS_LEN};
+pub use geofence::{Geofence, GEOFENCES_LEN, GEOFENCE_VERTICES_LEN};
+pub use gsv_check::GsvCountStats;
+pub use heading::HeadingSource;
+pub use jump::{JumpFilter, JumpStats, PositionJump};
+pub use motion::{CycleSource, MotionSolution};
+pub use nav_config::NavConfig;
+pub use odometer::{Odometer, OdometerConfig};
+pub use rtk::{RtkBaseline, RtkMode, RtkStatus, RtkTransition, RTK_HISTORY_LEN};
+pub use sat_id::{OrbitClass, SatId, SatIdPolicy};
+pub use schedule::{ScheduleReport, ScheduleStatus, SCHEDULE_LEN};
+pub use signal::{SignalCounts, SIGNAL_SNRS_LEN};
+pub use sink::{parse_str_ref, SentenceSink};
+pub use spans::{parse_str_with_spans, FieldSpans, FIELD_SPANS_LEN};
+pub use tag_block::{split_tag_block, TagBlock, TAG_ID_MAX_LEN};
+pub use time_systems::{TimeSystem, TimeSystemOffsets};
+pub use unsupported::{UnsupportedReason, UnsupportedSentence, UNSUPPORTED_LEN};
+pub use updates::{LastUpdate, UPDATES_LEN};
+pub use velocity::Velocity;
+
+pub use fix::Fix;
+pub use latency::Latency;
+pub use leap::{LeapSecondInfo, DEFAULT_GPS_UTC_OFFSET};
+pub use pps::UtcTimeEstimate;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use explain::{explain, ExplainedField, Explanation};
+#[cfg(feature = "std")]
+pub use shared::SharedNmea;
 
 #[doc(inline)]
 pub use parse::*;
 
+/// Floating point type of coordinates, depths and other values which need
+/// more precision than `f32`.
+///
+/// It is `f64`, or `f32` with the `f32-only` feature for targets without a
+/// double precision FPU. An `f32` has 24 significant bits, so coordinates
+/// only keep about 1 m of precision (e.g. `179.99999°` of longitude).
+///
+/// The `f32-only` feature is not additive: enabling it for one crate of a
+/// build changes the type of the public fields for all the others.
+pub type Float = FloatImpl;
+
+#[cfg(not(feature = "f32-only"))]
+type FloatImpl = f64;
+#[cfg(feature = "f32-only")]
+type FloatImpl = f32;
+
 #[cfg(doctest)]
 // Test the README examples
 doc_comment::doctest!("../README.md");
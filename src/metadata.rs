@@ -0,0 +1,687 @@
+//! Static metadata about every [`SentenceType`], for tools that build user
+//! interfaces or documentation from the crate, and checks of sentences
+//! against a revision of the standard.
+
+use core::fmt;
+
+use crate::{parse::parse_nmea_sentence, Error, NmeaSentence, SentenceType};
+
+/// Sentence groups, following the grouping of the [`SentenceType`] docs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SentenceCategory {
+    General,
+    Ais,
+    Autopilot,
+    Decca,
+    DGps,
+    Echo,
+    Radio,
+    Speed,
+    Gps,
+    Course,
+    LoranC,
+    Machine,
+    Navigation,
+    Omega,
+    Position,
+    Radar,
+    Rudder,
+    Temperature,
+    Transit,
+    Trawl,
+    Waypoint,
+    Wind,
+    DateTime,
+    Vendor,
+    Other,
+}
+
+/// Revisions of the NMEA 0183 standard.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NmeaVersion {
+    V2_0,
+    V2_1,
+    V2_3,
+    V3_0,
+    V4_0,
+    V4_10,
+    V4_11,
+}
+
+impl NmeaVersion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NmeaVersion::V2_0 => "2.0",
+            NmeaVersion::V2_1 => "2.1",
+            NmeaVersion::V2_3 => "2.3",
+            NmeaVersion::V3_0 => "3.0",
+            NmeaVersion::V4_0 => "4.0",
+            NmeaVersion::V4_10 => "4.10",
+            NmeaVersion::V4_11 => "4.11",
+        }
+    }
+}
+
+impl fmt::Display for NmeaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Metadata of a [`SentenceType`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SentenceMeta {
+    pub sentence_type: SentenceType,
+    /// Human readable name, e.g. `Global Positioning System Fix Data`
+    pub name: &'static str,
+    pub category: SentenceCategory,
+    /// First revision of the standard with the sentence, `None` if it was
+    /// part of the first revisions or it is unknown
+    pub min_version: Option<NmeaVersion>,
+    /// Minimum and maximum number of data fields accepted by the parser,
+    /// `None` for sentences the crate does not implement
+    pub fields: Option<(u8, u8)>,
+    /// Name of the crate feature implementing the sentence
+    pub feature: Option<&'static str>,
+    /// Whether this build of the crate parses the sentence, i.e. the feature
+    /// is enabled
+    pub parsed: bool,
+}
+
+macro_rules! meta {
+    ($sentence_type:ident, $name:literal, $category:ident, $min_version:expr, $fields:expr) => {
+        SentenceMeta {
+            sentence_type: SentenceType::$sentence_type,
+            name: $name,
+            category: SentenceCategory::$category,
+            min_version: $min_version,
+            fields: $fields,
+            feature: None,
+            parsed: false,
+        }
+    };
+    ($sentence_type:ident, $name:literal, $category:ident, $min_version:expr, $fields:expr, $feature:tt) => {
+        SentenceMeta {
+            sentence_type: SentenceType::$sentence_type,
+            name: $name,
+            category: SentenceCategory::$category,
+            min_version: $min_version,
+            fields: $fields,
+            feature: Some($feature),
+            parsed: cfg!(feature = $feature),
+        }
+    };
+}
+
+use NmeaVersion::*;
+
+/// Metadata of all sentence types, in the order of [`SentenceType::TYPES`].
+///
+/// # Examples
+///
+/// ```
+/// use nmea::metadata::REGISTRY;
+///
+/// let parsed = REGISTRY.iter().filter(|meta| meta.parsed);
+/// # #[cfg(feature = "all-sentences")]
+/// assert_eq!(parsed.count(), 33);
+/// ```
+pub static REGISTRY: [SentenceMeta; SentenceType::TYPES.len()] = REGISTRY_TABLE;
+
+/// [`REGISTRY`] for use in constant expressions, which can't read statics.
+pub(crate) const REGISTRY_TABLE: [SentenceMeta; SentenceType::TYPES.len()] = [
+    meta!(
+        AAM,
+        "Waypoint Arrival Alarm",
+        Waypoint,
+        None,
+        Some((5, 5)),
+        "AAM"
+    ),
+    meta!(
+        ABK,
+        "AIS Addressed and Binary Broadcast Acknowledgement",
+        Ais,
+        None,
+        None
+    ),
+    meta!(
+        ACA,
+        "AIS Regional Channel Assignment Message",
+        Ais,
+        None,
+        None
+    ),
+    meta!(ACK, "Acknowledge Alarm", General, None, None),
+    meta!(
+        ACS,
+        "AIS Channel Management Information Source",
+        Ais,
+        None,
+        None
+    ),
+    meta!(AIR, "AIS Interrogation Request", Ais, None, None),
+    meta!(ALM, "GPS Almanac Data", Gps, None, Some((15, 15)), "ALM"),
+    meta!(ALR, "Set Alarm State", General, None, None),
+    meta!(
+        APA,
+        "Autopilot Sentence \"A\"",
+        Autopilot,
+        None,
+        Some((12, 12)),
+        "APA"
+    ),
+    meta!(APB, "Autopilot Sentence \"B\"", Autopilot, None, None),
+    meta!(ASD, "Autopilot System Data", Autopilot, None, None),
+    meta!(
+        BEC,
+        "Bearing & Distance to Waypoint - Dead Reckoning",
+        Waypoint,
+        None,
+        None
+    ),
+    meta!(
+        BOD,
+        "Bearing - Waypoint to Waypoint",
+        Waypoint,
+        None,
+        Some((5, 6)),
+        "BOD"
+    ),
+    meta!(
+        BWC,
+        "Bearing & Distance to Waypoint - Great Circle",
+        Waypoint,
+        None,
+        Some((12, 13)),
+        "BWC"
+    ),
+    meta!(
+        BWR,
+        "Bearing and Distance to Waypoint - Rhumb Line",
+        Waypoint,
+        None,
+        None
+    ),
+    meta!(
+        BWW,
+        "Bearing - Waypoint to Waypoint",
+        Waypoint,
+        None,
+        Some((6, 6)),
+        "BWW"
+    ),
+    meta!(CUR, "Water Current Layer", Other, None, None),
+    meta!(DBK, "Depth Below Keel", Echo, None, Some((6, 6)), "DBK"),
+    meta!(DBS, "Depth Below Surface", Echo, None, Some((6, 6)), "DBS"),
+    meta!(
+        DBT,
+        "Depth below transducer",
+        Echo,
+        None,
+        Some((6, 6)),
+        "DBT"
+    ),
+    meta!(DCN, "Decca Position", Decca, None, None),
+    meta!(DPT, "Depth of Water", Course, None, Some((2, 3)), "DPT"),
+    meta!(
+        DSC,
+        "Digital Selective Calling Information",
+        Radio,
+        None,
+        None
+    ),
+    meta!(DSE, "Expanded Digital Selective Calling", Radio, None, None),
+    meta!(DSI, "DSC Transponder Initiate", Radio, None, None),
+    meta!(DSR, "DSC Transponder Response", Radar, None, None),
+    meta!(DTM, "Datum Reference", Position, None, Some((8, 8)), "DTM"),
+    meta!(FSI, "Frequency Set Information", Radio, None, None),
+    meta!(
+        GBS,
+        "GPS Satellite Fault Detection",
+        Gps,
+        Some(V3_0),
+        Some((8, 10)),
+        "GBS"
+    ),
+    meta!(
+        GGA,
+        "Global Positioning System Fix Data",
+        Gps,
+        None,
+        Some((12, 14)),
+        "GGA"
+    ),
+    meta!(GLC, "Geographic Position, Loran-C", LoranC, None, None),
+    meta!(
+        GLL,
+        "Geographic Position - Latitude/Longitude",
+        Position,
+        None,
+        Some((6, 7)),
+        "GLL"
+    ),
+    meta!(GMP, "GNSS Map Projection Fix Data", Gps, None, None),
+    meta!(GNS, "Fix data", Gps, Some(V3_0), Some((12, 13)), "GNS"),
+    meta!(GRS, "GPS Range Residuals", Gps, None, None),
+    meta!(
+        GSA,
+        "GPS DOP and active satellites",
+        Gps,
+        None,
+        Some((17, 18)),
+        "GSA"
+    ),
+    meta!(
+        GST,
+        "GPS Pseudorange Noise Statistics",
+        Gps,
+        None,
+        Some((8, 8)),
+        "GST"
+    ),
+    meta!(GSV, "Satellites in view", Gps, None, Some((3, 20)), "GSV"),
+    meta!(
+        GTD,
+        "Geographic Location in Time Differences",
+        DateTime,
+        None,
+        None
+    ),
+    meta!(
+        GXA,
+        "TRANSIT Position - Latitude/Longitude",
+        Transit,
+        None,
+        None
+    ),
+    meta!(HDG, "Heading - Deviation & Variation", Course, None, None),
+    meta!(HDM, "Heading - Magnetic", Course, None, None),
+    meta!(HDT, "Heading - True", Course, None, Some((2, 2)), "HDT"),
+    meta!(
+        HFB,
+        "Trawl Headrope to Footrope and Bottom",
+        Trawl,
+        None,
+        None
+    ),
+    meta!(HMR, "Heading Monitor Receive", Course, None, None),
+    meta!(HMS, "Heading Monitor Set", Course, None, None),
+    meta!(HSC, "Heading Steering Command", Course, None, None),
+    meta!(HWBIAS, "Unknown", Other, None, None),
+    meta!(HTC, "Heading/Track Control Command", Course, None, None),
+    meta!(HTD, "Heading/Track Control Data", Course, None, None),
+    meta!(ITS, "Trawl Door Spread 2 Distance", Trawl, None, None),
+    meta!(LCD, "Loran-C Signal Data", LoranC, None, None),
+    meta!(LRF, "AIS Long-Range Function", Ais, None, None),
+    meta!(LRI, "AIS Long-Range Interrogation", Ais, None, None),
+    meta!(LR1, "AIS Long-Range Reply Sentence 1", Ais, None, None),
+    meta!(LR2, "AIS Long-Range Reply Sentence 2", Ais, None, None),
+    meta!(LR3, "AIS Long-Range Reply Sentence 3", Ais, None, None),
+    meta!(
+        MDA,
+        "Meteorological Composite",
+        Other,
+        None,
+        Some((20, 20)),
+        "MDA"
+    ),
+    meta!(MLA, "GLONASS Almanac Data", Gps, None, None),
+    meta!(MSK, "Control for a Beacon Receiver", DGps, None, None),
+    meta!(MSS, "Beacon Receiver Status", DGps, None, None),
+    meta!(MWD, "Wind Direction and Speed", Wind, None, None),
+    meta!(
+        MTW,
+        "Mean Temperature of Water",
+        Temperature,
+        None,
+        Some((2, 2)),
+        "MTW"
+    ),
+    meta!(MWV, "Wind Speed and Angle", Wind, None, Some((5, 5)), "MWV"),
+    meta!(OLN, "Omega Lane Numbers", Omega, None, None),
+    meta!(OSD, "Own Ship Data", General, None, None),
+    meta!(ROO, "Waypoints in active route", Waypoint, None, None),
+    meta!(RLM, "Return Link Message", Other, None, None),
+    meta!(
+        RMA,
+        "Recommended Minimum Navigation Information",
+        Navigation,
+        None,
+        None
+    ),
+    meta!(
+        RMB,
+        "Recommended Minimum Navigation Information",
+        Navigation,
+        None,
+        None
+    ),
+    meta!(
+        RMC,
+        "Recommended Minimum Navigation Information",
+        Navigation,
+        None,
+        Some((11, 13)),
+        "RMC"
+    ),
+    meta!(RMZ, "Garmin Altitude", Vendor, None, Some((3, 3)), "RMZ"),
+    meta!(ROT, "Rate Of Turn", Course, None, None),
+    meta!(RPM, "Revolutions", Machine, None, None),
+    meta!(RSA, "Rudder Sensor Angle", Rudder, None, None),
+    meta!(RSD, "RADAR System Data", Radar, None, None),
+    meta!(RTE, "Routes", Waypoint, None, None),
+    meta!(SFI, "Scanning Frequency Information", Radio, None, None),
+    meta!(SSD, "AIS Ship Static Data", Ais, None, None),
+    meta!(STN, "Multiple Data ID", General, None, None),
+    meta!(TDS, "Trawl Door Spread Distance", Trawl, None, None),
+    meta!(TFI, "Trawl Filling Indicator", Trawl, None, None),
+    meta!(TLB, "Target Label", Radar, None, None),
+    meta!(TLL, "Target Latitude and Longitude", Radio, None, None),
+    meta!(
+        TPC,
+        "Trawl Position Cartesian Coordinates",
+        Trawl,
+        None,
+        None
+    ),
+    meta!(TPR, "Trawl Position Relative Vessel", Trawl, None, None),
+    meta!(TPT, "Trawl Position True", Trawl, None, None),
+    meta!(TRF, "TRANSIT Fix Data", Transit, None, None),
+    meta!(
+        TTM,
+        "Tracked Target Message",
+        Radar,
+        None,
+        Some((15, 15)),
+        "TTM"
+    ),
+    meta!(
+        TUT,
+        "Transmission of Multi-language Text",
+        General,
+        None,
+        None
+    ),
+    meta!(
+        TXT,
+        "Text Transmission",
+        General,
+        Some(V3_0),
+        Some((4, 4)),
+        "TXT"
+    ),
+    meta!(VBW, "Dual Ground/Water Speed", Speed, None, None),
+    meta!(VDM, "AIS VHF Data-link Message", Ais, None, None),
+    meta!(VDO, "AIS VHF Data-link Own-vessel Report", Ais, None, None),
+    meta!(VDR, "Set and Drift", Course, None, None),
+    meta!(
+        VHW,
+        "Water speed and heading",
+        Speed,
+        None,
+        Some((8, 8)),
+        "VHW"
+    ),
+    meta!(
+        VLW,
+        "Distance Traveled through Water",
+        Speed,
+        None,
+        Some((4, 8)),
+        "VLW"
+    ),
+    meta!(VPW, "Speed - Measured Parallel to Wind", Wind, None, None),
+    meta!(VSD, "AIS Voyage Static Data", Ais, None, None),
+    meta!(
+        VTG,
+        "Track made good and Ground speed",
+        Waypoint,
+        None,
+        Some((8, 9)),
+        "VTG"
+    ),
+    meta!(VWR, "Relative Wind Speed and Angle", Wind, None, None),
+    meta!(WCV, "Waypoint Closure Velocity", Waypoint, None, None),
+    meta!(
+        WNC,
+        "Distance - Waypoint to Waypoint",
+        Waypoint,
+        None,
+        Some((5, 6)),
+        "WNC"
+    ),
+    meta!(WPL, "Waypoint Location", Waypoint, None, None),
+    meta!(XDR, "Transducer Measurement", Waypoint, None, None),
+    meta!(XTE, "Cross-Track Error, Measured", Waypoint, None, None),
+    meta!(
+        XTR,
+        "Cross Track Error - Dead Reckoning",
+        Waypoint,
+        None,
+        None
+    ),
+    meta!(
+        ZDA,
+        "Time & Date - UTC, day, month, year and local time zone",
+        DateTime,
+        None,
+        Some((6, 6)),
+        "ZDA"
+    ),
+    meta!(
+        ZDL,
+        "Time and Distance to Variable Point",
+        DateTime,
+        None,
+        None
+    ),
+    meta!(
+        ZFO,
+        "UTC & Time from origin Waypoint",
+        DateTime,
+        None,
+        Some((3, 3)),
+        "ZFO"
+    ),
+    meta!(
+        ZTG,
+        "UTC & Time to Destination Waypoint",
+        DateTime,
+        None,
+        Some((3, 3)),
+        "ZTG"
+    ),
+];
+
+impl SentenceType {
+    /// Returns the metadata of the sentence type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{metadata::SentenceCategory, SentenceType};
+    ///
+    /// let meta = SentenceType::GGA.metadata();
+    /// assert_eq!(meta.name, "Global Positioning System Fix Data");
+    /// assert_eq!(meta.category, SentenceCategory::Gps);
+    /// assert_eq!(meta.fields, Some((12, 14)));
+    /// assert_eq!(meta.feature, Some("GGA"));
+    /// ```
+    pub fn metadata(&self) -> &'static SentenceMeta {
+        &REGISTRY[*self as usize]
+    }
+
+    /// Returns the category of the sentence type.
+    pub fn category(&self) -> SentenceCategory {
+        self.metadata().category
+    }
+
+    /// Returns all sentence types of a category.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{metadata::SentenceCategory, SentenceType};
+    ///
+    /// let radar: Vec<_> = SentenceType::iter_category(SentenceCategory::Radar).collect();
+    /// assert!(radar.contains(&SentenceType::TTM));
+    /// assert!(!radar.contains(&SentenceType::GGA));
+    /// ```
+    pub fn iter_category(category: SentenceCategory) -> impl Iterator<Item = SentenceType> {
+        REGISTRY
+            .iter()
+            .filter(move |meta| meta.category == category)
+            .map(|meta| meta.sentence_type)
+    }
+}
+
+/// Optional trailing fields added by later revisions of the standard: the
+/// sentence type, the field name, its 1-based position and the revision
+/// which added it.
+const VERSIONED_FIELDS: &[(SentenceType, &str, usize, NmeaVersion)] = &[
+    (SentenceType::BWC, "FAA mode", 13, V2_3),
+    (SentenceType::GBS, "system ID", 9, V4_10),
+    (SentenceType::GBS, "signal ID", 10, V4_10),
+    (SentenceType::GLL, "FAA mode", 7, V2_3),
+    (SentenceType::GNS, "navigational status", 13, V4_10),
+    (SentenceType::GSA, "system ID", 18, V4_10),
+    (SentenceType::RMC, "FAA mode", 12, V2_3),
+    (SentenceType::RMC, "navigational status", 13, V4_10),
+    (SentenceType::VTG, "FAA mode", 9, V2_3),
+];
+
+/// Checks that a sentence has exactly the optional trailing fields of a
+/// revision of the standard.
+///
+/// Fields added by later revisions are rejected, and fields of this or
+/// earlier revisions are required, even if empty. QZSS and NavIC system IDs
+/// in GSA sentences require NMEA 4.11. The sentence is not parsed otherwise.
+///
+/// # Errors
+///
+/// [`Error::VersionMismatch`] with the offending field, or the errors of
+/// [`parse_nmea_sentence()`] for malformed sentences.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::metadata::{check_version, NmeaVersion};
+///
+/// let rmc = "$GPRMC,225446.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E,A*2B";
+/// assert!(check_version(rmc, NmeaVersion::V3_0).is_ok());
+/// // no FAA mode before NMEA 2.3
+/// assert!(check_version(rmc, NmeaVersion::V2_1).is_err());
+/// // the navigational status is required since NMEA 4.10
+/// assert!(check_version(rmc, NmeaVersion::V4_10).is_err());
+/// ```
+pub fn check_version(sentence: &str, version: NmeaVersion) -> Result<(), Error<'_>> {
+    check_version_framed(&parse_nmea_sentence(sentence)?, version)
+}
+
+/// Checks the fields of a framed sentence, see [`check_version()`].
+pub(crate) fn check_version_framed<'a>(
+    nmea_sentence: &NmeaSentence<'a>,
+    version: NmeaVersion,
+) -> Result<(), Error<'a>> {
+    let sentence_type = nmea_sentence.message_id;
+    let field_count = nmea_sentence.data.split(',').count();
+    let mismatch = |field| Error::VersionMismatch {
+        sentence_type,
+        field,
+        version,
+    };
+
+    for &(_, field, position, since) in VERSIONED_FIELDS
+        .iter()
+        .filter(|(ty, ..)| *ty == sentence_type)
+    {
+        if (field_count >= position) != (version >= since) {
+            return Err(mismatch(field));
+        }
+    }
+
+    match sentence_type {
+        SentenceType::GSV => {
+            // header and satellites of 4 fields, then the signal ID
+            let has_signal_id = field_count >= 3 && (field_count - 3) % 4 == 1;
+            if has_signal_id != (version >= V4_10) {
+                return Err(mismatch("signal ID"));
+            }
+        }
+        SentenceType::GSA if version < V4_11 => {
+            // 5 is QZSS, 6 is NavIC
+            let system_id = nmea_sentence.data.split(',').nth(17);
+            if matches!(system_id, Some("5" | "6")) {
+                return Err(mismatch("system ID"));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_order() {
+        for (meta, sentence_type) in REGISTRY.iter().zip(SentenceType::TYPES) {
+            assert_eq!(meta.sentence_type, sentence_type);
+        }
+        assert_eq!(SentenceType::RMZ.metadata().feature, Some("RMZ"));
+        assert_eq!(SentenceType::APB.metadata().feature, None);
+        assert!(!SentenceType::APB.metadata().parsed);
+    }
+
+    #[test]
+    fn test_category() {
+        assert_eq!(SentenceType::MWV.category(), SentenceCategory::Wind);
+        assert_eq!(SentenceType::ZDA.category(), SentenceCategory::DateTime);
+        let wind: heapless::Vec<_, 8> =
+            SentenceType::iter_category(SentenceCategory::Wind).collect();
+        assert_eq!(
+            wind,
+            [
+                SentenceType::MWD,
+                SentenceType::MWV,
+                SentenceType::VPW,
+                SentenceType::VWR
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_version() {
+        let gsv = "$GPGSV,1,1,03,10,63,137,17,07,61,098,15,05,59,290,20*76";
+        let gsv_signal = "$GPGSV,1,1,03,10,63,137,17,07,61,098,15,05,59,290,20,1*55";
+        assert_eq!(check_version(gsv, NmeaVersion::V3_0), Ok(()));
+        assert_eq!(check_version(gsv_signal, NmeaVersion::V4_11), Ok(()));
+        assert_eq!(
+            check_version(gsv, NmeaVersion::V4_10),
+            Err(Error::VersionMismatch {
+                sentence_type: SentenceType::GSV,
+                field: "signal ID",
+                version: NmeaVersion::V4_10,
+            })
+        );
+        assert!(check_version(gsv_signal, NmeaVersion::V2_3).is_err());
+
+        let gsa_qzss = "$GNGSA,A,3,,,,,,,,,,,,,1.0,0.7,0.7,5*34";
+        assert!(check_version(gsa_qzss, NmeaVersion::V4_10).is_err());
+        assert_eq!(check_version(gsa_qzss, NmeaVersion::V4_11), Ok(()));
+
+        // sentences without versioned fields are always accepted
+        assert_eq!(
+            check_version("$GPHDT,274.07,T*03", NmeaVersion::V2_0),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,427 @@
+//! A scripted virtual receiver, to test navigation logic against realistic
+//! sentence sequences.
+//!
+//! A [`MockReceiver`] plays a scenario of [`ReceiverPhase`]s, e.g. a cold
+//! start, the acquisition of satellites, a fix, RTK and an outage, and emits
+//! the GGA, GSA, GSV and RMC sentences of one epoch per second, with valid
+//! checksums. The vehicle moves at a constant speed and course, which is
+//! only reported when there is a fix.
+
+use core::fmt::{self, Write};
+
+use arrayvec::ArrayString;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use heapless::{Deque, Vec};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use crate::SENTENCE_MAX_LEN;
+
+/// Maximum number of steps of a scenario.
+pub const MOCK_SCENARIO_STEPS: usize = 16;
+
+/// The sentences of an epoch: GGA, GSA, up to two GSV and RMC.
+const EPOCH_SENTENCES: usize = 5;
+
+/// The satellites in view, as PRN, elevation, azimuth and SNR.
+const SATELLITES: [(u8, u8, u16, u8); 8] = [
+    (2, 62, 84, 45),
+    (5, 41, 307, 42),
+    (12, 35, 152, 40),
+    (13, 18, 255, 36),
+    (15, 73, 18, 47),
+    (18, 24, 201, 38),
+    (24, 10, 45, 31),
+    (25, 52, 111, 44),
+];
+
+/// Number of satellites tracked while acquiring.
+const ACQUIRING_SATELLITES: usize = 4;
+
+/// The state of a [`MockReceiver`] during a step of its scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReceiverPhase {
+    /// No time and no satellites
+    ColdStart,
+    /// Time and satellites in view, some tracked, but no fix
+    Acquiring,
+    /// Autonomous 3D fix
+    Fix,
+    /// RTK with float ambiguities
+    RtkFloat,
+    /// RTK with fixed ambiguities
+    RtkFixed,
+    /// Satellites lost, e.g. in a tunnel, the time continues
+    Outage,
+}
+
+/// A phase of a scenario and its number of epochs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScenarioStep {
+    pub phase: ReceiverPhase,
+    pub epochs: u32,
+}
+
+impl ScenarioStep {
+    pub const fn new(phase: ReceiverPhase, epochs: u32) -> Self {
+        Self { phase, epochs }
+    }
+}
+
+/// A virtual receiver playing a scenario, see the
+/// [module documentation](self).
+///
+/// It is an iterator over the sentences of the scenario.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{
+///     mock::{MockReceiver, ReceiverPhase, ScenarioStep},
+///     Nmea,
+/// };
+///
+/// let mut receiver = MockReceiver::new(&[
+///     ScenarioStep::new(ReceiverPhase::ColdStart, 2),
+///     ScenarioStep::new(ReceiverPhase::Acquiring, 3),
+///     ScenarioStep::new(ReceiverPhase::Fix, 10),
+/// ])
+/// .motion(10., 45.);
+/// let mut nmea = Nmea::default();
+/// for sentence in &mut receiver {
+/// #   #[cfg(feature = "parse-for-fix")]
+///     nmea.parse(&sentence).unwrap();
+/// }
+/// # #[cfg(feature = "parse-for-fix")]
+/// assert_eq!(nmea.num_of_fix_satellites, Some(8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockReceiver {
+    scenario: Vec<ScenarioStep, MOCK_SCENARIO_STEPS>,
+    step: usize,
+    /// Epochs played in the current step
+    epoch: u32,
+    phase: Option<ReceiverPhase>,
+    datetime: NaiveDateTime,
+    latitude: f64,
+    longitude: f64,
+    altitude: f32,
+    speed: f32,
+    course: f32,
+    sentences: Deque<ArrayString<SENTENCE_MAX_LEN>, EPOCH_SENTENCES>,
+}
+
+impl MockReceiver {
+    /// Plays a scenario, only its first [`MOCK_SCENARIO_STEPS`] steps are
+    /// kept.
+    ///
+    /// The receiver starts at noon on 2024-01-01, at 53.36° N 6.51° W and
+    /// 61.7 m, and does not move.
+    pub fn new(scenario: &[ScenarioStep]) -> Self {
+        let scenario = &scenario[..scenario.len().min(MOCK_SCENARIO_STEPS)];
+        Self {
+            // the length was checked above
+            scenario: Vec::from_slice(scenario).unwrap_or_default(),
+            step: 0,
+            epoch: 0,
+            phase: None,
+            datetime: NaiveDate::from_ymd_opt(2024, 1, 1)
+                .and_then(|date| date.and_hms_opt(12, 0, 0))
+                .unwrap_or_default(),
+            latitude: 53.361_337,
+            longitude: -6.505_62,
+            altitude: 61.7,
+            speed: 0.,
+            course: 0.,
+            sentences: Deque::new(),
+        }
+    }
+
+    /// Sets the date and time of the first epoch.
+    pub fn start(mut self, datetime: NaiveDateTime) -> Self {
+        self.datetime = datetime;
+        self
+    }
+
+    /// Sets the initial position, in degrees and meters above mean sea
+    /// level.
+    pub fn position(mut self, latitude: f64, longitude: f64, altitude: f32) -> Self {
+        self.latitude = latitude;
+        self.longitude = longitude;
+        self.altitude = altitude;
+        self
+    }
+
+    /// Sets the speed over ground in knots and the course in degrees True.
+    pub fn motion(mut self, speed: f32, course: f32) -> Self {
+        self.speed = speed;
+        self.course = course;
+        self
+    }
+
+    /// Returns the phase of the last emitted epoch.
+    pub fn phase(&self) -> Option<ReceiverPhase> {
+        self.phase
+    }
+
+    /// Returns the date and time of the last emitted epoch.
+    pub fn datetime(&self) -> NaiveDateTime {
+        self.datetime
+    }
+
+    /// Returns the true position of the vehicle, as latitude and longitude
+    /// in degrees.
+    pub fn true_position(&self) -> (f64, f64) {
+        (self.latitude, self.longitude)
+    }
+
+    /// Starts the next epoch of the scenario, returns `false` at its end.
+    fn next_epoch(&mut self) -> bool {
+        while let Some(step) = self.scenario.get(self.step) {
+            if self.epoch < step.epochs {
+                break;
+            }
+            self.step += 1;
+            self.epoch = 0;
+        }
+        let Some(step) = self.scenario.get(self.step) else {
+            return false;
+        };
+        let phase = step.phase;
+        if self.phase.is_some() {
+            self.advance();
+        }
+        self.epoch += 1;
+        self.phase = Some(phase);
+        self.queue_epoch(phase);
+        true
+    }
+
+    /// Moves the vehicle by one second.
+    fn advance(&mut self) {
+        self.datetime += Duration::seconds(1);
+        // a knot is one minute of latitude per hour
+        let distance = f64::from(self.speed) / 3600. / 60.;
+        let course = f64::from(self.course).to_radians();
+        self.latitude += distance * course.cos();
+        self.longitude += distance * course.sin() / self.latitude.to_radians().cos();
+    }
+
+    fn queue_epoch(&mut self, phase: ReceiverPhase) {
+        let mut time = ArrayString::<9>::new();
+        let mut date = ArrayString::<6>::new();
+        if phase != ReceiverPhase::ColdStart {
+            let _ = write!(
+                time,
+                "{:02}{:02}{:02}.00",
+                self.datetime.hour(),
+                self.datetime.minute(),
+                self.datetime.second()
+            );
+            let _ = write!(
+                date,
+                "{:02}{:02}{:02}",
+                self.datetime.day(),
+                self.datetime.month(),
+                self.datetime.year().rem_euclid(100)
+            );
+        }
+        let mut position = ArrayString::<32>::new();
+        let _ = write_coordinate(&mut position, self.latitude, 2, ['N', 'S']);
+        let _ = position.try_push(',');
+        let _ = write_coordinate(&mut position, self.longitude, 3, ['E', 'W']);
+
+        let (quality, mode) = match phase {
+            ReceiverPhase::Fix => (1, 'A'),
+            ReceiverPhase::RtkFloat => (5, 'F'),
+            ReceiverPhase::RtkFixed => (4, 'R'),
+            _ => (0, 'N'),
+        };
+        let satellites: &[_] = match phase {
+            ReceiverPhase::ColdStart | ReceiverPhase::Outage => &[],
+            _ => &SATELLITES,
+        };
+
+        let (altitude, speed, course) = (self.altitude, self.speed, self.course);
+        if quality == 0 {
+            self.queue(format_args!("GPGGA,{},,,,,0,00,99.99,,,,,,", time));
+            self.queue(format_args!("GPGSA,A,1,,,,,,,,,,,,,99.99,99.99,99.99"));
+        } else {
+            let correction = if quality == 1 { "," } else { "1.0,0001" };
+            self.queue(format_args!(
+                "GPGGA,{},{},{},{:02},0.9,{:.1},M,55.2,M,{}",
+                time,
+                position,
+                quality,
+                SATELLITES.len(),
+                altitude,
+                correction
+            ));
+            let mut used = ArrayString::<48>::new();
+            for index in 0..12 {
+                if let Some((prn, ..)) = SATELLITES.get(index) {
+                    let _ = write!(used, "{:02}", prn);
+                }
+                let _ = used.try_push(',');
+            }
+            self.queue(format_args!("GPGSA,A,3,{}1.6,0.9,1.3", used));
+        }
+
+        let sentences = (satellites.len() + 3) / 4;
+        for sentence in 0..sentences {
+            let mut fields = ArrayString::<64>::new();
+            for (index, (prn, elevation, azimuth, snr)) in
+                satellites.iter().enumerate().skip(sentence * 4).take(4)
+            {
+                let _ = write!(fields, ",{:02},{:02},{:03},", prn, elevation, azimuth);
+                if phase != ReceiverPhase::Acquiring || index < ACQUIRING_SATELLITES {
+                    let _ = write!(fields, "{:02}", snr);
+                }
+            }
+            self.queue(format_args!(
+                "GPGSV,{},{},{:02}{}",
+                sentences,
+                sentence + 1,
+                satellites.len(),
+                fields
+            ));
+        }
+
+        if quality == 0 {
+            self.queue(format_args!("GPRMC,{},V,,,,,,,{},,,N", time, date));
+        } else {
+            self.queue(format_args!(
+                "GPRMC,{},A,{},{:.1},{:.1},{},,,{}",
+                time, position, speed, course, date, mode
+            ));
+        }
+    }
+
+    /// Queues a sentence with its `$` and checksum.
+    fn queue(&mut self, body: fmt::Arguments<'_>) {
+        let mut sentence = ArrayString::new();
+        let _ = write!(sentence, "${}", body);
+        let checksum = crate::parse::checksum(sentence.as_bytes()[1..].iter());
+        let _ = write!(sentence, "*{:02X}", checksum);
+        // there is room for the sentences of an epoch
+        let _ = self.sentences.push_back(sentence);
+    }
+}
+
+impl Iterator for MockReceiver {
+    type Item = ArrayString<SENTENCE_MAX_LEN>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.sentences.is_empty() && !self.next_epoch() {
+            return None;
+        }
+        self.sentences.pop_front()
+    }
+}
+
+/// Writes a coordinate as degrees and minutes with its hemisphere.
+fn write_coordinate(
+    output: &mut impl Write,
+    value: f64,
+    degree_digits: usize,
+    hemispheres: [char; 2],
+) -> fmt::Result {
+    let minutes = (value.abs() * 60. * 10_000.).round() / 10_000.;
+    let degrees = (minutes / 60.).trunc();
+    write!(
+        output,
+        "{:0width$}{:07.4},{}",
+        degrees,
+        minutes - degrees * 60.,
+        hemispheres[usize::from(value < 0.)],
+        width = degree_digits
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        let receiver = MockReceiver::new(&[
+            ScenarioStep::new(ReceiverPhase::ColdStart, 1),
+            ScenarioStep::new(ReceiverPhase::Acquiring, 1),
+            ScenarioStep::new(ReceiverPhase::RtkFixed, 1),
+        ]);
+        let sentences: std::vec::Vec<_> = receiver.collect();
+        assert_eq!(sentences.len(), 3 + 5 + 5);
+        assert_eq!(sentences[0].as_str(), "$GPGGA,,,,,,0,00,99.99,,,,,,*48");
+        assert_eq!(
+            sentences[5].as_str(),
+            "$GPGSV,2,1,08,02,62,084,45,05,41,307,42,12,35,152,40,13,18,255,36*70"
+        );
+        assert_eq!(
+            sentences[6].as_str(),
+            "$GPGSV,2,2,08,15,73,018,,18,24,201,,24,10,045,,25,52,111,*73"
+        );
+        for sentence in &sentences {
+            assert!(
+                crate::parse::parse_nmea_sentence(sentence).is_ok(),
+                "{}",
+                sentence
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "parse-for-fix")]
+    fn test_scenario() {
+        use crate::{sentences::FixType, Nmea};
+
+        let mut receiver = MockReceiver::new(&[
+            ScenarioStep::new(ReceiverPhase::ColdStart, 2),
+            ScenarioStep::new(ReceiverPhase::Acquiring, 2),
+            ScenarioStep::new(ReceiverPhase::Fix, 2),
+            ScenarioStep::new(ReceiverPhase::RtkFloat, 2),
+            ScenarioStep::new(ReceiverPhase::RtkFixed, 2),
+            ScenarioStep::new(ReceiverPhase::Outage, 2),
+        ])
+        .motion(20., 90.);
+        let mut nmea = Nmea::default();
+        let mut fix_types = std::vec::Vec::new();
+        let mut fix_positions = std::vec::Vec::new();
+        while let Some(sentence) = receiver.next() {
+            assert!(nmea.parse(&sentence).is_ok(), "{}", sentence);
+            if sentence.starts_with("$GPGGA") {
+                fix_types.push((receiver.phase().unwrap(), nmea.fix_type));
+                if let (Some(latitude), Some(longitude)) = (nmea.latitude, nmea.longitude) {
+                    let position = receiver.true_position();
+                    #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+                    fix_positions.push((latitude as f64, longitude as f64, position));
+                }
+            }
+        }
+        assert_eq!(
+            fix_types,
+            [
+                (ReceiverPhase::ColdStart, Some(FixType::Invalid)),
+                (ReceiverPhase::ColdStart, Some(FixType::Invalid)),
+                (ReceiverPhase::Acquiring, Some(FixType::Invalid)),
+                (ReceiverPhase::Acquiring, Some(FixType::Invalid)),
+                (ReceiverPhase::Fix, Some(FixType::Gps)),
+                (ReceiverPhase::Fix, Some(FixType::Gps)),
+                (ReceiverPhase::RtkFloat, Some(FixType::FloatRtk)),
+                (ReceiverPhase::RtkFloat, Some(FixType::FloatRtk)),
+                (ReceiverPhase::RtkFixed, Some(FixType::Rtk)),
+                (ReceiverPhase::RtkFixed, Some(FixType::Rtk)),
+                (ReceiverPhase::Outage, Some(FixType::Invalid)),
+                (ReceiverPhase::Outage, Some(FixType::Invalid)),
+            ]
+        );
+        assert_eq!(fix_positions.len(), 6);
+        for (latitude, longitude, (true_latitude, true_longitude)) in &fix_positions {
+            assert!((latitude - true_latitude).abs() < 1e-5);
+            assert!((longitude - true_longitude).abs() < 1e-5);
+        }
+        // moving east
+        assert!(fix_positions[5].1 > fix_positions[0].1);
+        assert_eq!(nmea.latitude, None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Navigation cycles without GNSS time, for heading and speed only
+//! instrument buses.
+//!
+//! [`Nmea::parse_for_fix()`] starts a new cycle when the time of the fix
+//! sentences changes. VTG and HDT carry no time, so a bus without GGA or RMC
+//! never completes a cycle. With a [`CycleSource`] other than
+//! [`CycleSource::FixTime`], cycles are started by [`Nmea::tick()`] or by the
+//! [`Clock`](crate::Clock), and [`Nmea::motion_solution()`] returns the
+//! course, speed and heading of the current cycle.
+
+use core::time::Duration;
+
+use crate::{Nmea, SentenceMask};
+
+/// What starts a new navigation cycle of [`Nmea::parse_for_fix()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CycleSource {
+    /// A change of the time of the fix sentences
+    #[default]
+    FixTime,
+    /// Calls to [`Nmea::tick()`]
+    Manual,
+    /// The [`Clock`](crate::Clock), once per period, as well as calls to
+    /// [`Nmea::tick()`]
+    Clock(#[cfg_attr(feature = "defmt-03", defmt(Debug2Format))] Duration),
+}
+
+/// Course, speed and heading of a navigation cycle, see
+/// [`Nmea::motion_solution()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MotionSolution {
+    /// Course over ground in `[0, 360)` degrees True
+    pub true_course: Option<f32>,
+    /// Speed over ground in knots
+    pub speed_over_ground: Option<f32>,
+    /// Heading in degrees True
+    pub heading: Option<f32>,
+}
+
+impl Nmea {
+    /// Sets what starts a new navigation cycle, see the [`CycleSource`].
+    ///
+    /// Without [`CycleSource::FixTime`], a change of the fix time no longer
+    /// starts a new cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{CycleSource, Nmea, SentenceType};
+    ///
+    /// let mut nmea = Nmea::create_for_navigation(&[SentenceType::VTG, SentenceType::HDT]).unwrap();
+    /// nmea.set_cycle_source(CycleSource::Manual);
+    /// # #[cfg(all(feature = "VTG", feature = "HDT"))]
+    /// # {
+    /// nmea.parse_for_fix("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48").unwrap();
+    /// nmea.parse_for_fix("$HEHDT,051.2,T*29").unwrap();
+    ///
+    /// let motion = nmea.motion_solution().unwrap();
+    /// assert_eq!(motion.speed_over_ground, Some(5.5));
+    /// assert_eq!(motion.heading, Some(51.2));
+    ///
+    /// nmea.tick();
+    /// assert_eq!(nmea.motion_solution(), None);
+    /// # }
+    /// ```
+    pub fn set_cycle_source(&mut self, source: CycleSource) {
+        self.cycle_source = source;
+        self.cycle_start = None;
+    }
+
+    /// Returns what starts a new navigation cycle.
+    pub fn cycle_source(&self) -> CycleSource {
+        self.cycle_source
+    }
+
+    /// Starts a new navigation cycle, forgetting the fix and motion of the
+    /// previous one.
+    pub fn tick(&mut self) {
+        self.cycle_start = self.now();
+        self.new_tick();
+    }
+
+    /// Returns the course, speed and heading of the current cycle once all
+    /// the sentences required for navigation have been received.
+    ///
+    /// Always `None` with [`CycleSource::FixTime`].
+    pub fn motion_solution(&self) -> Option<MotionSolution> {
+        let complete = self.cycle_source != CycleSource::FixTime
+            && self.required_sentences_for_nav != SentenceMask::default()
+            && self
+                .required_sentences_for_nav
+                .is_subset(&self.sentences_for_this_time);
+        complete.then_some(MotionSolution {
+            true_course: self.true_course,
+            speed_over_ground: self.speed_over_ground,
+            heading: self.heading,
+        })
+    }
+
+    /// Starts a new cycle when the period of [`CycleSource::Clock`] elapsed.
+    pub(crate) fn check_cycle(&mut self) {
+        let CycleSource::Clock(period) = self.cycle_source else {
+            return;
+        };
+        let Some(now) = self.now() else {
+            return;
+        };
+        match self.cycle_start {
+            Some(start) if now.saturating_sub(start) < period => {}
+            Some(_) => self.tick(),
+            None => self.cycle_start = Some(now),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "VTG", feature = "HDT"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_cycles() {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        use crate::SentenceType;
+
+        static NOW_MS: AtomicU64 = AtomicU64::new(0);
+        fn now() -> Duration {
+            Duration::from_millis(NOW_MS.load(Ordering::SeqCst))
+        }
+
+        let mut nmea =
+            Nmea::create_for_navigation(&[SentenceType::VTG, SentenceType::HDT]).unwrap();
+        nmea.set_clock(&(now as fn() -> Duration));
+        nmea.set_cycle_source(CycleSource::Clock(Duration::from_millis(200)));
+
+        nmea.parse_for_fix("$HEHDT,051.2,T*29").unwrap();
+        assert_eq!(nmea.motion_solution(), None);
+
+        // the HDT of the same cycle is kept
+        NOW_MS.store(150, Ordering::SeqCst);
+        nmea.parse_for_fix("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48")
+            .unwrap();
+        let motion = nmea.motion_solution().unwrap();
+        assert_eq!(motion.heading, Some(51.2));
+        assert_eq!(motion.true_course, Some(54.7));
+
+        // a new cycle without HDT yet
+        NOW_MS.store(250, Ordering::SeqCst);
+        nmea.parse_for_fix("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48")
+            .unwrap();
+        assert_eq!(nmea.motion_solution(), None);
+        assert_eq!(nmea.heading, None);
+        assert_eq!(nmea.speed_over_ground, Some(5.5));
+    }
+}
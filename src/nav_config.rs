@@ -0,0 +1,169 @@
+//! Presets of the sentences required for navigation by
+//! [`Nmea::parse_for_fix()`], see [`NavConfig`].
+
+use crate::{Error, Nmea, SentenceMask, SentenceType};
+
+/// The sentence types tracked in a navigation cycle by
+/// [`Nmea::parse_for_fix()`].
+const TRACKED: SentenceMask = SentenceMask::from_types(&[
+    SentenceType::GGA,
+    SentenceType::GLL,
+    SentenceType::GNS,
+    SentenceType::GSA,
+    SentenceType::HDT,
+    SentenceType::RMC,
+    SentenceType::VTG,
+]);
+
+/// The sentence types which must be received in a navigation cycle before
+/// [`Nmea::parse_for_fix()`] returns a fix.
+///
+/// Only the sentence types tracked by [`Nmea::parse_for_fix()`] can be
+/// required: GGA, GLL, GNS, GSA, HDT, RMC and VTG.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{NavConfig, Nmea, SentenceType};
+///
+/// let config = NavConfig::gga_rmc().with(SentenceType::GSA);
+/// # #[cfg(all(feature = "GGA", feature = "RMC", feature = "GSA"))]
+/// let nmea = Nmea::create_with_config(config).unwrap();
+///
+/// // ROT sentences are not parsed by the crate
+/// let config = NavConfig::gns_only().with(SentenceType::ROT);
+/// assert!(Nmea::create_with_config(config).is_err());
+/// // GSV sentences are parsed, but are not part of a fix
+/// let config = NavConfig::gns_only().with(SentenceType::GSV);
+/// assert!(Nmea::create_with_config(config).is_err());
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NavConfig {
+    sentences: SentenceMask,
+}
+
+impl NavConfig {
+    /// GGA and RMC, the most common configuration.
+    pub const fn gga_rmc() -> Self {
+        Self::from_types(&[SentenceType::GGA, SentenceType::RMC])
+    }
+
+    /// GNS only, for multi-constellation receivers.
+    pub const fn gns_only() -> Self {
+        Self::from_types(&[SentenceType::GNS])
+    }
+
+    /// All the sentence types with a position: GGA, GLL, GNS and RMC.
+    pub const fn all_position_sentences() -> Self {
+        Self::from_types(&[
+            SentenceType::GGA,
+            SentenceType::GLL,
+            SentenceType::GNS,
+            SentenceType::RMC,
+        ])
+    }
+
+    /// The configuration of the sentence types.
+    pub const fn from_types(sentence_types: &[SentenceType]) -> Self {
+        Self {
+            sentences: SentenceMask::from_types(sentence_types),
+        }
+    }
+
+    /// Adds a sentence type to the configuration.
+    #[must_use]
+    pub fn with(self, sentence_type: SentenceType) -> Self {
+        Self {
+            sentences: self.sentences | sentence_type,
+        }
+    }
+
+    /// Returns the required sentence types.
+    pub const fn sentences(&self) -> SentenceMask {
+        self.sentences
+    }
+
+    /// Checks that the configuration is not empty and that this build of
+    /// the crate can parse and track all its sentence types.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::EmptyNavConfig`], or [`Error::Unsupported`] with the first
+    /// sentence type which is not parsable, see
+    /// [`SentenceType::is_parsable()`], or not tracked in a navigation cycle.
+    pub fn validate(&self) -> Result<(), Error<'static>> {
+        if self.sentences == SentenceMask::default() {
+            return Err(Error::EmptyNavConfig);
+        }
+        match SentenceType::TYPES
+            .iter()
+            .find(|ty| self.sentences.contains(ty) && !(ty.is_parsable() && TRACKED.contains(ty)))
+        {
+            Some(&sentence_type) => Err(Error::Unsupported(sentence_type)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Nmea {
+    /// Constructs a new `Nmea` for navigation purposes, like
+    /// [`create_for_navigation()`](Self::create_for_navigation), with a
+    /// validated [`NavConfig`].
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`NavConfig::validate()`].
+    pub fn create_with_config<'a>(config: NavConfig) -> Result<Nmea, Error<'a>> {
+        config.validate()?;
+        let mut nmea = Self::default();
+        nmea.required_sentences_for_nav = config.sentences;
+        Ok(nmea)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(NavConfig::default().validate(), Err(Error::EmptyNavConfig));
+        assert_eq!(
+            NavConfig::gga_rmc().with(SentenceType::APB).validate(),
+            Err(Error::Unsupported(SentenceType::APB))
+        );
+        assert_eq!(
+            NavConfig::from_types(&[SentenceType::GSV]).validate(),
+            Err(Error::Unsupported(SentenceType::GSV))
+        );
+        let presets = [
+            NavConfig::gga_rmc(),
+            NavConfig::gns_only(),
+            NavConfig::all_position_sentences(),
+        ];
+        for config in presets {
+            let parsable = config.sentences().is_subset(&SentenceMask::ENABLED);
+            assert_eq!(config.validate().is_ok(), parsable, "{:?}", config);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "GLL", feature = "GNS", feature = "RMC"))]
+    fn test_all_position_sentences() {
+        use crate::sentences::FixType;
+
+        let mut nmea = Nmea::create_with_config(NavConfig::all_position_sentences()).unwrap();
+        let sentences = [
+            "$GPGGA,224749.00,3333.4268304,N,11153.3538273,W,2,19,0.6,406.110,M,-26.294,M,6.0,0138*78",
+            "$GPRMC,224749.00,A,3333.4268304,N,11153.3538273,W,0.0,0.0,010324,,,D*40",
+            "$GPGLL,3333.4268304,N,11153.3538273,W,224749.00,A,D*73",
+        ];
+        for sentence in sentences {
+            assert_eq!(nmea.parse_for_fix(sentence), Ok(FixType::Invalid));
+        }
+        let gns = "$GPGNS,224749.00,3333.4268304,N,11153.3538273,W,D,19,0.6,406.110,-26.294,6.0,0138,S,*46";
+        assert_eq!(nmea.parse_for_fix(gns), Ok(FixType::DGps));
+    }
+}
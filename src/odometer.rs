@@ -0,0 +1,276 @@
+//! Distance traveled, accumulated from the fixes and the VLW sentences, see
+//! [`Nmea::set_odometer()`].
+
+use chrono::{NaiveDateTime, NaiveTime};
+
+use crate::{geo, sentences::VlwData, Float, Nmea};
+
+/// Meters in a nautical mile.
+const METERS_PER_NAUTICAL_MILE: f64 = 1852.;
+
+/// Settings of the odometer, see [`Nmea::set_odometer()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OdometerConfig {
+    /// Distance in meters from the last counted position below which the
+    /// position is not counted, so the noise of a stationary receiver does
+    /// not add up
+    pub min_step: f32,
+    /// Maximum speed in knots, faster steps are rejected as outliers
+    pub max_speed: f32,
+    /// Number of consecutive rejected steps after which the counting starts
+    /// again from the current position, without adding the step
+    pub max_rejections: u8,
+}
+
+impl Default for OdometerConfig {
+    fn default() -> Self {
+        Self {
+            min_step: 5.,
+            max_speed: 500.,
+            max_rejections: 5,
+        }
+    }
+}
+
+/// Distances traveled, see [`Nmea::odometer()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Odometer {
+    /// Distance over ground in meters
+    pub total: f64,
+    /// Distance over ground in meters since [`Nmea::reset_trip()`]
+    pub trip: f64,
+    /// Distance through water in meters of the VLW sentences since
+    /// [`Nmea::reset_trip()`], `None` before the first VLW
+    pub water_trip: Option<f64>,
+    /// Total distance through water in meters of the last VLW
+    pub water_total: Option<f64>,
+    /// Steps rejected as outliers
+    pub rejected: u32,
+}
+
+impl Odometer {
+    /// Returns the trip distance over ground minus the one through water in
+    /// meters, which comes from the current and the leeway, `None` without
+    /// VLW.
+    pub fn drift(&self) -> Option<f64> {
+        Some(self.trip - self.water_trip?)
+    }
+}
+
+/// The last counted position.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Anchor {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    fix_time: NaiveTime,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    fix_datetime: Option<NaiveDateTime>,
+    latitude: Float,
+    longitude: Float,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct OdometerState {
+    config: Option<OdometerConfig>,
+    pub(crate) odometer: Odometer,
+    pub(crate) anchor: Option<Anchor>,
+    /// Consecutive rejected steps
+    rejections: u8,
+}
+
+impl OdometerState {
+    /// The state after a reset, keeping the distances but not the last
+    /// position.
+    pub(crate) fn cleared(&self) -> Self {
+        Self {
+            config: self.config,
+            odometer: self.odometer,
+            ..Self::default()
+        }
+    }
+}
+
+impl Nmea {
+    /// Enables the odometer, `None` to stop counting, see [`Odometer`].
+    ///
+    /// The valid positions of GGA, RMC, GNS and GLL are counted when they
+    /// moved at least [`OdometerConfig::min_step`] from the last counted
+    /// one, unless the implied speed is faster than
+    /// [`OdometerConfig::max_speed`]. The distances through water are taken
+    /// from the total distance of the VLW sentences, ignoring the resets of
+    /// the log. The distances are kept by [`reset()`](Self::reset) and in
+    /// the snapshots of the `snapshot` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{Nmea, OdometerConfig};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_odometer(Some(OdometerConfig::default()));
+    /// # #[cfg(feature = "GLL")]
+    /// # {
+    /// nmea.parse("$GPGLL,4830.000,N,01130.000,E,120000.00,A,A*66").unwrap();
+    /// // a nautical mile to the North
+    /// nmea.parse("$GPGLL,4831.000,N,01130.000,E,120200.00,A,A*65").unwrap();
+    /// assert!((nmea.odometer().trip - 1853.).abs() < 1.);
+    /// # }
+    /// ```
+    pub fn set_odometer(&mut self, config: Option<OdometerConfig>) {
+        self.odometer.config = config;
+        self.odometer.anchor = None;
+        self.odometer.rejections = 0;
+    }
+
+    /// Returns the distances traveled.
+    pub fn odometer(&self) -> Odometer {
+        self.odometer.odometer
+    }
+
+    /// Starts a new trip, setting the trip distances to zero.
+    pub fn reset_trip(&mut self) {
+        let odometer = &mut self.odometer.odometer;
+        odometer.trip = 0.;
+        odometer.water_trip = odometer.water_trip.map(|_| 0.);
+    }
+
+    /// Counts the position which was just stored.
+    pub(crate) fn update_odometer(&mut self) {
+        let Some(config) = self.odometer.config else {
+            return;
+        };
+        if !self.fix_type.is_some_and(|fix_type| fix_type.is_valid()) {
+            return;
+        }
+        let (Some(fix_time), Some(latitude), Some(longitude)) =
+            (self.fix_time, self.latitude, self.longitude)
+        else {
+            return;
+        };
+        let current = Anchor {
+            fix_time,
+            fix_datetime: self.fix_datetime,
+            latitude,
+            longitude,
+        };
+        let state = &mut self.odometer;
+        let Some(anchor) = state.anchor else {
+            state.anchor = Some(current);
+            return;
+        };
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let distance =
+            geo::distance((anchor.latitude, anchor.longitude), (latitude, longitude)) as f64;
+        if distance < f64::from(config.min_step) {
+            return;
+        }
+        let elapsed = geo::elapsed(
+            (anchor.fix_time, anchor.fix_datetime),
+            (fix_time, self.fix_datetime),
+        );
+        if elapsed < 0. {
+            // the time went back, e.g. a replayed log
+            state.anchor = Some(current);
+            return;
+        }
+        if elapsed == 0. {
+            return;
+        }
+        let speed = distance as f32 / elapsed / geo::METERS_PER_SECOND_PER_KNOT;
+        if speed > config.max_speed {
+            state.odometer.rejected = state.odometer.rejected.saturating_add(1);
+            state.rejections = state.rejections.saturating_add(1);
+            if state.rejections > config.max_rejections {
+                state.anchor = Some(current);
+                state.rejections = 0;
+            }
+            return;
+        }
+        state.odometer.total += distance;
+        state.odometer.trip += distance;
+        state.anchor = Some(current);
+        state.rejections = 0;
+    }
+
+    pub(crate) fn merge_vlw_data(&mut self, vlw: &VlwData) {
+        if self.odometer.config.is_none() {
+            return;
+        }
+        let Some(total) = vlw.total_water_distance else {
+            return;
+        };
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let total = total as f64 * METERS_PER_NAUTICAL_MILE;
+        let odometer = &mut self.odometer.odometer;
+        let step = odometer
+            .water_total
+            .map_or(0., |last| total - last)
+            // the log was reset
+            .max(0.);
+        odometer.water_trip = Some(odometer.water_trip.unwrap_or(0.) + step);
+        odometer.water_total = Some(total);
+    }
+}
+
+#[cfg(all(test, feature = "GLL", feature = "VLW"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_odometer() {
+        let mut nmea = Nmea::default();
+        nmea.set_odometer(Some(OdometerConfig {
+            max_rejections: 1,
+            ..OdometerConfig::default()
+        }));
+        let parse = |nmea: &mut Nmea, sentence: &str| {
+            let checksum = sentence.bytes().fold(0, |checksum, byte| checksum ^ byte);
+            nmea.parse(&format!("${}*{:02X}", sentence, checksum))
+                .unwrap();
+        };
+        parse(&mut nmea, "GPGLL,4830.000,N,01130.000,E,120000.00,A,A");
+        // the noise of a stationary receiver
+        parse(&mut nmea, "GPGLL,4830.001,N,01130.001,E,120001.00,A,A");
+        parse(&mut nmea, "GPGLL,4830.000,N,01130.000,E,120002.00,A,A");
+        assert_eq!(nmea.odometer().total, 0.);
+        parse(&mut nmea, "GPGLL,4830.010,N,01130.000,E,120003.00,A,A");
+        assert!((nmea.odometer().trip - 18.5).abs() < 2.);
+        // an outlier, 18 km in a second
+        parse(&mut nmea, "GPGLL,4840.010,N,01130.000,E,120004.00,A,A");
+        assert_eq!(nmea.odometer().rejected, 1);
+        // an invalid fix is not counted
+        parse(&mut nmea, "GPGLL,4830.020,N,01130.000,E,120005.00,V,N");
+        parse(&mut nmea, "GPGLL,4830.020,N,01130.000,E,120006.00,A,A");
+        assert!((nmea.odometer().total - 37.1).abs() < 2.);
+
+        // the receiver moved while off, counted from the second position
+        parse(&mut nmea, "GPGLL,5030.020,N,01130.000,E,120007.00,A,A");
+        parse(&mut nmea, "GPGLL,5030.020,N,01130.000,E,120008.00,A,A");
+        assert_eq!(nmea.odometer().rejected, 3);
+        parse(&mut nmea, "GPGLL,5030.030,N,01130.000,E,120009.00,A,A");
+        assert!((nmea.odometer().total - 55.6).abs() < 2.);
+
+        parse(&mut nmea, "IIVLW,100.0,N,1.0,N");
+        parse(&mut nmea, "IIVLW,100.5,N,1.5,N");
+        // the log was reset
+        parse(&mut nmea, "IIVLW,0.0,N,0.0,N");
+        parse(&mut nmea, "IIVLW,0.1,N,0.1,N");
+        let odometer = nmea.odometer();
+        assert!((odometer.water_trip.unwrap() - 0.6 * 1852.).abs() < 0.1);
+        assert!((odometer.water_total.unwrap() - 0.1 * 1852.).abs() < 0.1);
+        assert!((odometer.drift().unwrap() - (55.6 - 0.6 * 1852.)).abs() < 2.);
+
+        nmea.reset_trip();
+        nmea.reset();
+        let odometer = nmea.odometer();
+        assert_eq!((odometer.trip, odometer.water_trip), (0., Some(0.)));
+        assert!((odometer.total - 55.6).abs() < 2.);
+    }
+}
@@ -1,16 +1,15 @@
-use core::str;
+use core::{fmt, str};
 
+use arrayvec::ArrayString;
 use nom::{
-    bytes::complete::{take, take_until},
+    bytes::complete::{tag, take, take_until},
     character::complete::char,
-    combinator::map_res,
+    combinator::{map_res, verify},
     sequence::preceded,
     IResult,
 };
 
-use cfg_if::cfg_if;
-
-use crate::{sentences::*, Error, SentenceType};
+use crate::{sentences::*, tag_block::skip_tag_block, Error, SentenceType};
 
 /// The maximum message length parsable by the crate.
 ///
@@ -37,6 +36,7 @@ pub const TEXT_PARAMETER_MAX_LEN: usize = 64;
 /// A known and parsable Nmea sentence type.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct NmeaSentence<'a> {
     pub talker_id: &'a str,
     pub message_id: SentenceType,
@@ -44,7 +44,69 @@ pub struct NmeaSentence<'a> {
     pub checksum: u8,
 }
 
+/// A talker ID kept with the data of its sentence, e.g. `WI` for weather
+/// instruments or `II` for integrated instrumentation.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TalkerId([u8; 2]);
+
+impl TalkerId {
+    /// Returns `None` unless the talker ID is two ASCII characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::TalkerId;
+    ///
+    /// assert_eq!(TalkerId::new("WI").unwrap().as_str(), "WI");
+    /// assert_eq!(TalkerId::new("W"), None);
+    /// ```
+    pub fn new(talker_id: &str) -> Option<Self> {
+        match *talker_id.as_bytes() {
+            [first, second] if first.is_ascii() && second.is_ascii() => Some(Self([first, second])),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for TalkerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for TalkerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl NmeaSentence<'_> {
+    /// Returns the talker ID, to keep it with the data of the sentence.
+    pub fn talker(&self) -> Option<TalkerId> {
+        TalkerId::new(self.talker_id)
+    }
+
+    /// Returns the manufacturer mnemonic of proprietary sentences, e.g. `GRM`
+    /// for `$PGRMZ`.
+    ///
+    /// Proprietary sentences start with `P` and a 3 character mnemonic,
+    /// which the framing splits into the talker ID (`PG`) and the sentence
+    /// type (`RMZ`).
+    pub fn manufacturer(&self) -> Option<ArrayString<3>> {
+        let manufacturer = self.talker_id.strip_prefix('P')?;
+        let mut mnemonic = ArrayString::from(manufacturer).ok()?;
+        mnemonic
+            .try_push_str(self.message_id.as_str().get(..3 - manufacturer.len())?)
+            .ok()?;
+        Some(mnemonic)
+    }
+
     pub fn calc_checksum(&self) -> u8 {
         checksum(
             self.talker_id
@@ -75,7 +137,7 @@ fn parse_sentence_type(i: &str) -> IResult<&str, SentenceType> {
     })(i)
 }
 
-fn do_parse_nmea_sentence(i: &str) -> IResult<&str, NmeaSentence> {
+fn do_parse_nmea_sentence(i: &str) -> IResult<&str, NmeaSentence<'_>> {
     let (i, talker_id) = preceded(char('$'), take(2usize))(i)?;
     let (i, message_id) = parse_sentence_type(i)?;
     let (i, _) = char(',')(i)?;
@@ -93,7 +155,13 @@ fn do_parse_nmea_sentence(i: &str) -> IResult<&str, NmeaSentence> {
     ))
 }
 
-pub fn parse_nmea_sentence(sentence: &str) -> core::result::Result<NmeaSentence, Error<'_>> {
+/// Parses the framing of a sentence: talker ID, sentence type, data fields
+/// and checksum.
+///
+/// Leading whitespace and trailing whitespace, e.g. the `\r\n` line ending,
+/// are ignored.
+pub fn parse_nmea_sentence(sentence: &str) -> core::result::Result<NmeaSentence<'_>, Error<'_>> {
+    let sentence = skip_tag_block(sentence)?.trim_matches(|c: char| c.is_ascii_whitespace());
     if sentence.len() > SENTENCE_MAX_LEN {
         Err(Error::SentenceLength(sentence.len()))
     } else {
@@ -101,10 +169,157 @@ pub fn parse_nmea_sentence(sentence: &str) -> core::result::Result<NmeaSentence,
     }
 }
 
+/// A proprietary sentence: `$P`, a 3 character manufacturer mnemonic and a
+/// manufacturer specific message ID, e.g. `$PUBX,00,...` or `$PSRF103,...`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProprietarySentence<'a> {
+    pub manufacturer: &'a str,
+    /// The rest of the address field, empty if the message ID is the first
+    /// data field
+    pub message_id: &'a str,
+    pub data: &'a str,
+    pub checksum: u8,
+}
+
+impl ProprietarySentence<'_> {
+    pub fn calc_checksum(&self) -> u8 {
+        checksum(
+            b"P".iter()
+                .chain(self.manufacturer.as_bytes())
+                .chain(self.message_id.as_bytes())
+                .chain(b",")
+                .chain(self.data.as_bytes()),
+        )
+    }
+}
+
+fn do_parse_proprietary_sentence(i: &str) -> IResult<&str, ProprietarySentence<'_>> {
+    let (i, _) = tag("$P")(i)?;
+    let (i, manufacturer) = verify(take(3usize), |manufacturer: &str| {
+        manufacturer.bytes().all(|c| c.is_ascii_alphanumeric())
+    })(i)?;
+    let (i, message_id) = take_until(",")(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, data) = take_until("*")(i)?;
+    let (i, checksum) = parse_checksum(i)?;
+
+    Ok((
+        i,
+        ProprietarySentence {
+            manufacturer,
+            message_id,
+            data,
+            checksum,
+        },
+    ))
+}
+
+/// Parses the framing of a proprietary sentence, see [`ProprietarySentence`].
+///
+/// Like [`parse_nmea_sentence()`], surrounding whitespace is ignored and the
+/// checksum is not verified.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::parse_proprietary_sentence;
+///
+/// let sentence = parse_proprietary_sentence("$PSRF103,00,01,00,01*25").unwrap();
+/// assert_eq!(sentence.manufacturer, "SRF");
+/// assert_eq!(sentence.message_id, "103");
+/// assert_eq!(sentence.data, "00,01,00,01");
+/// assert_eq!(sentence.checksum, sentence.calc_checksum());
+/// ```
+pub fn parse_proprietary_sentence(
+    sentence: &str,
+) -> core::result::Result<ProprietarySentence<'_>, Error<'_>> {
+    let sentence = sentence.trim_matches(|c: char| c.is_ascii_whitespace());
+    if sentence.len() > SENTENCE_MAX_LEN {
+        Err(Error::SentenceLength(sentence.len()))
+    } else {
+        Ok(do_parse_proprietary_sentence(sentence)?.1)
+    }
+}
+
+/// Parses the framing of a sentence from bytes, like
+/// [`parse_nmea_sentence()`], without converting the whole buffer to a
+/// string first.
+///
+/// The bytes are checked to be ASCII while looking for the checksum, and
+/// only the talker ID and the data fields are converted to strings.
+/// Sentences with a TAG block or which fail to frame go through
+/// [`parse_nmea_sentence()`], for the same results and errors.
+///
+/// # Errors
+///
+/// [`Error::Utf8Decoding`] when the bytes of a sentence which fails to
+/// frame are not a valid UTF-8 string.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{parse_nmea_sentence_bytes, SentenceType};
+///
+/// let sentence = parse_nmea_sentence_bytes(b"$GPHDT,274.07,T*03\r\n").unwrap();
+/// assert_eq!(sentence.message_id, SentenceType::HDT);
+/// assert_eq!(sentence.data, "274.07,T");
+/// ```
+pub fn parse_nmea_sentence_bytes(
+    sentence: &[u8],
+) -> core::result::Result<NmeaSentence<'_>, Error<'_>> {
+    match frame_bytes(sentence) {
+        Some(nmea_sentence) => Ok(nmea_sentence),
+        None => parse_nmea_sentence(str::from_utf8(sentence).map_err(|_| Error::Utf8Decoding)?),
+    }
+}
+
+/// Frames an ASCII sentence without TAG block, `None` for the other inputs.
+fn frame_bytes(input: &[u8]) -> Option<NmeaSentence<'_>> {
+    let start = input.iter().position(|byte| !byte.is_ascii_whitespace())?;
+    let end = input.iter().rposition(|byte| !byte.is_ascii_whitespace())? + 1;
+    let sentence = &input[start..end];
+    if sentence.len() > SENTENCE_MAX_LEN {
+        return None;
+    }
+    let [b'$', _, _, _, _, _, b',', fields @ ..] = sentence else {
+        return None;
+    };
+    if !sentence[..7].is_ascii() {
+        return None;
+    }
+    // the data fields are ASCII up to the checksum
+    let star = fields
+        .iter()
+        .position(|&byte| byte == b'*' || !byte.is_ascii())?;
+    let [b'*', high, low, rest @ ..] = &fields[star..] else {
+        return None;
+    };
+    if !rest.is_ascii() {
+        return None;
+    }
+    let hex = |byte: u8| char::from(byte).to_digit(16);
+    let checksum = hex(*high)? << 4 | hex(*low)?;
+    Some(NmeaSentence {
+        talker_id: str::from_utf8(&sentence[1..3]).ok()?,
+        message_id: SentenceType::try_from(&sentence[3..6]).ok()?,
+        data: str::from_utf8(&fields[..star]).ok()?,
+        checksum: checksum as u8,
+    })
+}
+
 /// The result of parsing a single NMEA message.
+///
+/// It is as large as its largest variant, the sentences with two waypoint
+/// IDs. The variants are stored inline whatever the features, so the crate
+/// does not need an allocator, at the cost of copying the largest variant
+/// when moving it. To avoid moving it on small targets, see
+/// [`parse_str_ref()`](crate::parse_str_ref).
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum ParseResult {
     AAM(AamData),
     ALM(AlmData),
@@ -114,7 +329,9 @@ pub enum ParseResult {
     BWW(BwwData),
     DBK(DbkData),
     DBS(DbsData),
+    DBT(DbtData),
     DPT(DptData),
+    DTM(DtmData),
     GBS(GbsData),
     GGA(GgaData),
     GLL(GllData),
@@ -130,6 +347,7 @@ pub enum ParseResult {
     TTM(TtmData),
     TXT(TxtData),
     VHW(VhwData),
+    VLW(VlwData),
     VTG(VtgData),
     WNC(WncData),
     ZDA(ZdaData),
@@ -151,6 +369,7 @@ impl From<&ParseResult> for SentenceType {
             ParseResult::BWW(_) => SentenceType::BWW,
             ParseResult::DBK(_) => SentenceType::DBK,
             ParseResult::DBS(_) => SentenceType::DBS,
+            ParseResult::DBT(_) => SentenceType::DBT,
             ParseResult::GBS(_) => SentenceType::GBS,
             ParseResult::GGA(_) => SentenceType::GGA,
             ParseResult::GLL(_) => SentenceType::GLL,
@@ -166,6 +385,7 @@ impl From<&ParseResult> for SentenceType {
             ParseResult::TTM(_) => SentenceType::TTM,
             ParseResult::TXT(_) => SentenceType::TXT,
             ParseResult::VHW(_) => SentenceType::VHW,
+            ParseResult::VLW(_) => SentenceType::VLW,
             ParseResult::VTG(_) => SentenceType::VTG,
             ParseResult::WNC(_) => SentenceType::WNC,
             ParseResult::ZFO(_) => SentenceType::ZFO,
@@ -173,6 +393,7 @@ impl From<&ParseResult> for SentenceType {
             ParseResult::PGRMZ(_) => SentenceType::RMZ,
             ParseResult::ZDA(_) => SentenceType::ZDA,
             ParseResult::DPT(_) => SentenceType::DPT,
+            ParseResult::DTM(_) => SentenceType::DTM,
             ParseResult::Unsupported(sentence_type) => *sentence_type,
         }
     }
@@ -184,293 +405,231 @@ impl From<&ParseResult> for SentenceType {
 ///
 /// Apart from errors returned by the message parsing itself, it will return
 /// [`Error::Utf8Decoding`] when the bytes are not a valid UTF-8 string.
-pub fn parse_bytes(sentence_input: &[u8]) -> Result<ParseResult, Error> {
+pub fn parse_bytes(sentence_input: &[u8]) -> Result<ParseResult, Error<'_>> {
+    // the framing of bytes only succeeds for ASCII sentences
+    if let Some(nmea_sentence) = frame_bytes(sentence_input) {
+        return parse_framed(nmea_sentence);
+    }
     let string = core::str::from_utf8(sentence_input).map_err(|_err| Error::Utf8Decoding)?;
 
     parse_str(string)
 }
 
+/// Parse all NMEA 0183 sentences of a buffer, e.g. several sentences
+/// delivered by one read, see [`stream::sentences()`](crate::stream::sentences).
+///
+/// Sentences dropped by the framing are returned as errors too:
+/// [`Error::SentenceLength`] for a sentence longer than
+/// [`SENTENCE_MAX_LEN`] and [`Error::ASCII`] for a sentence with non-ASCII
+/// bytes.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{parse_all_bytes, ParseResult};
+///
+/// let buffer = b"$GPHDT,274.07,T*03\r\n$GPHDT,274.07,T*00\r\n";
+/// let results: Vec<_> = parse_all_bytes(buffer).collect();
+/// assert_eq!(results.len(), 2);
+/// # #[cfg(feature = "HDT")]
+/// assert!(matches!(results[0], Ok(ParseResult::HDT(_))));
+/// assert!(results[1].is_err());
+/// ```
+pub fn parse_all_bytes(
+    sentences_input: &[u8],
+) -> impl Iterator<Item = Result<ParseResult, Error<'_>>> {
+    let mut sentences = crate::stream::sentences(sentences_input);
+    core::iter::from_fn(move || sentences.next_framed())
+        .map(|sentence| sentence.and_then(parse_str))
+}
+
+/// The result of [`parse_str_or_raw()`].
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, PartialEq)]
+pub enum ParsedOrRaw<'a> {
+    /// The sentence is supported and enabled by the crate features.
+    Parsed(ParseResult),
+    /// The fields of a valid sentence that the crate does not parse,
+    /// either because it is not supported or because its feature is
+    /// disabled.
+    Unsupported(NmeaSentence<'a>),
+    /// A valid proprietary sentence with a message ID unknown to the crate.
+    Proprietary(ProprietarySentence<'a>),
+}
+
+/// Parse a NMEA 0183 sentence like [`parse_str()`], but return the raw
+/// fields of sentences the crate does not parse instead of discarding them.
+///
+/// This allows implementing a fallback parser for exotic sentences without
+/// reframing the string.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{parse_str_or_raw, ParsedOrRaw, SentenceType};
+///
+/// let raw = match parse_str_or_raw("$GPHDM,274.07,M*03").unwrap() {
+///     ParsedOrRaw::Unsupported(raw) => raw,
+///     _ => unreachable!(),
+/// };
+/// assert_eq!(raw.talker_id, "GP");
+/// assert_eq!(raw.message_id, SentenceType::HDM);
+/// assert_eq!(raw.data, "274.07,M");
+///
+/// let ParsedOrRaw::Proprietary(ubx) = parse_str_or_raw("$PUBX,40,GLL,0,0,0,0*5C").unwrap() else {
+///     unreachable!()
+/// };
+/// assert_eq!(ubx.manufacturer, "UBX");
+/// assert_eq!(ubx.data, "40,GLL,0,0,0,0");
+/// ```
+pub fn parse_str_or_raw(sentence_input: &str) -> Result<ParsedOrRaw<'_>, Error<'_>> {
+    match parse_str(sentence_input) {
+        Ok(ParseResult::Unsupported(_)) | Err(Error::DisabledSentence) => {
+            // the sentence was already validated
+            parse_nmea_sentence(sentence_input).map(ParsedOrRaw::Unsupported)
+        }
+        Ok(parse_result) => Ok(ParsedOrRaw::Parsed(parse_result)),
+        Err(err) => match parse_proprietary_sentence(sentence_input) {
+            Ok(proprietary) if proprietary.checksum == proprietary.calc_checksum() => {
+                Ok(ParsedOrRaw::Proprietary(proprietary))
+            }
+            _ => Err(err),
+        },
+    }
+}
+
+/// Parses the data of a sentence of a type with a parser.
+pub(crate) type ParseFn = for<'a> fn(NmeaSentence<'a>) -> Result<ParseResult, Error<'a>>;
+
+/// Passes the data of a sentence of a type to its method of a
+/// [`SentenceSink`](crate::SentenceSink).
+pub(crate) type SinkFn =
+    for<'a> fn(NmeaSentence<'a>, &mut dyn crate::SentenceSink) -> Result<(), Error<'a>>;
+
+/// How [`parse_str()`] and [`parse_str_ref()`](crate::parse_str_ref) handle
+/// a sentence type, see `SentenceType::DISPATCH`.
+#[derive(Clone, Copy)]
+#[allow(dead_code)] // depending on the enabled sentence features
+pub(crate) enum Dispatch {
+    Parser(ParseFn, SinkFn),
+    /// The type has a parser behind a disabled feature
+    Disabled,
+    Unsupported,
+}
+
+impl SentenceType {
+    /// Returns `true` if [`parse_str()`] can parse the sentence type with
+    /// the enabled features of this build.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::SentenceType;
+    ///
+    /// assert_eq!(SentenceType::GGA.is_parsable(), cfg!(feature = "GGA"));
+    /// assert!(!SentenceType::ROT.is_parsable());
+    /// ```
+    pub const fn is_parsable(self) -> bool {
+        matches!(Self::DISPATCH[self as usize], Dispatch::Parser(..))
+    }
+}
+
+const PARSABLE_COUNT: usize = {
+    let mut count = 0;
+    let mut i = 0;
+    while i < SentenceType::TYPES.len() {
+        if SentenceType::TYPES[i].is_parsable() {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+};
+
+const PARSABLE: [SentenceType; PARSABLE_COUNT] = {
+    let mut parsable = [SentenceType::AAM; PARSABLE_COUNT];
+    let mut count = 0;
+    let mut i = 0;
+    while i < SentenceType::TYPES.len() {
+        if SentenceType::TYPES[i].is_parsable() {
+            parsable[count] = SentenceType::TYPES[i];
+            count += 1;
+        }
+        i += 1;
+    }
+    parsable
+};
+
+/// Returns the sentence types which [`parse_str()`] can parse with the
+/// enabled features of this build, in the order of [`SentenceType`].
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{supported_sentences, SentenceType};
+///
+/// let supported = supported_sentences();
+/// assert_eq!(supported.contains(&SentenceType::GSV), cfg!(feature = "GSV"));
+/// assert!(supported.iter().all(|ty| ty.is_parsable()));
+/// ```
+pub fn supported_sentences() -> &'static [SentenceType] {
+    &PARSABLE
+}
+
 /// Parse a NMEA 0183 sentence from a string slice and extract data from it.
 ///
-/// Should not contain `\r\n` ending.
+/// Leading whitespace and trailing whitespace, e.g. the `\r\n` line ending,
+/// are ignored.
 ///
 /// # Errors
 ///
 /// - [`Error::ASCII`] when string contains non-ASCII characters.
-pub fn parse_str(sentence_input: &str) -> Result<ParseResult, Error> {
+pub fn parse_str(sentence_input: &str) -> Result<ParseResult, Error<'_>> {
+    let nmea_sentence = checked_sentence(sentence_input)?;
+    dispatch(nmea_sentence)
+}
+
+/// Extracts the data of a sentence framed by the caller, e.g. with
+/// [`parse_nmea_sentence()`] or a custom framer, like [`parse_str()`].
+///
+/// # Errors
+///
+/// - [`Error::ASCII`] when the talker ID or the fields are not ASCII.
+/// - [`Error::ChecksumMismatch`] when the checksum of the sentence does not
+///   match its fields.
+pub fn parse_framed(nmea_sentence: NmeaSentence<'_>) -> Result<ParseResult, Error<'_>> {
+    dispatch(check_sentence(nmea_sentence)?)
+}
+
+/// Extracts the data of a checked sentence.
+pub(crate) fn dispatch(nmea_sentence: NmeaSentence<'_>) -> Result<ParseResult, Error<'_>> {
+    match SentenceType::DISPATCH[nmea_sentence.message_id as usize] {
+        Dispatch::Parser(parse, _) => parse(nmea_sentence),
+        Dispatch::Disabled => Err(Error::DisabledSentence),
+        Dispatch::Unsupported => Ok(ParseResult::Unsupported(nmea_sentence.message_id)),
+    }
+}
+
+/// Frames a sentence and checks its characters and checksum.
+pub(crate) fn checked_sentence(sentence_input: &str) -> Result<NmeaSentence<'_>, Error<'_>> {
     if !sentence_input.is_ascii() {
         return Err(Error::ASCII);
     }
 
-    let nmea_sentence = parse_nmea_sentence(sentence_input)?;
-    let calculated_checksum = nmea_sentence.calc_checksum();
+    check_sentence(parse_nmea_sentence(sentence_input)?)
+}
 
+/// Checks the characters and checksum of a framed sentence.
+pub(crate) fn check_sentence(
+    nmea_sentence: NmeaSentence<'_>,
+) -> Result<NmeaSentence<'_>, Error<'_>> {
+    if !(nmea_sentence.talker_id.is_ascii() && nmea_sentence.data.is_ascii()) {
+        return Err(Error::ASCII);
+    }
+
+    let calculated_checksum = nmea_sentence.calc_checksum();
     if nmea_sentence.checksum == calculated_checksum {
-        // Ordered alphabetically
-        match nmea_sentence.message_id {
-            SentenceType::AAM => {
-                cfg_if! {
-                    if #[cfg(feature = "AAM")] {
-                        parse_aam(nmea_sentence).map(ParseResult::AAM)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::ALM => {
-                cfg_if! {
-                    if #[cfg(feature = "ALM")] {
-                        parse_alm(nmea_sentence).map(ParseResult::ALM)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::APA => {
-                cfg_if! {
-                    if #[cfg(feature = "APA")] {
-                        parse_apa(nmea_sentence).map(ParseResult::APA)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::BOD => {
-                cfg_if! {
-                    if #[cfg(feature = "BOD")] {
-                        parse_bod(nmea_sentence).map(ParseResult::BOD)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::BWC => {
-                cfg_if! {
-                    if #[cfg(feature = "BWC")] {
-                        parse_bwc(nmea_sentence).map(ParseResult::BWC)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::BWW => {
-                cfg_if! {
-                    if #[cfg(feature = "BWW")] {
-                        parse_bww(nmea_sentence).map(ParseResult::BWW)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::DBK => {
-                cfg_if! {
-                    if #[cfg(feature = "DBK")] {
-                        parse_dbk(nmea_sentence).map(Into::into)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::GBS => {
-                cfg_if! {
-                    if #[cfg(feature = "GBS")] {
-                        parse_gbs(nmea_sentence).map(ParseResult::GBS)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::GGA => {
-                cfg_if! {
-                    if #[cfg(feature = "GGA")] {
-                        parse_gga(nmea_sentence).map(ParseResult::GGA)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::GLL => {
-                cfg_if! {
-                    if #[cfg(feature = "GLL")] {
-                        parse_gll(nmea_sentence).map(ParseResult::GLL)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::GNS => {
-                cfg_if! {
-                    if #[cfg(feature = "GNS")] {
-                        parse_gns(nmea_sentence).map(ParseResult::GNS)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::GSA => {
-                cfg_if! {
-                    if #[cfg(feature = "GSA")] {
-                        parse_gsa(nmea_sentence).map(ParseResult::GSA)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::GST => {
-                cfg_if! {
-                    if #[cfg(feature = "GST")] {
-                        parse_gst(nmea_sentence).map(ParseResult::GST)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::GSV => {
-                cfg_if! {
-                    if #[cfg(feature = "GSV")] {
-                        parse_gsv(nmea_sentence).map(ParseResult::GSV)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::HDT => {
-                cfg_if! {
-                    if #[cfg(feature = "HDT")] {
-                        parse_hdt(nmea_sentence).map(ParseResult::HDT)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::MDA => {
-                cfg_if! {
-                    if #[cfg(feature = "MDA")] {
-                        parse_mda(nmea_sentence).map(ParseResult::MDA)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::MTW => {
-                cfg_if! {
-                    if #[cfg(feature = "MTW")] {
-                        parse_mtw(nmea_sentence).map(ParseResult::MTW)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::MWV => {
-                cfg_if! {
-                    if #[cfg(feature = "MWV")] {
-                        parse_mwv(nmea_sentence).map(ParseResult::MWV)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::RMC => {
-                cfg_if! {
-                    if #[cfg(feature = "RMC")] {
-                        parse_rmc(nmea_sentence).map(ParseResult::RMC)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::RMZ => {
-                cfg_if! {
-                    if #[cfg(feature = "RMZ")] {
-                        parse_pgrmz(nmea_sentence).map(ParseResult::PGRMZ)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::TTM => {
-                cfg_if! {
-                    if #[cfg(feature = "TTM")] {
-                        parse_ttm(nmea_sentence).map(ParseResult::TTM)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::TXT => {
-                cfg_if! {
-                    if #[cfg(feature = "TXT")] {
-                        parse_txt(nmea_sentence).map(ParseResult::TXT)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::VHW => {
-                cfg_if! {
-                    if #[cfg(feature = "VHW")] {
-                        parse_vhw(nmea_sentence).map(ParseResult::VHW)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::VTG => {
-                cfg_if! {
-                    if #[cfg(feature = "VTG")] {
-                        parse_vtg(nmea_sentence).map(ParseResult::VTG)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::WNC => {
-                cfg_if! {
-                    if #[cfg(feature = "WNC")] {
-                        parse_wnc(nmea_sentence).map(ParseResult::WNC)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::ZDA => {
-                cfg_if! {
-                    if #[cfg(feature = "ZDA")] {
-                        parse_zda(nmea_sentence).map(ParseResult::ZDA)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::ZFO => {
-                cfg_if! {
-                    if #[cfg(feature = "ZFO")] {
-                        parse_zfo(nmea_sentence).map(ParseResult::ZFO)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::ZTG => {
-                cfg_if! {
-                    if #[cfg(feature = "ZTG")] {
-                        parse_ztg(nmea_sentence).map(ParseResult::ZTG)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            SentenceType::DPT => {
-                cfg_if! {
-                    if #[cfg(feature = "DPT")] {
-                        parse_dpt(nmea_sentence).map(ParseResult::DPT)
-                    } else {
-                        return Err(Error::DisabledSentence);
-                    }
-                }
-            }
-            sentence_type => Ok(ParseResult::Unsupported(sentence_type)),
-        }
+        Ok(nmea_sentence)
     } else {
         Err(Error::ChecksumMismatch {
             calculated: calculated_checksum,
@@ -478,3 +637,65 @@ pub fn parse_str(sentence_input: &str) -> Result<ParseResult, Error> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nmea_sentence_bytes() {
+        for sentence in [
+            "$GPHDT,274.07,T*03",
+            " $GPHDT,274.07,T*03\r\n",
+            "$GPHDT,274.07,T*03 trailing",
+            "$GPHDT,274.07,T*3",
+            "$GPHDT,274.07,T*+3",
+            "$GPHDT,274.07,T",
+            "$GPXXX,274.07,T*03",
+            "$GPHDT*03",
+            "\\c:1622040996*5C\\$GPHDT,274.07,T*03",
+            "$GPHDT,274.07,é*03",
+            "$GPGSV,3,1,12,01,49,196,41,03,71,278,32,06,02,323,27,11,21,196,39,\
+             14,39,063,33*7F",
+            "",
+        ] {
+            assert_eq!(
+                parse_nmea_sentence_bytes(sentence.as_bytes()),
+                parse_nmea_sentence(sentence),
+                "{:?}",
+                sentence
+            );
+            assert_eq!(
+                parse_bytes(sentence.as_bytes()),
+                parse_str(sentence),
+                "{:?}",
+                sentence
+            );
+        }
+        assert_eq!(
+            parse_nmea_sentence_bytes(b"$GPHDT,274.07,\xff*03"),
+            Err(Error::Utf8Decoding)
+        );
+        assert_eq!(SentenceType::try_from(&b"GGA"[..]), Ok(SentenceType::GGA));
+        assert_eq!(
+            SentenceType::try_from(&b"\xffGA"[..]),
+            Err(Error::Utf8Decoding)
+        );
+    }
+
+    #[test]
+    fn test_parse_all_bytes_framing_errors() {
+        let long = std::format!("$GPHDT,{}*00", "1".repeat(SENTENCE_MAX_LEN));
+        let buffer = std::format!(
+            "$GPHDT,274.07,T*03\r\n{}\r\n$GPHDT,274.07,\u{e9}*03\r\n$GPHDT,274.07,T*03\r\n",
+            long
+        );
+        let results: std::vec::Vec<_> = parse_all_bytes(buffer.as_bytes()).collect();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[1], Err(Error::SentenceLength(long.len())));
+        assert_eq!(results[2], Err(Error::ASCII));
+        for result in [&results[0], &results[3]] {
+            assert_eq!(result, &parse_str("$GPHDT,274.07,T*03"));
+        }
+    }
+}
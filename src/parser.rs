@@ -2,17 +2,44 @@
 
 use core::{fmt, mem, ops::BitOr};
 
-use chrono::{NaiveDate, NaiveTime};
-use heapless::{Deque, Vec};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use heapless::Vec;
 
 use crate::{
-    parse_str,
+    angle,
+    clock::ClockRef,
+    consistency::PositionConsistency,
+    course::CourseFilterRef,
+    datetime::DateTracker,
+    datum::DatumState,
+    decimation::Decimation,
+    duplicates::DuplicateFilter,
+    events::FixEvents,
+    geofence::Geofences,
+    gsv_check::GsvCountCheck,
+    heading::{HeadingSelector, HeadingSource},
+    jump::JumpState,
+    latency::LatencyTable,
+    metadata::{check_version_framed, NmeaVersion, REGISTRY_TABLE},
+    odometer::OdometerState,
+    parse::Dispatch,
+    pps::PpsState,
+    schedule::Schedule,
     sentences::{rmc::RmcStatusOfFix, *},
-    Error, ParseResult,
+    signal::{SignalSnrs, SignalTable},
+    tag_block::TagBlock,
+    unsupported::UnsupportedTable,
+    updates::UpdateTable,
+    velocity::ClimbState,
+    weather::WeatherState,
+    Clock, CourseCheck, CourseDiscrepancy, CourseFilter, CycleSource, Device, DgpsAlarm,
+    DgpsWatchdog, Error, Float, LeapSecondInfo, LowSpeedCourseFreeze, NmeaSentence, OrbitClass,
+    ParseResult, Quirks, RtkStatus, SatId, SatIdPolicy, TimeSystemOffsets,
 };
 
-#[cfg(feature = "serde")]
-use serde::{de::Visitor, ser::SerializeSeq, Deserialize, Serialize};
+/// Number of satellites used for the fix which are kept, for all the
+/// constellations.
+const FIX_SATS_LEN: usize = 64;
 
 /// NMEA parser
 ///
@@ -43,12 +70,16 @@ pub struct Nmea {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_date: Option<NaiveDate>,
     pub fix_type: Option<FixType>,
-    pub latitude: Option<f64>,
-    pub longitude: Option<f64>,
+    pub latitude: Option<Float>,
+    pub longitude: Option<Float>,
     /// MSL Altitude in meters
     pub altitude: Option<f32>,
+    /// Speed over ground in knots
     pub speed_over_ground: Option<f32>,
+    /// Course over ground in `[0, 360)` degrees True
     pub true_course: Option<f32>,
+    /// Heading in degrees True
+    pub heading: Option<f32>,
     pub num_of_fix_satellites: Option<u32>,
     pub hdop: Option<f32>,
     pub vdop: Option<f32>,
@@ -56,12 +87,86 @@ pub struct Nmea {
     /// Geoid separation in meters
     pub geoid_separation: Option<f32>,
     pub fix_satellites_prns: Option<Vec<u32, 18>>,
+    /// Satellites used for the fix, from the GSA sentences of all the
+    /// constellations
+    fix_sats: Vec<SatId, FIX_SATS_LEN>,
+    /// Age of differential corrections in seconds
+    pub age_of_differential: Option<f32>,
+    /// Differential reference station ID
+    pub dgps_station_id: Option<u16>,
     satellites_scan: [SatsPack; GnssType::COUNT],
-    required_sentences_for_nav: SentenceMask,
+    /// Satellites of the `GN` GSV sentences
+    combined_scan: SatsPack,
+    gsv_talkers: GsvTalkers,
+    pub(crate) required_sentences_for_nav: SentenceMask,
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
-    last_fix_time: Option<NaiveTime>,
+    pub(crate) last_fix_time: Option<NaiveTime>,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub(crate) fix_datetime: Option<NaiveDateTime>,
+    pub(crate) date_tracker: DateTracker,
+    /// Date and time of the current epoch, which the ZDA sentences do not
+    /// advance unlike the date tracker
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    epoch_datetime: Option<NaiveDateTime>,
     last_txt: Option<TxtData>,
-    sentences_for_this_time: SentenceMask,
+    pub(crate) sentences_for_this_time: SentenceMask,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    clock: Option<ClockRef>,
+    dgps_watchdog: Option<DgpsWatchdog>,
+    rtk_status: RtkStatus,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    course_filter: Option<CourseFilterRef>,
+    course_gate: Option<LowSpeedCourseFreeze>,
+    last_course: Option<f32>,
+    course_check: Option<CourseCheck>,
+    /// Course and speed from the RMC and VTG of this epoch
+    rmc_motion: Option<(Option<f32>, Option<f32>)>,
+    vtg_motion: Option<(Option<f32>, Option<f32>)>,
+    /// Fix time and best fix type of the GNS sentences of this epoch
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    gns_fix: Option<(NaiveTime, FixType)>,
+    #[cfg(feature = "snr-history")]
+    snr_history: crate::snr::SnrHistory,
+    #[cfg(feature = "sat-rates")]
+    sat_rates: crate::sat_rates::SatRatesTracker,
+    #[cfg(feature = "almanac")]
+    pub(crate) almanac: crate::almanac::Almanac,
+    #[cfg(feature = "parse-timing")]
+    parse_timings: crate::timing::ParseTimings,
+    strict_version: Option<NmeaVersion>,
+    pub(crate) device: Option<Device>,
+    pub(crate) device_override: bool,
+    pub(crate) quirks: Quirks,
+    pub(crate) latency: LatencyTable,
+    pub(crate) pps: PpsState,
+    pub(crate) leap_seconds: LeapSecondInfo,
+    pub(crate) time_offsets: TimeSystemOffsets,
+    pub(crate) schedule: Schedule,
+    pub(crate) unsupported: UnsupportedTable,
+    skipped_binary_bytes: u32,
+    pub(crate) decimation: Decimation,
+    pub(crate) duplicates: DuplicateFilter,
+    pub(crate) gsv_check: GsvCountCheck,
+    pub(crate) heading_selector: HeadingSelector,
+    pub(crate) tag_block: Option<TagBlock>,
+    pub(crate) updates: UpdateTable,
+    pub(crate) cycle_source: CycleSource,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub(crate) cycle_start: Option<core::time::Duration>,
+    pub(crate) signal_table: SignalTable,
+    pub(crate) signal_snrs: SignalSnrs,
+    pub(crate) weather: WeatherState,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub(crate) datum: DatumState,
+    pub(crate) fix_events: FixEvents,
+    pub(crate) jump: JumpState,
+    pub(crate) geofences: Geofences,
+    pub(crate) odometer: OdometerState,
+    pub(crate) climb: ClimbState,
+    pub(crate) consistency: PositionConsistency,
 }
 
 impl<'a> Nmea {
@@ -105,51 +210,396 @@ impl<'a> Nmea {
     }
 
     /// Returns last fixed latitude in degrees. None if not fixed.
-    pub fn latitude(&self) -> Option<f64> {
+    pub fn latitude(&self) -> Option<Float> {
         self.latitude
     }
 
     /// Returns last fixed longitude in degrees. None if not fixed.
-    pub fn longitude(&self) -> Option<f64> {
+    pub fn longitude(&self) -> Option<Float> {
         self.longitude
     }
 
-    /// Returns altitude above WGS-84 ellipsoid, meters.
+    /// Returns the altitude above mean sea level, meters.
+    #[deprecated(note = "use `altitude_msl()`, this is not the height above the WGS-84 ellipsoid")]
     pub fn altitude(&self) -> Option<f32> {
         self.altitude
     }
 
+    /// Returns the altitude above mean sea level (geoid), meters, as sent by
+    /// GGA and GNS.
+    pub fn altitude_msl(&self) -> Option<f32> {
+        self.altitude
+    }
+
+    /// Returns the height above the WGS-84 ellipsoid, meters, i.e. the MSL
+    /// altitude plus the geoid separation.
+    pub fn altitude_ellipsoidal(&self) -> Option<f32> {
+        match (self.altitude, self.geoid_separation) {
+            (Some(alt), Some(separation)) => Some(alt + separation),
+            _ => None,
+        }
+    }
+
+    /// Returns the geoid separation, the height of the geoid (MSL) above
+    /// the WGS-84 ellipsoid, meters.
+    pub fn geoid_separation(&self) -> Option<f32> {
+        self.geoid_separation
+    }
+
     /// Returns the number of satellites use for fix.
     pub fn fix_satellites(&self) -> Option<u32> {
         self.num_of_fix_satellites
     }
 
+    /// Returns the IDs of the satellites used for the fix, see
+    /// [`GsaData::sat_ids()`].
+    ///
+    /// Receivers send one GSA sentence per constellation, e.g. GPGSA and
+    /// BDGSA, or GNGSA with System IDs: each one replaces the satellites of
+    /// its constellations, so all of them are returned.
+    pub fn fix_sat_ids(&self) -> impl Iterator<Item = SatId> + '_ {
+        self.fix_sats.iter().copied()
+    }
+
+    /// Returns the number of satellites used for the fix per constellation,
+    /// see [`fix_sat_ids()`](Self::fix_sat_ids).
+    pub fn fix_satellites_of(&self, gnss_type: GnssType) -> usize {
+        self.fix_sat_ids()
+            .filter(|id| id.gnss_type() == gnss_type)
+            .count()
+    }
+
     /// Returns the number fix HDOP
     pub fn hdop(&self) -> Option<f32> {
         self.hdop
     }
 
-    /// Returns the altitude above MSL (geoid), meters.
-    pub fn geoid_altitude(&self) -> Option<f32> {
-        match (self.altitude, self.geoid_separation) {
-            (Some(alt), Some(geoid_diff)) => Some(alt + geoid_diff),
-            _ => None,
+    /// Returns speed over ground in knots.
+    ///
+    /// VTG sentences carry both knots and km/h, the knots value is used
+    /// unless it is empty.
+    pub fn speed_over_ground(&self) -> Option<f32> {
+        self.speed_over_ground
+    }
+
+    /// Returns speed over ground in meters per second.
+    pub fn speed_over_ground_ms(&self) -> Option<f32> {
+        self.speed_over_ground.map(|knots| knots * 1852. / 3600.)
+    }
+
+    /// Returns speed over ground in kilometers per hour.
+    pub fn speed_over_ground_kmh(&self) -> Option<f32> {
+        self.speed_over_ground.map(|knots| knots * 1.852)
+    }
+
+    /// Sets the time source used by the time-based features, like the
+    /// [`DgpsWatchdog`].
+    pub fn set_clock(&mut self, clock: &'static (dyn Clock + Sync)) {
+        self.clock = Some(ClockRef(clock));
+    }
+
+    pub(crate) fn now(&self) -> Option<core::time::Duration> {
+        self.clock.map(|clock| clock.0.now())
+    }
+
+    /// Installs a watchdog for the age of differential corrections.
+    pub fn set_dgps_watchdog(&mut self, watchdog: DgpsWatchdog) {
+        self.dgps_watchdog = Some(watchdog);
+    }
+
+    /// Returns the installed differential corrections watchdog.
+    pub fn dgps_watchdog(&self) -> Option<&DgpsWatchdog> {
+        self.dgps_watchdog.as_ref()
+    }
+
+    /// Returns the active differential corrections alarm, if any.
+    pub fn dgps_alarm(&self) -> Option<DgpsAlarm> {
+        self.dgps_watchdog.as_ref().and_then(DgpsWatchdog::alarm)
+    }
+
+    /// Checks the age of the last differential corrections against the
+    /// [`Clock`], e.g. periodically from the main loop, and returns the
+    /// active alarm.
+    ///
+    /// Without a clock only the age reported by the receiver is checked.
+    pub fn check_dgps_watchdog(&mut self) -> Option<DgpsAlarm> {
+        let now = self.now();
+        let watchdog = self.dgps_watchdog.as_mut()?;
+        match now {
+            Some(now) => watchdog.check(now),
+            None => watchdog.alarm(),
+        }
+    }
+
+    fn update_dgps(&mut self, age: Option<f32>, station_id: Option<u16>) {
+        self.age_of_differential = age;
+        self.dgps_station_id = station_id;
+        let now = self.now();
+        if let Some(watchdog) = self.dgps_watchdog.as_mut() {
+            watchdog.update(age, station_id, now);
+        }
+    }
+
+    /// Installs a filter applied to the course over ground from RMC and VTG
+    /// before [`true_course`](Self::true_course) is updated.
+    pub fn set_course_filter(&mut self, filter: &'static (dyn CourseFilter + Sync)) {
+        self.course_filter = Some(CourseFilterRef(filter));
+    }
+
+    /// Sets the speed below which the course over ground from RMC and VTG
+    /// is held or cleared, see [`LowSpeedCourseFreeze`]. The gate is applied
+    /// before the [course filter](Self::set_course_filter) and, unlike it, is
+    /// kept in the serialized parser state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{LowSpeedCourseFreeze, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_course_speed_gate(Some(LowSpeedCourseFreeze::clearing(0.5)));
+    /// # #[cfg(feature = "VTG")]
+    /// # {
+    /// nmea.parse("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48").unwrap();
+    /// assert_eq!(nmea.true_course, Some(54.7));
+    /// nmea.parse("$GPVTG,270.0,T,,M,000.1,N,000.2,K*66").unwrap();
+    /// assert_eq!(nmea.true_course, None);
+    /// # }
+    /// ```
+    pub fn set_course_speed_gate(&mut self, gate: Option<LowSpeedCourseFreeze>) {
+        self.course_gate = gate;
+    }
+
+    /// Returns the speed gate of the course over ground.
+    pub fn course_speed_gate(&self) -> Option<LowSpeedCourseFreeze> {
+        self.course_gate
+    }
+
+    /// Enables the comparison of course and speed reported by RMC and VTG,
+    /// see [`course_discrepancy()`](Self::course_discrepancy).
+    pub fn set_course_check(&mut self, check: CourseCheck) {
+        self.course_check = Some(check);
+    }
+
+    /// Rejects sentences whose optional trailing fields (FAA mode,
+    /// navigational status, system and signal IDs) do not match exactly the
+    /// given revision of the standard, see
+    /// [`check_version()`](crate::metadata::check_version).
+    ///
+    /// `None`, the default, accepts the fields of any revision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{metadata::NmeaVersion, Error, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_strict_version(Some(NmeaVersion::V4_10));
+    /// # #[cfg(feature = "RMC")]
+    /// assert!(matches!(
+    ///     nmea.parse("$GPRMC,225446.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E,A*2B"),
+    ///     Err(Error::VersionMismatch { .. })
+    /// ));
+    /// ```
+    pub fn set_strict_version(&mut self, version: Option<NmeaVersion>) {
+        self.strict_version = version;
+    }
+
+    /// Sets the interpretation of the satellite numbers of GSV sentences,
+    /// see [`SatIdPolicy`] and [`Quirks`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{sentences::GnssType, Nmea, SatId, SatIdPolicy};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_sat_id_policy(SatIdPolicy::UBLOX_NMEA_4_0);
+    /// # #[cfg(feature = "GSV")]
+    /// # {
+    /// nmea.parse("$GLGSV,1,1,01,70,40,083,46*5E").unwrap();
+    /// assert_eq!(nmea.satellites()[0].sat_id(), SatId::new(GnssType::Glonass, 6));
+    /// # }
+    /// ```
+    pub fn set_sat_id_policy(&mut self, policy: SatIdPolicy) {
+        self.quirks.sat_id_policy = policy;
+    }
+
+    /// Returns the revision of the standard enforced by
+    /// [`set_strict_version()`](Self::set_strict_version).
+    pub fn strict_version(&self) -> Option<NmeaVersion> {
+        self.strict_version
+    }
+
+    /// Returns the difference between the unfiltered course and speed of
+    /// the last RMC and VTG sentences of this epoch if it exceeds the
+    /// tolerances of the [`CourseCheck`].
+    ///
+    /// Returns `None` if no check is enabled or if either sentence has not
+    /// been received yet.
+    pub fn course_discrepancy(&self) -> Option<CourseDiscrepancy> {
+        self.course_check?
+            .compare(self.rmc_motion?, self.vtg_motion?)
+    }
+
+    fn update_course(&mut self, course: Option<f32>, speed_over_ground: Option<f32>) {
+        let mut course = course.map(angle::normalize);
+        if let Some(gate) = self.course_gate {
+            course = gate.filter(self.last_course, course, speed_over_ground);
+        }
+        self.speed_over_ground = speed_over_ground;
+        self.true_course = match self.course_filter {
+            Some(filter) => filter.0.filter(self.last_course, course, speed_over_ground),
+            None => course,
+        };
+        self.last_course = self.true_course;
+        self.update_heading(HeadingSource::Cog, self.true_course);
+    }
+
+    /// Returns the SNR history of the satellites in view.
+    #[cfg(feature = "snr-history")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snr-history")))]
+    pub fn snr_history(&self) -> &crate::snr::SnrHistory {
+        &self.snr_history
+    }
+
+    /// Returns the azimuth and elevation rates of the satellites in view.
+    #[cfg(feature = "sat-rates")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sat-rates")))]
+    pub fn sat_rates(&self) -> &crate::sat_rates::SatRatesTracker {
+        &self.sat_rates
+    }
+
+    /// Returns SNR statistics over the satellites in view, see
+    /// [`SnrHistory::summary()`](crate::snr::SnrHistory::summary).
+    #[cfg(feature = "snr-history")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "snr-history")))]
+    pub fn signal_quality_summary(&self) -> crate::snr::SignalQualitySummary {
+        self.snr_history.summary()
+    }
+
+    /// Returns the parsing time per sentence type, measured with the
+    /// [`Clock`] when one is set.
+    #[cfg(feature = "parse-timing")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parse-timing")))]
+    pub fn parse_timings(&self) -> &crate::timing::ParseTimings {
+        &self.parse_timings
+    }
+
+    /// Returns the number of bytes of binary frames skipped between the
+    /// sentences, as counted by the stream reader, see
+    /// [`SentenceAccumulator::skipped_binary_bytes()`](crate::stream::SentenceAccumulator::skipped_binary_bytes).
+    ///
+    /// Like the [unsupported sentences](Self::unsupported_summary), it is
+    /// cleared by [`reset()`](Self::reset).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{stream::SentenceAccumulator, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// let mut accumulator = SentenceAccumulator::<82>::new();
+    /// for &byte in b"\xa0\xa2\x00\x01\x02\x00\x02\xb0\xb3$GPHDT,274.07,T*03\r\n" {
+    ///     if let Some(sentence) = accumulator.push(byte) {
+    ///         let _ = nmea.parse(sentence);
+    ///     }
+    /// }
+    /// nmea.count_skipped_binary_bytes(accumulator.skipped_binary_bytes());
+    /// assert_eq!(nmea.skipped_binary_bytes(), 9);
+    /// ```
+    pub fn skipped_binary_bytes(&self) -> u32 {
+        self.skipped_binary_bytes
+    }
+
+    /// Adds binary bytes skipped by the stream reader to
+    /// [`skipped_binary_bytes()`](Self::skipped_binary_bytes).
+    pub fn count_skipped_binary_bytes(&mut self, count: u32) {
+        self.skipped_binary_bytes = self.skipped_binary_bytes.wrapping_add(count);
+    }
+
+    /// Returns the RTK mode and its recent transitions.
+    pub fn rtk_status(&self) -> &RtkStatus {
+        &self.rtk_status
+    }
+
+    /// Parses a proprietary sentence and stores the RTK baseline of the
+    /// SkyTraq `$PSTI,032` and Trimble `$PTNL,AVR` sentences, see
+    /// [`RtkStatus::baseline()`].
+    ///
+    /// Returns `false` for the other proprietary sentences, which are
+    /// ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::default();
+    /// let sentence = "$PSTI,032,041457.000,170316,A,R,0.603,-0.837,-0.089,1.036,144.22,,,,,*1C";
+    /// assert_eq!(nmea.parse_proprietary(sentence), Ok(true));
+    /// assert_eq!(nmea.rtk_status().baseline_length(), Some(1.036));
+    /// assert_eq!(nmea.rtk_status().baseline_heading(), Some(144.22));
+    /// assert_eq!(nmea.parse_proprietary("$PSRF103,00,01,00,01*25"), Ok(false));
+    /// ```
+    #[cfg(feature = "vendor-specific")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "vendor-specific")))]
+    pub fn parse_proprietary(&mut self, sentence: &'a str) -> Result<bool, Error<'a>> {
+        if !sentence.is_ascii() {
+            return Err(Error::ASCII);
+        }
+        let sentence = crate::parse::parse_proprietary_sentence(sentence)?;
+        let calculated = sentence.calc_checksum();
+        if sentence.checksum != calculated {
+            return Err(Error::ChecksumMismatch {
+                calculated,
+                found: sentence.checksum,
+            });
+        }
+        match crate::rtk::parse_rtk_baseline(&sentence)? {
+            Some(baseline) => {
+                self.rtk_status.update_baseline(baseline);
+                Ok(true)
+            }
+            None => Ok(false),
         }
     }
 
-    /// Returns used satellites
+    /// Returns the height above the WGS-84 ellipsoid, meters.
+    #[deprecated(note = "use `altitude_ellipsoidal()`, this is not the altitude above MSL")]
+    pub fn geoid_altitude(&self) -> Option<f32> {
+        self.altitude_ellipsoidal()
+    }
+
+    /// Returns the satellites in view.
+    ///
+    /// The satellites of a constellation are those of its last complete
+    /// group of GSV sentences, or of the group being received before the
+    /// first one completes, so satellites of different cycles are not mixed.
     pub fn satellites(&self) -> Vec<Satellite, 58> {
         let mut ret = Vec::<Satellite, 58>::new();
         let sat_key = |sat: &Satellite| (sat.gnss_type() as u8, sat.prn());
-        for sns in &self.satellites_scan {
-            // for sat_pack in sns.data.iter().rev() {
-            for sat_pack in sns.data.iter().rev().flatten() {
-                for sat in sat_pack.iter() {
-                    match ret.binary_search_by_key(&sat_key(sat), sat_key) {
-                        //already set
-                        Ok(_pos) => {}
-                        Err(pos) => ret.insert(pos, sat.clone()).unwrap(),
+        let combined = self.combined_scan.satellites();
+        let specific = self
+            .satellites_scan
+            .iter()
+            .flat_map(SatsPack::satellites)
+            // the `GN` group replaces the specific ones of its constellations,
+            // see `GsvTalkerPolicy`
+            .filter(|sat| combined.iter().all(|c| c.gnss_type != sat.gnss_type));
+        for sat in combined.iter().chain(specific) {
+            match ret.binary_search_by_key(&sat_key(sat), sat_key) {
+                //already set
+                Ok(_pos) => {}
+                Err(pos) => {
+                    #[allow(unused_mut)]
+                    let mut sat = sat.clone();
+                    #[cfg(feature = "sat-rates")]
+                    {
+                        sat.rates = self.sat_rates.get(sat.gnss_type, sat.prn);
                     }
+                    // the satellites beyond the capacity are skipped
+                    let _ = ret.insert(pos, sat);
                 }
             }
         }
@@ -158,34 +608,133 @@ impl<'a> Nmea {
 
     fn merge_gga_data(&mut self, gga_data: GgaData) {
         self.fix_time = gga_data.fix_time;
+        self.update_fix_datetime(None);
         self.latitude = gga_data.latitude;
         self.longitude = gga_data.longitude;
         self.fix_type = gga_data.fix_type;
         self.num_of_fix_satellites = gga_data.fix_satellites;
         self.hdop = gga_data.hdop;
         self.altitude = gga_data.altitude;
+        self.transform_position(true);
+        self.check_position(SentenceType::GGA);
+        self.update_climb();
         self.geoid_separation = gga_data.geoid_separation;
+        self.update_dgps(gga_data.age_of_differential, gga_data.dgps_station_id);
+        self.rtk_status
+            .update(self.fix_type, self.fix_time, self.age_of_differential);
+        self.record_update(SentenceType::GGA);
     }
 
-    fn merge_gsv_data(&mut self, data: GsvData) -> Result<(), Error<'a>> {
-        {
-            let d = &mut self.satellites_scan[data.gnss_type as usize];
-            let full_pack_size: usize = data.sentence_num.into();
-            d.max_len = full_pack_size.max(d.max_len);
-            d.data
-                .push_back(data.sats_info)
-                .expect("Should not get the more than expected number of satellites");
-            if d.data.len() > d.max_len {
-                d.data.pop_front();
+    /// Returns the constellations whose satellites were reported by both
+    /// the last `GN` GSV group and GSV sentences of their own talker, e.g.
+    /// `GPGSV`, see [`GsvTalkerPolicy`](crate::GsvTalkerPolicy).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{sentences::GnssType, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "GSV")]
+    /// # {
+    /// nmea.parse("$GPGSV,1,1,01,05,18,299,39*4C").unwrap();
+    /// nmea.parse("$GNGSV,1,1,01,05,18,299,39*52").unwrap();
+    /// assert!(nmea.gsv_talker_duplicates().eq([GnssType::Gps]));
+    /// # }
+    /// ```
+    pub fn gsv_talker_duplicates(&self) -> impl Iterator<Item = GnssType> + '_ {
+        GnssType::ALL_TYPES
+            .into_iter()
+            .filter(|&gnss_type| self.gsv_talkers.duplicates & (1 << gnss_type as u8) != 0)
+    }
+
+    /// Detects the satellites of `GN` sentences which are also reported by
+    /// specific talkers, dropping them with [`GsvTalkerPolicy::PreferSpecific`](crate::GsvTalkerPolicy::PreferSpecific).
+    ///
+    /// Returns `false` if no satellite is left.
+    fn filter_gsv_talkers(&mut self, data: &mut GsvData) -> bool {
+        if !data.combined {
+            self.gsv_talkers.groups_since_specific[data.gnss_type as usize] = 0;
+            return true;
+        }
+        if data.sentence_num == 1 {
+            self.gsv_talkers.start_combined_group();
+        }
+        let prefer_specific = self.quirks.gsv_talkers == crate::GsvTalkerPolicy::PreferSpecific;
+        for sat in data.sats_info.iter_mut() {
+            let Some(gnss_type) = sat.as_ref().map(Satellite::gnss_type) else {
+                continue;
+            };
+            // seen since the previous `GN` group
+            if self.gsv_talkers.groups_since_specific[gnss_type as usize] <= 1 {
+                self.gsv_talkers.duplicates |= 1 << gnss_type as u8;
+                if prefer_specific {
+                    *sat = None;
+                }
+            }
+        }
+        match data.sats_info.iter().flatten().next() {
+            Some(first) => {
+                data.gnss_type = first.gnss_type;
+                true
             }
+            None => false,
         }
+    }
+
+    fn merge_gsv_data(&mut self, mut data: GsvData) -> Result<(), Error<'a>> {
+        // counted before the talker filter, for the check against the
+        // satellites in view
+        let reported = data.sats_info.iter().flatten().count() as u16;
+        if !self.filter_gsv_talkers(&mut data) {
+            // keeps the sequence of the group
+            self.push_gsv(&data, reported);
+            return Ok(());
+        }
+        for satellite in data.sats_info.iter_mut().flatten() {
+            let id = self
+                .quirks
+                .sat_id_policy
+                .resolve(satellite.gnss_type, satellite.prn);
+            satellite.prn = id.svid();
+        }
+        if let Some(signal_id) = data.signal_id {
+            self.signal_table
+                .update(data.gnss_type, signal_id, data.sats_in_view);
+        }
+        self.signal_snrs.update(&data);
+        #[cfg(feature = "snr-history")]
+        for satellite in data.sats_info.iter().flatten() {
+            self.snr_history.update(satellite);
+        }
+        #[cfg(feature = "sat-rates")]
+        if let Some(fix_time) = self.fix_time {
+            for satellite in data.sats_info.iter().flatten() {
+                self.sat_rates.update(satellite, fix_time);
+            }
+        }
+        self.push_gsv(&data, reported);
+        self.record_update(SentenceType::GSV);
 
         Ok(())
     }
 
+    fn push_gsv(&mut self, data: &GsvData, reported: u16) {
+        let reject = self.gsv_check.reject;
+        let pack = if data.combined {
+            &mut self.combined_scan
+        } else {
+            &mut self.satellites_scan[data.gnss_type as usize]
+        };
+        if let Some(consistent) = pack.push(data, reported, reject) {
+            self.gsv_check.record(consistent);
+        }
+    }
+
     fn merge_rmc_data(&mut self, rmc_data: RmcData) {
         self.fix_time = rmc_data.fix_time;
         self.fix_date = rmc_data.fix_date;
+        self.update_fix_datetime(rmc_data.fix_date);
         self.fix_type = Some(match rmc_data.status_of_fix {
             RmcStatusOfFix::Autonomous => FixType::Gps,
             RmcStatusOfFix::Differential => FixType::DGps,
@@ -193,36 +742,104 @@ impl<'a> Nmea {
         });
         self.latitude = rmc_data.lat;
         self.longitude = rmc_data.lon;
-        self.speed_over_ground = rmc_data.speed_over_ground;
-        self.true_course = rmc_data.true_course;
+        self.transform_position(false);
+        self.check_position(SentenceType::RMC);
+        self.rmc_motion = Some((rmc_data.true_course, rmc_data.speed_over_ground));
+        self.update_course(rmc_data.true_course, rmc_data.speed_over_ground);
+        self.record_update(SentenceType::RMC);
     }
 
+    /// Merges a GNS sentence.
+    ///
+    /// Receivers may send one GNS per constellation, e.g. GPGNS and GLGNS,
+    /// with different modes. The fix type is the best of the modes of all
+    /// the GNS sentences with the same fix time, see [`FixType::rank()`].
     fn merge_gns_data(&mut self, gns_data: GnsData) {
+        let mut fix_type = FixType::from(gns_data.faa_modes);
+        if let (Some(time), Some((epoch, best))) = (gns_data.fix_time, self.gns_fix) {
+            if time == epoch {
+                fix_type = fix_type.best(best);
+            }
+        }
+        self.gns_fix = gns_data.fix_time.map(|time| (time, fix_type));
         self.fix_time = gns_data.fix_time;
-        self.fix_type = Some(gns_data.faa_modes.into());
+        self.update_fix_datetime(None);
+        self.fix_type = Some(fix_type);
         self.latitude = gns_data.lat;
         self.longitude = gns_data.lon;
         self.altitude = gns_data.alt;
+        self.transform_position(true);
+        self.check_position(SentenceType::GNS);
+        self.update_climb();
         self.hdop = gns_data.hdop;
         self.geoid_separation = gns_data.geoid_separation;
+        self.update_dgps(gns_data.age_of_differential, gns_data.dgps_station_id);
+        self.rtk_status
+            .update(self.fix_type, self.fix_time, self.age_of_differential);
+        self.record_update(SentenceType::GNS);
+    }
+
+    /// Runs the checks of a new position, after its fix time and type.
+    fn check_position(&mut self, sentence_type: SentenceType) {
+        self.check_consistency(sentence_type);
+        self.check_jump();
+        self.check_geofences();
+        self.update_odometer();
     }
 
     fn merge_gsa_data(&mut self, gsa: GsaData) {
+        let ids: Vec<SatId, 18> = gsa.sat_ids().collect();
+        // a GSA without satellites nor constellation, e.g. without fix,
+        // forgets all of them
+        let forget_all = gsa.gnss_type.is_none() && ids.is_empty();
+        self.fix_sats.retain(|old| {
+            !forget_all
+                && gsa.gnss_type != Some(old.gnss_type())
+                && ids.iter().all(|id| id.gnss_type() != old.gnss_type())
+        });
+        for id in ids {
+            // only the first satellites are kept
+            let _ = self.fix_sats.push(id);
+        }
         self.fix_satellites_prns = Some(gsa.fix_sats_prn);
         self.hdop = gsa.hdop;
         self.vdop = gsa.vdop;
         self.pdop = gsa.pdop;
+        self.record_update(SentenceType::GSA);
+    }
+
+    fn merge_hdt_data(&mut self, hdt: HdtData) {
+        self.update_heading(HeadingSource::Hdt, hdt.heading);
+        self.record_update(SentenceType::HDT);
+    }
+
+    /// Keeps the date of a ZDA sentence for the following fix times, ZDA
+    /// is otherwise only used for the PPS pairing.
+    fn merge_zda_data(&mut self, zda: &ZdaData) {
+        if let Some(time) = zda.utc_time {
+            self.date_tracker.update(time, zda.utc_date());
+        }
+    }
+
+    fn merge_vhw_data(&mut self, vhw: VhwData) {
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let heading = vhw.heading_true.map(|heading| heading as f32);
+        self.update_heading(HeadingSource::Vhw, heading);
+        self.record_update(SentenceType::VHW);
     }
 
     fn merge_vtg_data(&mut self, vtg: VtgData) {
-        self.speed_over_ground = vtg.speed_over_ground;
-        self.true_course = vtg.true_course;
+        self.vtg_motion = Some((vtg.true_course, vtg.speed_over_ground));
+        self.update_course(vtg.true_course, vtg.speed_over_ground);
+        self.record_update(SentenceType::VTG);
     }
 
     fn merge_gll_data(&mut self, gll: GllData) {
         self.latitude = gll.latitude;
         self.longitude = gll.longitude;
+        self.transform_position(false);
         self.fix_time = Some(gll.fix_time);
+        self.update_fix_datetime(None);
         if let Some(faa_mode) = gll.faa_mode {
             self.fix_type = Some(faa_mode.into());
         } else {
@@ -232,10 +849,16 @@ impl<'a> Nmea {
                 FixType::Invalid
             });
         }
+        self.check_position(SentenceType::GLL);
+        self.record_update(SentenceType::GLL);
     }
 
     fn merge_txt_data(&mut self, txt: TxtData) {
+        self.detect_device(&txt.text);
+        self.leap_seconds.update_from_text(&txt.text, self.fix_date);
+        self.time_offsets.update_from_text(&txt.text);
         self.last_txt = Some(txt);
+        self.record_update(SentenceType::TXT);
     }
 
     /// Parse any NMEA sentence and stores the result of sentences that include:
@@ -244,9 +867,145 @@ impl<'a> Nmea {
     /// - speed_over_ground
     /// - and other
     ///
-    /// The type of sentence is returned if implemented and valid.
+    /// The type of sentence is returned if implemented and valid. Unsupported
+    /// sentences are counted in [`unsupported_summary()`](Self::unsupported_summary).
     pub fn parse(&mut self, sentence: &'a str) -> Result<SentenceType, Error<'a>> {
-        match parse_str(sentence)? {
+        let sentence = self.frame(sentence)?;
+        self.parse_checked(sentence)
+    }
+
+    /// Parses a sentence framed by the caller, e.g. by an async or ring
+    /// buffer reader, and stores its data like [`parse()`](Self::parse),
+    /// without formatting it back to a string.
+    ///
+    /// The sentence has no [TAG block](Self::tag_block), the other checks
+    /// and options of [`parse()`](Self::parse) apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_nmea_sentence, Nmea, SentenceType};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// let sentence =
+    ///     parse_nmea_sentence("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///         .unwrap();
+    /// # #[cfg(feature = "GGA")]
+    /// # {
+    /// assert_eq!(nmea.parse_sentence(sentence), Ok(SentenceType::GGA));
+    /// assert_eq!(nmea.fix_satellites(), Some(8));
+    /// # }
+    /// ```
+    pub fn parse_sentence(
+        &mut self,
+        sentence: NmeaSentence<'a>,
+    ) -> Result<SentenceType, Error<'a>> {
+        self.tag_block = None;
+        let sentence = crate::parse::check_sentence(sentence)?;
+        self.parse_checked(sentence)
+    }
+
+    /// Reads the TAG block of a sentence, then frames it and checks its
+    /// characters and checksum.
+    fn frame(&mut self, sentence: &'a str) -> Result<NmeaSentence<'a>, Error<'a>> {
+        self.read_tag_block(sentence)?;
+        crate::parse::checked_sentence(sentence)
+    }
+
+    /// Parses and stores a checked sentence, see [`parse()`](Self::parse).
+    fn parse_checked(&mut self, sentence: NmeaSentence<'a>) -> Result<SentenceType, Error<'a>> {
+        if self.skipped(&sentence) {
+            return Ok(sentence.message_id);
+        }
+        self.check_sentence(&sentence)?;
+        self.weather.talker = sentence.talker();
+        let result = self
+            .parse_and_time(sentence)
+            .and_then(|parse_result| self.merge_parse_result(parse_result));
+        if let Err(ref error) = result {
+            self.record_unsupported(&sentence, error);
+        }
+        result
+    }
+
+    /// Stores the data of a sentence parsed with [`parse_str()`](crate::parse_str),
+    /// [`parse_framed()`](crate::parse_framed) or another parser, like
+    /// [`parse()`](Self::parse).
+    ///
+    /// # Errors
+    ///
+    /// [`Error::Unsupported`] for the sentences whose data is not stored.
+    pub fn parse_result_merge(
+        &mut self,
+        parse_result: ParseResult,
+    ) -> Result<SentenceType, Error<'a>> {
+        self.weather.talker = None;
+        self.merge_parse_result(parse_result)
+    }
+
+    /// Parses a checked sentence, recording its parsing time with the
+    /// `parse-timing` feature.
+    fn parse_and_time(&mut self, sentence: NmeaSentence<'a>) -> Result<ParseResult, Error<'a>> {
+        #[cfg(feature = "parse-timing")]
+        if let Some(clock) = self.clock {
+            return crate::timing::timed(clock.0, &mut self.parse_timings, || {
+                crate::parse::dispatch(sentence)
+            })
+            .or_else(|error| self.parse_lenient(sentence, error));
+        }
+        crate::parse::dispatch(sentence).or_else(|error| self.parse_lenient(sentence, error))
+    }
+
+    /// Parses again sentences which failed because of the [`Quirks`].
+    fn parse_lenient(
+        &self,
+        sentence: NmeaSentence<'a>,
+        error: Error<'a>,
+    ) -> Result<ParseResult, Error<'a>> {
+        if self.quirks.lenient_gsa
+            && matches!(error, Error::ParsingError(_))
+            && sentence.message_id == SentenceType::GSA
+        {
+            return gsa::parse_gsa_lenient(sentence).map(ParseResult::GSA);
+        }
+        if self.quirks.lenient_mtw
+            && matches!(error, Error::ParsingError(_))
+            && sentence.message_id == SentenceType::MTW
+        {
+            return mtw::parse_mtw_lenient(sentence).map(ParseResult::MTW);
+        }
+        Err(error)
+    }
+
+    /// Returns `true` for sentences ignored because of the [`Quirks`],
+    /// skipped by the [decimation](Self::set_decimation) or as
+    /// [duplicates](Self::set_duplicate_suppression).
+    fn skipped(&mut self, sentence: &NmeaSentence) -> bool {
+        let ignored = self.quirks.ignore_gsa && sentence.message_id == SentenceType::GSA;
+        let duplicate =
+            self.duplicates.enabled && self.duplicates.is_duplicate(sentence, self.fix_time);
+        ignored || duplicate || self.decimation.skip(sentence)
+    }
+
+    /// Checks the length of a sentence with the [`Quirks`] and its fields
+    /// with the [strict version](Self::set_strict_version).
+    fn check_sentence(&self, sentence: &NmeaSentence<'a>) -> Result<(), Error<'a>> {
+        self.quirks.check_length(sentence)?;
+        match self.strict_version {
+            Some(version) => check_version_framed(sentence, version),
+            None => Ok(()),
+        }
+    }
+
+    /// Stores the data of an already parsed sentence, see [`Nmea::parse()`].
+    pub(crate) fn merge_parse_result(
+        &mut self,
+        parse_result: ParseResult,
+    ) -> Result<SentenceType, Error<'a>> {
+        self.update_latency(&parse_result);
+        self.pair_pps(&parse_result);
+        self.update_schedule(&parse_result);
+        match parse_result {
             ParseResult::VTG(vtg) => {
                 self.merge_vtg_data(vtg);
                 Ok(SentenceType::VTG)
@@ -279,6 +1038,60 @@ impl<'a> Nmea {
                 self.merge_txt_data(txt);
                 Ok(SentenceType::TXT)
             }
+            ParseResult::HDT(hdt) => {
+                self.merge_hdt_data(hdt);
+                Ok(SentenceType::HDT)
+            }
+            ParseResult::VHW(vhw) => {
+                self.merge_vhw_data(vhw);
+                Ok(SentenceType::VHW)
+            }
+            ParseResult::VLW(vlw) => {
+                self.merge_vlw_data(&vlw);
+                Ok(SentenceType::VLW)
+            }
+            ParseResult::ZDA(zda) => {
+                self.merge_zda_data(&zda);
+                Ok(SentenceType::ZDA)
+            }
+            ParseResult::MTW(mtw) => {
+                self.merge_mtw_data(mtw);
+                Ok(SentenceType::MTW)
+            }
+            ParseResult::MWV(mwv) => {
+                self.merge_mwv_data(mwv);
+                Ok(SentenceType::MWV)
+            }
+            ParseResult::MDA(mda) => {
+                self.merge_mda_data(mda);
+                Ok(SentenceType::MDA)
+            }
+            ParseResult::DTM(dtm) => {
+                self.merge_dtm_data(&dtm);
+                Ok(SentenceType::DTM)
+            }
+            ParseResult::DBK(dbk) => {
+                self.merge_water_depth(dbk.depth(), DepthReference::Keel);
+                Ok(SentenceType::DBK)
+            }
+            ParseResult::DBS(dbs) => {
+                self.merge_water_depth(dbs.depth(), DepthReference::Surface);
+                Ok(SentenceType::DBS)
+            }
+            ParseResult::DBT(dbt) => {
+                self.merge_water_depth(dbt.depth(), DepthReference::Transducer);
+                Ok(SentenceType::DBT)
+            }
+            ParseResult::DPT(dpt) => {
+                self.merge_water_depth(dpt.depth(), DepthReference::Transducer);
+                Ok(SentenceType::DPT)
+            }
+            #[cfg(feature = "almanac")]
+            ParseResult::ALM(alm) => {
+                self.almanac.update(&alm);
+                self.record_update(SentenceType::ALM);
+                Ok(SentenceType::ALM)
+            }
             ParseResult::Unsupported(sentence_type) => Err(Error::Unsupported(sentence_type)),
             // any other implemented sentence which is not part of the `Nmea` parsing is unsupported
             // at this time being
@@ -286,11 +1099,146 @@ impl<'a> Nmea {
         }
     }
 
-    fn new_tick(&mut self) {
+    pub(crate) fn new_tick(&mut self) {
         let old = mem::take(self);
         self.satellites_scan = old.satellites_scan;
+        self.combined_scan = old.combined_scan;
+        self.gsv_talkers = old.gsv_talkers;
+        self.signal_table = old.signal_table;
+        self.signal_snrs = old.signal_snrs;
         self.required_sentences_for_nav = old.required_sentences_for_nav;
         self.last_fix_time = old.last_fix_time;
+        self.date_tracker = old.date_tracker;
+        self.epoch_datetime = old.epoch_datetime;
+        self.clock = old.clock;
+        self.dgps_watchdog = old.dgps_watchdog;
+        self.rtk_status = old.rtk_status;
+        self.course_filter = old.course_filter;
+        self.course_gate = old.course_gate;
+        self.last_course = old.last_course;
+        self.course_check = old.course_check;
+        self.strict_version = old.strict_version;
+        self.device = old.device;
+        self.device_override = old.device_override;
+        self.quirks = old.quirks;
+        self.latency = old.latency;
+        self.pps = old.pps;
+        self.leap_seconds = old.leap_seconds;
+        self.time_offsets = old.time_offsets;
+        self.schedule = old.schedule;
+        self.unsupported = old.unsupported;
+        self.skipped_binary_bytes = old.skipped_binary_bytes;
+        self.decimation = old.decimation;
+        self.duplicates = old.duplicates;
+        self.gsv_check = old.gsv_check;
+        self.tag_block = old.tag_block;
+        self.heading_selector = old.heading_selector.restarted();
+        self.weather = old.weather.restarted();
+        self.datum = old.datum.restarted();
+        self.fix_events = old.fix_events;
+        self.jump = old.jump;
+        self.geofences = old.geofences;
+        self.odometer = old.odometer;
+        self.climb = old.climb;
+        self.consistency = old.consistency;
+        self.cycle_source = old.cycle_source;
+        self.cycle_start = old.cycle_start;
+        self.updates = old.updates;
+        #[cfg(feature = "snr-history")]
+        {
+            self.snr_history = old.snr_history;
+        }
+        #[cfg(feature = "sat-rates")]
+        {
+            self.sat_rates = old.sat_rates;
+        }
+        #[cfg(feature = "almanac")]
+        {
+            self.almanac = old.almanac;
+        }
+        #[cfg(feature = "parse-timing")]
+        {
+            self.parse_timings = old.parse_timings;
+        }
+    }
+
+    /// Forgets all received data, e.g. after a receiver reboot, keeping the
+    /// configuration: the sentences required for navigation, the clock, the
+    /// DGPS watchdog settings, the course filter, gate and check, the strict
+    /// version, the quirks, a device set with [`set_device()`](Self::set_device),
+    /// the leap second state, the time system offsets, the expected
+    /// sentences, the decimation, the duplicate suppression, the GSV count
+    /// rejection, the heading priority, the weather talker priority, the
+    /// datum transformation, the position check, the jump filter, the
+    /// geofences, the odometer with its distances and the cycle source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{Nmea, SentenceType};
+    ///
+    /// let mut nmea = Nmea::create_for_navigation(&[SentenceType::GGA]).unwrap();
+    /// # #[cfg(feature = "GGA")]
+    /// # {
+    /// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///     .unwrap();
+    /// nmea.reset();
+    /// assert_eq!(nmea.fix_satellites(), None);
+    /// # }
+    /// ```
+    pub fn reset(&mut self) {
+        let old = mem::take(self);
+        self.required_sentences_for_nav = old.required_sentences_for_nav;
+        self.clock = old.clock;
+        self.dgps_watchdog = old.dgps_watchdog.as_ref().map(DgpsWatchdog::cleared);
+        self.course_filter = old.course_filter;
+        self.course_gate = old.course_gate;
+        self.course_check = old.course_check;
+        self.strict_version = old.strict_version;
+        self.device_override = old.device_override;
+        if old.device_override {
+            self.device = old.device;
+        }
+        self.quirks = old.quirks;
+        self.leap_seconds = old.leap_seconds;
+        self.time_offsets = old.time_offsets;
+        self.schedule = old.schedule.restarted(self.now());
+        self.decimation = old.decimation;
+        self.duplicates.enabled = old.duplicates.enabled;
+        self.gsv_check.reject = old.gsv_check.reject;
+        self.heading_selector = old.heading_selector.restarted();
+        self.weather = old.weather.cleared();
+        self.datum = old.datum.cleared();
+        self.jump = old.jump.cleared();
+        self.geofences = old.geofences.cleared();
+        self.odometer = old.odometer.cleared();
+        self.consistency = old.consistency.cleared();
+        self.cycle_source = old.cycle_source;
+    }
+
+    /// Forgets the satellites in view and their signals, e.g. after an
+    /// antenna switch.
+    pub fn clear_satellites(&mut self) {
+        self.satellites_scan = Default::default();
+        self.combined_scan = SatsPack::default();
+        self.gsv_talkers = GsvTalkers::default();
+        self.signal_table = SignalTable::default();
+        self.signal_snrs = SignalSnrs::default();
+        self.fix_satellites_prns = None;
+        self.fix_sats.clear();
+        #[cfg(feature = "snr-history")]
+        {
+            self.snr_history = Default::default();
+        }
+        #[cfg(feature = "sat-rates")]
+        {
+            self.sat_rates = Default::default();
+        }
+    }
+
+    /// Forgets the current fix: its time, position, motion and DOPs.
+    pub fn clear_fix(&mut self) {
+        self.clear_position_info();
     }
 
     fn clear_position_info(&mut self) {
@@ -299,10 +1247,28 @@ impl<'a> Nmea {
     }
 
     pub fn parse_for_fix(&mut self, xs: &'a str) -> Result<FixType, Error<'a>> {
-        match parse_str(xs)? {
+        let sentence = self.frame(xs)?;
+        if self.skipped(&sentence) {
+            return Ok(FixType::Invalid);
+        }
+        self.check_cycle();
+        self.check_sentence(&sentence)?;
+        self.weather.talker = sentence.talker();
+        let parse_result = self.parse_and_time(sentence).map_err(|error| {
+            self.record_unsupported(&sentence, &error);
+            error
+        })?;
+        self.update_latency(&parse_result);
+        self.pair_pps(&parse_result);
+        self.update_schedule(&parse_result);
+        match parse_result {
             ParseResult::GSA(gsa) => {
                 self.merge_gsa_data(gsa);
-                return Ok(FixType::Invalid);
+                //have no time field, so only if user explicitly mention it
+                if !self.required_sentences_for_nav.contains(&SentenceType::GSA) {
+                    return Ok(FixType::Invalid);
+                }
+                self.sentences_for_this_time.insert(SentenceType::GSA);
             }
             ParseResult::GSV(gsv_data) => {
                 self.merge_gsv_data(gsv_data)?;
@@ -321,27 +1287,83 @@ impl<'a> Nmea {
                     return Ok(FixType::Invalid);
                 }
             }
+            ParseResult::HDT(hdt) => {
+                self.merge_hdt_data(hdt);
+                self.sentences_for_this_time.insert(SentenceType::HDT);
+            }
+            ParseResult::VHW(vhw) => {
+                self.merge_vhw_data(vhw);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::VLW(vlw) => {
+                self.merge_vlw_data(&vlw);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::ZDA(zda) => {
+                self.merge_zda_data(&zda);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::MTW(mtw) => {
+                self.merge_mtw_data(mtw);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::MWV(mwv) => {
+                self.merge_mwv_data(mwv);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::MDA(mda) => {
+                self.merge_mda_data(mda);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::DBK(dbk) => {
+                self.merge_water_depth(dbk.depth(), DepthReference::Keel);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::DBS(dbs) => {
+                self.merge_water_depth(dbs.depth(), DepthReference::Surface);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::DBT(dbt) => {
+                self.merge_water_depth(dbt.depth(), DepthReference::Transducer);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::DPT(dpt) => {
+                self.merge_water_depth(dpt.depth(), DepthReference::Transducer);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::DTM(dtm) => {
+                self.merge_dtm_data(&dtm);
+                return Ok(FixType::Invalid);
+            }
+            ParseResult::ALM(_alm) => {
+                #[cfg(feature = "almanac")]
+                {
+                    self.almanac.update(&_alm);
+                    self.record_update(SentenceType::ALM);
+                }
+                return Ok(FixType::Invalid);
+            }
             ParseResult::RMC(rmc_data) => {
                 if rmc_data.status_of_fix == RmcStatusOfFix::Invalid {
                     self.clear_position_info();
                     return Ok(FixType::Invalid);
                 }
-                if !self.update_fix_time(rmc_data.fix_time) {
+                if !self.update_fix_time(rmc_data.fix_time, rmc_data.fix_date) {
                     return Ok(FixType::Invalid);
                 }
                 self.merge_rmc_data(rmc_data);
                 self.sentences_for_this_time.insert(SentenceType::RMC);
             }
             ParseResult::GNS(gns_data) => {
-                let fix_type: FixType = gns_data.faa_modes.into();
-                if !fix_type.is_valid() {
-                    self.clear_position_info();
+                if !self.update_fix_time(gns_data.fix_time, None) {
                     return Ok(FixType::Invalid);
                 }
-                if !self.update_fix_time(gns_data.fix_time) {
+                // the fix type is the best mode of the GNS of the epoch
+                self.merge_gns_data(gns_data);
+                if !self.fix_type.is_some_and(FixType::is_valid) {
+                    self.clear_position_info();
                     return Ok(FixType::Invalid);
                 }
-                self.merge_gns_data(gns_data);
                 self.sentences_for_this_time.insert(SentenceType::GNS);
             }
             ParseResult::GGA(gga_data) => {
@@ -352,18 +1374,22 @@ impl<'a> Nmea {
                     }
                     _ => { /*nothing*/ }
                 }
-                if !self.update_fix_time(gga_data.fix_time) {
+                if !self.update_fix_time(gga_data.fix_time, None) {
                     return Ok(FixType::Invalid);
                 }
                 self.merge_gga_data(gga_data);
                 self.sentences_for_this_time.insert(SentenceType::GGA);
             }
             ParseResult::GLL(gll_data) => {
-                if !self.update_fix_time(Some(gll_data.fix_time)) {
+                if !gll_data.valid {
+                    self.clear_position_info();
+                    return Ok(FixType::Invalid);
+                }
+                if !self.update_fix_time(Some(gll_data.fix_time), None) {
                     return Ok(FixType::Invalid);
                 }
                 self.merge_gll_data(gll_data);
-                return Ok(FixType::Invalid);
+                self.sentences_for_this_time.insert(SentenceType::GLL);
             }
             ParseResult::TXT(txt_data) => {
                 self.merge_txt_data(txt_data);
@@ -372,27 +1398,18 @@ impl<'a> Nmea {
             ParseResult::BWC(_)
             | ParseResult::BWW(_)
             | ParseResult::BOD(_)
-            | ParseResult::DBK(_)
-            | ParseResult::DBS(_)
-            | ParseResult::DPT(_)
             | ParseResult::GBS(_)
             | ParseResult::GST(_)
             | ParseResult::AAM(_)
             | ParseResult::APA(_)
-            | ParseResult::ALM(_)
-            | ParseResult::HDT(_)
             | ParseResult::PGRMZ(_)
-            | ParseResult::MTW(_)
-            | ParseResult::MWV(_)
-            | ParseResult::MDA(_)
-            | ParseResult::VHW(_)
             | ParseResult::TTM(_)
-            | ParseResult::ZDA(_)
             | ParseResult::ZFO(_)
             | ParseResult::WNC(_)
             | ParseResult::ZTG(_) => return Ok(FixType::Invalid),
 
-            ParseResult::Unsupported(_) => {
+            ParseResult::Unsupported(sentence_type) => {
+                self.record_unsupported(&sentence, &Error::Unsupported(sentence_type));
                 return Ok(FixType::Invalid);
             }
         }
@@ -413,15 +1430,34 @@ impl<'a> Nmea {
         self.last_txt.as_ref()
     }
 
-    fn update_fix_time(&mut self, fix_time: Option<NaiveTime>) -> bool {
+    /// Starts a new epoch when the date and time of the fix changes, or its
+    /// time of day if the date is unknown.
+    fn update_fix_time(
+        &mut self,
+        fix_time: Option<NaiveTime>,
+        fix_date: Option<NaiveDate>,
+    ) -> bool {
         match (self.last_fix_time, fix_time) {
             (Some(ref last_fix_time), Some(ref new_fix_time)) => {
-                if *last_fix_time != *new_fix_time {
-                    self.new_tick();
-                    self.last_fix_time = Some(*new_fix_time);
+                let new_datetime = self.date_tracker.resolve(*new_fix_time, fix_date);
+                let changed = match (self.epoch_datetime, new_datetime) {
+                    (Some(last), Some(new)) => last != new,
+                    _ => *last_fix_time != *new_fix_time,
+                };
+                if changed {
+                    if self.cycle_source == CycleSource::FixTime {
+                        self.new_tick();
+                        self.last_fix_time = Some(*new_fix_time);
+                    }
+                    self.epoch_datetime = new_datetime;
+                } else if self.epoch_datetime.is_none() {
+                    self.epoch_datetime = new_datetime;
                 }
             }
-            (None, Some(ref new_fix_time)) => self.last_fix_time = Some(*new_fix_time),
+            (None, Some(ref new_fix_time)) => {
+                self.last_fix_time = Some(*new_fix_time);
+                self.epoch_datetime = self.date_tracker.resolve(*new_fix_time, fix_date);
+            }
             (Some(_), None) | (None, None) => {
                 self.clear_position_info();
                 return false;
@@ -445,66 +1481,164 @@ impl fmt::Display for Nmea {
     }
 }
 
+/// Number of satellites of a [`SatsPack`], for a complete group and the
+/// group being received.
+const SATS_PACK_LEN: usize = 64;
+
+/// The satellites of the GSV sentences of a talker.
+///
+/// A group of sentences replaces the previous one once all its declared
+/// sentences have been received in sequence. A group which is interrupted,
+/// or whose declared totals change, is dropped, so the satellites of a
+/// group which shrinks do not linger.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, Clone, Default)]
 struct SatsPack {
-    /// max number of visible GNSS satellites per hemisphere, assuming global coverage
-    /// GPS: 16
-    /// GLONASS: 12
-    /// BeiDou: 12 + 3 IGSO + 3 GEO
-    /// Galileo: 12
-    /// => 58 total Satellites => max 15 rows of data
-    #[cfg_attr(feature = "serde", serde(with = "serde_deq"))]
-    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
-    data: Deque<Vec<Option<Satellite>, 4>, 15>,
-    max_len: usize,
+    /// The satellites of the last complete group, followed by those of the
+    /// group being received
+    sats: Vec<Satellite, SATS_PACK_LEN>,
+    /// Number of satellites of the last complete group
+    complete_len: usize,
+    /// The group being received
+    group: Option<GsvGroup>,
+    /// The Signal ID of the groups, the first one received: the groups of
+    /// the other signals of dual-band receivers report the same satellites
+    signal_id: Option<u8>,
 }
 
-#[cfg(feature = "serde")]
-mod serde_deq {
-    use super::*;
+/// Declared totals of a group of GSV sentences, and its progress.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GsvGroup {
+    number_of_sentences: u16,
+    sats_in_view: u16,
+    /// The received sentence numbers, by bit
+    received: u16,
+    /// Number of satellites of the received sentences
+    reported: u16,
+}
 
-    pub fn serialize<S>(v: &Deque<Vec<Option<Satellite>, 4>, 15>, s: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let mut seq = s.serialize_seq(Some(15))?;
-        for e in v.iter() {
-            seq.serialize_element(e)?;
+impl GsvGroup {
+    /// The `received` bits of a complete group, `None` if there are more
+    /// sentences than bits.
+    fn full_mask(number_of_sentences: u16) -> Option<u16> {
+        match number_of_sentences {
+            1..=16 => Some(u16::MAX >> (16 - number_of_sentences)),
+            _ => None,
         }
-        seq.end()
     }
+}
 
-    struct DequeVisitor;
-
-    impl<'de> Visitor<'de> for DequeVisitor {
-        type Value = Deque<Vec<Option<Satellite>, 4>, 15>;
+impl SatsPack {
+    /// The satellites of the last complete group, or of the first group
+    /// while it is received.
+    fn satellites(&self) -> &[Satellite] {
+        if self.complete_len == 0 {
+            &self.sats
+        } else {
+            &self.sats[..self.complete_len]
+        }
+    }
 
-        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-            formatter.write_str("deque of vectors containing optional Satellite structs")
+    /// Adds a sentence with `reported` satellites to its group. The
+    /// sentences of a group may be received in any order, a sentence
+    /// received twice starts a new group.
+    ///
+    /// Returns whether the satellites of a completed group match its
+    /// satellites in view. Inconsistent groups are dropped with `reject`.
+    fn push(&mut self, data: &GsvData, reported: u16, reject: bool) -> Option<bool> {
+        let full = GsvGroup::full_mask(data.number_of_sentences)?;
+        if let Some(signal_id) = data.signal_id {
+            if *self.signal_id.get_or_insert(signal_id) != signal_id {
+                return None;
+            }
+        }
+        if data.sentence_num == 0 || data.sentence_num > data.number_of_sentences {
+            return None;
         }
+        let bit = 1 << (data.sentence_num - 1);
+        let same_group = self.group.is_some_and(|group| {
+            group.number_of_sentences == data.number_of_sentences
+                && group.sats_in_view == data.sats_in_view
+                && group.received & bit == 0
+        });
+        if !same_group {
+            // drops the incomplete group
+            self.sats.truncate(self.complete_len);
+            self.group = Some(GsvGroup {
+                number_of_sentences: data.number_of_sentences,
+                sats_in_view: data.sats_in_view,
+                received: 0,
+                reported: 0,
+            });
+        }
+        for sat in data.sats_info.iter().flatten() {
+            if self.sats.push(sat.clone()).is_err() {
+                if self.complete_len == 0 {
+                    // the satellites beyond the capacity are skipped
+                    break;
+                }
+                // no room for both groups, the complete one is dropped early
+                self.evict_complete();
+                let _ = self.sats.push(sat.clone());
+            }
+        }
+        let group = self.group.as_mut()?;
+        group.received |= bit;
+        group.reported = group.reported.saturating_add(reported);
+        if group.received != full {
+            return None;
+        }
+        let consistent = group.reported == group.sats_in_view;
+        self.group = None;
+        if consistent || !reject {
+            self.evict_complete();
+            self.complete_len = self.sats.len();
+        } else {
+            self.sats.truncate(self.complete_len);
+        }
+        Some(consistent)
+    }
 
-        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-        where
-            A: serde::de::SeqAccess<'de>,
-        {
-            let mut deq: Deque<Vec<Option<Satellite>, 4>, 15> = Deque::new();
+    fn evict_complete(&mut self) {
+        self.sats.rotate_left(self.complete_len);
+        self.sats.truncate(self.sats.len() - self.complete_len);
+        self.complete_len = 0;
+    }
+}
 
-            while let Some(v) = seq.next_element()? {
-                deq.push_back(v)
-                    .map_err(|_| serde::de::Error::invalid_length(deq.capacity() + 1, &self))?;
-            }
+/// GSV talkers per constellation, to detect satellites reported by both
+/// `GN` and specific GSV sentences.
+///
+/// The cycles are the `GN` groups: a constellation has specific sentences
+/// if they were received since the start of the previous `GN` group.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+struct GsvTalkers {
+    /// `GN` groups since the last specific sentence of each constellation
+    groups_since_specific: [u8; GnssType::COUNT],
+    /// Constellations of the last `GN` group with specific sentences, by bit
+    duplicates: u8,
+}
 
-            Ok(deq)
+impl Default for GsvTalkers {
+    fn default() -> Self {
+        Self {
+            groups_since_specific: [u8::MAX; GnssType::COUNT],
+            duplicates: 0,
         }
     }
+}
 
-    pub fn deserialize<'de, D>(d: D) -> Result<Deque<Vec<Option<Satellite>, 4>, 15>, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        d.deserialize_seq(DequeVisitor)
+impl GsvTalkers {
+    fn start_combined_group(&mut self) {
+        for groups in &mut self.groups_since_specific {
+            *groups = groups.saturating_add(1);
+        }
+        self.duplicates = 0;
     }
 }
 
@@ -512,12 +1646,17 @@ mod serde_deq {
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Clone, PartialEq)]
 /// Satellite information
+///
+/// Elevation, azimuth and SNR are whole numbers in NMEA, so they are stored as
+/// `i16` to keep [`GsvData`](crate::sentences::GsvData) small.
 pub struct Satellite {
     pub(crate) gnss_type: GnssType,
     pub(crate) prn: u32,
-    pub(crate) elevation: Option<f32>,
-    pub(crate) azimuth: Option<f32>,
-    pub(crate) snr: Option<f32>,
+    pub(crate) elevation: Option<i16>,
+    pub(crate) azimuth: Option<i16>,
+    pub(crate) snr: Option<i16>,
+    #[cfg(feature = "sat-rates")]
+    pub(crate) rates: Option<crate::sat_rates::SatRates>,
 }
 
 impl Satellite {
@@ -529,17 +1668,38 @@ impl Satellite {
     pub fn prn(&self) -> u32 {
         self.prn
     }
+    /// Returns the satellite's identity, see [`SatId`].
+    #[inline]
+    pub fn sat_id(&self) -> SatId {
+        SatId::new(self.gnss_type, self.prn)
+    }
     #[inline]
     pub fn elevation(&self) -> Option<f32> {
-        self.elevation
+        self.elevation.map(f32::from)
     }
     #[inline]
     pub fn azimuth(&self) -> Option<f32> {
-        self.azimuth
+        self.azimuth.map(f32::from)
     }
+    /// Returns the SNR, on the first Signal ID of the constellation with
+    /// dual-band receivers, see [`Nmea::best_snr()`].
     #[inline]
     pub fn snr(&self) -> Option<f32> {
-        self.snr
+        self.snr.map(f32::from)
+    }
+    /// Returns the orbit class of BeiDou satellites, e.g. to filter out
+    /// the GEO ones, see [`SatId::orbit_class()`].
+    #[inline]
+    pub fn orbit_class(&self) -> Option<OrbitClass> {
+        self.sat_id().orbit_class()
+    }
+    /// Returns the azimuth and elevation rates, set in the satellites of
+    /// [`Nmea::satellites()`], see [`sat_rates`](crate::sat_rates).
+    #[cfg(feature = "sat-rates")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "sat-rates")))]
+    #[inline]
+    pub fn rates(&self) -> Option<crate::sat_rates::SatRates> {
+        self.rates
     }
 }
 
@@ -550,9 +1710,9 @@ impl fmt::Display for Satellite {
             "{}: {} elv: {} ath: {} snr: {}",
             self.gnss_type,
             self.prn,
-            format_args!("{:?}", self.elevation),
-            format_args!("{:?}", self.azimuth),
-            format_args!("{:?}", self.snr),
+            format_args!("{:?}", self.elevation()),
+            format_args!("{:?}", self.azimuth()),
+            format_args!("{:?}", self.snr()),
         )
     }
 }
@@ -562,7 +1722,11 @@ impl fmt::Debug for Satellite {
         write!(
             f,
             "[{:?},{:?},{:?},{:?},{:?}]",
-            self.gnss_type, self.prn, self.elevation, self.azimuth, self.snr
+            self.gnss_type,
+            self.prn,
+            self.elevation(),
+            self.azimuth(),
+            self.snr()
         )
     }
 }
@@ -574,13 +1738,33 @@ macro_rules! count_tts {
 }
 pub(crate) use count_tts;
 
+/// The [`Dispatch`] of a sentence type: types with a parser are followed by
+/// `=> ("FEATURE", parser_fn, ParseResultVariant, sink_method)` in
+/// `define_sentence_type_enum!`, where `sink_method` is the method of
+/// [`SentenceSink`](crate::SentenceSink) receiving the data.
+macro_rules! dispatch_entry {
+    () => {
+        Dispatch::Unsupported
+    };
+    ($feature:tt, $parser:ident, $Result:ident, $method:ident) => {{
+        #[cfg(feature = $feature)]
+        const ENTRY: Dispatch = Dispatch::Parser(
+            |sentence| $parser(sentence).map(ParseResult::$Result),
+            |sentence, sink| $parser(sentence).map(|data| sink.$method(&data)),
+        );
+        #[cfg(not(feature = $feature))]
+        const ENTRY: Dispatch = Dispatch::Disabled;
+        ENTRY
+    }};
+}
+
 macro_rules! define_sentence_type_enum {
     (
         $(#[$outer:meta])*
         pub enum $Name:ident {
             $(
             $(#[$variant:meta])*
-            $Variant:ident
+            $Variant:ident $(=> ($feature:tt, $parser:ident, $Result:ident, $method:ident))?
             ),* $(,)* }
     ) => {
         $(#[$outer])*
@@ -602,15 +1786,30 @@ macro_rules! define_sentence_type_enum {
             }
         }
 
+        impl<'a> TryFrom<&'a [u8]> for SentenceType {
+            type Error = crate::Error<'a>;
+
+            /// Converts the sentence type of a byte buffer, without
+            /// converting the whole buffer to a string.
+            fn try_from(bytes: &'a [u8]) -> Result<$Name, Self::Error> {
+                let s = core::str::from_utf8(bytes).map_err(|_| Error::Utf8Decoding)?;
+                Self::try_from(s)
+            }
+        }
+
         impl $Name {
             const COUNT: usize = count_tts!($($Variant),*);
             pub const TYPES: [$Name; $Name::COUNT] = [$($Name::$Variant,)*];
 
-            pub fn to_mask_value(self) -> u128 {
+            /// How [`parse_str()`] handles each type, indexed by the type.
+            pub(crate) const DISPATCH: [Dispatch; $Name::COUNT] =
+                [$(dispatch_entry!($($feature, $parser, $Result, $method)?),)*];
+
+            pub const fn to_mask_value(self) -> u128 {
                 1 << self as u32
             }
 
-            pub fn as_str(&self) -> &str {
+            pub const fn as_str(&self) -> &str {
                 match self {
                     $($Name::$Variant => stringify!($Variant),)*
                 }
@@ -641,6 +1840,9 @@ define_sentence_type_enum! {
     ///
     /// ## Types
     ///
+    /// The groups are available at runtime with [`SentenceType::category()`]
+    /// and [`SentenceType::iter_category()`].
+    ///
     /// ### General
     ///
     /// - [`SentenceType::OSD`]
@@ -777,13 +1979,14 @@ define_sentence_type_enum! {
     #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
     #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
     #[repr(u32)]
+    #[non_exhaustive]
     pub enum SentenceType {
         /// AAM - Waypoint Arrival Alarm
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_aam_waypoint_arrival_alarm>
         ///
         /// Type: `Waypoints and tacks`
-        AAM,
+        AAM => ("AAM", parse_aam, AAM, aam),
         ABK,
         ACA,
         ACK,
@@ -794,14 +1997,14 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_alm_gps_almanac_data>
         ///
         /// Type: `GPS`
-        ALM,
+        ALM => ("ALM", parse_alm, ALM, alm),
         ALR,
         /// APA - Autopilot Sentence "A"
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_apa_autopilot_sentence_a>
         ///
         /// Type: `Autopilot`
-        APA,
+        APA => ("APA", parse_apa, APA, apa),
         /// APB - Autopilot Sentence "B"
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_apb_autopilot_sentence_b>
@@ -817,13 +2020,13 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_bod_bearing_waypoint_to_waypoint>
         ///
         /// Type: `Waypoints and tacks`
-        BOD,
+        BOD => ("BOD", parse_bod, BOD, bod),
         /// BWC - Bearing & Distance to Waypoint - Great Circle
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_bwc_bearing_distance_to_waypoint_great_circle>
         ///
         /// Type: `Waypoints and tacks`
-        BWC,
+        BWC => ("BWC", parse_bwc, BWC, bwc),
         /// BWR - Bearing and Distance to Waypoint - Rhumb Line
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_bwr_bearing_and_distance_to_waypoint_rhumb_line>
@@ -835,26 +2038,26 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_bww_bearing_waypoint_to_waypoint>
         ///
         /// Type: `Waypoints and tacks`
-        BWW,
+        BWW => ("BWW", parse_bww, BWW, bww),
         CUR,
         /// DBK - Depth Below Keel
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dbk_depth_below_keel>
         ///
         /// Type: `Echo`
-        DBK,
+        DBK => ("DBK", parse_dbk, DBK, dbk),
         /// DBS - Depth Below Surface
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dbs_depth_below_surface>
         ///
         /// Type: `Echo`
-        DBS,
+        DBS => ("DBS", parse_dbs, DBS, dbs),
         /// DBT - Depth below transducer
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dbt_depth_below_transducer>
         ///
         /// Type: `Echo`
-        DBT,
+        DBT => ("DBT", parse_dbt, DBT, dbt),
         /// DCN - Decca Position
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dcn_decca_position>
@@ -866,7 +2069,7 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dpt_depth_of_water>
         ///
         /// Type: `Course`
-        DPT,
+        DPT => ("DPT", parse_dpt, DPT, dpt),
         DSC,
         DSE,
         DSI,
@@ -877,7 +2080,7 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dtm_datum_reference>
         ///
         /// Type: `Position`
-        DTM,
+        DTM => ("DTM", parse_dtm, DTM, dtm),
         /// FSI - Frequency Set Information
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_fsi_frequency_set_information>
@@ -889,13 +2092,13 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gbs_gps_satellite_fault_detection>
         ///
         /// Type: `GPS`
-        GBS,
+        GBS => ("GBS", parse_gbs, GBS, gbs),
         /// GGA - Global Positioning System Fix Data
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gga_global_positioning_system_fix_data>
         ///
         /// Type: `GPS`
-        GGA,
+        GGA => ("GGA", parse_gga, GGA, gga),
         /// GLC - Geographic Position, Loran-C
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_glc_geographic_position_loran_c>
@@ -907,14 +2110,14 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gll_geographic_position_latitudelongitude>
         ///
         /// Type: `Position`
-        GLL,
+        GLL => ("GLL", parse_gll, GLL, gll),
         GMP,
         /// GNS - Fix data
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gns_fix_data>
         ///
         /// Type: `GPS`
-        GNS,
+        GNS => ("GNS", parse_gns, GNS, gns),
         /// GRS - GPS Range Residuals
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_grs_gps_range_residuals>
@@ -924,17 +2127,17 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gsa_gps_dop_and_active_satellites>
         ///
         /// Type: `GPS`
-        GSA,
+        GSA => ("GSA", parse_gsa, GSA, gsa),
         /// GST - GPS Pseudorange Noise Statistics
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gst_gps_pseudorange_noise_statistics>
-        GST,
+        GST => ("GST", parse_gst, GST, gst),
         /// GSV - Satellites in view
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gsv_satellites_in_view>
         ///
         /// Type: `GPS`
-        GSV,
+        GSV => ("GSV", parse_gsv, GSV, gsv),
         /// GTD - Geographic Location in Time Differences
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_gtd_geographic_location_in_time_differences>
@@ -965,7 +2168,7 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_hdt_heading_true>
         ///
         /// Type: `Course`
-        HDT,
+        HDT => ("HDT", parse_hdt, HDT, hdt),
         /// HFB - Trawl Headrope to Footrope and Bottom
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_hfb_trawl_headrope_to_footrope_and_bottom>
@@ -1002,7 +2205,7 @@ define_sentence_type_enum! {
         /// MDA - Meteorological Composite
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_mda_meteorological_composite>
-        MDA,
+        MDA => ("MDA", parse_mda, MDA, mda),
         MLA,
         /// MSK - Control for a Beacon Receiver
         ///
@@ -1020,13 +2223,13 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_mtw_mean_temperature_of_water>
         ///
         /// Type: `Temperature`
-        MTW,
+        MTW => ("MTW", parse_mtw, MTW, mtw),
         /// MWV - Wind Speed and Angle
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_mwv_wind_speed_and_angle>
         ///
         /// Type: `Wind`
-        MWV,
+        MWV => ("MWV", parse_mwv, MWV, mwv),
         /// OLN - Omega Lane Numbers
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_oln_omega_lane_numbers>
@@ -1065,13 +2268,13 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_rmc_recommended_minimum_navigation_information>
         ///
         /// Type: `Navigation`
-        RMC,
+        RMC => ("RMC", parse_rmc, RMC, rmc),
         /// PGRMZ - Garmin Altitude
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_pgrmz_garmin_altitude>
         ///
         /// Type: `Vendor extensions`
-        RMZ,
+        RMZ => ("RMZ", parse_pgrmz, PGRMZ, pgrmz),
         /// ROT - Rate Of Turn
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_rot_rate_of_turn>
@@ -1151,9 +2354,9 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_ttm_tracked_target_message>
         ///
         /// Type: `Radar`
-        TTM,
+        TTM => ("TTM", parse_ttm, TTM, ttm),
         TUT,
-        TXT,
+        TXT => ("TXT", parse_txt, TXT, txt),
         /// VBW - Dual Ground/Water Speed
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_vbw_dual_groundwater_speed>
@@ -1173,13 +2376,13 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_vhw_water_speed_and_heading>
         ///
         /// Type: `Speed`
-        VHW,
+        VHW => ("VHW", parse_vhw, VHW, vhw),
         /// VLW - Distance Traveled through Water
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_vlw_distance_traveled_through_water>
         ///
         /// Type: `Speed`
-        VLW,
+        VLW => ("VLW", parse_vlw, VLW, vlw),
         /// VPW - Speed - Measured Parallel to Wind
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_vpw_speed_measured_parallel_to_wind>
@@ -1192,7 +2395,7 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_vtg_track_made_good_and_ground_speed>
         ///
         /// Type: `Waypoints and tacks`
-        VTG,
+        VTG => ("VTG", parse_vtg, VTG, vtg),
         /// VWR - Relative Wind Speed and Angle
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_vwr_relative_wind_speed_and_angle>
@@ -1210,7 +2413,7 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_wnc_distance_waypoint_to_waypoint>
         ///
         /// Type: `Waypoints and tacks`
-        WNC,
+        WNC => ("WNC", parse_wnc, WNC, wnc),
         /// WPL - Waypoint Location
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_wpl_waypoint_location>
@@ -1240,20 +2443,20 @@ define_sentence_type_enum! {
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_zda_time_date_utc_day_month_year_and_local_time_zone>
         ///
         /// Type: `Date and Time`
-        ZDA,
+        ZDA => ("ZDA", parse_zda, ZDA, zda),
         ZDL,
         /// ZFO - UTC & Time from origin Waypoint
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_zfo_utc_time_from_origin_waypoint>
         ///
         /// Type: `Date and Time`
-        ZFO,
+        ZFO => ("ZFO", parse_zfo, ZFO, zfo),
         /// ZTG - UTC & Time to Destination Waypoint
         ///
         /// <https://gpsd.gitlab.io/gpsd/NMEA.html#_ztg_utc_time_to_destination_waypoint>
         ///
         /// Type: `Date and Time`
-        ZTG,
+        ZTG => ("ZTG", parse_ztg, ZTG, ztg),
     }
 }
 
@@ -1265,15 +2468,100 @@ pub struct SentenceMask {
 }
 
 impl SentenceMask {
-    fn contains(&self, sentence_type: &SentenceType) -> bool {
+    /// The sentence types parsed by this build of the crate, i.e. whose
+    /// features are enabled.
+    ///
+    /// It can be used to check the features at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{SentenceMask, SentenceType};
+    ///
+    /// # #[cfg(feature = "GGA")]
+    /// const _: () = assert!(
+    ///     SentenceMask::ENABLED.contains(&SentenceType::GGA),
+    ///     "enable the `GGA` feature of nmea"
+    /// );
+    /// ```
+    pub const ENABLED: Self = {
+        let mut mask = 0;
+        let mut i = 0;
+        while i < REGISTRY_TABLE.len() {
+            if REGISTRY_TABLE[i].parsed {
+                mask |= REGISTRY_TABLE[i].sentence_type.to_mask_value();
+            }
+            i += 1;
+        }
+        Self { mask }
+    };
+
+    /// The sentence types merged by [`Nmea::parse()`] into the fix, which
+    /// are enabled by the `parse-for-fix` feature.
+    pub const FIX: Self = Self::from_types(&[
+        SentenceType::GGA,
+        SentenceType::GLL,
+        SentenceType::GNS,
+        SentenceType::GSA,
+        SentenceType::GSV,
+        SentenceType::RMC,
+        SentenceType::TXT,
+        SentenceType::VTG,
+    ]);
+
+    /// The sentence types of the `profile-gnss-min` feature.
+    pub const PROFILE_GNSS_MIN: Self = Self::from_types(&[
+        SentenceType::GGA,
+        SentenceType::GSA,
+        SentenceType::GSV,
+        SentenceType::RMC,
+    ]);
+
+    /// The sentence types of the `profile-marine` feature.
+    pub const PROFILE_MARINE: Self = Self::from_types(&[
+        SentenceType::DBK,
+        SentenceType::DBS,
+        SentenceType::DBT,
+        SentenceType::DPT,
+        SentenceType::HDT,
+        SentenceType::MDA,
+        SentenceType::MTW,
+        SentenceType::MWV,
+        SentenceType::VHW,
+        SentenceType::VLW,
+    ]);
+
+    /// The sentence types of the `profile-surveying` feature.
+    pub const PROFILE_SURVEYING: Self = Self::from_types(&[
+        SentenceType::DTM,
+        SentenceType::GBS,
+        SentenceType::GGA,
+        SentenceType::GNS,
+        SentenceType::GSA,
+        SentenceType::GST,
+    ]);
+
+    /// Returns the mask of the sentence types.
+    pub const fn from_types(sentence_types: &[SentenceType]) -> Self {
+        let mut mask = 0;
+        let mut i = 0;
+        while i < sentence_types.len() {
+            mask |= sentence_types[i].to_mask_value();
+            i += 1;
+        }
+        Self { mask }
+    }
+
+    pub const fn contains(&self, sentence_type: &SentenceType) -> bool {
         sentence_type.to_mask_value() & self.mask != 0
     }
 
-    fn is_subset(&self, mask: &Self) -> bool {
+    /// Returns `true` if all the sentence types of `self` are in `mask`.
+    pub const fn is_subset(&self, mask: &Self) -> bool {
         (mask.mask | self.mask) == mask.mask
     }
 
-    fn insert(&mut self, sentence_type: SentenceType) {
+    pub(crate) fn insert(&mut self, sentence_type: SentenceType) {
         self.mask |= sentence_type.to_mask_value()
     }
 }
@@ -1300,11 +2588,19 @@ impl BitOr<SentenceType> for SentenceMask {
 mod tests {
     use core::convert::TryFrom;
 
+    #[cfg(all(feature = "GGA", not(feature = "f32-only")))]
     use quickcheck::{QuickCheck, TestResult};
 
-    use crate::{parse::checksum, sentences::FixType, Error, Nmea, SentenceType};
+    // some are only used with the tests of enabled sentences
+    #[allow(unused_imports)]
+    use crate::{
+        parse::checksum,
+        sentences::{FixType, GnssType},
+        Error, Nmea, SentenceMask, SentenceType,
+    };
 
-    #[cfg(feature = "GGA")]
+    // the precision of `f32` coordinates is too low for this check
+    #[cfg(all(feature = "GGA", not(feature = "f32-only")))]
     fn check_parsing_lat_lon_in_gga(lat: f64, lon: f64) -> TestResult {
         fn scale(val: f64, max: f64) -> f64 {
             val % max
@@ -1338,6 +2634,35 @@ mod tests {
         )
     }
 
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "GSV"))]
+    fn test_reset() {
+        let mut nmea = Nmea::create_for_navigation(&[SentenceType::GGA]).unwrap();
+        nmea.set_course_check(Default::default());
+        nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+            .unwrap();
+        nmea.parse("$GPGSV,1,1,03,10,63,137,17,07,61,098,15,05,59,290,20,1*55")
+            .unwrap();
+
+        nmea.clear_fix();
+        assert_eq!(nmea.latitude(), None);
+        assert_eq!(nmea.satellites().len(), 3);
+
+        nmea.clear_satellites();
+        assert!(nmea.satellites().is_empty());
+        assert_eq!(nmea.signal_counts(GnssType::Gps).l1_count(), 0);
+
+        nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+            .unwrap();
+        nmea.reset();
+        assert_eq!(nmea.fix_satellites(), None);
+        assert!(nmea.course_check.is_some());
+        assert_eq!(
+            nmea.required_sentences_for_nav,
+            SentenceMask::default() | SentenceType::GGA
+        );
+    }
+
     #[test]
     fn test_fix_type() {
         assert_eq!(FixType::from('A'), FixType::Invalid);
@@ -1352,13 +2677,64 @@ mod tests {
         assert_eq!(FixType::from('8'), FixType::Simulation);
     }
 
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "RMC", feature = "ZDA"))]
+    fn test_zda_before_epoch() {
+        let mut nmea =
+            Nmea::create_for_navigation(&[SentenceType::GGA, SentenceType::RMC]).unwrap();
+        let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+        assert_eq!(nmea.parse_for_fix(gga), Ok(FixType::Invalid));
+        // the ZDA of the next epoch comes first, and does not merge the epochs
+        assert_eq!(
+            nmea.parse_for_fix("$GPZDA,092751.000,28,05,2011,00,00*53"),
+            Ok(FixType::Invalid)
+        );
+        let rmc = "$GPRMC,092751.000,A,5321.6802,N,00630.3372,W,0.06,31.66,280511,,,A*46";
+        assert_eq!(nmea.parse_for_fix(rmc), Ok(FixType::Invalid));
+        let gga = "$GPGGA,092751.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*77";
+        assert_eq!(nmea.parse_for_fix(gga), Ok(FixType::Gps));
+    }
+
+    #[test]
+    #[cfg(feature = "GNS")]
+    fn test_gns_best_mode_of_epoch() {
+        let mut nmea = Nmea::default();
+        nmea.parse("$GPGNS,224749.00,3333.4268304,N,11153.3538273,W,D,19,0.6,406.110,-26.294,6.0,0138,S,*46")
+            .unwrap();
+        nmea.parse("$GLGNS,224749.00,3333.4268304,N,11153.3538273,W,N,0,,406.110,-26.294,,,S,*62")
+            .unwrap();
+        assert_eq!(nmea.fix_type(), Some(FixType::DGps));
+
+        // the next epoch
+        nmea.parse("$GLGNS,224750.00,3333.4268304,N,11153.3538273,W,N,0,,406.110,-26.294,,,S,*6A")
+            .unwrap();
+        assert_eq!(nmea.fix_type(), Some(FixType::Invalid));
+
+        // the same with `parse_for_fix()`
+        let mut nmea = Nmea::create_for_navigation(&[SentenceType::GNS]).unwrap();
+        let gns = [
+            "$GPGNS,224749.00,3333.4268304,N,11153.3538273,W,D,19,0.6,406.110,-26.294,6.0,0138,S,*46",
+            "$GLGNS,224749.00,3333.4268304,N,11153.3538273,W,N,0,,406.110,-26.294,,,S,*62",
+        ];
+        for gns in gns {
+            assert_eq!(nmea.parse_for_fix(gns), Ok(FixType::DGps));
+        }
+        assert_eq!(nmea.fix_type(), Some(FixType::DGps));
+        assert_eq!(
+            nmea.parse_for_fix(
+                "$GLGNS,224750.00,3333.4268304,N,11153.3538273,W,N,0,,406.110,-26.294,,,S,*6A"
+            ),
+            Ok(FixType::Invalid)
+        );
+    }
+
     #[test]
     fn test_checksum() {
         let valid = "$GNGSA,A,1,,,,,,,,,,,,,99.99,99.99,99.99*2E";
         let invalid = "$GNZDA,165118.00,13,05,2016,00,00*71";
-        assert_eq!(checksum(valid[1..valid.len() - 3].as_bytes().iter()), 0x2E);
+        assert_eq!(checksum(valid.as_bytes()[1..valid.len() - 3].iter()), 0x2E);
         assert_ne!(
-            checksum(invalid[1..invalid.len() - 3].as_bytes().iter()),
+            checksum(invalid.as_bytes()[1..invalid.len() - 3].iter()),
             0x71
         );
     }
@@ -1373,7 +2749,7 @@ mod tests {
 
     #[test]
     // FIXME: remove dependency on GGA and instead use quickcheck for `do_parse_lat_lon` parser
-    #[cfg(feature = "GGA")]
+    #[cfg(all(feature = "GGA", not(feature = "f32-only")))]
     fn test_parsing_lat_lon_in_gga() {
         // regressions found by quickcheck,
         // explicit because of quickcheck use random gen
@@ -1384,6 +2760,49 @@ mod tests {
             .quickcheck(check_parsing_lat_lon_in_gga as fn(f64, f64) -> TestResult);
     }
 
+    #[test]
+    fn test_sentence_mask_consts() {
+        const GGA_RMC: SentenceMask =
+            SentenceMask::from_types(&[SentenceType::GGA, SentenceType::RMC]);
+        assert_eq!(GGA_RMC, SentenceType::GGA | SentenceType::RMC);
+        assert!(GGA_RMC.is_subset(&SentenceMask::FIX));
+        assert!(!SentenceMask::FIX.is_subset(&GGA_RMC));
+        assert!(SentenceMask::PROFILE_GNSS_MIN.is_subset(&SentenceMask::FIX));
+        assert!(!SentenceMask::PROFILE_MARINE.contains(&SentenceType::GGA));
+        assert!(!SentenceMask::ENABLED.contains(&SentenceType::APB));
+        assert_eq!(
+            SentenceMask::ENABLED.contains(&SentenceType::GGA),
+            cfg!(feature = "GGA")
+        );
+        if cfg!(feature = "parse-for-fix") {
+            assert!(SentenceMask::FIX.is_subset(&SentenceMask::ENABLED));
+        }
+    }
+
+    #[test]
+    fn test_masks_match_features() {
+        let manifest = include_str!("../Cargo.toml");
+        for (feature, mask) in [
+            ("parse-for-fix", SentenceMask::FIX),
+            ("profile-gnss-min", SentenceMask::PROFILE_GNSS_MIN),
+            ("profile-marine", SentenceMask::PROFILE_MARINE),
+            ("profile-surveying", SentenceMask::PROFILE_SURVEYING),
+        ] {
+            let prefix = std::format!("{feature} = [");
+            let line = manifest
+                .lines()
+                .find(|line| line.starts_with(&prefix))
+                .unwrap();
+            let types: std::vec::Vec<_> = line
+                .split('"')
+                .skip(1)
+                .step_by(2)
+                .map(|name| SentenceType::try_from(name).unwrap())
+                .collect();
+            assert_eq!(SentenceMask::from_types(&types), mask, "{feature}");
+        }
+    }
+
     #[test]
     fn test_sentence_type_enum() {
         // So we don't trip over the max value of u128 when shifting it with
@@ -1392,4 +2811,246 @@ mod tests {
             assert!((sentence_type as u32) < 127);
         }
     }
+
+    #[test]
+    #[cfg(feature = "GGA")]
+    fn test_dgps_watchdog_with_clock() {
+        use core::{
+            sync::atomic::{AtomicU64, Ordering},
+            time::Duration,
+        };
+
+        use crate::{DgpsAlarm, DgpsWatchdog};
+
+        static NOW_MS: AtomicU64 = AtomicU64::new(0);
+        fn now() -> Duration {
+            Duration::from_millis(NOW_MS.load(Ordering::SeqCst))
+        }
+
+        let mut nmea = Nmea::default();
+        nmea.set_clock(&(now as fn() -> Duration));
+        nmea.set_dgps_watchdog(DgpsWatchdog::new(5.0));
+        nmea.parse("$GPGGA,172814.0,3723.46587704,N,12202.26957864,W,2,6,1.2,18.893,M,-25.669,M,2.0,0031*4F")
+            .unwrap();
+        assert_eq!(nmea.age_of_differential, Some(2.0));
+        assert_eq!(nmea.dgps_station_id, Some(31));
+        assert_eq!(nmea.check_dgps_watchdog(), None);
+
+        NOW_MS.store(3500, Ordering::SeqCst);
+        assert_eq!(
+            nmea.check_dgps_watchdog(),
+            Some(DgpsAlarm::AgeExceeded { age: 5.5 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "VTG")]
+    fn test_speed_over_ground_units() {
+        let mut nmea = Nmea::default();
+        nmea.parse("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48")
+            .unwrap();
+        assert_eq!(nmea.speed_over_ground(), Some(5.5));
+        approx::assert_relative_eq!(nmea.speed_over_ground_kmh().unwrap(), 10.186);
+        approx::assert_relative_eq!(nmea.speed_over_ground_ms().unwrap(), 2.829_444_4);
+    }
+
+    #[test]
+    #[cfg(all(feature = "RMC", feature = "VTG"))]
+    fn test_course_discrepancy() {
+        let mut nmea = Nmea::default();
+        nmea.set_course_check(crate::CourseCheck::default());
+        nmea.parse("$GPRMC,225446.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*46")
+            .unwrap();
+        assert_eq!(nmea.course_discrepancy(), None);
+        nmea.parse("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48")
+            .unwrap();
+        let discrepancy = nmea.course_discrepancy().unwrap();
+        assert_eq!(discrepancy.course, Some(0.));
+        assert_eq!(discrepancy.speed, Some(5.));
+    }
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "VTG"))]
+    fn test_parse_sentence() {
+        let mut nmea = Nmea::default();
+        let mut gga = crate::parse_nmea_sentence(
+            "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76",
+        )
+        .unwrap();
+        assert_eq!(nmea.parse_sentence(gga), Ok(SentenceType::GGA));
+        assert_eq!(nmea.fix_satellites(), Some(8));
+
+        gga.checksum = 0;
+        assert!(matches!(
+            nmea.parse_sentence(gga),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+
+        let rot = crate::parse_nmea_sentence("$HEROT,-0.3,A*05").unwrap();
+        assert_eq!(
+            nmea.parse_sentence(rot),
+            Err(Error::Unsupported(SentenceType::ROT))
+        );
+        assert_eq!(nmea.unsupported_summary().count(), 1);
+
+        let vtg = crate::parse_str("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48").unwrap();
+        assert_eq!(nmea.parse_result_merge(vtg), Ok(SentenceType::VTG));
+        assert_eq!(nmea.true_course, Some(54.7));
+    }
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "GSA"))]
+    fn test_parse_sentence_options() {
+        use crate::{metadata::NmeaVersion, parse_nmea_sentence, NmeaSentence, Quirks};
+
+        let mut nmea = Nmea::default();
+        nmea.set_quirks(Quirks {
+            lenient_gsa: true,
+            ..Quirks::default()
+        });
+        let gsa = parse_nmea_sentence("$GPGSA,A,3,1.8,1.1,1.4*3E").unwrap();
+        assert_eq!(nmea.parse_sentence(gsa), Ok(SentenceType::GSA));
+        assert_eq!(nmea.pdop, Some(1.8));
+
+        nmea.set_duplicate_suppression(true);
+        let gga = parse_nmea_sentence(
+            "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76",
+        )
+        .unwrap();
+        // the first one sets the fix time of the epoch
+        for _ in 0..3 {
+            assert_eq!(nmea.parse_sentence(gga), Ok(SentenceType::GGA));
+        }
+        assert_eq!(nmea.duplicates_dropped(), 1);
+
+        nmea.set_strict_version(Some(NmeaVersion::V4_10));
+        assert!(matches!(
+            nmea.parse_sentence(gsa),
+            Err(Error::VersionMismatch { .. })
+        ));
+
+        let non_ascii = NmeaSentence {
+            talker_id: "GP",
+            message_id: SentenceType::GSA,
+            data: "A,3,1.8,1.1,1.4\u{e9}",
+            checksum: 0,
+        };
+        assert_eq!(nmea.parse_sentence(non_ascii), Err(Error::ASCII));
+        assert_eq!(crate::parse_framed(non_ascii), Err(Error::ASCII));
+    }
+
+    #[test]
+    #[cfg(feature = "GSV")]
+    fn test_gsv_group_eviction() {
+        let prns = |nmea: &Nmea| {
+            nmea.satellites()
+                .iter()
+                .map(|sat| sat.prn())
+                .collect::<Vec<_>>()
+        };
+        let mut nmea = Nmea::default();
+        nmea.parse("$GPGSV,2,1,05,01,40,083,46,02,17,308,41,03,07,344,39,04,22,228,45*79")
+            .unwrap();
+        // the first group is shown while it is received
+        assert_eq!(prns(&nmea), [1, 2, 3, 4]);
+        nmea.parse("$GPGSV,2,2,05,05,10,100,30*4A").unwrap();
+        assert_eq!(prns(&nmea), [1, 2, 3, 4, 5]);
+
+        // the next group replaces the complete one once received
+        nmea.parse("$GPGSV,2,1,05,01,40,083,46,02,17,308,41,03,07,344,39,04,22,228,45*79")
+            .unwrap();
+        assert_eq!(prns(&nmea), [1, 2, 3, 4, 5]);
+        // a shrinking group leaves no ghost satellites
+        nmea.parse("$GPGSV,1,1,03,01,41,083,46,02,17,308,40,06,07,344,39*44")
+            .unwrap();
+        assert_eq!(prns(&nmea), [1, 2, 6]);
+        assert_eq!(nmea.satellites()[0].elevation(), Some(41.));
+
+        nmea.parse("$GPGSV,1,1,03,01,42,083,46,02,17,308,40,06,07,344,39*47")
+            .unwrap();
+        assert_eq!(prns(&nmea), [1, 2, 6]);
+        assert_eq!(nmea.satellites()[0].elevation(), Some(42.));
+    }
+
+    #[test]
+    #[cfg(feature = "GSV")]
+    fn test_gsv_dual_band_groups() {
+        // the E1 and E5b groups of a Galileo epoch
+        let epoch = [
+            "$GAGSV,2,1,05,02,45,120,38,07,30,250,35,11,60,045,41,12,20,310,30,7*79",
+            "$GAGSV,2,2,05,30,15,180,28,7*42",
+            "$GAGSV,2,1,05,02,45,120,42,07,30,250,39,11,60,045,44,12,20,310,,2*7B",
+            "$GAGSV,2,2,05,30,15,180,33,2*4D",
+        ];
+        let mut nmea = Nmea::default();
+        for _ in 0..2 {
+            for sentence in epoch {
+                nmea.parse(sentence).unwrap();
+            }
+            // the satellites of E1, the first signal
+            let snrs: Vec<_> = nmea
+                .satellites()
+                .iter()
+                .map(|sat| (sat.prn(), sat.snr()))
+                .collect();
+            assert_eq!(
+                snrs,
+                [
+                    (2, Some(38.)),
+                    (7, Some(35.)),
+                    (11, Some(41.)),
+                    (12, Some(30.)),
+                    (30, Some(28.))
+                ]
+            );
+        }
+        assert_eq!(nmea.signal_snr(GnssType::Galileo, 11, 2), Some(44.));
+        assert_eq!(nmea.signal_snr(GnssType::Galileo, 12, 2), None);
+        assert_eq!(nmea.best_snr(GnssType::Galileo, 12), Some(30.));
+        assert_eq!(nmea.best_snr(GnssType::Galileo, 30), Some(33.));
+        assert_eq!(nmea.signal_snrs(GnssType::Galileo, 30).count(), 2);
+        assert_eq!(nmea.gsv_count_stats().mismatches, 0);
+
+        nmea.clear_satellites();
+        assert_eq!(nmea.best_snr(GnssType::Galileo, 30), None);
+    }
+
+    #[test]
+    #[cfg(feature = "GSV")]
+    fn test_gsv_talker_duplicates() {
+        use crate::{GsvTalkerPolicy, Quirks};
+
+        let sats = |nmea: &Nmea| {
+            nmea.satellites()
+                .iter()
+                .map(|sat| (sat.gnss_type(), sat.prn()))
+                .collect::<Vec<_>>()
+        };
+        for policy in [GsvTalkerPolicy::KeepAll, GsvTalkerPolicy::PreferSpecific] {
+            let mut nmea = Nmea::default();
+            nmea.set_quirks(Quirks {
+                gsv_talkers: policy,
+                ..Quirks::default()
+            });
+            nmea.parse("$GPGSV,1,1,01,05,18,299,39*4C").unwrap();
+            nmea.parse("$GNGSV,1,1,02,07,18,299,39,70,10,100,30*67")
+                .unwrap();
+            assert!(nmea.gsv_talker_duplicates().eq([GnssType::Gps]));
+            let expected = match policy {
+                // the `GN` group replaces the GPS satellites of `GPGSV`
+                GsvTalkerPolicy::KeepAll => vec![(GnssType::Gps, 7), (GnssType::Glonass, 6)],
+                GsvTalkerPolicy::PreferSpecific => {
+                    vec![(GnssType::Gps, 5), (GnssType::Glonass, 6)]
+                }
+            };
+            assert_eq!(sats(&nmea), expected);
+
+            // GPGSV stopped two `GN` groups ago
+            nmea.parse("$GNGSV,1,1,02,07,18,299,39,70,10,100,30*67")
+                .unwrap();
+            nmea.parse("$GNGSV,1,1,02,07,18,299,39,70,10,100,30*67")
+                .unwrap();
+            assert_eq!(nmea.gsv_talker_duplicates().count(), 0);
+        }
+    }
 }
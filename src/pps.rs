@@ -0,0 +1,155 @@
+//! Time discipline with a PPS (pulse per second) input.
+//!
+//! The PPS pulse marks the start of a UTC second, and the RMC or ZDA
+//! sentence that follows it reports which second it was. Once paired, the
+//! UTC time can be estimated from any local timestamp.
+
+use core::time::Duration;
+
+use chrono::{NaiveDateTime, Timelike};
+
+use crate::{sentences::rmc::RmcStatusOfFix, Nmea, ParseResult};
+
+/// Resolution of the PPS and local timestamps.
+const TIMESTAMP_RESOLUTION: Duration = Duration::from_millis(1);
+
+/// Frequency error of the local oscillator assumed for the uncertainty,
+/// in parts per million, typical of uncompensated crystals.
+const OSCILLATOR_PPM: u32 = 100;
+
+/// An estimate of the UTC time, see [`Nmea::utc_time_estimate()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UtcTimeEstimate {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub utc: NaiveDateTime,
+    /// Bound of the error of `utc`, from the timestamp resolution and the
+    /// oscillator drift since the pulse
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub uncertainty: Duration,
+}
+
+/// A PPS pulse, paired with its UTC second once the time is received.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct PpsState {
+    /// Timestamp of the last pulse, in milliseconds
+    pending: Option<u64>,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    paired: Option<(u64, NaiveDateTime)>,
+}
+
+impl Nmea {
+    /// Records a PPS pulse, with a local timestamp in milliseconds.
+    ///
+    /// The pulse is paired with the time of the next RMC (with a valid fix)
+    /// or ZDA sentence which reports a whole second. Until then, the
+    /// previous pairing is used by [`utc_time_estimate()`](Self::utc_time_estimate).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{NaiveDate, NaiveDateTime};
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "ZDA")]
+    /// # {
+    /// nmea.associate_pps(10_000);
+    /// nmea.parse("$GPZDA,160012.00,11,03,2004,-1,00*7B").unwrap();
+    ///
+    /// let estimate = nmea.utc_time_estimate(10_250).unwrap();
+    /// let utc = NaiveDate::from_ymd_opt(2004, 3, 11)
+    ///     .unwrap()
+    ///     .and_hms_milli_opt(16, 0, 12, 250)
+    ///     .unwrap();
+    /// assert_eq!(estimate.utc, utc);
+    /// assert!(estimate.uncertainty.as_millis() <= 2);
+    /// # }
+    /// ```
+    pub fn associate_pps(&mut self, pps_timestamp_ms: u64) {
+        self.pps.pending = Some(pps_timestamp_ms);
+    }
+
+    /// Returns the UTC time at a local timestamp in milliseconds, from the
+    /// last pulse paired with a UTC second.
+    ///
+    /// Returns `None` if no pulse has been paired, the timestamp is before
+    /// the pulse or the time is out of the range of [`NaiveDateTime`].
+    pub fn utc_time_estimate(&self, timestamp_ms: u64) -> Option<UtcTimeEstimate> {
+        let (pps, utc) = self.pps.paired?;
+        let elapsed = Duration::from_millis(timestamp_ms.checked_sub(pps)?);
+        let utc = chrono::Duration::from_std(elapsed)
+            .ok()
+            .and_then(|elapsed| utc.checked_add_signed(elapsed))?;
+        Some(UtcTimeEstimate {
+            utc,
+            uncertainty: TIMESTAMP_RESOLUTION * 2 + elapsed * OSCILLATOR_PPM / 1_000_000,
+        })
+    }
+
+    pub(crate) fn pair_pps(&mut self, parse_result: &ParseResult) {
+        let Some(pps) = self.pps.pending else {
+            return;
+        };
+        let utc = match parse_result {
+            ParseResult::RMC(rmc) if rmc.status_of_fix != RmcStatusOfFix::Invalid => {
+                rmc.fix_date.zip(rmc.fix_time)
+            }
+            ParseResult::ZDA(zda) => zda.utc_date().zip(zda.utc_time),
+            _ => None,
+        };
+        if let Some((date, time)) = utc {
+            if time.nanosecond() == 0 {
+                self.pps.paired = Some((pps, date.and_time(time)));
+                self.pps.pending = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::*;
+    use crate::sentences::ZdaData;
+
+    fn zda(time: NaiveTime) -> ParseResult {
+        ParseResult::ZDA(ZdaData {
+            utc_time: Some(time),
+            day: Some(1),
+            month: Some(6),
+            year: Some(2024),
+            local_zone_hours: None,
+            local_zone_minutes: None,
+        })
+    }
+
+    #[test]
+    fn test_pps_pairing() {
+        let mut nmea = Nmea::default();
+        let second = NaiveTime::from_hms_opt(12, 0, 1).unwrap();
+        // no pulse yet
+        nmea.pair_pps(&zda(second));
+        assert_eq!(nmea.utc_time_estimate(0), None);
+
+        nmea.associate_pps(1_000);
+        // not the pulse's second
+        nmea.pair_pps(&zda(NaiveTime::from_hms_milli_opt(12, 0, 0, 900).unwrap()));
+        assert_eq!(nmea.utc_time_estimate(1_000), None);
+        nmea.pair_pps(&zda(second));
+
+        let estimate = nmea.utc_time_estimate(11_000).unwrap();
+        let utc = NaiveDate::from_ymd_opt(2024, 6, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 11)
+            .unwrap();
+        assert_eq!(estimate.utc, utc);
+        assert_eq!(estimate.uncertainty, Duration::from_millis(3));
+        assert_eq!(nmea.utc_time_estimate(999), None);
+        assert_eq!(nmea.utc_time_estimate(u64::MAX), None);
+    }
+}
@@ -0,0 +1,372 @@
+//! Python bindings, exposed as the `nmea_rs` extension module.
+//!
+//! Build the module with [maturin](https://www.maturin.rs/), enabling the
+//! `python` feature together with `pyo3/extension-module`:
+//!
+//! ```text
+//! maturin build --release --features python,pyo3/extension-module
+//! ```
+//!
+//! ```python
+//! import nmea_rs
+//!
+//! nmea = nmea_rs.Nmea()
+//! nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+//! print(nmea.fix().latitude)
+//!
+//! gga = nmea_rs.parse_str("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+//! print(gga.fix_satellites)
+//! ```
+//!
+//! Enum values (fix types, modes, GNSS types) are exposed as their names.
+// pyo3 macros expand to `unsafe` blocks and to `PyErr` into `PyErr` conversions
+#![allow(unsafe_code, clippy::useless_conversion)]
+
+use std::{format, string::String, vec::Vec};
+
+use chrono::{NaiveDate, NaiveTime};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{sentences::*, Error, Fix, Float, Nmea, ParseResult, Satellite};
+
+fn py_error(err: Error<'_>) -> PyErr {
+    PyValueError::new_err(format!("{}", err))
+}
+
+fn name<T: core::fmt::Debug>(value: T) -> String {
+    format!("{:?}", value)
+}
+
+/// Stateful parser, see [`Nmea`].
+#[pyclass(name = "Nmea")]
+#[derive(Debug, Default)]
+pub struct PyNmea {
+    inner: Nmea,
+}
+
+#[pymethods]
+impl PyNmea {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a sentence and returns its type, see [`Nmea::parse()`].
+    fn parse(&mut self, sentence: &str) -> PyResult<String> {
+        self.inner
+            .parse(sentence)
+            .map(|sentence_type| sentence_type.as_str().into())
+            .map_err(py_error)
+    }
+
+    /// Parses a sentence for navigation and returns the fix type, see
+    /// [`Nmea::parse_for_fix()`].
+    fn parse_for_fix(&mut self, sentence: &str) -> PyResult<String> {
+        self.inner
+            .parse_for_fix(sentence)
+            .map(name)
+            .map_err(py_error)
+    }
+
+    fn fix(&self) -> PyFix {
+        self.inner.fix().into()
+    }
+
+    fn satellites(&self) -> Vec<PySatellite> {
+        self.inner.satellites().iter().map(Into::into).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{}", self.inner)
+    }
+}
+
+/// See [`Fix`].
+#[pyclass(name = "Fix", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyFix {
+    fix_time: Option<NaiveTime>,
+    fix_date: Option<NaiveDate>,
+    fix_type: Option<String>,
+    latitude: Option<Float>,
+    longitude: Option<Float>,
+    altitude_msl: Option<f32>,
+    altitude_ellipsoidal: Option<f32>,
+    speed_over_ground: Option<f32>,
+    true_course: Option<f32>,
+    fix_satellites: Option<u32>,
+    hdop: Option<f32>,
+    vdop: Option<f32>,
+    pdop: Option<f32>,
+    geoid_separation: Option<f32>,
+}
+
+impl From<Fix> for PyFix {
+    fn from(fix: Fix) -> Self {
+        Self {
+            fix_time: fix.fix_time,
+            fix_date: fix.fix_date,
+            fix_type: fix.fix_type.map(name),
+            latitude: fix.latitude,
+            longitude: fix.longitude,
+            altitude_msl: fix.altitude_msl,
+            altitude_ellipsoidal: fix.altitude_ellipsoidal,
+            speed_over_ground: fix.speed_over_ground,
+            true_course: fix.true_course,
+            fix_satellites: fix.fix_satellites,
+            hdop: fix.hdop,
+            vdop: fix.vdop,
+            pdop: fix.pdop,
+            geoid_separation: fix.geoid_separation,
+        }
+    }
+}
+
+/// See [`Satellite`].
+#[pyclass(name = "Satellite", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PySatellite {
+    gnss_type: String,
+    prn: u32,
+    elevation: Option<f32>,
+    azimuth: Option<f32>,
+    snr: Option<f32>,
+}
+
+impl From<&Satellite> for PySatellite {
+    fn from(sat: &Satellite) -> Self {
+        Self {
+            gnss_type: name(sat.gnss_type()),
+            prn: sat.prn(),
+            elevation: sat.elevation(),
+            azimuth: sat.azimuth(),
+            snr: sat.snr(),
+        }
+    }
+}
+
+/// See [`GgaData`].
+#[pyclass(name = "GgaData", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyGga {
+    fix_time: Option<NaiveTime>,
+    fix_type: Option<String>,
+    latitude: Option<Float>,
+    longitude: Option<Float>,
+    fix_satellites: Option<u32>,
+    hdop: Option<f32>,
+    altitude: Option<f32>,
+    geoid_separation: Option<f32>,
+}
+
+impl From<GgaData> for PyGga {
+    fn from(gga: GgaData) -> Self {
+        Self {
+            fix_time: gga.fix_time,
+            fix_type: gga.fix_type.map(name),
+            latitude: gga.latitude,
+            longitude: gga.longitude,
+            fix_satellites: gga.fix_satellites,
+            hdop: gga.hdop,
+            altitude: gga.altitude,
+            geoid_separation: gga.geoid_separation,
+        }
+    }
+}
+
+/// See [`RmcData`].
+#[pyclass(name = "RmcData", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyRmc {
+    fix_time: Option<NaiveTime>,
+    fix_date: Option<NaiveDate>,
+    status_of_fix: String,
+    lat: Option<Float>,
+    lon: Option<Float>,
+    speed_over_ground: Option<f32>,
+    true_course: Option<f32>,
+    magnetic_variation: Option<f32>,
+    faa_mode: Option<String>,
+    nav_status: Option<String>,
+}
+
+impl From<RmcData> for PyRmc {
+    fn from(rmc: RmcData) -> Self {
+        Self {
+            fix_time: rmc.fix_time,
+            fix_date: rmc.fix_date,
+            status_of_fix: name(rmc.status_of_fix),
+            lat: rmc.lat,
+            lon: rmc.lon,
+            speed_over_ground: rmc.speed_over_ground,
+            true_course: rmc.true_course,
+            magnetic_variation: rmc.magnetic_variation,
+            faa_mode: rmc.faa_mode.map(name),
+            nav_status: rmc.nav_status.map(name),
+        }
+    }
+}
+
+/// See [`GllData`].
+#[pyclass(name = "GllData", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyGll {
+    latitude: Option<Float>,
+    longitude: Option<Float>,
+    fix_time: NaiveTime,
+    valid: bool,
+    faa_mode: Option<String>,
+}
+
+impl From<GllData> for PyGll {
+    fn from(gll: GllData) -> Self {
+        Self {
+            latitude: gll.latitude,
+            longitude: gll.longitude,
+            fix_time: gll.fix_time,
+            valid: gll.valid,
+            faa_mode: gll.faa_mode.map(name),
+        }
+    }
+}
+
+/// See [`VtgData`].
+#[pyclass(name = "VtgData", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyVtg {
+    true_course: Option<f32>,
+    magnetic_course: Option<f32>,
+    speed_over_ground: Option<f32>,
+    speed_over_ground_kmh: Option<f32>,
+    faa_mode: Option<String>,
+}
+
+impl From<VtgData> for PyVtg {
+    fn from(vtg: VtgData) -> Self {
+        Self {
+            true_course: vtg.true_course,
+            magnetic_course: vtg.magnetic_course,
+            speed_over_ground: vtg.speed_over_ground,
+            speed_over_ground_kmh: vtg.speed_over_ground_kmh,
+            faa_mode: vtg.faa_mode.map(name),
+        }
+    }
+}
+
+/// See [`GsaData`].
+#[pyclass(name = "GsaData", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyGsa {
+    mode1: String,
+    mode2: String,
+    fix_sats_prn: Vec<u32>,
+    pdop: Option<f32>,
+    hdop: Option<f32>,
+    vdop: Option<f32>,
+}
+
+impl From<GsaData> for PyGsa {
+    fn from(gsa: GsaData) -> Self {
+        Self {
+            mode1: name(gsa.mode1),
+            mode2: name(gsa.mode2),
+            fix_sats_prn: gsa.fix_sats_prn.to_vec(),
+            pdop: gsa.pdop,
+            hdop: gsa.hdop,
+            vdop: gsa.vdop,
+        }
+    }
+}
+
+/// See [`GsvData`].
+#[pyclass(name = "GsvData", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PyGsv {
+    gnss_type: String,
+    number_of_sentences: u16,
+    sentence_num: u16,
+    sats_in_view: u16,
+    satellites: Vec<PySatellite>,
+    signal_id: Option<u8>,
+}
+
+impl From<GsvData> for PyGsv {
+    fn from(gsv: GsvData) -> Self {
+        Self {
+            gnss_type: name(gsv.gnss_type),
+            number_of_sentences: gsv.number_of_sentences,
+            sentence_num: gsv.sentence_num,
+            sats_in_view: gsv.sats_in_view,
+            satellites: gsv.sats_info.iter().flatten().map(Into::into).collect(),
+            signal_id: gsv.signal_id,
+        }
+    }
+}
+
+/// Any other valid sentence, only its type is exposed.
+#[pyclass(name = "Sentence", get_all, frozen)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PySentence {
+    sentence_type: String,
+}
+
+/// Parses a single sentence without keeping state, see [`crate::parse_str()`].
+#[pyfunction]
+fn parse_str(py: Python<'_>, sentence: &str) -> PyResult<PyObject> {
+    let object = match crate::parse_str(sentence).map_err(py_error)? {
+        ParseResult::GGA(gga) => PyGga::from(gga).into_py(py),
+        ParseResult::RMC(rmc) => PyRmc::from(rmc).into_py(py),
+        ParseResult::GLL(gll) => PyGll::from(gll).into_py(py),
+        ParseResult::VTG(vtg) => PyVtg::from(vtg).into_py(py),
+        ParseResult::GSA(gsa) => PyGsa::from(gsa).into_py(py),
+        ParseResult::GSV(gsv) => PyGsv::from(gsv).into_py(py),
+        ref other => PySentence {
+            sentence_type: crate::SentenceType::from(other).as_str().into(),
+        }
+        .into_py(py),
+    };
+    Ok(object)
+}
+
+#[pymodule]
+fn nmea_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyNmea>()?;
+    m.add_class::<PyFix>()?;
+    m.add_class::<PySatellite>()?;
+    m.add_class::<PyGga>()?;
+    m.add_class::<PyRmc>()?;
+    m.add_class::<PyGll>()?;
+    m.add_class::<PyVtg>()?;
+    m.add_class::<PyGsa>()?;
+    m.add_class::<PyGsv>()?;
+    m.add_class::<PySentence>()?;
+    m.add_function(wrap_pyfunction!(parse_str, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "GSV"))]
+    fn test_conversions() {
+        let mut nmea = PyNmea::new();
+        assert_eq!(
+            nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+                .unwrap(),
+            "GGA"
+        );
+        nmea.parse("$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70")
+            .unwrap();
+
+        let fix = nmea.fix();
+        assert_eq!(fix.fix_type.as_deref(), Some("Gps"));
+        assert_eq!(fix.fix_satellites, Some(8));
+
+        let satellites = nmea.satellites();
+        assert_eq!(satellites.len(), 4);
+        assert_eq!(satellites[0].gnss_type, "Gps");
+        assert_eq!(satellites[0].prn, 5);
+    }
+}
@@ -0,0 +1,479 @@
+//! Rewriting sentences, e.g. in multiplexers and bridges.
+//!
+//! A [`Rewriter`] checks a sentence, applies its [`Transform`]s in order and
+//! emits the result with a new checksum, or drops it. [`EditableSentence`]
+//! gives access to the fields for custom transforms.
+//!
+//! TAG blocks are not emitted, see [`split_tag_block()`](crate::split_tag_block)
+//! to keep them.
+
+use core::{fmt::Write, ops::Range};
+
+use arrayvec::ArrayString;
+use chrono::{Datelike, Duration, NaiveDate};
+use heapless::Vec;
+
+use crate::{
+    parse::{checked_sentence, checksum},
+    Error, SentenceType, TalkerId, SENTENCE_MAX_LEN,
+};
+
+/// Default maximum number of transforms of a [`Rewriter`].
+pub const REWRITE_STEPS: usize = 8;
+
+/// Length of `$` and `*hh` around the body of a sentence.
+const FRAMING_LEN: usize = 4;
+
+/// A checked sentence whose address and fields can be changed.
+///
+/// Field 0 is the address, e.g. `GPGGA`, and the data fields are numbered
+/// from 1, like in the documentation of the sentences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditableSentence {
+    /// The sentence between `$` and `*`
+    body: ArrayString<SENTENCE_MAX_LEN>,
+    sentence_type: SentenceType,
+}
+
+impl EditableSentence {
+    /// Checks a sentence, like [`parse_str()`](crate::parse_str), and
+    /// prepares it for editing. Its TAG block is dropped.
+    pub fn new(sentence: &str) -> Result<Self, Error<'_>> {
+        let nmea_sentence = checked_sentence(sentence)?;
+        let mut body = ArrayString::new();
+        // the framing checks the length
+        let _ = write!(
+            body,
+            "{}{},{}",
+            nmea_sentence.talker_id,
+            nmea_sentence.message_id.as_str(),
+            nmea_sentence.data
+        );
+        Ok(Self {
+            body,
+            sentence_type: nmea_sentence.message_id,
+        })
+    }
+
+    pub fn talker_id(&self) -> &str {
+        &self.body[..2]
+    }
+
+    pub fn sentence_type(&self) -> SentenceType {
+        self.sentence_type
+    }
+
+    /// Replaces the talker ID.
+    pub fn set_talker_id(&mut self, talker_id: TalkerId) {
+        let mut body = ArrayString::new();
+        // as long as before
+        let _ = body.try_push_str(talker_id.as_str());
+        let _ = body.try_push_str(&self.body[2..]);
+        self.body = body;
+    }
+
+    /// Returns whether the sentence is proprietary, its talker ID starting
+    /// with `P`.
+    pub fn is_proprietary(&self) -> bool {
+        self.body.starts_with('P')
+    }
+
+    /// Returns the number of data fields.
+    pub fn field_count(&self) -> usize {
+        self.body.split(',').count() - 1
+    }
+
+    /// Returns a field, 0 being the address.
+    pub fn field(&self, index: usize) -> Option<&str> {
+        self.body.split(',').nth(index)
+    }
+
+    /// Replaces a data field, adding empty fields up to it if needed.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::SentenceLength`] if the sentence would be longer than
+    /// [`SENTENCE_MAX_LEN`], it is then unchanged.
+    pub fn set_field(&mut self, index: usize, value: &str) -> Result<(), Error<'static>> {
+        let mut body = ArrayString::<SENTENCE_MAX_LEN>::new();
+        let fields = self.field_count().max(index);
+        let mut length = 0;
+        for i in 0..=fields {
+            let field = if i == index && i != 0 {
+                value
+            } else {
+                self.field(i).unwrap_or_default()
+            };
+            length += field.len() + usize::from(i != 0);
+            if i != 0 {
+                let _ = body.try_push(',');
+            }
+            let _ = body.try_push_str(field);
+        }
+        if length + FRAMING_LEN > SENTENCE_MAX_LEN {
+            return Err(Error::SentenceLength(length + FRAMING_LEN));
+        }
+        self.body = body;
+        Ok(())
+    }
+
+    /// Keeps the first `count` data fields, e.g. to drop the fields added
+    /// by a vendor or a later revision of the standard.
+    pub fn truncate_fields(&mut self, count: usize) {
+        if let Some((end, _)) = self.body.match_indices(',').nth(count) {
+            self.body.truncate(end);
+        }
+    }
+
+    /// Shifts the UTC time of the sentence by some seconds, and its date
+    /// across midnight. The fraction of the seconds is kept.
+    ///
+    /// Only the time of GGA, GNS, GST, GBS, RMC, ZDA and GLL sentences is
+    /// shifted, with the date of RMC and ZDA. Empty times are kept.
+    pub fn shift_time(&mut self, seconds: i32) -> Result<(), Error<'static>> {
+        let index = match self.sentence_type {
+            SentenceType::GGA
+            | SentenceType::GNS
+            | SentenceType::GST
+            | SentenceType::GBS
+            | SentenceType::RMC
+            | SentenceType::ZDA => 1,
+            SentenceType::GLL => 5,
+            _ => return Ok(()),
+        };
+        let Some((time, days)) = self.field(index).and_then(|time| shift_hms(time, seconds)) else {
+            return Ok(());
+        };
+        self.set_field(index, &time)?;
+        if days == 0 {
+            return Ok(());
+        }
+        match self.sentence_type {
+            SentenceType::RMC => {
+                let date = self
+                    .field(9)
+                    .filter(|date| date.len() == 6)
+                    .and_then(|date| {
+                        let number = |range: Range<usize>| date.get(range)?.parse::<u32>().ok();
+                        let year = 2000 + number(4..6)? as i32;
+                        NaiveDate::from_ymd_opt(year, number(2..4)?, number(0..2)?)
+                    });
+                let Some(date) = date else {
+                    return Ok(());
+                };
+                let date = date + Duration::days(days);
+                let mut field = ArrayString::<6>::new();
+                let _ = write!(
+                    field,
+                    "{:02}{:02}{:02}",
+                    date.day(),
+                    date.month(),
+                    date.year() % 100
+                );
+                self.set_field(9, &field)
+            }
+            SentenceType::ZDA => {
+                let date = (self.field(2), self.field(3), self.field(4));
+                let (Some(day), Some(month), Some(year)) = date else {
+                    return Ok(());
+                };
+                let (Ok(day), Ok(month), Ok(year)) = (day.parse(), month.parse(), year.parse())
+                else {
+                    return Ok(());
+                };
+                let Some(date) = NaiveDate::from_ymd_opt(year, month, day) else {
+                    return Ok(());
+                };
+                let date = date + Duration::days(days);
+                let mut field = ArrayString::<4>::new();
+                let _ = write!(field, "{:02}", date.day());
+                self.set_field(2, &field)?;
+                field.clear();
+                let _ = write!(field, "{:02}", date.month());
+                self.set_field(3, &field)?;
+                field.clear();
+                let _ = write!(field, "{:04}", date.year());
+                self.set_field(4, &field)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the sentence with its `$` and new checksum.
+    pub fn to_sentence(&self) -> ArrayString<SENTENCE_MAX_LEN> {
+        let mut sentence = ArrayString::new();
+        // the fields are never longer than the maximum
+        let _ = write!(
+            sentence,
+            "${}*{:02X}",
+            self.body,
+            checksum(self.body.as_bytes().iter())
+        );
+        sentence
+    }
+}
+
+/// Shifts a `hhmmss[.ss]` time, returning the new time and the number of
+/// days it moved.
+fn shift_hms(time: &str, seconds: i32) -> Option<(ArrayString<16>, i64)> {
+    let (whole, fraction) = time.split_at(time.find('.').unwrap_or(time.len()));
+    if whole.len() != 6 || !whole.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let number = |range: Range<usize>| whole[range].parse::<i64>().ok();
+    let of_day = number(0..2)? * 3600 + number(2..4)? * 60 + number(4..6)? + i64::from(seconds);
+    let days = of_day.div_euclid(86_400);
+    let of_day = of_day.rem_euclid(86_400);
+    let mut shifted = ArrayString::new();
+    write!(
+        shifted,
+        "{:02}{:02}{:02}",
+        of_day / 3600,
+        of_day / 60 % 60,
+        of_day % 60
+    )
+    .ok()?;
+    shifted.try_push_str(fraction).ok()?;
+    Some((shifted, days))
+}
+
+/// A change applied by a [`Rewriter`].
+#[derive(Debug, Clone, Copy)]
+pub enum Transform {
+    /// Replaces the talker ID of all the sentences, except the proprietary
+    /// ones
+    SetTalker(TalkerId),
+    /// Replaces a talker ID by another one
+    MapTalker(TalkerId, TalkerId),
+    /// Drops the sentences of a type
+    Drop(SentenceType),
+    /// Drops the proprietary sentences
+    DropProprietary,
+    /// Keeps the first data fields of the sentences of a type
+    TruncateFields(SentenceType, u8),
+    /// Shifts the time of the sentences by some seconds, see
+    /// [`EditableSentence::shift_time()`]
+    ShiftTime(i32),
+    /// Calls a function, which returns `false` to drop the sentence
+    Custom(fn(&mut EditableSentence) -> bool),
+}
+
+/// A sequence of [`Transform`]s, see the [module documentation](self).
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{
+///     rewrite::{Rewriter, Transform},
+///     SentenceType, TalkerId,
+/// };
+///
+/// let mut rewriter = Rewriter::<4>::new();
+/// rewriter.push(Transform::SetTalker(TalkerId::new("GN").unwrap())).unwrap();
+/// rewriter.push(Transform::Drop(SentenceType::GSV)).unwrap();
+/// rewriter.push(Transform::ShiftTime(-18)).unwrap();
+///
+/// let sentence = rewriter
+///     .rewrite("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(
+///     sentence.as_str(),
+///     "$GNGGA,092732.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*6C"
+/// );
+/// assert_eq!(rewriter.rewrite("$GPGSV,1,1,01,02,62,084,45*43").unwrap(), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Rewriter<const N: usize = REWRITE_STEPS> {
+    transforms: Vec<Transform, N>,
+}
+
+impl<const N: usize> Rewriter<N> {
+    pub const fn new() -> Self {
+        Self {
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Adds a transform, applied after the previous ones.
+    ///
+    /// Returns the transform back if there are already `N` transforms.
+    pub fn push(&mut self, transform: Transform) -> Result<(), Transform> {
+        self.transforms.push(transform)
+    }
+
+    pub fn transforms(&self) -> &[Transform] {
+        &self.transforms
+    }
+
+    /// Applies the transforms to a sentence.
+    ///
+    /// Returns `None` for dropped sentences.
+    ///
+    /// # Errors
+    ///
+    /// The errors of [`parse_str()`](crate::parse_str) for malformed
+    /// sentences, and [`Error::SentenceLength`] when a transform makes the
+    /// sentence too long.
+    pub fn rewrite<'a>(
+        &self,
+        sentence: &'a str,
+    ) -> Result<Option<ArrayString<SENTENCE_MAX_LEN>>, Error<'a>> {
+        let mut sentence = EditableSentence::new(sentence)?;
+        for transform in &self.transforms {
+            let keep = match *transform {
+                Transform::SetTalker(talker_id) => {
+                    if !sentence.is_proprietary() {
+                        sentence.set_talker_id(talker_id);
+                    }
+                    true
+                }
+                Transform::MapTalker(from, to) => {
+                    if sentence.talker_id() == from.as_str() {
+                        sentence.set_talker_id(to);
+                    }
+                    true
+                }
+                Transform::Drop(sentence_type) => sentence.sentence_type() != sentence_type,
+                Transform::DropProprietary => !sentence.is_proprietary(),
+                Transform::TruncateFields(sentence_type, count) => {
+                    if sentence.sentence_type() == sentence_type {
+                        sentence.truncate_fields(count.into());
+                    }
+                    true
+                }
+                Transform::ShiftTime(seconds) => {
+                    sentence.shift_time(seconds)?;
+                    true
+                }
+                Transform::Custom(transform) => transform(&mut sentence),
+            };
+            if !keep {
+                return Ok(None);
+            }
+        }
+        Ok(Some(sentence.to_sentence()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields() {
+        let mut sentence = EditableSentence::new("\\c:1622040996*5C\\$GPHDT,274.07,T*03").unwrap();
+        assert_eq!(sentence.field_count(), 2);
+        assert_eq!(sentence.field(0), Some("GPHDT"));
+        assert_eq!(sentence.field(2), Some("T"));
+        assert_eq!(sentence.field(3), None);
+
+        sentence.set_field(1, "274.1").unwrap();
+        sentence.set_field(4, "X").unwrap();
+        assert_eq!(sentence.to_sentence().as_str(), "$GPHDT,274.1,T,,X*6D");
+        assert!(matches!(
+            sentence.set_field(5, &"0".repeat(90)),
+            Err(Error::SentenceLength(_))
+        ));
+        sentence.truncate_fields(1);
+        assert_eq!(sentence.to_sentence().as_str(), "$GPHDT,274.1*4D");
+        sentence.truncate_fields(0);
+        assert_eq!(sentence.to_sentence().as_str(), "$GPHDT*4F");
+
+        assert!(matches!(
+            EditableSentence::new("$GPHDT,274.07,T*00"),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_shift_time() {
+        let mut rmc = EditableSentence::new(
+            "$GPRMC,235959.50,A,4916.45,N,12311.12,W,000.5,054.7,311224,020.3,E*43",
+        )
+        .unwrap();
+        rmc.shift_time(2).unwrap();
+        assert_eq!(rmc.field(1), Some("000001.50"));
+        assert_eq!(rmc.field(9), Some("010125"));
+        rmc.shift_time(-2).unwrap();
+        assert_eq!(rmc.field(1), Some("235959.50"));
+        assert_eq!(rmc.field(9), Some("311224"));
+
+        let mut zda = EditableSentence::new("$GPZDA,000010.00,01,03,2024,00,00*61").unwrap();
+        zda.shift_time(-18).unwrap();
+        assert_eq!(
+            zda.to_sentence().as_str(),
+            "$GPZDA,235952.00,29,02,2024,00,00*61"
+        );
+
+        // sentences without time, or with an empty one, are unchanged
+        let mut hdt = EditableSentence::new("$GPHDT,274.07,T*03").unwrap();
+        hdt.shift_time(10).unwrap();
+        assert_eq!(hdt.to_sentence().as_str(), "$GPHDT,274.07,T*03");
+        let mut gga = EditableSentence::new("$GPGGA,,,,,,0,00,99.99,,,,,,*48").unwrap();
+        gga.shift_time(10).unwrap();
+        assert_eq!(
+            gga.to_sentence().as_str(),
+            "$GPGGA,,,,,,0,00,99.99,,,,,,*48"
+        );
+    }
+
+    #[test]
+    fn test_rewriter() {
+        let mut rewriter = Rewriter::<3>::new();
+        rewriter
+            .push(Transform::MapTalker(
+                TalkerId::new("II").unwrap(),
+                TalkerId::new("WI").unwrap(),
+            ))
+            .unwrap();
+        rewriter.push(Transform::DropProprietary).unwrap();
+        rewriter
+            .push(Transform::Custom(|sentence| {
+                sentence.sentence_type() != SentenceType::MWV || sentence.field(5) == Some("A")
+            }))
+            .unwrap();
+        assert!(rewriter.push(Transform::ShiftTime(1)).is_err());
+
+        assert_eq!(
+            rewriter
+                .rewrite("$IIMTW,17.5,C*10")
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "$WIMTW,17.5,C*0E"
+        );
+        assert_eq!(rewriter.rewrite("$PGRMZ,246,f,3*1B").unwrap(), None);
+        assert_eq!(rewriter.rewrite("$WIMWV,200.0,T,5.0,M,V*36").unwrap(), None);
+        assert!(rewriter
+            .rewrite("$WIMWV,041.1,R,01.0,N,A*16")
+            .unwrap()
+            .is_some());
+        assert!(rewriter.rewrite("$GPHDT,274.07,T*00").is_err());
+
+        let mut rewriter = Rewriter::<2>::new();
+        rewriter
+            .push(Transform::TruncateFields(SentenceType::RMC, 11))
+            .unwrap();
+        rewriter
+            .push(Transform::SetTalker(TalkerId::new("GN").unwrap()))
+            .unwrap();
+        assert_eq!(
+            rewriter
+                .rewrite("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E,A*05")
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "$GNRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*76"
+        );
+        // proprietary sentences keep their talker ID
+        assert_eq!(
+            rewriter
+                .rewrite("$PGRMZ,246,f,3*1B")
+                .unwrap()
+                .unwrap()
+                .as_str(),
+            "$PGRMZ,246,f,3*1B"
+        );
+    }
+}
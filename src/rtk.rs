@@ -0,0 +1,333 @@
+//! RTK solution status tracking.
+
+use chrono::NaiveTime;
+use heapless::Vec;
+#[cfg(feature = "vendor-specific")]
+use nom::{
+    bytes::complete::{tag, take_until},
+    character::complete::{char, one_of},
+    combinator::opt,
+    sequence::{preceded, terminated},
+    IResult,
+};
+
+#[cfg(feature = "vendor-specific")]
+use crate::{parse::ProprietarySentence, sentences::utils::float_number, Error};
+use crate::{sentences::FixType, Float};
+
+/// Number of mode transitions kept by [`RtkStatus`].
+pub const RTK_HISTORY_LEN: usize = 16;
+
+/// Carrier-phase solution mode derived from the [`FixType`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RtkMode {
+    /// Any fix without carrier-phase ambiguity resolution, or no fix.
+    #[default]
+    None,
+    /// RTK with floating point ambiguities.
+    Float,
+    /// RTK with fixed integer ambiguities.
+    Fixed,
+}
+
+impl From<FixType> for RtkMode {
+    fn from(fix_type: FixType) -> Self {
+        match fix_type {
+            FixType::Rtk => RtkMode::Fixed,
+            FixType::FloatRtk => RtkMode::Float,
+            _ => RtkMode::None,
+        }
+    }
+}
+
+/// A change of the [`RtkMode`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RtkTransition {
+    pub from: RtkMode,
+    pub to: RtkMode,
+    /// Fix time of the first sentence with the new mode
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub fix_time: Option<NaiveTime>,
+    /// Age of differential corrections in seconds when the mode changed
+    pub age_of_differential: Option<f32>,
+}
+
+/// Baseline from the base station to the rover antenna.
+///
+/// It is reported by the SkyTraq `$PSTI,032` and the Trimble `$PTNL,AVR`
+/// sentences, with the `vendor-specific` feature, see
+/// [`Nmea::parse_proprietary()`](crate::Nmea::parse_proprietary). Trimble
+/// `$PTNL,GGK` has no baseline, only the RTK fix quality also sent in GGA.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RtkBaseline {
+    /// Length of the baseline, meters
+    pub length: Option<Float>,
+    /// Direction of the rover from the base, degrees true
+    pub heading: Option<Float>,
+}
+
+/// RTK mode, correction age and the recent mode transitions, updated from
+/// GGA and GNS sentences, and the baseline of vendor sentences.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{Nmea, RtkMode};
+///
+/// let mut nmea = Nmea::default();
+/// # #[cfg(feature = "GGA")]
+/// # {
+/// nmea.parse("$GPGGA,172814.0,3723.46587704,N,12202.26957864,W,5,6,1.2,18.893,M,-25.669,M,2.0,0031*48")
+///     .unwrap();
+/// nmea.parse("$GPGGA,172815.0,3723.46587704,N,12202.26957864,W,4,6,1.2,18.893,M,-25.669,M,1.0,0031*4B")
+///     .unwrap();
+///
+/// let rtk = nmea.rtk_status();
+/// assert_eq!(rtk.mode(), RtkMode::Fixed);
+/// assert_eq!(rtk.transitions().len(), 2);
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RtkStatus {
+    mode: RtkMode,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    mode_since: Option<NaiveTime>,
+    age_of_differential: Option<f32>,
+    transitions: Vec<RtkTransition, RTK_HISTORY_LEN>,
+    baseline: RtkBaseline,
+}
+
+impl RtkStatus {
+    /// Returns the current RTK mode.
+    pub fn mode(&self) -> RtkMode {
+        self.mode
+    }
+
+    /// Returns the fix time at which the current mode was entered.
+    pub fn mode_since(&self) -> Option<NaiveTime> {
+        self.mode_since
+    }
+
+    /// Returns the last reported age of differential corrections in seconds.
+    pub fn age_of_differential(&self) -> Option<f32> {
+        self.age_of_differential
+    }
+
+    /// Returns the last [`RTK_HISTORY_LEN`] mode transitions, oldest first.
+    pub fn transitions(&self) -> &[RtkTransition] {
+        &self.transitions
+    }
+
+    /// Returns the last reported baseline, see [`RtkBaseline`].
+    pub fn baseline(&self) -> RtkBaseline {
+        self.baseline
+    }
+
+    /// Returns the length of the last reported baseline, meters.
+    pub fn baseline_length(&self) -> Option<Float> {
+        self.baseline.length
+    }
+
+    /// Returns the heading of the last reported baseline, degrees true.
+    pub fn baseline_heading(&self) -> Option<Float> {
+        self.baseline.heading
+    }
+
+    #[cfg(feature = "vendor-specific")]
+    pub(crate) fn update_baseline(&mut self, baseline: RtkBaseline) {
+        self.baseline = baseline;
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        fix_type: Option<FixType>,
+        fix_time: Option<NaiveTime>,
+        age_of_differential: Option<f32>,
+    ) {
+        self.age_of_differential = age_of_differential;
+        let mode = fix_type.map(RtkMode::from).unwrap_or_default();
+        if mode == self.mode {
+            return;
+        }
+        if self.transitions.is_full() {
+            self.transitions.remove(0);
+        }
+        // cannot fail, there is space after the removal above
+        let _ = self.transitions.push(RtkTransition {
+            from: self.mode,
+            to: mode,
+            fix_time,
+            age_of_differential,
+        });
+        self.mode = mode;
+        self.mode_since = fix_time;
+    }
+}
+
+/// Parses the baseline of a `$PSTI,032` or `$PTNL,AVR` sentence.
+///
+/// Returns `None` for the other proprietary sentences, and an empty baseline
+/// when the receiver reports it as invalid.
+#[cfg(feature = "vendor-specific")]
+pub(crate) fn parse_rtk_baseline<'a>(
+    sentence: &ProprietarySentence<'a>,
+) -> Result<Option<RtkBaseline>, Error<'a>> {
+    let parser = match (sentence.manufacturer, sentence.message_id) {
+        ("STI", "") if sentence.data.starts_with("032,") => do_parse_psti_032,
+        ("TNL", "") if sentence.data.starts_with("AVR,") => do_parse_ptnl_avr,
+        _ => return Ok(None),
+    };
+    Ok(Some(parser(sentence.data)?.1))
+}
+
+/// SkyTraq RTK baseline data
+///
+/// ```text
+///  $PSTI,032,hhmmss.sss,ddmmyy,a,c,x.x,x.x,x.x,x.x,x.x,,,,,*hh
+/// ```
+///
+/// ID, UTC time, date, status (`A` valid, `V` invalid), mode, east, north
+/// and up projections of the baseline in meters, baseline length in meters,
+/// baseline course in degrees and reserved fields.
+#[cfg(feature = "vendor-specific")]
+fn do_parse_psti_032(i: &str) -> IResult<&str, RtkBaseline> {
+    let (i, _) = tag("032,")(i)?;
+    let (i, _time) = terminated(take_until(","), char(','))(i)?;
+    let (i, _date) = terminated(take_until(","), char(','))(i)?;
+    let (i, status) = one_of("AV")(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, _mode) = opt(one_of("NADEFR"))(i)?;
+    let (i, _east) = preceded(char(','), opt(float_number))(i)?;
+    let (i, _north) = preceded(char(','), opt(float_number))(i)?;
+    let (i, _up) = preceded(char(','), opt(float_number))(i)?;
+    let (i, length) = preceded(char(','), opt(float_number))(i)?;
+    let (i, heading) = preceded(char(','), opt(float_number))(i)?;
+    if status == 'V' {
+        return Ok((i, RtkBaseline::default()));
+    }
+    Ok((i, RtkBaseline { length, heading }))
+}
+
+/// Trimble time, yaw, tilt and range for moving baseline RTK
+///
+/// ```text
+///  $PTNL,AVR,hhmmss.ss,+x.x,Yaw,+x.x,Tilt,+x.x,Roll,x.x,x,x.x,x*hh
+/// ```
+///
+/// ID, UTC time, yaw in degrees, tilt in degrees, roll in degrees, each
+/// followed by its label, range in meters, GPS quality (0 no fix), PDOP and
+/// number of satellites.
+#[cfg(feature = "vendor-specific")]
+fn do_parse_ptnl_avr(i: &str) -> IResult<&str, RtkBaseline> {
+    let (i, _) = tag("AVR,")(i)?;
+    let (i, _time) = take_until(",")(i)?;
+    let (i, heading) = preceded(char(','), opt(float_number))(i)?;
+    let (i, _) = preceded(char(','), take_until(","))(i)?;
+    let (i, _tilt) = preceded(char(','), opt(float_number))(i)?;
+    let (i, _) = preceded(char(','), take_until(","))(i)?;
+    let (i, _roll) = preceded(char(','), opt(float_number))(i)?;
+    let (i, _) = preceded(char(','), take_until(","))(i)?;
+    let (i, length) = preceded(char(','), opt(float_number))(i)?;
+    let (i, quality) = preceded(char(','), opt(one_of("0123456789")))(i)?;
+    if matches!(quality, None | Some('0')) {
+        return Ok((i, RtkBaseline::default()));
+    }
+    Ok((i, RtkBaseline { length, heading }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rtk_transitions() {
+        let mut status = RtkStatus::default();
+        let time = |s| NaiveTime::from_hms_opt(12, 0, s);
+
+        status.update(Some(FixType::DGps), time(0), Some(1.0));
+        assert_eq!(status.mode(), RtkMode::None);
+        assert!(status.transitions().is_empty());
+
+        status.update(Some(FixType::FloatRtk), time(1), Some(1.0));
+        status.update(Some(FixType::Rtk), time(2), Some(1.0));
+        status.update(Some(FixType::Rtk), time(3), Some(2.0));
+        assert_eq!(status.mode(), RtkMode::Fixed);
+        assert_eq!(status.mode_since(), time(2));
+        assert_eq!(status.age_of_differential(), Some(2.0));
+        assert_eq!(
+            status.transitions(),
+            [
+                RtkTransition {
+                    from: RtkMode::None,
+                    to: RtkMode::Float,
+                    fix_time: time(1),
+                    age_of_differential: Some(1.0),
+                },
+                RtkTransition {
+                    from: RtkMode::Float,
+                    to: RtkMode::Fixed,
+                    fix_time: time(2),
+                    age_of_differential: Some(1.0),
+                },
+            ]
+        );
+
+        for s in 0..RTK_HISTORY_LEN as u32 {
+            status.update(Some(FixType::Gps), time(10 + s), None);
+            status.update(Some(FixType::FloatRtk), time(10 + s), None);
+        }
+        assert_eq!(status.transitions().len(), RTK_HISTORY_LEN);
+        assert_eq!(status.transitions()[0].fix_time, time(18));
+    }
+
+    #[test]
+    #[cfg(feature = "vendor-specific")]
+    fn test_parse_rtk_baseline() {
+        use crate::parse_proprietary_sentence;
+
+        let psti = parse_proprietary_sentence(
+            "$PSTI,032,041457.000,170316,A,R,0.603,-0.837,-0.089,1.036,144.22,,,,,*1C",
+        )
+        .unwrap();
+        assert_eq!(
+            parse_rtk_baseline(&psti),
+            Ok(Some(RtkBaseline {
+                length: Some(1.036),
+                heading: Some(144.22),
+            }))
+        );
+
+        let invalid =
+            parse_proprietary_sentence("$PSTI,032,041457.000,170316,V,N,,,,,,,,,,*04").unwrap();
+        assert_eq!(
+            parse_rtk_baseline(&invalid),
+            Ok(Some(RtkBaseline::default()))
+        );
+
+        let avr = parse_proprietary_sentence(
+            "$PTNL,AVR,212405.20,+52.1531,Yaw,-0.0806,Tilt,,,12.575,3,1.4,16*39",
+        )
+        .unwrap();
+        assert_eq!(
+            parse_rtk_baseline(&avr),
+            Ok(Some(RtkBaseline {
+                length: Some(12.575),
+                heading: Some(52.1531),
+            }))
+        );
+
+        let other = parse_proprietary_sentence("$PSRF103,00,01,00,01*25").unwrap();
+        assert_eq!(parse_rtk_baseline(&other), Ok(None));
+
+        let broken = parse_proprietary_sentence("$PSTI,032,041457.000,170316,X*00").unwrap();
+        assert!(parse_rtk_baseline(&broken).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! Stable satellite identities across talkers and receivers.
+//!
+//! By the standard, GSV sentences of the `GN` talker carry NMEA PRNs (e.g.
+//! 65-96 for GLONASS) while the other talkers carry the system specific
+//! satellite IDs (1-24 for GLONASS). Receivers get this wrong in various
+//! conflicting ways, so the interpretation is configurable with a
+//! [`SatIdPolicy`].
+
+use core::fmt;
+
+use crate::sentences::GnssType;
+
+/// System specific satellite ID, e.g. the PRN for GPS or the slot number for
+/// GLONASS.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{sentences::GnssType, SatId};
+///
+/// let id = SatId::from_nmea_prn(70).unwrap();
+/// assert_eq!(id, SatId::new(GnssType::Glonass, 6));
+/// assert_eq!(id.to_string(), "R06");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SatId {
+    gnss_type: GnssType,
+    svid: u32,
+}
+
+/// Range of the NMEA PRNs of SBAS satellites, which are their PRN minus 87.
+const SBAS_NMEA_PRNS: core::ops::RangeInclusive<u32> = 33..=64;
+
+/// Ranges of NMEA PRNs and their offset to the satellite ID.
+const NMEA_PRN_RANGES: [(GnssType, u32, u32, u32); 6] = [
+    (GnssType::Gps, 1, 32, 0),
+    (GnssType::Glonass, 65, 96, 64),
+    (GnssType::Qzss, 193, 199, 192),
+    (GnssType::Beidou, 201, 237, 200),
+    (GnssType::Galileo, 301, 336, 300),
+    (GnssType::Beidou, 401, 437, 400),
+];
+
+impl SatId {
+    pub const fn new(gnss_type: GnssType, svid: u32) -> Self {
+        Self { gnss_type, svid }
+    }
+
+    /// Converts an NMEA PRN, as sent by the `GN` talker, to a satellite ID.
+    ///
+    /// The constellation is inferred from the ranges of NMEA 4.11: 1-32 GPS,
+    /// 65-96 GLONASS, 193-199 QZSS, 201-237 and 401-437 BeiDou and 301-336
+    /// Galileo. This also works for the PRNs of GSA and GBS sentences
+    /// without a System ID.
+    ///
+    /// Returns `None` for PRNs outside of these ranges, e.g. SBAS, see
+    /// [`is_sbas_prn()`](Self::is_sbas_prn).
+    pub fn from_nmea_prn(prn: u32) -> Option<Self> {
+        NMEA_PRN_RANGES
+            .iter()
+            .find(|(_, first, last, _)| (*first..=*last).contains(&prn))
+            .map(|&(gnss_type, _, _, offset)| Self::new(gnss_type, prn - offset))
+    }
+
+    /// Returns `true` for the NMEA PRNs of SBAS satellites, 33-64.
+    pub fn is_sbas_prn(prn: u32) -> bool {
+        SBAS_NMEA_PRNS.contains(&prn)
+    }
+
+    #[inline]
+    pub fn gnss_type(&self) -> GnssType {
+        self.gnss_type
+    }
+
+    /// Returns the system specific satellite ID.
+    #[inline]
+    pub fn svid(&self) -> u32 {
+        self.svid
+    }
+}
+
+/// Orbit of a satellite, see [`SatId::orbit_class()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OrbitClass {
+    /// Geostationary orbit: the satellite stays at the same azimuth and
+    /// elevation and its SNR barely changes
+    Geo,
+    /// Inclined geosynchronous orbit
+    Igso,
+    /// Medium Earth orbit
+    Meo,
+}
+
+impl SatId {
+    /// Returns the orbit class of a BeiDou satellite, by its PRN: 1-5 and
+    /// 59-63 are GEO, 6-10, 13, 16, 31, 38-40 and 56 IGSO and the others up
+    /// to 63 MEO.
+    ///
+    /// Returns `None` for the other constellations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{sentences::GnssType, OrbitClass, SatId};
+    ///
+    /// assert_eq!(SatId::new(GnssType::Beidou, 3).orbit_class(), Some(OrbitClass::Geo));
+    /// assert_eq!(SatId::new(GnssType::Beidou, 23).orbit_class(), Some(OrbitClass::Meo));
+    /// assert_eq!(SatId::new(GnssType::Gps, 3).orbit_class(), None);
+    /// ```
+    pub fn orbit_class(&self) -> Option<OrbitClass> {
+        if self.gnss_type != GnssType::Beidou {
+            return None;
+        }
+        match self.svid {
+            1..=5 | 59..=63 => Some(OrbitClass::Geo),
+            6..=10 | 13 | 16 | 31 | 38..=40 | 56 => Some(OrbitClass::Igso),
+            11..=63 => Some(OrbitClass::Meo),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SatId {
+    /// Formats the ID like RINEX, e.g. `G05` or `E11`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let system = match self.gnss_type {
+            GnssType::Beidou => 'C',
+            GnssType::Galileo => 'E',
+            GnssType::Gps => 'G',
+            GnssType::Glonass => 'R',
+            GnssType::NavIC => 'I',
+            GnssType::Qzss => 'J',
+        };
+        write!(f, "{}{:02}", system, self.svid)
+    }
+}
+
+/// Interpretation of the satellite numbers of GSV sentences from system
+/// specific talkers (`GP`, `GL`, `GA`, `GB`, ...).
+///
+/// Satellites of `GN` GSV sentences are always NMEA PRNs, it is the only way
+/// to tell their constellation, and they are converted while parsing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SatIdPolicy {
+    /// The numbers are satellite IDs, as required by the standard, and are
+    /// kept as is.
+    #[default]
+    Svid,
+    /// The numbers are NMEA PRNs and are converted to satellite IDs.
+    Prn,
+    /// Numbers in the NMEA PRN range of the talker's constellation are
+    /// converted, others are kept as is, for receivers mixing both.
+    Auto,
+}
+
+impl SatIdPolicy {
+    /// NMEA 4.10+ receivers, e.g. u-blox M9 and later.
+    pub const NMEA_4_10: Self = Self::Svid;
+    /// u-blox receivers with the NMEA 4.0 "extended" numbering, which sends
+    /// PRNs for all talkers.
+    pub const UBLOX_NMEA_4_0: Self = Self::Prn;
+    /// Receivers known to send inconsistent numbers, e.g. some Quectel,
+    /// Telit and Jackson Labs firmwares.
+    pub const MIXED: Self = Self::Auto;
+
+    /// Returns the ID of a satellite reported by a talker of the given
+    /// constellation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{sentences::GnssType, SatId, SatIdPolicy};
+    ///
+    /// assert_eq!(
+    ///     SatIdPolicy::Auto.resolve(GnssType::Galileo, 311),
+    ///     SatId::new(GnssType::Galileo, 11)
+    /// );
+    /// assert_eq!(
+    ///     SatIdPolicy::Auto.resolve(GnssType::Galileo, 11),
+    ///     SatId::new(GnssType::Galileo, 11)
+    /// );
+    /// ```
+    pub fn resolve(&self, gnss_type: GnssType, number: u32) -> SatId {
+        let from_prn = || SatId::from_nmea_prn(number).filter(|id| id.gnss_type == gnss_type);
+        let id = match self {
+            SatIdPolicy::Svid => None,
+            // GPS PRNs are also satellite IDs
+            SatIdPolicy::Prn | SatIdPolicy::Auto => from_prn(),
+        };
+        id.unwrap_or(SatId::new(gnss_type, number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_nmea_prn() {
+        assert_eq!(SatId::from_nmea_prn(5), Some(SatId::new(GnssType::Gps, 5)));
+        assert_eq!(
+            SatId::from_nmea_prn(196),
+            Some(SatId::new(GnssType::Qzss, 4))
+        );
+        assert_eq!(SatId::from_nmea_prn(200), None);
+        assert_eq!(
+            SatId::from_nmea_prn(201),
+            Some(SatId::new(GnssType::Beidou, 1))
+        );
+        assert_eq!(
+            SatId::from_nmea_prn(202),
+            Some(SatId::new(GnssType::Beidou, 2))
+        );
+        assert_eq!(
+            SatId::from_nmea_prn(220),
+            Some(SatId::new(GnssType::Beidou, 20))
+        );
+        assert_eq!(
+            SatId::from_nmea_prn(420),
+            Some(SatId::new(GnssType::Beidou, 20))
+        );
+        // SBAS
+        assert_eq!(SatId::from_nmea_prn(40), None);
+        assert_eq!(SatId::from_nmea_prn(0), None);
+    }
+
+    #[test]
+    fn test_beidou_orbit_class() {
+        let class = |svid| SatId::new(GnssType::Beidou, svid).orbit_class();
+        assert_eq!(class(1), Some(OrbitClass::Geo));
+        assert_eq!(class(61), Some(OrbitClass::Geo));
+        assert_eq!(class(38), Some(OrbitClass::Igso));
+        assert_eq!(class(12), Some(OrbitClass::Meo));
+        assert_eq!(class(46), Some(OrbitClass::Meo));
+        assert_eq!(class(0), None);
+        assert_eq!(class(64), None);
+    }
+
+    #[test]
+    fn test_policy() {
+        assert_eq!(
+            SatIdPolicy::Svid.resolve(GnssType::Glonass, 70),
+            SatId::new(GnssType::Glonass, 70)
+        );
+        assert_eq!(
+            SatIdPolicy::Prn.resolve(GnssType::Glonass, 70),
+            SatId::new(GnssType::Glonass, 6)
+        );
+        // a GLONASS PRN reported by the GPS talker is not converted
+        assert_eq!(
+            SatIdPolicy::Prn.resolve(GnssType::Gps, 70),
+            SatId::new(GnssType::Gps, 70)
+        );
+    }
+}
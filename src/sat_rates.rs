@@ -0,0 +1,211 @@
+//! Azimuth and elevation change rates of the satellites in view, for
+//! antenna pointing and obstruction prediction.
+//!
+//! GSV sentences report whole degrees, which satellites cross in a few
+//! minutes, so the rates of consecutive epochs would be mostly zero. The
+//! rates are instead estimated over a baseline of at least
+//! [`SAT_RATES_MIN_BASELINE`] seconds of GSV epochs, and returned by
+//! [`Satellite::rates()`].
+
+use chrono::{NaiveTime, Timelike};
+use heapless::Vec;
+
+use crate::{sentences::GnssType, Satellite};
+
+/// Number of satellites tracked by [`SatRatesTracker`].
+pub const SAT_RATES_SATELLITES: usize = 58;
+
+/// Shortest time in seconds between the two reports of a satellite the
+/// rates are estimated from.
+pub const SAT_RATES_MIN_BASELINE: f32 = 30.;
+
+/// Longest time in seconds between two reports of a satellite: after a
+/// longer gap, its rates are forgotten.
+pub const SAT_RATES_MAX_GAP: f32 = 300.;
+
+const SECONDS_PER_DAY: f32 = 86_400.;
+
+/// Change rates of the position of a satellite in the sky.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SatRates {
+    /// Elevation rate in degrees per second, positive when rising
+    pub elevation_rate: f32,
+    /// Azimuth rate in degrees per second, positive clockwise
+    pub azimuth_rate: f32,
+}
+
+/// A report of a satellite, at seconds since midnight.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sample {
+    time: f32,
+    elevation: i16,
+    azimuth: i16,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+struct TrackedSatellite {
+    gnss_type: GnssType,
+    prn: u32,
+    /// The start of the baseline
+    reference: Sample,
+    /// The last report
+    last: Sample,
+    rates: Option<SatRates>,
+    last_update: u32,
+}
+
+/// Estimates the azimuth and elevation rates of the satellites in view from
+/// their GSV reports, see the [module documentation](self).
+///
+/// When more than [`SAT_RATES_SATELLITES`] satellites have been seen, the
+/// one that was reported least recently is forgotten.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SatRatesTracker {
+    satellites: Vec<TrackedSatellite, SAT_RATES_SATELLITES>,
+    updates: u32,
+}
+
+impl SatRatesTracker {
+    /// Returns the rates of a satellite.
+    pub fn get(&self, gnss_type: GnssType, prn: u32) -> Option<SatRates> {
+        self.satellites
+            .iter()
+            .find(|sat| sat.gnss_type == gnss_type && sat.prn == prn)
+            .and_then(|sat| sat.rates)
+    }
+
+    /// Adds a report of a satellite at a fix time.
+    pub(crate) fn update(&mut self, satellite: &Satellite, fix_time: NaiveTime) {
+        let (Some(elevation), Some(azimuth)) = (satellite.elevation, satellite.azimuth) else {
+            return;
+        };
+        let sample = Sample {
+            time: fix_time.num_seconds_from_midnight() as f32 + fix_time.nanosecond() as f32 / 1e9,
+            elevation,
+            azimuth,
+        };
+        self.updates = self.updates.wrapping_add(1);
+        let updates = self.updates;
+        let position = self
+            .satellites
+            .iter()
+            .position(|sat| sat.gnss_type == satellite.gnss_type && sat.prn == satellite.prn);
+        let Some(index) = position else {
+            let new = TrackedSatellite {
+                gnss_type: satellite.gnss_type,
+                prn: satellite.prn,
+                reference: sample,
+                last: sample,
+                rates: None,
+                last_update: updates,
+            };
+            if let Err(new) = self.satellites.push(new) {
+                let oldest = self
+                    .satellites
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, sat)| updates.wrapping_sub(sat.last_update))
+                    .map(|(index, _)| index);
+                // the tracker is full, so there is an oldest entry
+                self.satellites[oldest.unwrap_or_default()] = new;
+            }
+            return;
+        };
+        let sat = &mut self.satellites[index];
+        sat.last_update = updates;
+        if elapsed(sat.last.time, sample.time) > SAT_RATES_MAX_GAP {
+            sat.reference = sample;
+            sat.rates = None;
+        }
+        sat.last = sample;
+        let baseline = elapsed(sat.reference.time, sample.time);
+        if baseline >= SAT_RATES_MIN_BASELINE {
+            let mut azimuth = f32::from(sample.azimuth - sat.reference.azimuth);
+            if azimuth > 180. {
+                azimuth -= 360.;
+            } else if azimuth <= -180. {
+                azimuth += 360.;
+            }
+            sat.rates = Some(SatRates {
+                elevation_rate: f32::from(sample.elevation - sat.reference.elevation) / baseline,
+                azimuth_rate: azimuth / baseline,
+            });
+            sat.reference = sample;
+        }
+    }
+}
+
+/// Seconds from `from` to `to`, across midnight.
+fn elapsed(from: f32, to: f32) -> f32 {
+    let seconds = to - from;
+    if seconds < 0. {
+        seconds + SECONDS_PER_DAY
+    } else {
+        seconds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn satellite(elevation: i16, azimuth: i16) -> Satellite {
+        Satellite {
+            gnss_type: GnssType::Gps,
+            prn: 5,
+            elevation: Some(elevation),
+            azimuth: Some(azimuth),
+            snr: None,
+            rates: None,
+        }
+    }
+
+    #[test]
+    fn test_sat_rates() {
+        let time = |s| NaiveTime::from_hms_opt(23, 59, 0).unwrap() + chrono::Duration::seconds(s);
+        let mut tracker = SatRatesTracker::default();
+        tracker.update(&satellite(40, 358), time(0));
+        tracker.update(&satellite(40, 359), time(20));
+        // the baseline is too short
+        assert_eq!(tracker.get(GnssType::Gps, 5), None);
+
+        // across midnight and north
+        tracker.update(&satellite(41, 1), time(60));
+        let rates = tracker.get(GnssType::Gps, 5).unwrap();
+        assert_relative_eq!(rates.elevation_rate, 1. / 60.);
+        assert_relative_eq!(rates.azimuth_rate, 3. / 60.);
+
+        // the satellite was lost
+        tracker.update(&satellite(20, 10), time(1000));
+        assert_eq!(tracker.get(GnssType::Gps, 5), None);
+        assert_eq!(tracker.get(GnssType::Gps, 6), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "GSV"))]
+    fn test_nmea_satellite_rates() {
+        let mut nmea = crate::Nmea::default();
+        nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+            .unwrap();
+        nmea.parse("$GPGSV,1,1,01,05,40,100,42*4E").unwrap();
+        assert_eq!(nmea.satellites()[0].rates(), None);
+
+        nmea.parse("$GPGGA,092850.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*79")
+            .unwrap();
+        nmea.parse("$GPGSV,1,1,01,05,39,103,42*43").unwrap();
+        let rates = nmea.satellites()[0].rates().unwrap();
+        assert_relative_eq!(rates.elevation_rate, -1. / 60.);
+        assert_relative_eq!(rates.azimuth_rate, 3. / 60.);
+        assert_eq!(nmea.sat_rates().get(GnssType::Gps, 5), Some(rates));
+    }
+}
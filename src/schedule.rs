@@ -0,0 +1,231 @@
+//! Expected sentence schedules, to detect sentences that stop arriving.
+//!
+//! A receiver whose configuration drifted, e.g. with GSA disabled, keeps
+//! sending the other sentences, and the data of the missing ones just goes
+//! stale. Declaring the expected interval of each sentence type with
+//! [`Nmea::expect_sentence()`] makes such gaps visible in
+//! [`Nmea::schedule_report()`] and [`Nmea::missing_sentences()`], next to
+//! the other checks of the parser like
+//! [`Nmea::unsupported_summary()`]. Timing uses the
+//! [`Clock`](crate::Clock) of the parser.
+
+use core::time::Duration;
+
+use crate::{sentence_table::SentenceTable, Nmea, ParseResult, SentenceType};
+
+/// Number of sentence types with an expected schedule.
+pub const SCHEDULE_LEN: usize = 8;
+
+/// How well a sentence type keeps its schedule.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleStatus {
+    /// Received within 1.5 intervals.
+    OnTime,
+    /// Not received for more than 1.5 intervals.
+    Late,
+    /// Not received for more than 3 intervals.
+    Missing,
+}
+
+/// Schedule status of one sentence type, see [`Nmea::schedule_report()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleReport {
+    pub sentence_type: SentenceType,
+    /// Expected interval between sentences
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub interval: Duration,
+    /// Time since the last sentence, or since the schedule was declared if
+    /// none was received
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub elapsed: Duration,
+    /// Number of sentences received since the schedule was declared
+    pub received: u32,
+    pub status: ScheduleStatus,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Expectation {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    interval: Duration,
+    /// Clock time of the last sentence or of the declaration
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    since: Option<Duration>,
+    received: u32,
+}
+
+impl Expectation {
+    fn report(&self, sentence_type: SentenceType, now: Duration) -> Option<ScheduleReport> {
+        let elapsed = now.saturating_sub(self.since?);
+        let status = if elapsed > self.interval * 3 {
+            ScheduleStatus::Missing
+        } else if elapsed > self.interval * 3 / 2 {
+            ScheduleStatus::Late
+        } else {
+            ScheduleStatus::OnTime
+        };
+        Some(ScheduleReport {
+            sentence_type,
+            interval: self.interval,
+            elapsed,
+            received: self.received,
+            status,
+        })
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Schedule {
+    expectations: SentenceTable<Expectation, SCHEDULE_LEN>,
+}
+
+impl Schedule {
+    fn received(&mut self, sentence_type: SentenceType, now: Option<Duration>) {
+        if let Some(expectation) = self.expectations.get_mut(sentence_type) {
+            expectation.since = now.or(expectation.since);
+            expectation.received = expectation.received.saturating_add(1);
+        }
+    }
+
+    /// Restarts all schedules at `now`, keeping the expected intervals.
+    pub(crate) fn restarted(&self, now: Option<Duration>) -> Self {
+        let mut schedule = self.clone();
+        for expectation in schedule.expectations.values_mut() {
+            expectation.since = now;
+            expectation.received = 0;
+        }
+        schedule
+    }
+}
+
+impl Nmea {
+    /// Declares that a sentence type is expected every `interval`, e.g.
+    /// RMC every second or GSV every 5 seconds.
+    ///
+    /// Declaring a type again replaces its interval. Returns the sentence
+    /// type back if [`SCHEDULE_LEN`] types are already expected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use nmea::{Nmea, ScheduleStatus, SentenceType};
+    ///
+    /// fn uptime() -> Duration {
+    ///     Duration::from_secs(10)
+    /// }
+    ///
+    /// let mut nmea = Nmea::default();
+    /// nmea.set_clock(&(uptime as fn() -> Duration));
+    /// nmea.expect_sentence(SentenceType::GSA, Duration::from_secs(1)).unwrap();
+    ///
+    /// // with this clock, no time passes
+    /// let report = nmea.schedule_report().next().unwrap();
+    /// assert_eq!(report.status, ScheduleStatus::OnTime);
+    /// assert_eq!(nmea.missing_sentences().count(), 0);
+    /// ```
+    pub fn expect_sentence(
+        &mut self,
+        sentence_type: SentenceType,
+        interval: Duration,
+    ) -> Result<(), SentenceType> {
+        let now = self.now();
+        let expectations = &mut self.schedule.expectations;
+        match expectations.get_mut(sentence_type) {
+            Some(existing) => {
+                existing.interval = interval;
+                Ok(())
+            }
+            None => {
+                let expectation = Expectation {
+                    interval,
+                    since: now,
+                    received: 0,
+                };
+                expectations
+                    .insert(sentence_type, expectation)
+                    .map_err(|_| sentence_type)
+            }
+        }
+    }
+
+    /// Stops expecting a sentence type.
+    pub fn unexpect_sentence(&mut self, sentence_type: SentenceType) {
+        self.schedule.expectations.remove(sentence_type);
+    }
+
+    /// Returns the schedule status of all expected sentence types.
+    ///
+    /// Returns nothing without a [`Clock`](crate::Clock), and skips types
+    /// declared before the clock was set until they are first received.
+    pub fn schedule_report(&self) -> impl Iterator<Item = ScheduleReport> + '_ {
+        let now = self.now();
+        self.schedule
+            .expectations
+            .iter()
+            .filter_map(move |(sentence_type, expectation)| expectation.report(sentence_type, now?))
+    }
+
+    /// Returns the expected sentence types which are late or missing.
+    pub fn missing_sentences(&self) -> impl Iterator<Item = ScheduleReport> + '_ {
+        self.schedule_report()
+            .filter(|report| report.status != ScheduleStatus::OnTime)
+    }
+
+    pub(crate) fn update_schedule(&mut self, parse_result: &ParseResult) {
+        let now = self.now();
+        self.schedule.received(parse_result.into(), now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule() {
+        let mut schedule = Schedule::default();
+        let second = Duration::from_secs(1);
+        for sentence_type in [SentenceType::RMC, SentenceType::GSV] {
+            schedule
+                .expectations
+                .insert(
+                    sentence_type,
+                    Expectation {
+                        interval: second,
+                        since: Some(Duration::ZERO),
+                        received: 0,
+                    },
+                )
+                .unwrap();
+        }
+        schedule.received(SentenceType::RMC, Some(second * 2));
+        schedule.received(SentenceType::GGA, Some(second * 2));
+
+        let now = Duration::from_millis(3_600);
+        let report = |schedule: &Schedule, sentence_type| {
+            let expectation = schedule.expectations.get(sentence_type).unwrap();
+            expectation.report(sentence_type, now).unwrap()
+        };
+        let rmc = report(&schedule, SentenceType::RMC);
+        assert_eq!(rmc.status, ScheduleStatus::Late);
+        assert_eq!(rmc.elapsed, Duration::from_millis(1_600));
+        assert_eq!(rmc.received, 1);
+        let gsv = report(&schedule, SentenceType::GSV);
+        assert_eq!(gsv.status, ScheduleStatus::Missing);
+        assert_eq!(gsv.received, 0);
+
+        let restarted = schedule.restarted(Some(now));
+        assert_eq!(
+            report(&restarted, SentenceType::GSV).status,
+            ScheduleStatus::OnTime
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! A self-test of the parser, to run on the target at boot.
+//!
+//! [`run_selftest()`] parses a set of embedded sentences with the enabled
+//! sentence features and compares the results with their expected values,
+//! within the precision of the [`Float`] of the build. It checks the float
+//! parsing and math of the target, e.g. a soft-float library or the `libm`
+//! functions used without `std`, and does not allocate.
+//!
+//! Only the sentence parsers are checked: a [`Nmea`](crate::Nmea) takes
+//! about 16 KiB, more than the stack of many targets at boot.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::float::FloatCore;
+
+use crate::{parse::parse_nmea_sentence, parse_str, Error, Float};
+
+/// Tolerance of the coordinates in degrees, about a meter, within the
+/// precision of `f32`.
+const DEGREES_TOLERANCE: Float = 1e-5;
+
+/// Tolerance of the other values.
+const TOLERANCE: f32 = 1e-3;
+
+/// Result of [`run_selftest()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelfTestReport {
+    /// Number of passed checks
+    pub passed: u16,
+    /// Number of failed checks
+    pub failed: u16,
+    /// Name of the first failed check
+    pub first_failure: Option<&'static str>,
+    /// Size of [`Float`] in bits, 32 with `f32-only`
+    pub float_bits: u8,
+    /// The target is little endian
+    pub little_endian: bool,
+}
+
+impl SelfTestReport {
+    /// Returns `true` if all the checks passed.
+    pub fn is_ok(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// A named check of the self-test.
+struct Check {
+    name: &'static str,
+    run: fn() -> bool,
+}
+
+fn close(value: Option<f32>, expected: f32) -> bool {
+    value.is_some_and(|value| (value - expected).abs() <= TOLERANCE * expected.abs().max(1.))
+}
+
+fn close_degrees(value: Option<Float>, expected: Float) -> bool {
+    value.is_some_and(|value| (value - expected).abs() <= DEGREES_TOLERANCE)
+}
+
+fn check_checksum() -> bool {
+    let valid = parse_nmea_sentence("$GPHDT,274.07,T*03")
+        .is_ok_and(|sentence| sentence.checksum == sentence.calc_checksum());
+    let corrupted = matches!(
+        parse_str("$GPHDT,274.97,T*03"),
+        Err(Error::ChecksumMismatch {
+            calculated: 0x0A,
+            found: 0x03,
+        })
+    );
+    valid && corrupted
+}
+
+fn check_float() -> bool {
+    let latitude = crate::sentences::utils::do_parse_lat_lon("5321.6802,N,00630.3372,W");
+    let Ok((_, (latitude, longitude))) = latitude else {
+        return false;
+    };
+    // a minute of latitude
+    #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+    let distance = crate::geo::distance((0., 0.), (1. / 60., 0.)) as f32;
+    close_degrees(Some(latitude), 53.361337)
+        && close_degrees(Some(longitude), -6.50562)
+        && close(Some(distance), 1853.25)
+}
+
+fn check_time() -> bool {
+    use chrono::Timelike;
+    crate::sentences::utils::parse_hms("235959.999,").is_ok_and(|(_, time)| {
+        (time.hour(), time.minute(), time.second(), time.nanosecond()) == (23, 59, 59, 999_000_000)
+    })
+}
+
+#[cfg(feature = "GGA")]
+fn check_gga() -> bool {
+    let Ok(crate::ParseResult::GGA(gga)) =
+        parse_str("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    else {
+        return false;
+    };
+    gga.fix_time == chrono::NaiveTime::from_hms_opt(9, 27, 50)
+        && gga.fix_type == Some(crate::sentences::FixType::Gps)
+        && close_degrees(gga.latitude, 53.361337)
+        && close_degrees(gga.longitude, -6.50562)
+        && gga.fix_satellites == Some(8)
+        && close(gga.hdop, 1.03)
+        && close(gga.altitude, 61.7)
+        && close(gga.geoid_separation, 55.2)
+}
+
+#[cfg(feature = "RMC")]
+fn check_rmc() -> bool {
+    let Ok(crate::ParseResult::RMC(rmc)) =
+        parse_str("$GPRMC,225446.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*46")
+    else {
+        return false;
+    };
+    rmc.fix_date == chrono::NaiveDate::from_ymd_opt(1994, 11, 19)
+        && close_degrees(rmc.lat, 49.274167)
+        && close_degrees(rmc.lon, -123.18533)
+        && close(rmc.speed_over_ground, 0.5)
+        && close(rmc.true_course, 54.7)
+        && close(rmc.magnetic_variation, 20.3)
+}
+
+#[cfg(feature = "GSV")]
+fn check_gsv() -> bool {
+    let Ok(crate::ParseResult::GSV(gsv)) =
+        parse_str("$GPGSV,3,3,11,22,42,067,42,24,14,311,43,27,05,244,00,,,,*4D")
+    else {
+        return false;
+    };
+    let satellites = || gsv.sats_info.iter().flatten();
+    (gsv.number_of_sentences, gsv.sentence_num, gsv.sats_in_view) == (3, 3, 11)
+        && satellites().count() == 3
+        && satellites()
+            .next()
+            .is_some_and(|satellite| satellite.prn() == 22 && close(satellite.snr(), 42.))
+}
+
+#[cfg(feature = "VTG")]
+fn check_vtg() -> bool {
+    let Ok(crate::ParseResult::VTG(vtg)) = parse_str("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48")
+    else {
+        return false;
+    };
+    close(vtg.true_course, 54.7) && close(vtg.speed_over_ground, 5.5)
+}
+
+/// The checks, with the sentence features of the build.
+const CHECKS: &[Check] = &[
+    Check {
+        name: "checksum",
+        run: check_checksum,
+    },
+    Check {
+        name: "float",
+        run: check_float,
+    },
+    Check {
+        name: "time",
+        run: check_time,
+    },
+    #[cfg(feature = "GGA")]
+    Check {
+        name: "GGA",
+        run: check_gga,
+    },
+    #[cfg(feature = "RMC")]
+    Check {
+        name: "RMC",
+        run: check_rmc,
+    },
+    #[cfg(feature = "GSV")]
+    Check {
+        name: "GSV",
+        run: check_gsv,
+    },
+    #[cfg(feature = "VTG")]
+    Check {
+        name: "VTG",
+        run: check_vtg,
+    },
+];
+
+/// Runs the self-test, see the [module](self).
+///
+/// # Examples
+///
+/// ```
+/// let report = nmea::selftest::run_selftest();
+/// assert!(report.is_ok(), "{:?} failed", report.first_failure);
+/// ```
+pub fn run_selftest() -> SelfTestReport {
+    let mut report = SelfTestReport {
+        passed: 0,
+        failed: 0,
+        first_failure: None,
+        float_bits: (core::mem::size_of::<Float>() * 8) as u8,
+        little_endian: cfg!(target_endian = "little"),
+    };
+    for check in CHECKS {
+        if (check.run)() {
+            report.passed += 1;
+        } else {
+            report.failed += 1;
+            report.first_failure = report.first_failure.or(Some(check.name));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_selftest() {
+        let report = run_selftest();
+        assert_eq!(report.first_failure, None);
+        assert_eq!(usize::from(report.passed), CHECKS.len());
+        assert_eq!(
+            report.float_bits,
+            if cfg!(feature = "f32-only") { 32 } else { 64 }
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Fixed-capacity tables of per sentence type values.
+//!
+//! The statistics and settings kept per sentence type only hold the first
+//! `N` types, which covers the sentences of a receiver without allocating.
+
+use heapless::Vec;
+
+use crate::SentenceType;
+
+/// A value per sentence type, for the first `N` types.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SentenceTable<T, const N: usize>(Vec<(SentenceType, T), N>);
+
+impl<T, const N: usize> Default for SentenceTable<T, N> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T, const N: usize> SentenceTable<T, N> {
+    pub(crate) fn get(&self, sentence_type: SentenceType) -> Option<&T> {
+        self.0
+            .iter()
+            .find(|(ty, _)| *ty == sentence_type)
+            .map(|(_, value)| value)
+    }
+
+    pub(crate) fn get_mut(&mut self, sentence_type: SentenceType) -> Option<&mut T> {
+        self.0
+            .iter_mut()
+            .find(|(ty, _)| *ty == sentence_type)
+            .map(|(_, value)| value)
+    }
+
+    /// Sets the value of a type, returning it back if the table is full.
+    pub(crate) fn insert(&mut self, sentence_type: SentenceType, value: T) -> Result<(), T> {
+        match self.get_mut(sentence_type) {
+            Some(existing) => {
+                *existing = value;
+                Ok(())
+            }
+            None => self
+                .0
+                .push((sentence_type, value))
+                .map_err(|(_, value)| value),
+        }
+    }
+
+    /// Updates the value of a type, or adds the value of `insert()` if the
+    /// table is not full yet.
+    pub(crate) fn update_or_insert(
+        &mut self,
+        sentence_type: SentenceType,
+        update: impl FnOnce(&mut T),
+        insert: impl FnOnce() -> T,
+    ) {
+        match self.get_mut(sentence_type) {
+            Some(value) => update(value),
+            None => {
+                // only the first types are kept
+                let _ = self.0.push((sentence_type, insert()));
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, sentence_type: SentenceType) {
+        self.0.retain(|(ty, _)| *ty != sentence_type);
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (SentenceType, &T)> {
+        self.0.iter().map(|(ty, value)| (*ty, value))
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0.iter_mut().map(|(_, value)| value)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentence_table() {
+        let mut table = SentenceTable::<u32, 2>::default();
+        assert_eq!(table.insert(SentenceType::GGA, 1), Ok(()));
+        table.update_or_insert(SentenceType::GGA, |count| *count += 1, || 0);
+        table.update_or_insert(SentenceType::RMC, |count| *count += 1, || 10);
+        // full
+        table.update_or_insert(SentenceType::GSV, |count| *count += 1, || 20);
+        assert_eq!(table.insert(SentenceType::GSA, 30), Err(30));
+        assert_eq!(table.insert(SentenceType::RMC, 11), Ok(()));
+
+        assert_eq!(table.get(SentenceType::GGA), Some(&2));
+        assert_eq!(table.get(SentenceType::GSV), None);
+        let entries: [_; 2] = core::array::from_fn(|i| table.iter().nth(i).unwrap());
+        assert_eq!(entries, [(SentenceType::GGA, &2), (SentenceType::RMC, &11)]);
+
+        table.remove(SentenceType::GGA);
+        assert_eq!(table.iter().count(), 1);
+        table.clear();
+        assert_eq!(table.get(SentenceType::RMC), None);
+    }
+}
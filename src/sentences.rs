@@ -1,4 +1,67 @@
 //! All the supported sentence type data and parsers.
+//!
+//! The data structs are `#[non_exhaustive]`, so fields can be added to them
+//! in minor releases. Outside of the crate, build them from their
+//! `Default` (or [`GsvData::new()`]) with the `with_*()` builder methods,
+//! and read them with the getters named like the fields:
+//!
+//! ```
+//! use nmea::sentences::{gsa::GsaMode2, GsaData, HdtData};
+//!
+//! let hdt = HdtData::default().with_heading(Some(274.07));
+//! assert_eq!(hdt.heading(), Some(274.07));
+//!
+//! let gsa = GsaData::default().with_mode2(GsaMode2::Fix3D).with_pdop(Some(1.5));
+//! assert_eq!(gsa.mode2(), GsaMode2::Fix3D);
+//! assert!(gsa.fix_sats_prn().is_empty());
+//! ```
+//!
+//! The fields only present with a crate feature, like the exact
+//! coordinates, have no builder method.
+
+/// Implements the getters and the `with_*()` builder methods of the public
+/// fields of a sentence data struct.
+///
+/// The fields after `ref` are returned by reference.
+macro_rules! accessors {
+    (
+        $data:ident {
+            $($field:ident, $with:ident: $ty:ty;)*
+        }
+        $(ref {
+            $($ref_field:ident, $ref_with:ident: $ref_ty:ty;)*
+        })?
+    ) => {
+        impl $data {
+            $(
+                #[doc = concat!("Returns the `", stringify!($field), "` field.")]
+                pub fn $field(&self) -> $ty {
+                    self.$field
+                }
+
+                #[doc = concat!("Sets the `", stringify!($field), "` field.")]
+                #[must_use]
+                pub fn $with(mut self, $field: $ty) -> Self {
+                    self.$field = $field;
+                    self
+                }
+            )*
+            $($(
+                #[doc = concat!("Returns the `", stringify!($ref_field), "` field.")]
+                pub fn $ref_field(&self) -> &$ref_ty {
+                    &self.$ref_field
+                }
+
+                #[doc = concat!("Sets the `", stringify!($ref_field), "` field.")]
+                #[must_use]
+                pub fn $ref_with(mut self, $ref_field: $ref_ty) -> Self {
+                    self.$ref_field = $ref_field;
+                    self
+                }
+            )*)?
+        }
+    };
+}
 
 pub mod aam;
 pub mod alm;
@@ -8,7 +71,9 @@ pub mod bwc;
 pub mod bww;
 pub mod dbk;
 pub mod dbs;
+pub mod dbt;
 pub mod dpt;
+pub mod dtm;
 pub mod gbs;
 pub mod gga;
 pub mod gll;
@@ -26,12 +91,17 @@ pub mod ttm;
 pub mod txt;
 pub mod utils;
 pub mod vhw;
+pub mod vlw;
 pub mod vtg;
 pub mod wnc;
 pub mod zda;
 pub mod zfo;
 pub mod ztg;
 
+pub mod depth;
+#[cfg(feature = "exact-coordinates")]
+#[cfg_attr(docsrs, doc(cfg(feature = "exact-coordinates")))]
+pub mod exact;
 pub mod faa_mode;
 pub mod fix_type;
 pub mod gnss_type;
@@ -46,20 +116,23 @@ pub use {
     bww::{parse_bww, BwwData},
     dbk::{parse_dbk, DbkData},
     dbs::{parse_dbs, DbsData},
+    dbt::{parse_dbt, DbtData},
+    depth::{Depth, DepthReference, DepthUnit},
     dpt::{parse_dpt, DptData},
+    dtm::{parse_dtm, Datum, DtmData},
     faa_mode::{FaaMode, FaaModes},
     fix_type::FixType,
     gbs::{parse_gbs, GbsData},
     gga::{parse_gga, GgaData},
     gll::{parse_gll, GllData},
     gns::{parse_gns, GnsData},
-    gnss_type::GnssType,
+    gnss_type::{FrequencyBand, GnssType},
     gsa::{parse_gsa, GsaData},
     gst::{parse_gst, GstData},
     gsv::{parse_gsv, GsvData},
     hdt::{parse_hdt, HdtData},
     mda::{parse_mda, MdaData},
-    mtw::{parse_mtw, MtwData},
+    mtw::{parse_mtw, MtwData, TemperatureUnit},
     mwv::{parse_mwv, MwvData},
     rmc::{parse_rmc, RmcData},
     rmz::{parse_pgrmz, PgrmzData},
@@ -69,6 +142,7 @@ pub use {
     },
     txt::{parse_txt, TxtData},
     vhw::{parse_vhw, VhwData},
+    vlw::{parse_vlw, VlwData},
     vtg::{parse_vtg, VtgData},
     wnc::{parse_wnc, WncData},
     zda::{parse_zda, ZdaData},
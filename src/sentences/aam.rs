@@ -32,7 +32,8 @@ use crate::{parse::NmeaSentence, sentences::utils::array_string, Error, Sentence
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct AamData {
     pub arrival_circle_entered: Option<bool>,
     pub perpendicular_passed: Option<bool>,
@@ -42,6 +43,14 @@ pub struct AamData {
     pub waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
 }
 
+accessors!(AamData {
+    arrival_circle_entered, with_arrival_circle_entered: Option<bool>;
+    perpendicular_passed, with_perpendicular_passed: Option<bool>;
+    arrival_circle_radius, with_arrival_circle_radius: Option<f32>;
+    radius_units, with_radius_units: Option<char>;
+    waypoint_id, with_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+});
+
 /// Parse AAM message
 pub fn parse_aam(sentence: NmeaSentence) -> Result<AamData, Error> {
     if sentence.message_id != SentenceType::AAM {
@@ -54,7 +63,7 @@ pub fn parse_aam(sentence: NmeaSentence) -> Result<AamData, Error> {
     }
 }
 
-fn do_parse_aam(i: &str) -> Result<AamData, Error> {
+fn do_parse_aam(i: &str) -> Result<AamData, Error<'_>> {
     let (i, arrival_circle_entered) = one_of("AV")(i)?;
     let arrival_circle_entered = match arrival_circle_entered {
         'A' => Some(true),
@@ -1,3 +1,7 @@
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
 use crate::sentences::utils::parse_number_in_range;
 use nom::{
     character::{
@@ -31,7 +35,7 @@ use super::utils::number;
 ///     - 8191 is the week that precedes the next rollover on January 6th 2137;
 ///
 ///     Note: the legacy representation started at the same epoch, but
-///           the number is 10-bit wide only, with a rollover every 19.7 years.
+///     the number is 10-bit wide only, with a rollover every 19.7 years.
 ///  6. Eccentricity
 ///  7. Almanac Reference Time
 ///  8. Inclination Angle
@@ -47,7 +51,8 @@ use super::utils::number;
 ///  Fields 5 through 15 are dumped as raw hex.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct AlmData {
     pub total_number_of_messages: Option<u16>,
     pub sentence_number: Option<u16>,
@@ -69,11 +74,119 @@ pub struct AlmData {
     pub f1_clock_parameter: Option<u16>,
 }
 
+accessors!(AlmData {
+    total_number_of_messages, with_total_number_of_messages: Option<u16>;
+    sentence_number, with_sentence_number: Option<u16>;
+    satellite_prn_number, with_satellite_prn_number: Option<u8>;
+    gps_week_number, with_gps_week_number: Option<u16>;
+    sv_health, with_sv_health: Option<u8>;
+    eccentricity, with_eccentricity: Option<u16>;
+    almanac_reference_time, with_almanac_reference_time: Option<u8>;
+    inclination_angle, with_inclination_angle: Option<u16>;
+    rate_of_right_ascension, with_rate_of_right_ascension: Option<u16>;
+    root_of_semi_major_axis, with_root_of_semi_major_axis: Option<u32>;
+    argument_of_perigee, with_argument_of_perigee: Option<u32>;
+    longitude_of_ascension_node, with_longitude_of_ascension_node: Option<u32>;
+    mean_anomaly, with_mean_anomaly: Option<u32>;
+    f0_clock_parameter, with_f0_clock_parameter: Option<u16>;
+    f1_clock_parameter, with_f1_clock_parameter: Option<u16>;
+});
+
 impl AlmData {
     /// Returns the 10-bit representation of the GPS week number (range 0 to 1023)
     pub fn get_10bit_gps_week_number(&self) -> Option<u16> {
         self.gps_week_number.map(|n| n % 1024)
     }
+
+    /// Converts the raw almanac fields to engineering units, with the scale
+    /// factors of IS-GPS-200.
+    ///
+    /// Returns `None` if the PRN, the week or an orbital parameter is
+    /// missing. The missing clock parameters are taken as zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_str, ParseResult};
+    ///
+    /// # #[cfg(feature = "ALM")]
+    /// # {
+    /// let sentence =
+    ///     "$GPALM,31,1,02,1617,00,50F6,0F,FD98,FD39,A10CF3,81389B,423632,BD913C,148,001*3C";
+    /// let Ok(ParseResult::ALM(alm)) = parse_str(sentence) else {
+    ///     panic!()
+    /// };
+    /// let orbit = alm.orbit().unwrap();
+    /// assert_eq!(orbit.reference_time, 61_440.);
+    /// assert!((orbit.semi_major_axis - 26_560_000.).abs() < 10_000.);
+    /// # }
+    /// ```
+    pub fn orbit(&self) -> Option<AlmanacOrbit> {
+        const SEMICIRCLE: f64 = core::f64::consts::PI;
+        let scale = |exponent: i32| 2_f64.powi(exponent);
+        let root_of_semi_major_axis = f64::from(self.root_of_semi_major_axis?) * scale(-11);
+        Some(AlmanacOrbit {
+            prn: self.satellite_prn_number?,
+            week: self.gps_week_number?,
+            healthy: self.sv_health.unwrap_or(0) == 0,
+            eccentricity: f64::from(self.eccentricity?) * scale(-21),
+            reference_time: f64::from(self.almanac_reference_time?) * scale(12),
+            inclination: (0.3 + f64::from(self.inclination_angle? as i16) * scale(-19))
+                * SEMICIRCLE,
+            rate_of_right_ascension: f64::from(self.rate_of_right_ascension? as i16)
+                * scale(-38)
+                * SEMICIRCLE,
+            semi_major_axis: root_of_semi_major_axis * root_of_semi_major_axis,
+            argument_of_perigee: f64::from(signed(self.argument_of_perigee?, 24))
+                * scale(-23)
+                * SEMICIRCLE,
+            longitude_of_ascension_node: f64::from(signed(self.longitude_of_ascension_node?, 24))
+                * scale(-23)
+                * SEMICIRCLE,
+            mean_anomaly: f64::from(signed(self.mean_anomaly?, 24)) * scale(-23) * SEMICIRCLE,
+            clock_bias: f64::from(signed(self.f0_clock_parameter.unwrap_or(0).into(), 11))
+                * scale(-20),
+            clock_drift: f64::from(signed(self.f1_clock_parameter.unwrap_or(0).into(), 11))
+                * scale(-38),
+        })
+    }
+}
+
+/// Sign extends the lowest `bits` of a two's complement value.
+fn signed(value: u32, bits: u32) -> i32 {
+    let shift = 32 - bits;
+    ((value << shift) as i32) >> shift
+}
+
+/// The almanac of a satellite in engineering units, see [`AlmData::orbit()`].
+///
+/// The angles are in radians.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlmanacOrbit {
+    pub prn: u8,
+    /// GPS week of the almanac, 10 or 13 bits depending on the receiver
+    pub week: u16,
+    /// The satellite health bits are all zero
+    pub healthy: bool,
+    pub eccentricity: f64,
+    /// Reference time in seconds of the GPS week
+    pub reference_time: f64,
+    pub inclination: f64,
+    /// Rate of right ascension in radians per second
+    pub rate_of_right_ascension: f64,
+    /// Semi-major axis in meters
+    pub semi_major_axis: f64,
+    pub argument_of_perigee: f64,
+    /// Longitude of the ascending node at the start of the week
+    pub longitude_of_ascension_node: f64,
+    /// Mean anomaly at the reference time
+    pub mean_anomaly: f64,
+    /// Clock bias in seconds
+    pub clock_bias: f64,
+    /// Clock drift in seconds per second
+    pub clock_drift: f64,
 }
 
 pub fn parse_alm(sentence: NmeaSentence) -> Result<AlmData, Error> {
@@ -165,8 +278,32 @@ fn do_parse_alm(i: &str) -> IResult<&str, AlmData> {
 
 #[cfg(test)]
 mod tests {
+    use approx::assert_relative_eq;
+
+    use super::signed;
     use crate::{parse_nmea_sentence, sentences::parse_alm};
 
+    #[test]
+    fn test_orbit() {
+        let sentence = parse_nmea_sentence(
+            "$GPALM,31,1,02,1617,00,50F6,0F,FD98,FD39,A10CF3,81389B,423632,BD913C,148,001*3C",
+        )
+        .unwrap();
+        let orbit = parse_alm(sentence).unwrap().orbit().unwrap();
+        assert_eq!(orbit.prn, 2);
+        assert!(orbit.healthy);
+        assert_relative_eq!(orbit.eccentricity, 20726. / 2_097_152.);
+        // 0.3 semicircles minus 616 * 2^-19
+        assert_relative_eq!(orbit.inclination.to_degrees(), 53.788, epsilon = 1e-3);
+        assert!(orbit.rate_of_right_ascension < 0.);
+        assert_relative_eq!(orbit.semi_major_axis.sqrt(), 5153.619, epsilon = 1e-3);
+        assert_relative_eq!(orbit.clock_bias, 328. / 1_048_576.);
+
+        assert_eq!(signed(0xFFFFFF, 24), -1);
+        assert_eq!(signed(0x7FFFFF, 24), 0x7FFFFF);
+        assert_eq!(signed(0x400, 11), -1024);
+    }
+
     #[test]
     fn test() {
         let total_number_of_messages = 31;
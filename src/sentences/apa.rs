@@ -43,10 +43,10 @@ use crate::{parse::NmeaSentence, sentences::utils::array_string, Error, Sentence
 ///
 /// Example: `$GPAPA,A,A,0.10,R,N,V,V,011,M,DEST,011,M*82`
 /// Where the last "M" is the waypoint name
-
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
+#[non_exhaustive]
 pub struct ApaData {
     pub status_warning: Option<bool>,
     pub status_cycle_warning: Option<bool>,
@@ -61,6 +61,19 @@ pub struct ApaData {
     pub waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
 }
 
+accessors!(ApaData {
+    status_warning, with_status_warning: Option<bool>;
+    status_cycle_warning, with_status_cycle_warning: Option<bool>;
+    cross_track_error_magnitude, with_cross_track_error_magnitude: Option<f32>;
+    steer_direction, with_steer_direction: Option<SteerDirection>;
+    cross_track_units, with_cross_track_units: Option<CrossTrackUnits>;
+    status_arrived, with_status_arrived: Option<bool>;
+    status_passed, with_status_passed: Option<bool>;
+    bearing_origin_destination, with_bearing_origin_destination: Option<f32>;
+    magnetic_true, with_magnetic_true: Option<MagneticTrue>;
+    waypoint_id, with_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+});
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -97,7 +110,7 @@ pub fn parse_apa(sentence: NmeaSentence) -> Result<ApaData, Error> {
     }
 }
 
-fn do_parse_apa(i: &str) -> Result<ApaData, Error> {
+fn do_parse_apa(i: &str) -> Result<ApaData, Error<'_>> {
     let (i, status_warning) = one_of("AV")(i)?;
     let status_warning = match status_warning {
         'A' => Some(true),
@@ -20,7 +20,8 @@ use nom::{
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
 pub struct BodData {
     pub bearing_true: Option<f32>,
     pub bearing_magnetic: Option<f32>,
@@ -30,6 +31,13 @@ pub struct BodData {
     pub from_waypoint: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
 }
 
+accessors!(BodData {
+    bearing_true, with_bearing_true: Option<f32>;
+    bearing_magnetic, with_bearing_magnetic: Option<f32>;
+    to_waypoint, with_to_waypoint: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+    from_waypoint, with_from_waypoint: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+});
+
 /// BOD - Bearing - Waypoint to Waypoint
 ///
 /// ```text
@@ -37,7 +45,7 @@ pub struct BodData {
 ///        |   | |   | |    |    |
 /// $--BOD,x.x,T,x.x,M,c--c,c--c*hh<CR><LF>
 /// ```
-fn do_parse_bod(i: &str) -> Result<BodData, Error> {
+fn do_parse_bod(i: &str) -> Result<BodData, Error<'_>> {
     // 1. Bearing Degrees, True
     let (i, bearing_true) = opt(map_parser(take_until(","), float))(i)?;
     let (i, _) = char(',')(i)?;
@@ -6,8 +6,13 @@ use nom::{
 
 use crate::{
     parse::{NmeaSentence, TEXT_PARAMETER_MAX_LEN},
-    sentences::utils::{parse_hms, parse_lat_lon},
-    Error, SentenceType,
+    sentences::{
+        faa_mode::parse_faa_mode_field,
+        nom_parse_failure,
+        utils::{parse_hms, parse_lat_lon, parse_optional_tail},
+        FaaMode,
+    },
+    Error, Float, SentenceType,
 };
 
 /// BWC - Bearing & Distance to Waypoint - Great Circle
@@ -22,19 +27,33 @@ use crate::{
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct BwcData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_time: Option<NaiveTime>,
-    pub latitude: Option<f64>,
-    pub longitude: Option<f64>,
+    pub latitude: Option<Float>,
+    pub longitude: Option<Float>,
     pub true_bearing: Option<f32>,
     pub magnetic_bearing: Option<f32>,
     pub distance: Option<f32>,
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
+    /// FAA mode indicator (NMEA 2.3 and later)
+    pub faa_mode: Option<FaaMode>,
 }
 
+accessors!(BwcData {
+    fix_time, with_fix_time: Option<NaiveTime>;
+    latitude, with_latitude: Option<Float>;
+    longitude, with_longitude: Option<Float>;
+    true_bearing, with_true_bearing: Option<f32>;
+    magnetic_bearing, with_magnetic_bearing: Option<f32>;
+    distance, with_distance: Option<f32>;
+    waypoint_id, with_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+    faa_mode, with_faa_mode: Option<FaaMode>;
+});
+
 /// BWC - Bearing & Distance to Waypoint - Great Circle
 /// ```text
 ///                                                         12
@@ -42,7 +61,7 @@ pub struct BwcData {
 ///         |         |       | |        | |   | |   | |   | |    |   |
 /// $--BWC,hhmmss.ss,llll.ll,a,yyyyy.yy,a,x.x,T,x.x,M,x.x,N,c--c,m,*hh<CR><LF>
 /// ```
-fn do_parse_bwc(i: &str) -> Result<BwcData, Error> {
+fn do_parse_bwc(i: &str) -> Result<BwcData, Error<'_>> {
     // 1. UTC Time or observation
     let (i, fix_time) = opt(parse_hms)(i)?;
     let (i, _) = char(',')(i)?;
@@ -76,9 +95,13 @@ fn do_parse_bwc(i: &str) -> Result<BwcData, Error> {
     let (i, _) = char(',')(i)?;
 
     // 12. Waypoint ID
-    let (_i, waypoint_id) = opt(is_not(",*"))(i)?;
+    let (i, waypoint_id) = opt(is_not(",*"))(i)?;
 
     // 13. FAA mode indicator (NMEA 2.3 and later, optional)
+    let (_i, [mode]) = parse_optional_tail(i)?;
+    let faa_mode = mode
+        .map(|mode| parse_faa_mode_field(mode).ok_or_else(|| nom_parse_failure(i)))
+        .transpose()?;
 
     let waypoint_id = if let Some(waypoint_id) = waypoint_id {
         Some(
@@ -97,6 +120,7 @@ fn do_parse_bwc(i: &str) -> Result<BwcData, Error> {
         magnetic_bearing,
         distance,
         waypoint_id,
+        faa_mode,
     })
 }
 
@@ -142,6 +166,28 @@ mod tests {
         assert_relative_eq!(data.magnetic_bearing.unwrap(), 218.0);
         assert_relative_eq!(data.distance.unwrap(), 4.6);
         assert_eq!(&data.waypoint_id.unwrap(), "EGLM");
+        assert_eq!(data.faa_mode, None);
+    }
+
+    #[test]
+    fn test_parse_bwc_faa_mode() {
+        let sentence = parse_nmea_sentence(
+            "$GPBWC,220516,5130.02,N,00046.34,W,213.8,T,218.0,M,0004.6,N,EGLM,D*49",
+        )
+        .unwrap();
+        assert_eq!(sentence.checksum, sentence.calc_checksum());
+        let data = parse_bwc(sentence).unwrap();
+        assert_eq!(&data.waypoint_id.unwrap(), "EGLM");
+        assert_eq!(data.faa_mode, Some(FaaMode::Differential));
+
+        // empty mode
+        let data = do_parse_bwc("081837,,,,,,T,,M,,N,,").unwrap();
+        assert_eq!(data.waypoint_id, None);
+        assert_eq!(data.faa_mode, None);
+
+        let data = do_parse_bwc("081837,,,,,,T,,M,,N,EGLM,X").unwrap();
+        assert_eq!(data.faa_mode, Some(FaaMode::Unknown('X')));
+        assert!(do_parse_bwc("081837,,,,,,T,,M,,N,EGLM,1").is_err());
     }
 
     #[test]
@@ -161,6 +207,7 @@ mod tests {
                 magnetic_bearing: None,
                 distance: None,
                 waypoint_id: None,
+                faa_mode: None,
             },
             data
         );
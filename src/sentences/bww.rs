@@ -31,7 +31,8 @@ use crate::{
 /// Example: `$GPBWW,213.8,T,218.0,M,TOWPT,FROMWPT*42`
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct BwwData {
     pub true_bearing: Option<f32>,
     pub magnetic_bearing: Option<f32>,
@@ -41,7 +42,14 @@ pub struct BwwData {
     pub from_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
 }
 
-fn do_parse_bww(i: &str) -> Result<BwwData, Error> {
+accessors!(BwwData {
+    true_bearing, with_true_bearing: Option<f32>;
+    magnetic_bearing, with_magnetic_bearing: Option<f32>;
+    to_waypoint_id, with_to_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+    from_waypoint_id, with_from_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+});
+
+fn do_parse_bww(i: &str) -> Result<BwwData, Error<'_>> {
     // 1. Bearing, degrees True
     let (i, true_bearing) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
@@ -1,12 +1,15 @@
 use nom::{
     character::complete::{char, one_of},
     combinator::opt,
-    number::complete::double,
     sequence::preceded,
     IResult,
 };
 
-use crate::{parse::NmeaSentence, Error, ParseResult, SentenceType};
+use crate::{
+    parse::NmeaSentence,
+    sentences::{utils::float_number, Depth},
+    Error, Float, ParseResult, SentenceType,
+};
 
 /// DBK - Depth Below Keel
 ///
@@ -29,11 +32,25 @@ use crate::{parse::NmeaSentence, Error, ParseResult, SentenceType};
 /// Example: `$SDDBK,1330.5,f,0405.5,M,0221.6,F*2E`
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct DbkData {
-    pub depth_feet: Option<f64>,
-    pub depth_meters: Option<f64>,
-    pub depth_fathoms: Option<f64>,
+    pub depth_feet: Option<Float>,
+    pub depth_meters: Option<Float>,
+    pub depth_fathoms: Option<Float>,
+}
+
+accessors!(DbkData {
+    depth_feet, with_depth_feet: Option<Float>;
+    depth_meters, with_depth_meters: Option<Float>;
+    depth_fathoms, with_depth_fathoms: Option<Float>;
+});
+
+impl DbkData {
+    /// Returns the depth below keel, see [`Depth::reconcile()`].
+    pub fn depth(&self) -> Option<Depth> {
+        Depth::reconcile(self.depth_feet, self.depth_meters, self.depth_fathoms)
+    }
 }
 
 impl From<DbkData> for ParseResult {
@@ -73,13 +90,13 @@ pub fn parse_dbk(sentence: NmeaSentence) -> Result<DbkData, Error> {
 }
 
 fn do_parse_dbk(i: &str) -> IResult<&str, DbkData> {
-    let (i, depth_feet_value) = opt(double)(i)?;
+    let (i, depth_feet_value) = opt(float_number)(i)?;
     let (i, _) = preceded(char(','), one_of("f"))(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, depth_meters_value) = opt(double)(i)?;
+    let (i, depth_meters_value) = opt(float_number)(i)?;
     let (i, _) = preceded(char(','), one_of("M"))(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, depth_fathoms_value) = opt(double)(i)?;
+    let (i, depth_fathoms_value) = opt(float_number)(i)?;
     let (i, _) = preceded(char(','), one_of("F"))(i)?;
     Ok((
         i,
@@ -105,6 +122,7 @@ mod tests {
         assert_eq!(Some(1330.5), dbk_data.depth_feet);
         assert_eq!(Some(405.5), dbk_data.depth_meters);
         assert_eq!(Some(221.6), dbk_data.depth_fathoms);
+        assert_eq!(405.5, dbk_data.depth().unwrap().meters);
     }
     #[test]
     fn test_parse_dbk_invalid_depth_feet_value() {
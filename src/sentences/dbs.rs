@@ -1,6 +1,6 @@
 use nom::{character::complete::char, combinator::opt, number::complete::float};
 
-use crate::{Error, NmeaSentence, ParseResult, SentenceType};
+use crate::{sentences::Depth, Error, Float, NmeaSentence, ParseResult, SentenceType};
 
 /// DBS - Depth Below Surface
 ///
@@ -28,13 +28,32 @@ use crate::{Error, NmeaSentence, ParseResult, SentenceType};
 ///
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct DbsData {
     pub water_depth_feet: Option<f32>,
     pub water_depth_meters: Option<f32>,
     pub water_depth_fathoms: Option<f32>,
 }
 
+accessors!(DbsData {
+    water_depth_feet, with_water_depth_feet: Option<f32>;
+    water_depth_meters, with_water_depth_meters: Option<f32>;
+    water_depth_fathoms, with_water_depth_fathoms: Option<f32>;
+});
+
+impl DbsData {
+    /// Returns the depth below surface, see [`Depth::reconcile()`].
+    #[cfg_attr(feature = "f32-only", allow(clippy::useless_conversion))]
+    pub fn depth(&self) -> Option<Depth> {
+        Depth::reconcile(
+            self.water_depth_feet.map(Float::from),
+            self.water_depth_meters.map(Float::from),
+            self.water_depth_fathoms.map(Float::from),
+        )
+    }
+}
+
 impl From<DbsData> for ParseResult {
     fn from(value: DbsData) -> Self {
         ParseResult::DBS(value)
@@ -52,7 +71,7 @@ pub fn parse_dbs(sentence: NmeaSentence) -> Result<DbsData, Error> {
     }
 }
 
-fn do_parse_dbs(i: &str) -> Result<DbsData, Error> {
+fn do_parse_dbs(i: &str) -> Result<DbsData, Error<'_>> {
     let (i, water_depth_feet) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
     let (i, _) = char('f')(i)?;
@@ -96,6 +115,14 @@ mod test {
         assert_relative_eq!(data.water_depth_fathoms.unwrap(), 7.5);
     }
 
+    #[test]
+    fn parse_dbs_depth() {
+        let sentence = parse_nmea_sentence("$SDDBS,45.0,f,,M,7.5,F*1C").unwrap();
+        let depth = parse_dbs(sentence).unwrap().depth().unwrap();
+        assert_eq!(depth.source_unit, crate::sentences::DepthUnit::Feet);
+        assert_relative_eq!(depth.meters, 13.716, epsilon = 1e-5);
+    }
+
     #[test]
     fn test_invalid_sentence() {
         let sentence = parse_nmea_sentence("$SDDBS,,,M,F*68").unwrap();
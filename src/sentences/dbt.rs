@@ -0,0 +1,143 @@
+use nom::{
+    character::complete::{char, one_of},
+    combinator::opt,
+    sequence::preceded,
+    IResult,
+};
+
+use crate::{
+    parse::NmeaSentence,
+    sentences::{utils::float_number, Depth},
+    Error, Float, ParseResult, SentenceType,
+};
+
+/// DBT - Depth below transducer
+///
+/// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dbt_depth_below_transducer>
+///
+/// ```text
+///        1   2 3   4 5   6 7
+///        |   | |   | |   | |
+/// $--DBT,x.x,f,x.x,M,x.x,F*hh<CR><LF>
+/// ```
+///
+/// 1. Water depth, feet
+/// 2. `f` = feet
+/// 3. Water depth, meters
+/// 4. `M` = meters
+/// 5. Water depth, Fathoms
+/// 6. `F` = Fathoms
+/// 7. Mandatory NMEA checksum
+///
+/// Example: `$SDDBT,7.8,f,2.4,M,1.3,F*0D`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
+pub struct DbtData {
+    pub depth_feet: Option<Float>,
+    pub depth_meters: Option<Float>,
+    pub depth_fathoms: Option<Float>,
+}
+
+accessors!(DbtData {
+    depth_feet, with_depth_feet: Option<Float>;
+    depth_meters, with_depth_meters: Option<Float>;
+    depth_fathoms, with_depth_fathoms: Option<Float>;
+});
+
+impl DbtData {
+    /// Returns the depth below the transducer, see [`Depth::reconcile()`].
+    pub fn depth(&self) -> Option<Depth> {
+        Depth::reconcile(self.depth_feet, self.depth_meters, self.depth_fathoms)
+    }
+}
+
+impl From<DbtData> for ParseResult {
+    fn from(value: DbtData) -> Self {
+        ParseResult::DBT(value)
+    }
+}
+
+/// # Parse DBT message
+///
+/// Information from DBT:
+///
+/// NMEA 0183 standard Depth below transducer.
+/// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dbt_depth_below_transducer>
+///
+/// ## Example (Ignore the line break):
+/// ```text
+/// $SDDBT,7.8,f,2.4,M,1.3,F*0D
+///```
+///
+/// 1:    7.8    Depth feet
+/// 2:    f      Units: f = feet
+/// 3:    2.4    Depth meters
+/// 4:    M      Units: M = meters
+/// 5:    1.3    Depth Fathoms
+/// 6:    F      Units: F = Fathoms
+/// 7:    0D     CRC Checksum of NMEA data
+pub fn parse_dbt(sentence: NmeaSentence) -> Result<DbtData, Error> {
+    if sentence.message_id != SentenceType::DBT {
+        Err(Error::WrongSentenceHeader {
+            expected: SentenceType::DBT,
+            found: sentence.message_id,
+        })
+    } else {
+        Ok(do_parse_dbt(sentence.data)?.1)
+    }
+}
+
+fn do_parse_dbt(i: &str) -> IResult<&str, DbtData> {
+    let (i, depth_feet) = opt(float_number)(i)?;
+    let (i, _) = preceded(char(','), one_of("f"))(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, depth_meters) = opt(float_number)(i)?;
+    let (i, _) = preceded(char(','), one_of("M"))(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, depth_fathoms) = opt(float_number)(i)?;
+    let (i, _) = preceded(char(','), one_of("F"))(i)?;
+    Ok((
+        i,
+        DbtData {
+            depth_feet,
+            depth_meters,
+            depth_fathoms,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::parse::parse_nmea_sentence;
+
+    #[test]
+    fn test_parse_dbt() {
+        let s = parse_nmea_sentence("$SDDBT,7.8,f,2.4,M,1.3,F*0D").unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        let dbt_data = parse_dbt(s).unwrap();
+        assert_eq!(Some(7.8), dbt_data.depth_feet);
+        assert_eq!(Some(2.4), dbt_data.depth_meters);
+        assert_eq!(Some(1.3), dbt_data.depth_fathoms);
+        assert_eq!(2.4, dbt_data.depth().unwrap().meters);
+    }
+
+    #[test]
+    fn test_parse_dbt_meters_only() {
+        let s = parse_nmea_sentence("$SDDBT,,f,22.5,M,,F*33").unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        let depth = parse_dbt(s).unwrap().depth().unwrap();
+        assert_relative_eq!(depth.feet(), 73.8189, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_parse_dbt_invalid_unit() {
+        let s = parse_nmea_sentence("$SDDBT,7.8,f,2.4,X,1.3,F*18").unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        assert!(parse_dbt(s).is_err());
+    }
+}
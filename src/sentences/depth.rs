@@ -0,0 +1,129 @@
+use crate::Float;
+
+/// Meters per foot
+const FOOT: Float = 0.3048;
+/// Meters per fathom
+const FATHOM: Float = 1.8288;
+
+/// Unit of a depth field
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthUnit {
+    Feet,
+    Meters,
+    Fathoms,
+}
+
+impl DepthUnit {
+    /// Returns the length of the unit in meters.
+    pub const fn meters(self) -> Float {
+        match self {
+            DepthUnit::Feet => FOOT,
+            DepthUnit::Meters => 1.,
+            DepthUnit::Fathoms => FATHOM,
+        }
+    }
+}
+
+/// Point from which a depth is measured.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepthReference {
+    /// Below the transducer, of DBT and DPT
+    Transducer,
+    /// Below the keel, of DBK
+    Keel,
+    /// Below the water surface, of DBS
+    Surface,
+}
+
+/// A depth in meters, with the unit of the field it comes from.
+///
+/// The depth sentences give the same depth in feet, meters and fathoms,
+/// which sometimes disagree because of the rounding of the device.
+/// [`Depth::reconcile()`] picks one of them the same way for all sentences.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Depth {
+    /// Depth in meters
+    pub meters: Float,
+    /// Unit of the field the depth was taken from
+    pub source_unit: DepthUnit,
+}
+
+impl Depth {
+    /// Creates a depth from a value in the given unit.
+    pub fn new(value: Float, unit: DepthUnit) -> Self {
+        Self {
+            meters: value * unit.meters(),
+            source_unit: unit,
+        }
+    }
+
+    /// Picks the depth of the meters field, or else converts the feet or
+    /// the fathoms field, in this order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::sentences::{Depth, DepthUnit};
+    ///
+    /// let depth = Depth::reconcile(Some(45.0), Some(13.7), Some(7.5)).unwrap();
+    /// assert_eq!(depth.meters, 13.7);
+    ///
+    /// let depth = Depth::reconcile(None, None, Some(2.0)).unwrap();
+    /// assert_eq!(depth.source_unit, DepthUnit::Fathoms);
+    /// assert_eq!(depth.meters, 3.6576);
+    /// ```
+    pub fn reconcile(
+        feet: Option<Float>,
+        meters: Option<Float>,
+        fathoms: Option<Float>,
+    ) -> Option<Self> {
+        meters
+            .map(|meters| Depth::new(meters, DepthUnit::Meters))
+            .or_else(|| feet.map(|feet| Depth::new(feet, DepthUnit::Feet)))
+            .or_else(|| fathoms.map(|fathoms| Depth::new(fathoms, DepthUnit::Fathoms)))
+    }
+
+    /// Returns the depth in the given unit.
+    pub fn in_unit(&self, unit: DepthUnit) -> Float {
+        self.meters / unit.meters()
+    }
+
+    /// Returns the depth in feet.
+    pub fn feet(&self) -> Float {
+        self.in_unit(DepthUnit::Feet)
+    }
+
+    /// Returns the depth in fathoms.
+    pub fn fathoms(&self) -> Float {
+        self.in_unit(DepthUnit::Fathoms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_reconcile() {
+        // the meters field wins over disagreeing conversions
+        let depth = Depth::reconcile(Some(1330.5), Some(405.5), Some(221.6)).unwrap();
+        assert_eq!(depth.source_unit, DepthUnit::Meters);
+        assert_eq!(depth.meters, 405.5);
+
+        let depth = Depth::reconcile(Some(100.), None, Some(1.)).unwrap();
+        assert_eq!(depth.source_unit, DepthUnit::Feet);
+        assert_relative_eq!(depth.meters, 30.48);
+        assert_relative_eq!(depth.feet(), 100.);
+        assert_relative_eq!(depth.fathoms(), 16.666_666, epsilon = 1e-5);
+
+        assert_eq!(Depth::reconcile(None, None, None), None);
+    }
+}
@@ -2,13 +2,15 @@ use nom::{
     bytes::complete::is_not,
     character::complete::char,
     combinator::{map_res, opt},
-    number::complete::double,
     IResult,
 };
 
 use crate::{
-    sentences::utils::{parse_float_num, parse_until_end},
-    Error, ParseResult, SentenceType,
+    sentences::{
+        utils::{float_number, parse_float_num, parse_until_end},
+        Depth, DepthUnit,
+    },
+    Error, Float, ParseResult, SentenceType,
 };
 
 /// DPT - Depth of Water
@@ -34,11 +36,26 @@ use crate::{
 /// `$SDDPT` is the sentence identifier (`SD` for the talker ID, `DPT` for Depth)
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
 pub struct DptData {
-    pub water_depth: Option<f64>,
-    pub offset: Option<f64>,
-    pub max_range_scale: Option<f64>,
+    pub water_depth: Option<Float>,
+    pub offset: Option<Float>,
+    pub max_range_scale: Option<Float>,
+}
+
+accessors!(DptData {
+    water_depth, with_water_depth: Option<Float>;
+    offset, with_offset: Option<Float>;
+    max_range_scale, with_max_range_scale: Option<Float>;
+});
+
+impl DptData {
+    /// Returns the depth relative to the transducer, given in meters.
+    pub fn depth(&self) -> Option<Depth> {
+        self.water_depth
+            .map(|meters| Depth::new(meters, DepthUnit::Meters))
+    }
 }
 
 impl From<DptData> for ParseResult {
@@ -61,8 +78,8 @@ pub fn parse_dpt(sentence: crate::NmeaSentence) -> Result<DptData, crate::Error>
     }
 }
 
-fn parse_positive_f64(input: &str) -> IResult<&str, f64> {
-    let (input, value) = double(input)?;
+fn parse_positive_float(input: &str) -> IResult<&str, Float> {
+    let (input, value) = float_number(input)?;
     if value < 0.0 {
         Err(nom::Err::Failure(nom::error::Error::new(
             input,
@@ -73,16 +90,16 @@ fn parse_positive_f64(input: &str) -> IResult<&str, f64> {
     }
 }
 
-fn take_and_make_f64(input: &str) -> IResult<&str, f64> {
+fn take_and_make_float(input: &str) -> IResult<&str, Float> {
     map_res(is_not(","), parse_float_num)(input)
 }
 
 fn do_parse_dpt(i: &str) -> IResult<&str, DptData> {
-    let (i, water_depth) = opt(parse_positive_f64)(i)?;
+    let (i, water_depth) = opt(parse_positive_float)(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, offset) = opt(parse_positive_f64)(i)?;
+    let (i, offset) = opt(parse_positive_float)(i)?;
     let (i, _) = opt(char(','))(i)?;
-    let (i, max_range_scale) = opt(take_and_make_f64)(i)?;
+    let (i, max_range_scale) = opt(take_and_make_float)(i)?;
 
     let (i, leftover) = parse_until_end(i)?;
 
@@ -0,0 +1,236 @@
+use arrayvec::ArrayString;
+use nom::{
+    bytes::complete::is_not,
+    character::complete::{char, one_of},
+    combinator::opt,
+    number::complete::float,
+    IResult,
+};
+
+use crate::{
+    parse::NmeaSentence,
+    sentences::utils::{array_string, float_number},
+    Error, Float, SentenceType,
+};
+
+/// Maximum length of the datum codes of a DTM sentence.
+pub const DATUM_CODE_MAX_LEN: usize = 5;
+
+/// DTM - Datum Reference
+///
+/// <https://gpsd.gitlab.io/gpsd/NMEA.html#_dtm_datum_reference>
+///
+/// ```text
+///        1   2 3       4 5       6 7     8
+///        |   | |       | |       | |     |
+/// $--DTM,ref,x,llll.ll,c,llll.ll,c,aaa.a,ref*hh<CR><LF>
+/// ```
+/// 1. Local datum code, e.g. `W84`, `999` for a user defined datum
+/// 2. Local datum subdivision code
+/// 3. Latitude offset, minutes
+/// 4. N or S
+/// 5. Longitude offset, minutes
+/// 6. E or W
+/// 7. Altitude offset, meters
+/// 8. Reference datum code, `W84` by the standard
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
+pub struct DtmData {
+    /// Datum of the positions of the other sentences
+    pub datum: Option<Datum>,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub subdivision: Option<ArrayString<DATUM_CODE_MAX_LEN>>,
+    /// Offset of the local datum from the reference datum in minutes,
+    /// positive to the North
+    pub latitude_offset: Option<Float>,
+    /// Offset of the local datum from the reference datum in minutes,
+    /// positive to the East
+    pub longitude_offset: Option<Float>,
+    /// Offset of the local datum from the reference datum in meters
+    pub altitude_offset: Option<f32>,
+    pub reference_datum: Option<Datum>,
+}
+
+accessors!(DtmData {
+    datum, with_datum: Option<Datum>;
+    subdivision, with_subdivision: Option<ArrayString<DATUM_CODE_MAX_LEN>>;
+    latitude_offset, with_latitude_offset: Option<Float>;
+    longitude_offset, with_longitude_offset: Option<Float>;
+    altitude_offset, with_altitude_offset: Option<f32>;
+    reference_datum, with_reference_datum: Option<Datum>;
+});
+
+/// A geodetic datum, named by its NMEA code.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Datum {
+    /// WGS 84, `W84`
+    Wgs84,
+    /// WGS 72, `W72`
+    Wgs72,
+    /// SGS 85, `S85`
+    Sgs85,
+    /// PE 90, `P90`
+    Pe90,
+    /// NAD 83, IHO code `NAR`
+    Nad83,
+    /// ETRS 89, which has no NMEA code, see
+    /// [`DatumTransform`](crate::datum::DatumTransform)
+    Etrs89,
+    /// A datum defined by the user of the receiver, `999`
+    UserDefined,
+    /// Another IHO datum code
+    Other(#[cfg_attr(feature = "defmt-03", defmt(Debug2Format))] ArrayString<DATUM_CODE_MAX_LEN>),
+}
+
+impl Datum {
+    /// Returns the datum of a code, `None` if it is longer than
+    /// [`DATUM_CODE_MAX_LEN`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::sentences::dtm::Datum;
+    ///
+    /// assert_eq!(Datum::from_code("W84"), Some(Datum::Wgs84));
+    /// assert_eq!(Datum::from_code("EUR").unwrap().code(), "EUR");
+    /// ```
+    pub fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            "W84" => Datum::Wgs84,
+            "W72" => Datum::Wgs72,
+            "S85" => Datum::Sgs85,
+            "P90" => Datum::Pe90,
+            "NAR" => Datum::Nad83,
+            "999" => Datum::UserDefined,
+            code => Datum::Other(ArrayString::from(code).ok()?),
+        })
+    }
+
+    /// Returns the code of the datum, `ETRS89` for [`Datum::Etrs89`].
+    pub fn code(&self) -> &str {
+        match self {
+            Datum::Wgs84 => "W84",
+            Datum::Wgs72 => "W72",
+            Datum::Sgs85 => "S85",
+            Datum::Pe90 => "P90",
+            Datum::Nad83 => "NAR",
+            Datum::Etrs89 => "ETRS89",
+            Datum::UserDefined => "999",
+            Datum::Other(code) => code,
+        }
+    }
+}
+
+fn parse_datum(i: &str) -> IResult<&str, Option<Datum>> {
+    let (rest, code) = opt(is_not(",*"))(i)?;
+    let datum = code
+        .map(|code| Datum::from_code(code).ok_or_else(|| super::nom_parse_failure(i)))
+        .transpose()?;
+    Ok((rest, datum))
+}
+
+/// Parses an offset in minutes and its direction, `negative` being `S` or
+/// `W`.
+fn parse_offset<'a>(i: &'a str, directions: &'static str) -> IResult<&'a str, Option<Float>> {
+    let (i, offset) = opt(float_number)(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, direction) = opt(one_of(directions))(i)?;
+    let negative = direction.is_some_and(|direction| directions.ends_with(direction));
+    Ok((
+        i,
+        offset.map(|offset| if negative { -offset } else { offset }),
+    ))
+}
+
+fn do_parse_dtm(i: &str) -> Result<DtmData, Error<'_>> {
+    let (i, datum) = parse_datum(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, subdivision) = opt(is_not(",*"))(i)?;
+    let subdivision = subdivision
+        .map(array_string::<DATUM_CODE_MAX_LEN>)
+        .transpose()?;
+    let (i, _) = char(',')(i)?;
+    let (i, latitude_offset) = parse_offset(i, "NS")?;
+    let (i, _) = char(',')(i)?;
+    let (i, longitude_offset) = parse_offset(i, "EW")?;
+    let (i, _) = char(',')(i)?;
+    let (i, altitude_offset) = opt(float)(i)?;
+    let (i, _) = char(',')(i)?;
+    let (_i, reference_datum) = parse_datum(i)?;
+
+    Ok(DtmData {
+        datum,
+        subdivision,
+        latitude_offset,
+        longitude_offset,
+        altitude_offset,
+        reference_datum,
+    })
+}
+
+/// # Parse DTM message
+///
+/// See: <https://gpsd.gitlab.io/gpsd/NMEA.html#_dtm_datum_reference>
+pub fn parse_dtm(sentence: NmeaSentence) -> Result<DtmData, Error> {
+    if sentence.message_id != SentenceType::DTM {
+        Err(Error::WrongSentenceHeader {
+            expected: SentenceType::DTM,
+            found: sentence.message_id,
+        })
+    } else {
+        do_parse_dtm(sentence.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::parse::parse_nmea_sentence;
+
+    #[test]
+    fn test_parse_dtm() {
+        let s = parse_nmea_sentence("$GPDTM,999,,0.08,N,0.07,E,-47.7,W84*1B").unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        let dtm = parse_dtm(s).unwrap();
+        assert_eq!(dtm.datum, Some(Datum::UserDefined));
+        assert_eq!(dtm.subdivision, None);
+        assert_relative_eq!(dtm.latitude_offset.unwrap(), 0.08);
+        assert_relative_eq!(dtm.longitude_offset.unwrap(), 0.07);
+        assert_relative_eq!(dtm.altitude_offset.unwrap(), -47.7);
+        assert_eq!(dtm.reference_datum, Some(Datum::Wgs84));
+
+        let s = parse_nmea_sentence("$GPDTM,W72,,0.00,S,0.01,W,-2.8,W84*4F").unwrap();
+        let dtm = parse_dtm(s).unwrap();
+        assert_eq!(dtm.datum, Some(Datum::Wgs72));
+        assert_relative_eq!(dtm.longitude_offset.unwrap(), -0.01);
+
+        let s = parse_nmea_sentence("$GPDTM,NAR,A,,,,,,W84*0D").unwrap();
+        let dtm = parse_dtm(s).unwrap();
+        assert_eq!(dtm.datum, Some(Datum::Nad83));
+        assert_eq!(dtm.subdivision.as_deref(), Some("A"));
+        assert_eq!(dtm.latitude_offset, None);
+        assert_eq!(dtm.altitude_offset, None);
+    }
+
+    #[test]
+    fn test_parse_dtm_invalid() {
+        // missing reference datum field
+        let s = parse_nmea_sentence("$GPDTM,W84,,0.0,N,0.0,E,0.0*18").unwrap();
+        assert!(matches!(parse_dtm(s), Err(Error::ParsingError(_))));
+        // bad direction
+        let s = parse_nmea_sentence("$GPDTM,W84,,0.0,X,0.0,E,0.0,W84*79").unwrap();
+        assert!(matches!(parse_dtm(s), Err(Error::ParsingError(_))));
+
+        let s = parse_nmea_sentence("$GPHDT,274.07,T*03").unwrap();
+        assert!(matches!(
+            parse_dtm(s),
+            Err(Error::WrongSentenceHeader { .. })
+        ));
+    }
+}
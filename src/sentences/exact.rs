@@ -0,0 +1,241 @@
+//! Positions kept exactly as written in the sentences.
+//!
+//! The [`Float`](crate::Float) coordinates cannot represent most decimal
+//! minutes exactly. With the `exact-coordinates` feature, the GGA, RMC, GLL
+//! and GNS data also keep an [`ExactPosition`] of the digits of the
+//! `ddmm.mmmm` fields, for the workflows which audit or re-emit the
+//! positions and need them bit-exact.
+
+use core::fmt;
+
+/// Maximum number of decimals of the minutes, more are not kept exactly.
+pub const EXACT_MAX_DECIMALS: u8 = 12;
+
+/// A latitude or longitude field as written, e.g. `4807.038,N`.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::sentences::exact::ExactCoordinate;
+///
+/// let latitude = ExactCoordinate {
+///     value: 4807038,
+///     decimals: 3,
+///     hemisphere: 'N',
+/// };
+/// assert_eq!(latitude.degrees_e7(), 481_173_000);
+/// assert_eq!(latitude.to_string(), "4807.038,N");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExactCoordinate {
+    /// The degrees and minutes without the decimal point, e.g. `4807038`
+    /// for `4807.038`
+    pub value: u64,
+    /// Number of decimals of the minutes, at most [`EXACT_MAX_DECIMALS`]:
+    /// more are handled as [`EXACT_MAX_DECIMALS`]
+    pub decimals: u8,
+    /// `N`, `S`, `E` or `W`
+    pub hemisphere: char,
+}
+
+impl ExactCoordinate {
+    /// Returns the coordinate in ten-millionths of a degree, rounded to the
+    /// nearest, negative to the South and West.
+    pub fn degrees_e7(&self) -> i64 {
+        let scale = i128::from(self.scale());
+        let value = i128::from(self.value);
+        let degrees = value / (100 * scale);
+        let minutes = value % (100 * scale);
+        // rounded half up of minutes * 1e7 / (60 * scale)
+        let fraction = (2 * minutes * 10_000_000 + 60 * scale) / (120 * scale);
+        let degrees_e7 = degrees * 10_000_000 + fraction;
+        // at most 10^16 with the digits of the fields, saturated otherwise
+        let degrees_e7 = i64::try_from(degrees_e7).unwrap_or(i64::MAX);
+        if self.is_negative() {
+            -degrees_e7
+        } else {
+            degrees_e7
+        }
+    }
+
+    /// Returns the coordinate in degrees, negative to the South and West.
+    pub fn to_degrees(&self) -> f64 {
+        let scale = self.scale();
+        let degrees = (self.value / (100 * scale)) as f64;
+        let minutes = (self.value % (100 * scale)) as f64 / scale as f64;
+        let degrees = degrees + minutes / 60.;
+        if self.is_negative() {
+            -degrees
+        } else {
+            degrees
+        }
+    }
+
+    fn decimals(&self) -> u8 {
+        self.decimals.min(EXACT_MAX_DECIMALS)
+    }
+
+    /// `10^decimals`
+    fn scale(&self) -> u64 {
+        10_u64.pow(u32::from(self.decimals()))
+    }
+
+    fn is_negative(&self) -> bool {
+        matches!(self.hemisphere, 'S' | 'W')
+    }
+
+    fn is_latitude(&self) -> bool {
+        matches!(self.hemisphere, 'N' | 'S')
+    }
+}
+
+/// Writes the field as in the sentences, with 2 digits of degrees for the
+/// latitudes, 3 for the longitudes and 2 of whole minutes.
+impl fmt::Display for ExactCoordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scale = self.scale();
+        let width = if self.is_latitude() { 2 } else { 3 };
+        let minutes = self.value % (100 * scale);
+        write!(
+            f,
+            "{:0width$}{:02}",
+            self.value / (100 * scale),
+            minutes / scale,
+            width = width
+        )?;
+        if self.decimals() > 0 {
+            let decimals = usize::from(self.decimals());
+            write!(f, ".{:0decimals$}", minutes % scale, decimals = decimals)?;
+        }
+        write!(f, ",{}", self.hemisphere)
+    }
+}
+
+/// A position as written in a sentence, see the [module](self).
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExactPosition {
+    pub latitude: ExactCoordinate,
+    pub longitude: ExactCoordinate,
+}
+
+/// Writes the four fields as in the sentences, e.g.
+/// `4807.038,N,01131.324,E`.
+impl fmt::Display for ExactPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.latitude, self.longitude)
+    }
+}
+
+/// Parses a coordinate field of `degree_digits` digits of degrees, `None`
+/// unless made of digits with 2 digits of whole minutes, less than 60, and
+/// at most [`EXACT_MAX_DECIMALS`] decimals, so it is written back as is.
+fn parse_coordinate(
+    field: &str,
+    hemisphere: &str,
+    degree_digits: usize,
+    hemispheres: [char; 2],
+) -> Option<ExactCoordinate> {
+    let hemisphere = match hemisphere.as_bytes() {
+        [byte] if hemispheres.contains(&char::from(*byte)) => char::from(*byte),
+        _ => return None,
+    };
+    let (degrees, minutes) = (field.get(..degree_digits)?, field.get(degree_digits..)?);
+    let (whole, fraction) = minutes.split_once('.').unwrap_or((minutes, ""));
+    let decimals = u8::try_from(fraction.len())
+        .ok()
+        .filter(|decimals| *decimals <= EXACT_MAX_DECIMALS)?;
+    let digits = [degrees, whole, fraction];
+    if whole.len() != 2 || !digits.iter().all(|d| d.bytes().all(|b| b.is_ascii_digit())) {
+        return None;
+    }
+    let scale = 10_u64.pow(u32::from(decimals));
+    let whole: u64 = whole.parse().ok()?;
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse().ok()?
+    };
+    if whole >= 60 {
+        return None;
+    }
+    let degrees: u64 = degrees.parse().ok()?;
+    Some(ExactCoordinate {
+        value: (degrees * 100 + whole) * scale + fraction,
+        decimals,
+        hemisphere,
+    })
+}
+
+/// Parses the `ddmm.mmmm,a,dddmm.mmmm,a` fields at the start of `i`, `None`
+/// if they are empty or not kept exactly.
+pub(crate) fn parse_exact_position(i: &str) -> Option<ExactPosition> {
+    let mut fields = i.splitn(5, ',');
+    let mut next = || fields.next();
+    let latitude = parse_coordinate(next()?, next()?, 2, ['N', 'S'])?;
+    let longitude = parse_coordinate(next()?, next()?, 3, ['E', 'W'])?;
+    Some(ExactPosition {
+        latitude,
+        longitude,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_position() {
+        let position = parse_exact_position("4807.038,N,01131.324,E,1,08").unwrap();
+        assert_eq!(position.latitude.value, 4807038);
+        assert_eq!(position.latitude.degrees_e7(), 481_173_000);
+        assert_eq!(position.longitude.degrees_e7(), 115_220_667);
+        assert_eq!(position.to_string(), "4807.038,N,01131.324,E");
+
+        let text = "3723.46587704,N,12202.26957864,W";
+        let position = parse_exact_position(text).unwrap();
+        assert_eq!(position.to_string(), text);
+        assert_eq!(position.latitude.decimals, 8);
+        assert_eq!(position.longitude.degrees_e7(), -1_220_378_263);
+        assert!((position.longitude.to_degrees() + 122.037_826_310_67).abs() < 1e-9);
+
+        // the sign of zero is kept
+        let text = "0000.0000,S,00000,W";
+        assert_eq!(parse_exact_position(text).unwrap().to_string(), text);
+        // 59.99999995 minutes are rounded up to the next degree
+        let position = parse_exact_position("0059.99999995,N,00000.0,E").unwrap();
+        assert_eq!(position.latitude.degrees_e7(), 10_000_000);
+
+        assert_eq!(parse_exact_position(",,,"), None);
+        assert_eq!(parse_exact_position("4860.000,N,01131.324,E"), None);
+        assert_eq!(parse_exact_position("4807.0e3,N,01131.324,E"), None);
+        // non-standard widths are not written back as is
+        assert_eq!(parse_exact_position("487.038,N,01131.324,E"), None);
+        assert_eq!(parse_exact_position("4807.038,N,1131.324,E"), None);
+        assert_eq!(parse_exact_position("4807.038,E,01131.324,E"), None);
+        assert_eq!(
+            parse_exact_position("4807.0000000000001,N,01131.324,E"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_too_many_decimals() {
+        let coordinate = ExactCoordinate {
+            value: u64::MAX,
+            decimals: u8::MAX,
+            hemisphere: 'W',
+        };
+        assert_eq!(coordinate.degrees_e7(), -1_844_677_345_618);
+        assert!(coordinate.to_degrees() < 0.);
+        assert!(coordinate.to_string().ends_with(",W"));
+        let coordinate = ExactCoordinate {
+            decimals: 0,
+            ..coordinate
+        };
+        assert_eq!(coordinate.degrees_e7(), -i64::MAX);
+    }
+}
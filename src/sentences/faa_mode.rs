@@ -1,34 +1,47 @@
 use nom::{character::complete::anychar, combinator::opt, IResult};
 
-use super::{nom_parse_failure, FixType};
+use super::{nom_parse_failure, FixType, GnssType};
 
-/// for now let's handle only two GPS and GLONASS
+/// The mode indicators of a GNS sentence, one per constellation in the order
+/// of NMEA 4.11: GPS, GLONASS, Galileo, BeiDou, QZSS and NavIC.
+///
+/// Older receivers only send the first one or two of them.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub struct FaaModes {
-    sys_state0: FaaMode,
-    sys_state1: Option<FaaMode>,
+    modes: [Option<FaaMode>; 6],
 }
 
+impl FaaModes {
+    /// Returns the mode of a constellation, if it was sent.
+    pub fn mode(&self, gnss_type: GnssType) -> Option<FaaMode> {
+        let index = GnssType::SYSTEM_ID_ORDER
+            .iter()
+            .position(|&ty| ty == gnss_type)?;
+        self.modes[index]
+    }
+
+    /// Returns the modes which were sent, in the order of the sentence.
+    pub fn iter(&self) -> impl Iterator<Item = FaaMode> + '_ {
+        self.modes.iter().flatten().copied()
+    }
+}
+
+/// The best fix type of the modes of the systems, see [`FixType::rank()`].
 impl From<FaaModes> for FixType {
     fn from(modes: FaaModes) -> Self {
-        let fix_type: FixType = modes.sys_state0.into();
-        if fix_type.is_valid() {
-            return fix_type;
-        }
-        if let Some(fix_type2) = modes.sys_state1.map(FixType::from) {
-            if fix_type2.is_valid() {
-                return fix_type2;
-            }
-        }
-        fix_type
+        modes
+            .iter()
+            .map(FixType::from)
+            .fold(FixType::Invalid, FixType::best)
     }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum FaaMode {
     /// A - Autonomous mode
     Autonomous,
@@ -54,6 +67,8 @@ pub enum FaaMode {
     Simulator,
     /// U - Quectel Querk, "Unsafe"
     Unsafe,
+    /// Any other letter, sent by some receivers for vendor-specific modes
+    Unknown(char),
 }
 
 impl From<FaaMode> for FixType {
@@ -70,31 +85,31 @@ impl From<FaaMode> for FixType {
             FaaMode::Manual => FixType::Manual,
             FaaMode::Simulator => FixType::Simulation,
             FaaMode::Unsafe => FixType::Invalid,
+            // gpsd assumes an autonomous fix, but the meaning is unknown
+            FaaMode::Unknown(_) => FixType::Invalid,
         }
     }
 }
 
 pub(crate) fn parse_faa_modes(i: &str) -> IResult<&str, FaaModes> {
-    let (rest, sym) = anychar(i)?;
-
-    let mut ret = FaaModes {
-        sys_state0: parse_faa_mode(sym).ok_or_else(|| nom_parse_failure(i))?,
-        sys_state1: None,
-    };
+    let (mut rest, sym) = anychar(i)?;
 
-    let (rest2, sym) = opt(anychar)(rest)?;
+    let mut modes = [None; 6];
+    modes[0] = Some(parse_faa_mode(sym).ok_or_else(|| nom_parse_failure(i))?);
 
-    match sym {
-        Some(sym) => {
-            ret.sys_state1 = Some(parse_faa_mode(sym).ok_or_else(|| nom_parse_failure(rest))?);
-        }
-        None => return Ok((rest, ret)),
-    };
+    for mode in modes.iter_mut().skip(1) {
+        let (rest2, sym) = opt(anychar)(rest)?;
+        let Some(sym) = sym else {
+            break;
+        };
+        *mode = Some(parse_faa_mode(sym).ok_or_else(|| nom_parse_failure(rest))?);
+        rest = rest2;
+    }
 
-    if rest2.is_empty() {
-        Ok((rest2, ret))
+    if rest.is_empty() {
+        Ok((rest, FaaModes { modes }))
     } else {
-        Err(nom_parse_failure(rest2))
+        Err(nom_parse_failure(rest))
     }
 }
 
@@ -111,6 +126,16 @@ pub(crate) fn parse_faa_mode(value: char) -> Option<FaaMode> {
         'M' => Some(FaaMode::Manual),
         'S' => Some(FaaMode::Simulator),
         'U' => Some(FaaMode::Unsafe),
+        c if c.is_ascii_alphabetic() => Some(FaaMode::Unknown(c)),
+        _ => None,
+    }
+}
+
+/// Parses an FAA mode field of a single character.
+pub(crate) fn parse_faa_mode_field(field: &str) -> Option<FaaMode> {
+    let mut chars = field.chars();
+    match (chars.next(), chars.next()) {
+        (Some(mode), None) => parse_faa_mode(mode),
         _ => None,
     }
 }
@@ -130,8 +155,7 @@ mod test {
             (
                 "",
                 FaaModes {
-                    sys_state0: FaaMode::Autonomous,
-                    sys_state1: None,
+                    modes: [Some(FaaMode::Autonomous), None, None, None, None, None],
                 }
             ),
             parse_faa_modes("A").unwrap()
@@ -141,11 +165,29 @@ mod test {
             (
                 "",
                 FaaModes {
-                    sys_state0: FaaMode::DataNotValid,
-                    sys_state1: Some(FaaMode::Autonomous),
+                    modes: [
+                        Some(FaaMode::DataNotValid),
+                        Some(FaaMode::Autonomous),
+                        None,
+                        None,
+                        None,
+                        None
+                    ],
                 }
             ),
             parse_faa_modes("NA").unwrap()
         );
+
+        let (_, modes) = parse_faa_modes("AX").unwrap();
+        assert_eq!(modes.mode(GnssType::Glonass), Some(FaaMode::Unknown('X')));
+        assert_eq!(FixType::from(modes), FixType::Gps);
+        assert!(parse_faa_modes("A1").is_err());
+
+        // NMEA 4.11: GPS, GLONASS, Galileo, BeiDou, QZSS and NavIC
+        let (_, modes) = parse_faa_modes("NNNNND").unwrap();
+        assert_eq!(modes.mode(GnssType::NavIC), Some(FaaMode::Differential));
+        assert_eq!(modes.iter().count(), 6);
+        assert_eq!(FixType::from(modes), FixType::DGps);
+        assert!(parse_faa_modes("AAAAAAA").is_err());
     }
 }
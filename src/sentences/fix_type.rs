@@ -2,6 +2,7 @@
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum FixType {
     Invalid,
     Gps,
@@ -23,6 +24,37 @@ impl FixType {
             FixType::DGps | FixType::Gps | FixType::Rtk | FixType::FloatRtk | FixType::Pps => true,
         }
     }
+
+    /// Returns the quality of the fix type, used to pick the best of the
+    /// modes of several systems: from [`FixType::Invalid`] (0) up to
+    /// [`FixType::Rtk`] (7).
+    ///
+    /// Fixes without satellites rank below any GNSS fix, and differential
+    /// and RTK fixes above autonomous ones. [`FixType::DGps`] and
+    /// [`FixType::Pps`] rank equal, as the accuracy of the precise
+    /// positioning service is not comparable with a differential fix.
+    pub fn rank(self) -> u8 {
+        match self {
+            FixType::Invalid => 0,
+            FixType::Simulation => 1,
+            FixType::Manual => 2,
+            FixType::Estimated => 3,
+            FixType::Gps => 4,
+            FixType::DGps | FixType::Pps => 5,
+            FixType::FloatRtk => 6,
+            FixType::Rtk => 7,
+        }
+    }
+
+    /// Returns the fix type of higher [`rank()`](Self::rank), `self` if
+    /// they rank equal.
+    pub fn best(self, other: FixType) -> FixType {
+        if other.rank() > self.rank() {
+            other
+        } else {
+            self
+        }
+    }
 }
 
 impl From<char> for FixType {
@@ -3,8 +3,8 @@ use nom::{character::complete::char, combinator::opt, number::complete::float, I
 
 use crate::{
     parse::NmeaSentence,
-    sentences::utils::{number, parse_hms, parse_lat_lon},
-    Error, SentenceType,
+    sentences::utils::{float_number, number, parse_hms},
+    Error, Float, SentenceType,
 };
 
 /// GBS - GPS Satellite Fault Detection
@@ -18,12 +18,13 @@ use crate::{
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
 pub struct GbsData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub time: Option<NaiveTime>,
-    pub lat_error: Option<f64>,
-    pub lon_error: Option<f64>,
+    pub lat_error: Option<Float>,
+    pub lon_error: Option<Float>,
     pub alt_error: Option<f32>,
     pub most_likely_failed_sat: Option<u8>,
     pub missed_probability: Option<f32>,
@@ -31,6 +32,17 @@ pub struct GbsData {
     pub bias_standard_deviation: Option<f32>,
 }
 
+accessors!(GbsData {
+    time, with_time: Option<NaiveTime>;
+    lat_error, with_lat_error: Option<Float>;
+    lon_error, with_lon_error: Option<Float>;
+    alt_error, with_alt_error: Option<f32>;
+    most_likely_failed_sat, with_most_likely_failed_sat: Option<u8>;
+    missed_probability, with_missed_probability: Option<f32>;
+    bias_estimate, with_bias_estimate: Option<f32>;
+    bias_standard_deviation, with_bias_standard_deviation: Option<f32>;
+});
+
 /// GBS - GPS Satellite Fault Detection
 ///
 /// ```text
@@ -44,8 +56,11 @@ fn do_parse_gbs(i: &str) -> IResult<&str, GbsData> {
     let (i, _) = char(',')(i)?;
 
     // 2. Expected 1-sigma error in latitude (meters)
+    let (i, lat_error) = opt(float_number)(i)?;
+    let (i, _) = char(',')(i)?;
+
     // 3. Expected 1-sigma error in longitude (meters)
-    let (i, lat_lon_errors) = parse_lat_lon(i)?;
+    let (i, lon_error) = opt(float_number)(i)?;
     let (i, _) = char(',')(i)?;
 
     // 4. Expected 1-sigma error in altitude (meters)
@@ -71,8 +86,8 @@ fn do_parse_gbs(i: &str) -> IResult<&str, GbsData> {
         i,
         GbsData {
             time,
-            lat_error: lat_lon_errors.map(|(lat, _lon)| lat),
-            lon_error: lat_lon_errors.map(|(_lat, lon)| lon),
+            lat_error,
+            lon_error,
             alt_error,
             most_likely_failed_sat,
             missed_probability,
@@ -95,3 +110,29 @@ pub fn parse_gbs(sentence: NmeaSentence) -> Result<GbsData, Error> {
         Ok(do_parse_gbs(sentence.data)?.1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::parse::parse_nmea_sentence;
+
+    #[test]
+    fn test_parse_gbs() {
+        let s =
+            parse_nmea_sentence("$GPGBS,015509.00,1.2,0.8,2.1,19,0.000,-0.354,6.972*42").unwrap();
+        let gbs = parse_gbs(s).unwrap();
+        assert_eq!(gbs.time, NaiveTime::from_hms_opt(1, 55, 9));
+        assert_relative_eq!(gbs.lat_error.unwrap(), 1.2);
+        assert_relative_eq!(gbs.lon_error.unwrap(), 0.8);
+        assert_relative_eq!(gbs.alt_error.unwrap(), 2.1);
+        assert_eq!(gbs.most_likely_failed_sat, Some(19));
+        assert_relative_eq!(gbs.bias_estimate.unwrap(), -0.354);
+
+        let s = parse_nmea_sentence("$GNGBS,181604.00,,,,,,,*7B").unwrap();
+        let gbs = parse_gbs(s).unwrap();
+        assert_eq!(gbs.lat_error, None);
+        assert_eq!(gbs.bias_standard_deviation, None);
+    }
+}
@@ -4,16 +4,19 @@ use nom::{
     character::complete::{char, one_of},
     combinator::{map_res, opt},
     number::complete::float,
+    sequence::preceded,
     IResult,
 };
 
+#[cfg(feature = "exact-coordinates")]
+use super::exact::{parse_exact_position, ExactPosition};
 use crate::{
     parse::NmeaSentence,
     sentences::{
         utils::{number, parse_float_num, parse_hms, parse_lat_lon},
         FixType,
     },
-    Error, SentenceType,
+    Error, Float, SentenceType,
 };
 
 /// GGA - Global Positioning System Fix Data
@@ -28,7 +31,8 @@ use crate::{
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct GgaData {
     #[cfg_attr(
         not(feature = "std"),
@@ -37,17 +41,42 @@ pub struct GgaData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_time: Option<NaiveTime>,
     pub fix_type: Option<FixType>,
-    pub latitude: Option<f64>,
-    pub longitude: Option<f64>,
+    pub latitude: Option<Float>,
+    pub longitude: Option<Float>,
     pub fix_satellites: Option<u32>,
     pub hdop: Option<f32>,
+    /// Altitude above mean sea level (geoid) in meters
     pub altitude: Option<f32>,
+    /// Height of the geoid above the WGS-84 ellipsoid in meters
     pub geoid_separation: Option<f32>,
+    /// Time in seconds since the last DGPS update
+    pub age_of_differential: Option<f32>,
+    /// DGPS reference station ID (0000-1023)
+    pub dgps_station_id: Option<u16>,
+    /// The position exactly as written, see [`ExactPosition`]
+    #[cfg(feature = "exact-coordinates")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "exact-coordinates")))]
+    pub exact_position: Option<ExactPosition>,
 }
 
+accessors!(GgaData {
+    fix_time, with_fix_time: Option<NaiveTime>;
+    fix_type, with_fix_type: Option<FixType>;
+    latitude, with_latitude: Option<Float>;
+    longitude, with_longitude: Option<Float>;
+    fix_satellites, with_fix_satellites: Option<u32>;
+    hdop, with_hdop: Option<f32>;
+    altitude, with_altitude: Option<f32>;
+    geoid_separation, with_geoid_separation: Option<f32>;
+    age_of_differential, with_age_of_differential: Option<f32>;
+    dgps_station_id, with_dgps_station_id: Option<u16>;
+});
+
 fn do_parse_gga(i: &str) -> IResult<&str, GgaData> {
     let (i, fix_time) = opt(parse_hms)(i)?;
     let (i, _) = char(',')(i)?;
+    #[cfg(feature = "exact-coordinates")]
+    let exact_position = parse_exact_position(i);
     let (i, lat_lon) = parse_lat_lon(i)?;
     let (i, _) = char(',')(i)?;
     let (i, fix_quality) = one_of("012345678")(i)?;
@@ -63,6 +92,11 @@ fn do_parse_gga(i: &str) -> IResult<&str, GgaData> {
     let (i, geoid_height) = opt(map_res(take_until(","), parse_float_num::<f32>))(i)?;
     let (i, _) = char(',')(i)?;
     let (i, _) = opt(char('M'))(i)?;
+    let (i, age_of_differential) = opt(preceded(
+        char(','),
+        opt(map_res(take_until(","), parse_float_num::<f32>)),
+    ))(i)?;
+    let (i, dgps_station_id) = opt(preceded(char(','), opt(number::<u16>)))(i)?;
 
     Ok((
         i,
@@ -75,6 +109,10 @@ fn do_parse_gga(i: &str) -> IResult<&str, GgaData> {
             hdop,
             altitude,
             geoid_separation: geoid_height,
+            age_of_differential: age_of_differential.flatten(),
+            dgps_station_id: dgps_station_id.flatten(),
+            #[cfg(feature = "exact-coordinates")]
+            exact_position,
         },
     ))
 }
@@ -96,8 +134,8 @@ fn do_parse_gga(i: &str) -> IResult<&str, GgaData> {
 /// 9,10  545.4,M      Altitude, Metres above mean sea level
 /// 11,12 46.9,M       Height of geoid (mean sea level) above WGS84
 /// ellipsoid, in Meters
-/// (empty field) time in seconds since last DGPS update
-/// (empty field) DGPS station ID number (0000-1023)
+/// 13    (empty field) time in seconds since last DGPS update
+/// 14    (empty field) DGPS station ID number (0000-1023)
 pub fn parse_gga(sentence: NmeaSentence) -> Result<GgaData, Error> {
     if sentence.message_id != SentenceType::GGA {
         Err(Error::WrongSentenceHeader {
@@ -225,11 +263,40 @@ mod tests {
                 hdop: None,
                 altitude: None,
                 geoid_separation: None,
+                age_of_differential: None,
+                dgps_station_id: None,
+                #[cfg(feature = "exact-coordinates")]
+                exact_position: None,
             },
             data
         );
     }
 
+    #[test]
+    fn test_parse_gga_dgps() {
+        let s = parse_nmea_sentence(
+            "$GPGGA,172814.0,3723.46587704,N,12202.26957864,W,2,6,1.2,18.893,M,-25.669,M,2.0,0031*4F",
+        )
+        .unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        let data = parse_gga(s).unwrap();
+        assert_eq!(data.fix_type, Some(FixType::DGps));
+        assert_relative_eq!(data.age_of_differential.unwrap(), 2.0);
+        assert_eq!(data.dgps_station_id, Some(31));
+        #[cfg(feature = "exact-coordinates")]
+        assert_eq!(
+            data.exact_position.unwrap().to_string(),
+            "3723.46587704,N,12202.26957864,W"
+        );
+
+        // sentences ending right after the geoid separation unit
+        let data = do_parse_gga("033745.0,5650.82344,N,03548.9778,E,1,07,1.8,101.2,M,14.7,M")
+            .unwrap()
+            .1;
+        assert_eq!(data.age_of_differential, None);
+        assert_eq!(data.dgps_station_id, None);
+    }
+
     #[test]
     fn test_parse_gga_with_optional_fields() {
         let sentence =
@@ -1,15 +1,16 @@
 use chrono::NaiveTime;
 use nom::{
-    character::complete::{anychar, char, one_of},
-    combinator::opt,
+    character::complete::{char, one_of},
     IResult,
 };
 
-use super::{faa_mode::parse_faa_mode, nom_parse_failure, FaaMode};
+#[cfg(feature = "exact-coordinates")]
+use super::exact::{parse_exact_position, ExactPosition};
+use super::{faa_mode::parse_faa_mode_field, nom_parse_failure, FaaMode};
 use crate::{
     parse::NmeaSentence,
-    sentences::utils::{parse_hms, parse_lat_lon},
-    Error, SentenceType,
+    sentences::utils::{parse_hms, parse_lat_lon, parse_optional_tail},
+    Error, Float, SentenceType,
 };
 
 /// GLL - Geographic Position - Latitude/Longitude
@@ -30,16 +31,29 @@ use crate::{
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
 pub struct GllData {
-    pub latitude: Option<f64>,
-    pub longitude: Option<f64>,
+    pub latitude: Option<Float>,
+    pub longitude: Option<Float>,
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_time: NaiveTime,
     pub valid: bool,
     pub faa_mode: Option<FaaMode>,
+    /// The position exactly as written, see [`ExactPosition`]
+    #[cfg(feature = "exact-coordinates")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "exact-coordinates")))]
+    pub exact_position: Option<ExactPosition>,
 }
 
+accessors!(GllData {
+    latitude, with_latitude: Option<Float>;
+    longitude, with_longitude: Option<Float>;
+    fix_time, with_fix_time: NaiveTime;
+    valid, with_valid: bool;
+    faa_mode, with_faa_mode: Option<FaaMode>;
+});
+
 /// # Parse GLL (Geographic position) message
 ///
 /// From <https://docs.novatel.com/OEM7/Content/Logs/GPGLL.htm>
@@ -67,6 +81,8 @@ pub fn parse_gll(sentence: NmeaSentence) -> Result<GllData, Error> {
 }
 
 fn do_parse_gll(i: &str) -> IResult<&str, GllData> {
+    #[cfg(feature = "exact-coordinates")]
+    let exact_position = parse_exact_position(i);
     let (i, lat_lon) = parse_lat_lon(i)?;
     let (i, _) = char(',')(i)?;
     let (i, fix_time) = parse_hms(i)?;
@@ -77,10 +93,10 @@ fn do_parse_gll(i: &str) -> IResult<&str, GllData> {
         'V' => false,
         _ => unreachable!(),
     };
-    let (i, _) = char(',')(i)?;
-    let (rest, mode) = opt(anychar)(i)?;
+    // the FAA mode of NMEA 2.3 and later is optional
+    let (rest, [mode]) = parse_optional_tail(i)?;
     let faa_mode = mode
-        .map(|mode| parse_faa_mode(mode).ok_or_else(|| nom_parse_failure(i)))
+        .map(|mode| parse_faa_mode_field(mode).ok_or_else(|| nom_parse_failure(i)))
         .transpose()?;
 
     Ok((
@@ -91,6 +107,8 @@ fn do_parse_gll(i: &str) -> IResult<&str, GllData> {
             valid,
             fix_time,
             faa_mode,
+            #[cfg(feature = "exact-coordinates")]
+            exact_position,
         },
     ))
 }
@@ -132,4 +150,33 @@ mod tests {
         );
         assert!(!gll_data.valid);
     }
+
+    #[test]
+    fn test_parse_gll_optional_mode() {
+        for data in [
+            // NMEA 2.3+
+            "4916.45,N,12311.12,W,225444,A,D",
+            " 4916.45,N,12311.12,W,225444,A , D ",
+        ] {
+            let gll_data = do_parse_gll(data.trim_start()).unwrap().1;
+            assert!(gll_data.valid);
+            assert_eq!(gll_data.faa_mode, Some(FaaMode::Differential));
+        }
+        for data in [
+            // pre NMEA 2.3
+            "4916.45,N,12311.12,W,225444,A",
+            "4916.45,N,12311.12,W,225444,A,",
+            "4916.45,N,12311.12,W,225444,A ",
+            "4916.45,N,12311.12,W,225444,A,,",
+        ] {
+            let gll_data = do_parse_gll(data).unwrap().1;
+            assert!(gll_data.valid);
+            assert_eq!(gll_data.faa_mode, None);
+        }
+        let gll_data = do_parse_gll("4916.45,N,12311.12,W,225444,A,X").unwrap().1;
+        assert_eq!(gll_data.faa_mode, Some(FaaMode::Unknown('X')));
+        assert!(do_parse_gll("4916.45,N,12311.12,W,225444,A,1").is_err());
+        assert!(do_parse_gll("4916.45,N,12311.12,W,225444,A,DD").is_err());
+        assert!(do_parse_gll("4916.45,N,12311.12,W,225444,AD").is_err());
+    }
 }
@@ -8,12 +8,14 @@ use nom::{
     IResult,
 };
 
+#[cfg(feature = "exact-coordinates")]
+use super::exact::{parse_exact_position, ExactPosition};
 use super::{
     faa_mode::parse_faa_modes,
-    utils::{number, parse_hms, parse_lat_lon},
+    utils::{number, parse_float_num, parse_hms, parse_lat_lon, parse_num},
     FaaModes,
 };
-use crate::{parse::NmeaSentence, Error, SentenceType};
+use crate::{parse::NmeaSentence, Error, Float, SentenceType};
 
 /// GNS - Fix data
 ///
@@ -26,20 +28,45 @@ use crate::{parse::NmeaSentence, Error, SentenceType};
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct GnsData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_time: Option<NaiveTime>,
-    pub lat: Option<f64>,
-    pub lon: Option<f64>,
+    pub lat: Option<Float>,
+    pub lon: Option<Float>,
     pub faa_modes: FaaModes,
     pub nsattelites: u16,
     pub hdop: Option<f32>,
+    /// Orthometric height (above mean sea level) in meters
     pub alt: Option<f32>,
+    /// Height of the geoid above the WGS-84 ellipsoid in meters
     pub geoid_separation: Option<f32>,
+    /// Age of differential corrections in seconds
+    pub age_of_differential: Option<f32>,
+    /// Differential reference station ID
+    pub dgps_station_id: Option<u16>,
     pub nav_status: Option<NavigationStatus>,
+    /// The position exactly as written, see [`ExactPosition`]
+    #[cfg(feature = "exact-coordinates")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "exact-coordinates")))]
+    pub exact_position: Option<ExactPosition>,
 }
 
+accessors!(GnsData {
+    fix_time, with_fix_time: Option<NaiveTime>;
+    lat, with_lat: Option<Float>;
+    lon, with_lon: Option<Float>;
+    faa_modes, with_faa_modes: FaaModes;
+    nsattelites, with_nsattelites: u16;
+    hdop, with_hdop: Option<f32>;
+    alt, with_alt: Option<f32>;
+    geoid_separation, with_geoid_separation: Option<f32>;
+    age_of_differential, with_age_of_differential: Option<f32>;
+    dgps_station_id, with_dgps_station_id: Option<u16>;
+    nav_status, with_nav_status: Option<NavigationStatus>;
+});
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -105,6 +132,8 @@ pub fn parse_gns(sentence: NmeaSentence) -> Result<GnsData, Error> {
 fn do_parse_gns(i: &str) -> IResult<&str, GnsData> {
     let (i, fix_time) = opt(parse_hms)(i)?;
     let (i, _) = char(',')(i)?;
+    #[cfg(feature = "exact-coordinates")]
+    let exact_position = parse_exact_position(i);
     let (i, lat_lon) = parse_lat_lon(i)?;
     let (i, _) = char(',')(i)?;
     let (i, faa_modes) = map_parser(take_until(","), parse_faa_modes)(i)?;
@@ -117,9 +146,9 @@ fn do_parse_gns(i: &str) -> IResult<&str, GnsData> {
     let (i, _) = char(',')(i)?;
     let (i, geoid_separation) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, _age_of_diff) = take_until(",")(i)?; // TODO parse age of diff. corr.
+    let (i, age_of_differential) = take_until(",")(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, _station_id) = take_while(|c| c != ',')(i)?;
+    let (i, dgps_station_id) = take_while(|c| c != ',')(i)?;
     let (i, nav_status) = opt(preceded(char(','), one_of("SCUV")))(i)?;
     let nav_status = nav_status.map(|ch| match ch {
         'S' => NavigationStatus::Safe,
@@ -139,7 +168,11 @@ fn do_parse_gns(i: &str) -> IResult<&str, GnsData> {
             hdop,
             alt,
             geoid_separation,
+            age_of_differential: parse_float_num(age_of_differential).ok(),
+            dgps_station_id: parse_num(dgps_station_id).ok(),
             nav_status,
+            #[cfg(feature = "exact-coordinates")]
+            exact_position,
         },
     ))
 }
@@ -152,6 +185,7 @@ mod tests {
     use crate::parse::parse_nmea_sentence;
 
     #[test]
+    #[cfg_attr(feature = "f32-only", allow(clippy::excessive_precision))]
     fn test_parse_gns() {
         let s = parse_nmea_sentence("$GPGNS,224749.00,3333.4268304,N,11153.3538273,W,D,19,0.6,406.110,-26.294,6.0,0138,S,*46").unwrap();
         assert_eq!(s.checksum, s.calc_checksum());
@@ -167,6 +201,8 @@ mod tests {
         assert_relative_eq!(0.6, gns_data.hdop.unwrap());
         assert_relative_eq!(406.110, gns_data.alt.unwrap());
         assert_relative_eq!(-26.294, gns_data.geoid_separation.unwrap());
+        assert_relative_eq!(6.0, gns_data.age_of_differential.unwrap());
+        assert_eq!(Some(138), gns_data.dgps_station_id);
         assert_eq!(Some(NavigationStatus::Safe), gns_data.nav_status);
     }
 }
@@ -14,6 +14,7 @@ macro_rules! define_enum_with_count {
         #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
         #[derive(PartialEq, Debug, Hash, Eq, Clone, Copy)]
         #[repr(u8)]
+        #[non_exhaustive]
         pub enum $Name {
             $($Variant),*
         }
@@ -44,6 +45,27 @@ define_enum_with_count!(
     }
 );
 
+impl GnssType {
+    /// The constellations in the order of their NMEA 4.11 System IDs,
+    /// which is also the order of the GNS mode indicators.
+    pub(crate) const SYSTEM_ID_ORDER: [GnssType; 6] = [
+        GnssType::Gps,
+        GnssType::Glonass,
+        GnssType::Galileo,
+        GnssType::Beidou,
+        GnssType::Qzss,
+        GnssType::NavIC,
+    ];
+
+    /// Returns the constellation of an NMEA 4.10+ System ID, as found in GSA
+    /// and GBS sentences: 1 GPS, 2 GLONASS, 3 Galileo, 4 BeiDou, 5 QZSS and
+    /// 6 NavIC.
+    pub fn from_system_id(system_id: u8) -> Option<Self> {
+        let index = usize::from(system_id).checked_sub(1)?;
+        Self::SYSTEM_ID_ORDER.get(index).copied()
+    }
+}
+
 impl fmt::Display for GnssType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -56,3 +78,58 @@ impl fmt::Display for GnssType {
         }
     }
 }
+
+define_enum_with_count!(
+    /// Carrier frequency band of a GNSS signal.
+    enum FrequencyBand {
+        /// L1 / E1 / B1 / G1 band, around 1575 MHz (1561 MHz for B1I,
+        /// 1602 MHz for GLONASS G1).
+        L1,
+        /// L2 / G2 band, around 1227 MHz (1246 MHz for GLONASS G2).
+        L2,
+        /// L5 / E5a / B2a band, 1176 MHz, including the Galileo E5 and
+        /// BeiDou B2 wide-band signals.
+        L5,
+        /// E5b / B2I / B2b band, 1207 MHz.
+        E5b,
+        /// L6 / E6 / B3 band, around 1270 MHz.
+        L6,
+        /// NavIC S band, 2492 MHz.
+        S,
+    }
+);
+
+impl FrequencyBand {
+    /// Returns the band of an NMEA 4.10+ Signal ID (as found in GSV
+    /// sentences) for the given constellation.
+    ///
+    /// Signal ID `0` (all signals) and unknown IDs return `None`.
+    pub fn from_signal_id(gnss_type: GnssType, signal_id: u8) -> Option<Self> {
+        use FrequencyBand::*;
+
+        let band = match (gnss_type, signal_id) {
+            (GnssType::Gps, 1..=3) => L1,
+            (GnssType::Gps, 4..=6) => L2,
+            (GnssType::Gps, 7..=8) => L5,
+            (GnssType::Glonass, 1..=2) => L1,
+            (GnssType::Glonass, 3..=4) => L2,
+            (GnssType::Galileo, 1 | 3) => L5,
+            (GnssType::Galileo, 2) => E5b,
+            (GnssType::Galileo, 4..=5) => L6,
+            (GnssType::Galileo, 6..=7) => L1,
+            (GnssType::Beidou, 1..=4) => L1,
+            (GnssType::Beidou, 5 | 7) => L5,
+            (GnssType::Beidou, 6 | 0xB..=0xC) => E5b,
+            (GnssType::Beidou, 8..=0xA) => L6,
+            (GnssType::Qzss, 1..=4) => L1,
+            (GnssType::Qzss, 5..=6) => L2,
+            (GnssType::Qzss, 7..=8) => L5,
+            (GnssType::Qzss, 9..=0xA) => L6,
+            (GnssType::NavIC, 1 | 3) => L5,
+            (GnssType::NavIC, 2 | 4) => S,
+            (GnssType::NavIC, 5) => L1,
+            _ => return None,
+        };
+        Some(band)
+    }
+}
@@ -3,27 +3,37 @@ use nom::{
     branch::alt,
     bytes::complete::take_while1,
     character::complete::{char, one_of},
-    combinator::{all_consuming, opt, value},
+    combinator::{all_consuming, cond, opt, value},
     error::{ErrorKind, ParseError},
     number::complete::float,
-    sequence::terminated,
+    sequence::{preceded, terminated},
     Err, IResult, InputLength, Parser,
 };
 
-use crate::{parse::NmeaSentence, sentences::utils::number, Error, SentenceType};
+use crate::{
+    parse::NmeaSentence,
+    sentences::{utils::number, GnssType},
+    Error, SatId, SentenceType,
+};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum GsaMode1 {
+    /// Forced to operate in 2D or 3D mode
     Manual,
+    /// Switches automatically between 2D and 3D, the default, as it is the
+    /// mode of nearly all receivers
+    #[default]
     Automatic,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum GsaMode2 {
+    /// The default, as nothing is known about the fix
+    #[default]
     NoFix,
     Fix2D,
     Fix3D,
@@ -40,7 +50,8 @@ pub enum GsaMode2 {
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
+#[non_exhaustive]
 pub struct GsaData {
     pub mode1: GsaMode1,
     pub mode2: GsaMode2,
@@ -48,6 +59,54 @@ pub struct GsaData {
     pub pdop: Option<f32>,
     pub hdop: Option<f32>,
     pub vdop: Option<f32>,
+    /// Constellation of the satellites, from the System ID of NMEA 4.10 or
+    /// from the talker, e.g. `BD`. `None` for the `GP` and `GN` talkers
+    /// without System ID, whose satellites can be of several constellations.
+    pub gnss_type: Option<GnssType>,
+}
+
+accessors!(GsaData {
+    mode1, with_mode1: GsaMode1;
+    mode2, with_mode2: GsaMode2;
+    pdop, with_pdop: Option<f32>;
+    hdop, with_hdop: Option<f32>;
+    vdop, with_vdop: Option<f32>;
+    gnss_type, with_gnss_type: Option<GnssType>;
+} ref {
+    fix_sats_prn, with_fix_sats_prn: Vec<u32, 18>;
+});
+
+impl GsaData {
+    /// Returns the IDs of the satellites used for the fix.
+    ///
+    /// Without a [`gnss_type`](Self::gnss_type), the constellation is
+    /// inferred from the NMEA PRN ranges, see [`SatId::from_nmea_prn()`].
+    /// Otherwise, PRNs above 64 are still NMEA PRNs, and the others are the
+    /// satellite IDs of the constellation, e.g. BeiDou 1-63 in `BDGSA`.
+    ///
+    /// SBAS satellites and PRNs outside of the known ranges are skipped.
+    pub fn sat_ids(&self) -> impl Iterator<Item = SatId> + '_ {
+        self.fix_sats_prn.iter().filter_map(move |&prn| {
+            match (self.gnss_type, SatId::from_nmea_prn(prn)) {
+                (None, id) => id,
+                (Some(gnss_type), Some(id)) if id.gnss_type() == gnss_type || prn > 64 => Some(id),
+                (Some(GnssType::Gps), _) if SatId::is_sbas_prn(prn) => None,
+                (Some(gnss_type), _) => Some(SatId::new(gnss_type, prn)),
+            }
+        })
+    }
+}
+
+/// Returns the constellation of the satellites of a GSA talker.
+fn talker_gnss_type(talker_id: &str) -> Option<GnssType> {
+    match talker_id {
+        "GA" => Some(GnssType::Galileo),
+        "GL" => Some(GnssType::Glonass),
+        "BD" | "GB" => Some(GnssType::Beidou),
+        "GI" => Some(GnssType::NavIC),
+        "GQ" | "PQ" | "QZ" => Some(GnssType::Qzss),
+        _ => None,
+    }
 }
 
 /// This function is take from `nom`, see `nom::multi::many0`
@@ -85,7 +144,13 @@ fn gsa_prn_fields_parse(i: &str) -> IResult<&str, Vec<Option<u32>, 18>> {
     many0(terminated(opt(number::<u32>), char(',')))(i)
 }
 
-type GsaTail = (Vec<Option<u32>, 18>, Option<f32>, Option<f32>, Option<f32>);
+type GsaTail = (
+    Vec<Option<u32>, 18>,
+    Option<f32>,
+    Option<f32>,
+    Option<f32>,
+    Option<u8>,
+);
 
 fn do_parse_gsa_tail(i: &str) -> IResult<&str, GsaTail> {
     let (i, prns) = gsa_prn_fields_parse(i)?;
@@ -94,7 +159,8 @@ fn do_parse_gsa_tail(i: &str) -> IResult<&str, GsaTail> {
     let (i, hdop) = float(i)?;
     let (i, _) = char(',')(i)?;
     let (i, vdop) = float(i)?;
-    Ok((i, (prns, Some(pdop), Some(hdop), Some(vdop))))
+    let (i, system_id) = opt(preceded(char(','), number::<u8>))(i)?;
+    Ok((i, (prns, Some(pdop), Some(hdop), Some(vdop), system_id)))
 }
 
 fn is_comma(x: char) -> bool {
@@ -103,17 +169,70 @@ fn is_comma(x: char) -> bool {
 
 fn do_parse_empty_gsa_tail(i: &str) -> IResult<&str, GsaTail> {
     value(
-        (Vec::new(), None, None, None),
+        (Vec::new(), None, None, None, None),
         all_consuming(take_while1(is_comma)),
     )(i)
 }
 
-fn do_parse_gsa(i: &str) -> IResult<&str, GsaData> {
+/// Number of PRN fields of the standard.
+const GSA_PRN_FIELDS: usize = 12;
+
+/// Parses any prefix of the PRN and DOP fields, filling the missing DOPs
+/// with `None`.
+///
+/// The DOPs are located from the end: the last field of a sentence with the
+/// 12 PRN fields, the DOPs and a fourth field is the System ID of NMEA 4.11,
+/// and the three fields before it, or the last three fields of longer
+/// sentences without System ID, are the DOPs. Sentences truncated after
+/// the 12 PRN fields have the DOPs which are left. In shorter sentences, the
+/// DOPs start at the first field with a decimal point, so DOPs without one
+/// are only found in the former cases.
+fn do_parse_truncated_gsa_tail(i: &str) -> IResult<&str, GsaTail> {
+    let mut fields_count = i.split(',').count();
+    let last = i.rsplit(',').next().unwrap_or_default();
+    let system_id = (fields_count == GSA_PRN_FIELDS + 4 && last.len() == 1)
+        .then(|| u8::from_str_radix(last, 16).ok())
+        .flatten()
+        .filter(|&id| id > 0);
+    if system_id.is_some() {
+        fields_count -= 1;
+    }
+    let dops_start = if fields_count >= GSA_PRN_FIELDS + 3 {
+        fields_count - 3
+    } else if fields_count > GSA_PRN_FIELDS {
+        GSA_PRN_FIELDS
+    } else {
+        i.split(',')
+            .position(|field| field.contains('.'))
+            .unwrap_or(fields_count)
+    };
+
+    let mut prns = Vec::<Option<u32>, 18>::new();
+    let mut dops = Vec::<Option<f32>, 3>::new();
+    for (index, field) in i.split(',').take(fields_count).enumerate() {
+        if index >= dops_start {
+            let dop = cond(!field.is_empty(), all_consuming(float))(field)?.1;
+            dops.push(dop)
+                .map_err(|_| Err::Error(nom::error::Error::new(field, ErrorKind::TooLarge)))?;
+        } else {
+            let prn = cond(!field.is_empty(), all_consuming(number::<u32>))(field)?.1;
+            prns.push(prn)
+                .map_err(|_| Err::Error(nom::error::Error::new(field, ErrorKind::TooLarge)))?;
+        }
+    }
+    let dop = |index: usize| dops.get(index).copied().flatten();
+    Ok(("", (prns, dop(0), dop(1), dop(2), system_id)))
+}
+
+fn do_parse_gsa_with<'a>(
+    i: &'a str,
+    mut tail_parser: impl Parser<&'a str, GsaTail, nom::error::Error<&'a str>>,
+) -> IResult<&'a str, GsaData> {
     let (i, mode1) = one_of("MA")(i)?;
     let (i, _) = char(',')(i)?;
     let (i, mode2) = one_of("123")(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, mut tail) = alt((do_parse_empty_gsa_tail, do_parse_gsa_tail))(i)?;
+    let (i, mut tail) = tail_parser.parse(i)?;
     Ok((
         i,
         GsaData {
@@ -141,10 +260,15 @@ fn do_parse_gsa(i: &str) -> IResult<&str, GsaData> {
             pdop: tail.1,
             hdop: tail.2,
             vdop: tail.3,
+            gnss_type: tail.4.and_then(GnssType::from_system_id),
         },
     ))
 }
 
+fn do_parse_gsa(i: &str) -> IResult<&str, GsaData> {
+    do_parse_gsa_with(i, alt((do_parse_empty_gsa_tail, do_parse_gsa_tail)))
+}
+
 /// # Parse GSA message
 ///
 /// From gpsd:
@@ -199,7 +323,37 @@ pub fn parse_gsa(sentence: NmeaSentence) -> Result<GsaData, Error> {
             found: sentence.message_id,
         })
     } else {
-        Ok(do_parse_gsa(sentence.data)?.1)
+        let mut data = do_parse_gsa(sentence.data)?.1;
+        data.gnss_type = data.gnss_type.or(talker_gnss_type(sentence.talker_id));
+        Ok(data)
+    }
+}
+
+/// Parses a GSA sentence like [`parse_gsa()`], also accepting sentences
+/// truncated after the modes, e.g. with PRNs but without DOPs, as sent by
+/// the i.Trek M3 and some SiRFstarIII receivers.
+///
+/// Missing PRNs are left out and missing DOPs are `None`. Enabled in
+/// [`Nmea`](crate::Nmea) with [`Quirks::lenient_gsa`](crate::Quirks::lenient_gsa).
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{parse_nmea_sentence, sentences::gsa::parse_gsa_lenient};
+///
+/// let sentence = parse_nmea_sentence("$GPGSA,A,3,19,28*32").unwrap();
+/// let gsa = parse_gsa_lenient(sentence).unwrap();
+/// assert_eq!(gsa.fix_sats_prn, [19, 28]);
+/// assert_eq!(gsa.pdop, None);
+/// ```
+pub fn parse_gsa_lenient(sentence: NmeaSentence) -> Result<GsaData, Error> {
+    match parse_gsa(sentence) {
+        Err(Error::ParsingError(_)) => {
+            let mut data = do_parse_gsa_with(sentence.data, do_parse_truncated_gsa_tail)?.1;
+            data.gnss_type = data.gnss_type.or(talker_gnss_type(sentence.talker_id));
+            Ok(data)
+        }
+        result => result,
     }
 }
 
@@ -232,6 +386,7 @@ mod tests {
                 pdop: Some(3.6),
                 hdop: Some(2.1),
                 vdop: Some(2.2),
+                gnss_type: None,
             },
             gsa
         );
@@ -249,4 +404,101 @@ mod tests {
             parse_gsa(s).unwrap();
         }
     }
+
+    #[test]
+    fn test_sat_ids() {
+        use crate::sentences::GnssType;
+
+        let s = parse_nmea_sentence("$GNGSA,A,3,31,46,75,,,,,,,,,,3.77,2.55,2.77*1D").unwrap();
+        let gsa = parse_gsa(s).unwrap();
+        let ids: Vec<SatId, 18> = gsa.sat_ids().collect();
+        assert_eq!(
+            ids,
+            [
+                SatId::new(GnssType::Gps, 31),
+                SatId::new(GnssType::Glonass, 11)
+            ]
+        );
+        assert!(SatId::is_sbas_prn(46));
+
+        // NMEA 4.10 System ID: BeiDou
+        let s = parse_nmea_sentence("$GNGSA,A,3,05,40,214,,,,,,,,,,1.8,1.1,1.4,4*0E").unwrap();
+        let gsa = parse_gsa(s).unwrap();
+        assert_eq!(gsa.gnss_type, Some(GnssType::Beidou));
+        let ids: Vec<SatId, 18> = gsa.sat_ids().collect();
+        assert_eq!(
+            ids,
+            [
+                SatId::new(GnssType::Beidou, 5),
+                SatId::new(GnssType::Beidou, 40),
+                SatId::new(GnssType::Beidou, 14)
+            ]
+        );
+
+        // talker of the constellation
+        let s = parse_nmea_sentence("$GIGSA,A,3,02,03,,,,,,,,,,,1.8,1.1,1.4*26").unwrap();
+        let gsa = parse_gsa(s).unwrap();
+        assert_eq!(gsa.gnss_type, Some(GnssType::NavIC));
+        assert_eq!(gsa.sat_ids().next(), Some(SatId::new(GnssType::NavIC, 2)));
+    }
+
+    #[test]
+    fn test_parse_gsa_lenient() {
+        let parse = |line| parse_gsa_lenient(parse_nmea_sentence(line).unwrap());
+        // i.Trek M3 without fix
+        let gsa = parse("$GPGSA,A,1,,,,*32").unwrap();
+        assert_eq!(gsa.mode2, GsaMode2::NoFix);
+        assert!(gsa.fix_sats_prn.is_empty());
+        assert_eq!(gsa.pdop, None);
+
+        // PRNs without DOPs
+        let gsa = parse("$GPGSA,A,3,19,28*32").unwrap();
+        assert_eq!(gsa.fix_sats_prn, [19, 28]);
+        assert_eq!((gsa.pdop, gsa.hdop, gsa.vdop), (None, None, None));
+
+        // DOPs without PRNs
+        let gsa = parse("$GPGSA,A,3,1.8,1.1,1.4*3E").unwrap();
+        assert!(gsa.fix_sats_prn.is_empty());
+        assert_eq!(
+            (gsa.pdop, gsa.hdop, gsa.vdop),
+            (Some(1.8), Some(1.1), Some(1.4))
+        );
+
+        // some PRNs and the PDOP only
+        let gsa = parse("$GPGSA,A,3,23,31,22,2.5*1A").unwrap();
+        assert_eq!(gsa.fix_sats_prn, [23, 31, 22]);
+        assert_eq!((gsa.pdop, gsa.hdop), (Some(2.5), None));
+
+        // all PRN fields but no VDOP
+        let gsa = parse("$GPGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1*39").unwrap();
+        assert_eq!(gsa.fix_sats_prn, [23, 31, 22, 16, 3, 7]);
+        assert_eq!((gsa.pdop, gsa.hdop, gsa.vdop), (Some(1.8), Some(1.1), None));
+
+        // standard sentences with an empty PDOP are aligned at the end
+        let gsa = parse("$GPGSA,A,3,23,31,,,,,,,,,,,,1.1,1.4*1A").unwrap();
+        assert_eq!(gsa.fix_sats_prn, [23, 31]);
+        assert_eq!((gsa.pdop, gsa.hdop, gsa.vdop), (None, Some(1.1), Some(1.4)));
+
+        // DOPs without decimal point after the PRN fields
+        let gsa = parse("$GPGSA,A,3,23,31,,,,,,,,,,,2,1*30").unwrap();
+        assert_eq!(gsa.fix_sats_prn, [23, 31]);
+        assert_eq!((gsa.pdop, gsa.hdop, gsa.vdop), (Some(2.), Some(1.), None));
+
+        // System ID of NMEA 4.11 after the DOPs
+        let gsa = parse("$GNGSA,A,3,23,31,,,,,,,,,,,2,1,1.4,3*36").unwrap();
+        assert_eq!(gsa.fix_sats_prn, [23, 31]);
+        assert_eq!(
+            (gsa.pdop, gsa.hdop, gsa.vdop),
+            (Some(2.), Some(1.), Some(1.4))
+        );
+        assert_eq!(gsa.gnss_type, Some(GnssType::Galileo));
+
+        // valid sentences are parsed like `parse_gsa()`
+        let line = "$GPGSA,A,3,,,,,,16,18,,22,24,,,3.6,2.1,2.2*3C";
+        assert_eq!(parse(line), parse_gsa(parse_nmea_sentence(line).unwrap()));
+
+        assert!(parse("$GPGSA,A,3,23,X1*58").is_err());
+        assert!(parse("$GPGSA,A,1,,,,*32").is_ok());
+        assert!(parse_gsa(parse_nmea_sentence("$GPGSA,A,3,19,28*32").unwrap()).is_err());
+    }
 }
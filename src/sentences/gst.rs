@@ -1,6 +1,9 @@
 use crate::{parse::NmeaSentence, sentences::utils::parse_hms, Error, SentenceType};
 use chrono::NaiveTime;
 use nom::{character::complete::char, combinator::opt, number::complete::float, IResult};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
 
 /// GST - GPS Pseudorange Noise Statistics
 /// ```text
@@ -19,10 +22,10 @@ use nom::{character::complete::char, combinator::opt, number::complete::float, I
 /// 7. Standard deviation (meters) of longitude error
 /// 8. Standard deviation (meters) of altitude error
 /// 9. Checksum
-
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct GstData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub time: Option<NaiveTime>,
@@ -35,6 +38,77 @@ pub struct GstData {
     pub alt_sd: Option<f32>,
 }
 
+accessors!(GstData {
+    time, with_time: Option<NaiveTime>;
+    rms_sd, with_rms_sd: Option<f32>;
+    ellipse_semi_major_sd, with_ellipse_semi_major_sd: Option<f32>;
+    ellipse_semi_minor_sd, with_ellipse_semi_minor_sd: Option<f32>;
+    err_ellipse_orientation, with_err_ellipse_orientation: Option<f32>;
+    lat_sd, with_lat_sd: Option<f32>;
+    long_sd, with_long_sd: Option<f32>;
+    alt_sd, with_alt_sd: Option<f32>;
+});
+
+impl GstData {
+    /// Returns the horizontal position covariance in m², as the matrix
+    /// `[[north, north-east], [north-east, east]]`, e.g. for the measurement
+    /// noise of a Kalman filter.
+    ///
+    /// It is computed from the error ellipse, rotated from its orientation
+    /// (clockwise from true north), or from the latitude and longitude
+    /// standard deviations, without correlation, when the ellipse is
+    /// missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_nmea_sentence, sentences::parse_gst};
+    ///
+    /// let sentence = parse_nmea_sentence("$GPGST,182141.000,15.5,2.0,1.0,90.0,,,0.8*6B").unwrap();
+    /// let gst = parse_gst(sentence).unwrap();
+    /// // the semi-major axis points east
+    /// let [[north, north_east], [_, east]] = gst.horizontal_covariance().unwrap();
+    /// assert_eq!(north, 1.0);
+    /// assert_eq!(east, 4.0);
+    /// assert!(north_east.abs() < 1e-6);
+    /// ```
+    pub fn horizontal_covariance(&self) -> Option<[[f32; 2]; 2]> {
+        let ellipse = self
+            .ellipse_semi_major_sd
+            .zip(self.ellipse_semi_minor_sd)
+            .zip(self.err_ellipse_orientation);
+        let Some(((major, minor), orientation)) = ellipse else {
+            let (lat_sd, long_sd) = self.lat_sd.zip(self.long_sd)?;
+            return Some([[lat_sd * lat_sd, 0.], [0., long_sd * long_sd]]);
+        };
+        let (major, minor) = (f64::from(major), f64::from(minor));
+        let (sin, cos) = f64::from(orientation).to_radians().sin_cos();
+        // a² - b² as a product, without the cancellation of close axes
+        let difference = (major - minor) * (major + minor);
+        let north = minor * minor + difference * cos * cos;
+        let east = minor * minor + difference * sin * sin;
+        let north_east = difference * sin * cos;
+        Some([
+            [north as f32, north_east as f32],
+            [north_east as f32, east as f32],
+        ])
+    }
+
+    /// Returns the standard deviation of the horizontal position in meters,
+    /// the square root of the trace of the
+    /// [covariance](Self::horizontal_covariance).
+    pub fn horizontal_sd(&self) -> Option<f32> {
+        let [[north, _], [_, east]] = self.horizontal_covariance()?;
+        Some((north + east).sqrt())
+    }
+
+    /// Returns the combined standard deviation of the 3D position in
+    /// meters, of the horizontal position and the altitude.
+    pub fn position_sd(&self) -> Option<f32> {
+        Some(self.horizontal_sd()?.hypot(self.alt_sd?))
+    }
+}
+
 fn do_parse_gst(i: &str) -> IResult<&str, GstData> {
     let (i, time) = opt(parse_hms)(i)?;
     let (i, _) = char(',')(i)?;
@@ -86,10 +160,12 @@ pub fn parse_gst(sentence: NmeaSentence) -> Result<GstData, Error> {
 
 #[cfg(test)]
 mod tests {
+    use approx::assert_relative_eq;
+
     use super::*;
     use crate::{parse::parse_nmea_sentence, Error};
 
-    fn run_parse_gst(line: &str) -> Result<GstData, Error> {
+    fn run_parse_gst(line: &str) -> Result<GstData, Error<'_>> {
         let s = parse_nmea_sentence(line).expect("GST sentence initial parse failed");
         assert_eq!(s.checksum, s.calc_checksum());
         parse_gst(s)
@@ -124,4 +200,38 @@ mod tests {
             run_parse_gst("$GPGST,,,,,,,,*57").unwrap()
         );
     }
+
+    #[test]
+    fn test_covariance() {
+        // tan(21.8°) = 0.4, so cos² = 1 / 1.16 and sin² = 0.16 / 1.16
+        let gst = run_parse_gst("$GPGST,182141.000,15.5,15.3,7.2,21.8,0.9,0.5,0.8*54").unwrap();
+        let [[north, north_east], [east_north, east]] = gst.horizontal_covariance().unwrap();
+        // 15.3² / 1.16 + 7.2² * 0.16 / 1.16
+        assert_relative_eq!(north, 208.952, epsilon = 1e-2);
+        // 15.3² * 0.16 / 1.16 + 7.2² / 1.16
+        assert_relative_eq!(east, 76.978, epsilon = 1e-2);
+        // (15.3² - 7.2²) * 0.4 / 1.16
+        assert_relative_eq!(north_east, 62.845, epsilon = 1e-2);
+        assert_eq!(north_east, east_north);
+        // √(15.3² + 7.2²)
+        assert_relative_eq!(gst.horizontal_sd().unwrap(), 16.9095, epsilon = 1e-3);
+        // √(15.3² + 7.2² + 0.8²)
+        assert_relative_eq!(gst.position_sd().unwrap(), 16.9284, epsilon = 1e-3);
+
+        // a circle has no correlation, whatever its orientation
+        let gst = run_parse_gst("$GPGST,182141.000,15.5,3.0,3.0,33.0,,,*47").unwrap();
+        let [[north, north_east], [_, east]] = gst.horizontal_covariance().unwrap();
+        assert_eq!((north, north_east, east), (9., 0., 9.));
+        assert_eq!(gst.position_sd(), None);
+
+        // without ellipse, from the latitude and longitude errors
+        let gst = run_parse_gst("$GPGST,182141.000,15.5,,,,0.9,0.5,0.8*73").unwrap();
+        let covariance = gst.horizontal_covariance().unwrap();
+        assert_relative_eq!(covariance[0][0], 0.81);
+        assert_relative_eq!(covariance[1][1], 0.25);
+        assert_eq!(covariance[0][1], 0.);
+
+        let gst = run_parse_gst("$GPGST,,,,,,,,*57").unwrap();
+        assert_eq!(gst.horizontal_covariance(), None);
+    }
 }
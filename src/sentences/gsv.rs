@@ -8,7 +8,7 @@ use nom::{
 use crate::{
     parse::NmeaSentence,
     sentences::{utils::number, GnssType},
-    Error, Satellite, SentenceType,
+    Error, SatId, Satellite, SentenceType,
 };
 
 /// GSV - Satellites in view
@@ -52,10 +52,12 @@ use crate::{
 /// `Signal ID`, just before the checksum. See the description of `Signal ID`'s above.
 ///
 /// Note: `$GNGSV` uses `PRN` in field 4. Other `$GxGSV` use the `satellite ID` in field 4.
-/// Jackson Labs, Quectel, Telit, and others get this wrong, in various conflicting ways.
+/// Jackson Labs, Quectel, Telit, and others get this wrong, in various conflicting ways,
+/// see [`SatIdPolicy`](crate::SatIdPolicy).
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct GsvData {
     pub gnss_type: GnssType,
     pub number_of_sentences: u16,
@@ -63,25 +65,60 @@ pub struct GsvData {
     pub sats_in_view: u16,
     // see SatPack in lib.rs
     pub sats_info: Vec<Option<Satellite>, 4>,
+    /// Signal ID (NMEA 4.10+), see
+    /// [`FrequencyBand::from_signal_id()`](crate::sentences::FrequencyBand::from_signal_id)
+    pub signal_id: Option<u8>,
+    /// `true` for sentences of the `GN` talker, whose satellites can be of
+    /// several constellations
+    pub combined: bool,
+}
+
+accessors!(GsvData {
+    gnss_type, with_gnss_type: GnssType;
+    number_of_sentences, with_number_of_sentences: u16;
+    sentence_num, with_sentence_num: u16;
+    sats_in_view, with_sats_in_view: u16;
+    signal_id, with_signal_id: Option<u8>;
+    combined, with_combined: bool;
+} ref {
+    sats_info, with_sats_info: Vec<Option<Satellite>, 4>;
+});
+
+impl GsvData {
+    /// A sentence of a constellation without satellites, whose fields are
+    /// then set.
+    pub fn new(gnss_type: GnssType) -> Self {
+        Self {
+            gnss_type,
+            number_of_sentences: 0,
+            sentence_num: 0,
+            sats_in_view: 0,
+            sats_info: Vec::new(),
+            signal_id: None,
+            combined: false,
+        }
+    }
 }
 
 fn parse_gsv_sat_info(i: &str) -> IResult<&str, Satellite> {
     let (i, prn) = number::<u32>(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, elevation) = opt(number::<i32>)(i)?;
+    let (i, elevation) = opt(number::<i16>)(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, azimuth) = opt(number::<i32>)(i)?;
+    let (i, azimuth) = opt(number::<i16>)(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, snr) = opt(number::<i32>)(i)?;
+    let (i, snr) = opt(number::<i16>)(i)?;
     let (i, _) = cond(rest_len(i)?.1 > 0, char(','))(i)?;
     Ok((
         i,
         Satellite {
             gnss_type: GnssType::Galileo,
             prn,
-            elevation: elevation.map(|v| v as f32),
-            azimuth: azimuth.map(|v| v as f32),
-            snr: snr.map(|v| v as f32),
+            elevation,
+            azimuth,
+            snr,
+            #[cfg(feature = "sat-rates")]
+            rates: None,
         },
     ))
 }
@@ -93,6 +130,18 @@ fn do_parse_gsv(i: &str) -> IResult<&str, GsvData> {
     let (i, _) = char(',')(i)?;
     let (i, sats_in_view) = number::<u16>(i)?;
     let (i, _) = char(',')(i)?;
+
+    // the Signal ID is a single hex digit after the last quadruple
+    let (i, signal_id) = match i.rsplit_once(',') {
+        Some((sats_data, id)) if i.split(',').count() % 4 == 1 && id.len() == 1 => {
+            match u8::from_str_radix(id, 16) {
+                Ok(id) => (sats_data, Some(id)),
+                Err(_) => (i, None),
+            }
+        }
+        _ => (i, None),
+    };
+
     let sats = Vec::<Option<Satellite>, 4>::new();
 
     // We loop through the indices and parse the satellite data
@@ -112,6 +161,8 @@ fn do_parse_gsv(i: &str) -> IResult<&str, GsvData> {
             sentence_num,
             sats_in_view,
             sats_info: sats,
+            signal_id,
+            combined: false,
         },
     ))
 }
@@ -146,12 +197,30 @@ fn do_parse_gsv(i: &str) -> IResult<&str, GsvData> {
 /// GL may be (incorrectly) used when GSVs are mixed containing
 /// GLONASS, GN may be (incorrectly) used when GSVs contain GLONASS
 /// only.  Usage is inconsistent.
+///
+/// The satellites of `GN` sentences are NMEA PRNs and are converted to
+/// satellite IDs of their constellation, see [`SatId::from_nmea_prn()`].
+/// PRNs outside of the known ranges, e.g. SBAS, are kept as GPS satellites.
+/// The sentence's [`GsvData::gnss_type`] is the constellation of its first
+/// satellite.
 pub fn parse_gsv(sentence: NmeaSentence) -> Result<GsvData, Error> {
     if sentence.message_id != SentenceType::GSV {
         Err(Error::WrongSentenceHeader {
             expected: SentenceType::GSV,
             found: sentence.message_id,
         })
+    } else if sentence.talker_id == "GN" {
+        let mut res = do_parse_gsv(sentence.data)?.1;
+        let mut gnss_type = None;
+        for sat in res.sats_info.iter_mut().flatten() {
+            let id = SatId::from_nmea_prn(sat.prn).unwrap_or(SatId::new(GnssType::Gps, sat.prn));
+            sat.gnss_type = id.gnss_type();
+            sat.prn = id.svid();
+            gnss_type.get_or_insert(id.gnss_type());
+        }
+        res.gnss_type = gnss_type.ok_or(Error::UnknownGnssType(sentence.talker_id))?;
+        res.combined = true;
+        Ok(res)
     } else {
         let gnss_type = match sentence.talker_id {
             "GA" => GnssType::Galileo,
@@ -159,7 +228,7 @@ pub fn parse_gsv(sentence: NmeaSentence) -> Result<GsvData, Error> {
             "GL" => GnssType::Glonass,
             "BD" | "GB" => GnssType::Beidou,
             "GI" => GnssType::NavIC,
-            "PQ" | "QZ" => GnssType::Qzss,
+            "GQ" | "PQ" | "QZ" => GnssType::Qzss,
             _ => return Err(Error::UnknownGnssType(sentence.talker_id)),
         };
         let mut res = do_parse_gsv(sentence.data)?.1;
@@ -177,6 +246,32 @@ pub fn parse_gsv(sentence: NmeaSentence) -> Result<GsvData, Error> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_gn_gsv() {
+        let data = parse_gsv(NmeaSentence {
+            talker_id: "GN",
+            message_id: SentenceType::GSV,
+            data: "1,1,03,70,40,083,46,05,17,308,41,305,07,344,39",
+            checksum: 0,
+        })
+        .unwrap();
+        assert_eq!(data.gnss_type, GnssType::Glonass);
+        let ids: Vec<_, 4> = data
+            .sats_info
+            .iter()
+            .flatten()
+            .map(Satellite::sat_id)
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                SatId::new(GnssType::Glonass, 6),
+                SatId::new(GnssType::Gps, 5),
+                SatId::new(GnssType::Galileo, 5)
+            ]
+        );
+    }
+
     #[test]
     fn test_parse_gsv_full() {
         let data = parse_gsv(NmeaSentence {
@@ -196,8 +291,10 @@ mod tests {
                 gnss_type: data.gnss_type,
                 prn: 1,
                 elevation: None,
-                azimuth: Some(83.),
-                snr: Some(46.),
+                azimuth: Some(83),
+                snr: Some(46),
+                #[cfg(feature = "sat-rates")]
+                rates: None,
             }
         );
         assert_eq!(
@@ -205,9 +302,11 @@ mod tests {
             Satellite {
                 gnss_type: data.gnss_type,
                 prn: 2,
-                elevation: Some(17.),
-                azimuth: Some(308.),
+                elevation: Some(17),
+                azimuth: Some(308),
                 snr: None,
+                #[cfg(feature = "sat-rates")]
+                rates: None,
             }
         );
         assert_eq!(
@@ -215,9 +314,11 @@ mod tests {
             Satellite {
                 gnss_type: data.gnss_type,
                 prn: 12,
-                elevation: Some(7.),
-                azimuth: Some(344.),
-                snr: Some(39.),
+                elevation: Some(7),
+                azimuth: Some(344),
+                snr: Some(39),
+                #[cfg(feature = "sat-rates")]
+                rates: None,
             }
         );
         assert_eq!(
@@ -225,9 +326,11 @@ mod tests {
             Satellite {
                 gnss_type: data.gnss_type,
                 prn: 14,
-                elevation: Some(22.),
-                azimuth: Some(228.),
+                elevation: Some(22),
+                azimuth: Some(228),
                 snr: None,
+                #[cfg(feature = "sat-rates")]
+                rates: None,
             }
         );
 
@@ -243,4 +346,44 @@ mod tests {
         assert_eq!(data.sentence_num, 3);
         assert_eq!(data.sats_in_view, 10);
     }
+
+    #[test]
+    fn test_parse_gsv_signal_id() {
+        let data = parse_gsv(NmeaSentence {
+            talker_id: "GA",
+            message_id: SentenceType::GSV,
+            data: "3,3,10,04,25,170,38,10,57,208,,7",
+            checksum: 0,
+        })
+        .unwrap();
+        assert_eq!(data.signal_id, Some(7));
+        assert_eq!(data.sats_info.iter().flatten().count(), 2);
+        assert_eq!(data.sats_info[1].as_ref().unwrap().snr, None);
+
+        let data = parse_gsv(NmeaSentence {
+            talker_id: "GB",
+            message_id: SentenceType::GSV,
+            data: "1,1,04,01,40,083,46,02,17,308,41,12,07,344,39,14,22,228,45,B",
+            checksum: 0,
+        })
+        .unwrap();
+        assert_eq!(data.signal_id, Some(0xB));
+        assert_eq!(data.sats_info[3].as_ref().unwrap().snr, Some(45));
+
+        let data = parse_gsv(NmeaSentence {
+            talker_id: "GL",
+            message_id: SentenceType::GSV,
+            data: "3,3,09,88,07,028",
+            checksum: 0,
+        })
+        .unwrap();
+        assert_eq!(data.signal_id, None);
+    }
+
+    #[test]
+    #[cfg(not(feature = "sat-rates"))]
+    fn test_satellite_size() {
+        // four satellites are moved with every `GsvData` and `ParseResult`
+        assert_eq!(core::mem::size_of::<Satellite>(), 20);
+    }
 }
@@ -22,12 +22,17 @@ use crate::{Error, NmeaSentence, SentenceType};
 /// 3. Checksum
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct HdtData {
     /// Heading, degrees True
     pub heading: Option<f32>,
 }
 
+accessors!(HdtData {
+    heading, with_heading: Option<f32>;
+});
+
 /// # Parse HDT message
 ///
 /// From gpsd/driver_nmea0183.c
@@ -1,6 +1,12 @@
-use nom::{character::complete::char, combinator::opt, number::complete::float, IResult};
+use nom::{
+    character::complete::{char, one_of},
+    combinator::opt,
+    number::complete::float,
+    sequence::preceded,
+    IResult,
+};
 
-use crate::{parse::NmeaSentence, Error, SentenceType};
+use crate::{parse::NmeaSentence, sentences::TemperatureUnit, Error, SentenceType};
 
 /// MDA - Meterological Composite
 ///
@@ -13,7 +19,8 @@ use crate::{parse::NmeaSentence, Error, SentenceType};
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct MdaData {
     /// Pressure in inches of mercury
     pub pressure_in_hg: Option<f32>,
@@ -21,14 +28,20 @@ pub struct MdaData {
     pub pressure_bar: Option<f32>,
     /// Air temp, deg celsius
     pub air_temp_deg: Option<f32>,
+    /// Unit letter of the air temp, converted to celsius
+    pub air_temp_unit: Option<TemperatureUnit>,
     /// Water temp, deg celsius
     pub water_temp_deg: Option<f32>,
+    /// Unit letter of the water temp, converted to celsius
+    pub water_temp_unit: Option<TemperatureUnit>,
     /// Relative humidity, percent
     pub rel_humidity: Option<f32>,
     /// Absolute humidity, percent
     pub abs_humidity: Option<f32>,
     /// Dew point, degrees celsius
     pub dew_point: Option<f32>,
+    /// Unit letter of the dew point, converted to celsius
+    pub dew_point_unit: Option<TemperatureUnit>,
     /// True Wind Direction, NED degrees
     pub wind_direction_true: Option<f32>,
     /// Magnetic Wind Direction, NED degrees
@@ -39,6 +52,23 @@ pub struct MdaData {
     pub wind_speed_ms: Option<f32>,
 }
 
+accessors!(MdaData {
+    pressure_in_hg, with_pressure_in_hg: Option<f32>;
+    pressure_bar, with_pressure_bar: Option<f32>;
+    air_temp_deg, with_air_temp_deg: Option<f32>;
+    air_temp_unit, with_air_temp_unit: Option<TemperatureUnit>;
+    water_temp_deg, with_water_temp_deg: Option<f32>;
+    water_temp_unit, with_water_temp_unit: Option<TemperatureUnit>;
+    rel_humidity, with_rel_humidity: Option<f32>;
+    abs_humidity, with_abs_humidity: Option<f32>;
+    dew_point, with_dew_point: Option<f32>;
+    dew_point_unit, with_dew_point_unit: Option<TemperatureUnit>;
+    wind_direction_true, with_wind_direction_true: Option<f32>;
+    wind_direction_magnetic, with_wind_direction_magnetic: Option<f32>;
+    wind_speed_knots, with_wind_speed_knots: Option<f32>;
+    wind_speed_ms, with_wind_speed_ms: Option<f32>;
+});
+
 /// # Parse MDA message
 ///
 /// Information from mda:
@@ -71,6 +101,11 @@ pub struct MdaData {
 /// 20. M
 /// 21. *16        Mandatory NMEA checksum
 ///
+/// Temperatures in Fahrenheit (`F`) are converted to celsius. Missing unit
+/// letters are accepted, the values are then taken as celsius.
+///
+/// Weather stations without wind sensor omit the wind fields 13 to 20, and
+/// some add vendor specific fields after them, which are ignored.
 pub fn parse_mda(sentence: NmeaSentence) -> Result<MdaData, Error> {
     if sentence.message_id != SentenceType::MDA {
         Err(Error::WrongSentenceHeader {
@@ -82,6 +117,26 @@ pub fn parse_mda(sentence: NmeaSentence) -> Result<MdaData, Error> {
     }
 }
 
+/// Parses a temperature and its unit letter, converted to celsius.
+fn do_parse_temperature(i: &str) -> IResult<&str, (Option<f32>, Option<TemperatureUnit>)> {
+    let (i, degrees) = opt(float)(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, unit) = opt(one_of("CF"))(i)?;
+    let unit = unit.map(TemperatureUnit::from_char);
+    let degrees =
+        degrees.map(|degrees| unit.unwrap_or(TemperatureUnit::Celsius).to_celsius(degrees));
+    Ok((i, (degrees, unit)))
+}
+
+/// Parses an optional `,value,unit` pair of the trailing wind fields.
+fn do_parse_wind_field(unit: char) -> impl Fn(&str) -> IResult<&str, Option<f32>> {
+    move |i| {
+        let (i, value) = opt(preceded(char(','), opt(float)))(i)?;
+        let (i, _) = opt(preceded(char(','), opt(char(unit))))(i)?;
+        Ok((i, value.flatten()))
+    }
+}
+
 fn do_parse_mda(i: &str) -> IResult<&str, MdaData> {
     let (i, pressure_in_hg) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
@@ -91,37 +146,19 @@ fn do_parse_mda(i: &str) -> IResult<&str, MdaData> {
     let (i, _) = char(',')(i)?;
     let (i, _) = opt(char('B'))(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, air_temp_deg) = opt(float)(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, _) = opt(char('C'))(i)?;
+    let (i, (air_temp_deg, air_temp_unit)) = do_parse_temperature(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, water_temp_deg) = opt(float)(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, _) = opt(char('C'))(i)?;
+    let (i, (water_temp_deg, water_temp_unit)) = do_parse_temperature(i)?;
     let (i, _) = char(',')(i)?;
     let (i, rel_humidity) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
     let (i, abs_humidity) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, dew_point) = opt(float)(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, _) = opt(char('C'))(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, wind_direction_true) = opt(float)(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, _) = opt(char('T'))(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, wind_direction_magnetic) = opt(float)(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, _) = opt(char('M'))(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, wind_speed_knots) = opt(float)(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, _) = opt(char('N'))(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, wind_speed_ms) = opt(float)(i)?;
-    let (i, _) = char(',')(i)?;
-    let (i, _) = opt(char('M'))(i)?;
+    let (i, (dew_point, dew_point_unit)) = do_parse_temperature(i)?;
+    let (i, wind_direction_true) = do_parse_wind_field('T')(i)?;
+    let (i, wind_direction_magnetic) = do_parse_wind_field('M')(i)?;
+    let (i, wind_speed_knots) = do_parse_wind_field('N')(i)?;
+    let (i, wind_speed_ms) = do_parse_wind_field('M')(i)?;
 
     Ok((
         i,
@@ -129,10 +166,13 @@ fn do_parse_mda(i: &str) -> IResult<&str, MdaData> {
             pressure_in_hg,
             pressure_bar,
             air_temp_deg,
+            air_temp_unit,
             water_temp_deg,
+            water_temp_unit,
             rel_humidity,
             abs_humidity,
             dew_point,
+            dew_point_unit,
             wind_direction_true,
             wind_direction_magnetic,
             wind_speed_knots,
@@ -169,5 +209,55 @@ mod tests {
         assert_relative_eq!(107.7, mda_data.wind_direction_magnetic.unwrap());
         assert_relative_eq!(1.2, mda_data.wind_speed_knots.unwrap());
         assert_relative_eq!(0.6, mda_data.wind_speed_ms.unwrap());
+        assert_eq!(mda_data.air_temp_unit, Some(TemperatureUnit::Celsius));
+        assert_eq!(mda_data.water_temp_unit, None);
+    }
+
+    #[test]
+    fn test_parse_mda_fahrenheit() {
+        let s = parse_nmea_sentence(
+            "$WIMDA,29.7544,I,1.0076,B,95.9,F,64.4,,42.1,,69.08,F,116.4,T,107.7,M,1.2,N,0.6,M*4B",
+        )
+        .unwrap();
+        let mda_data = parse_mda(s).unwrap();
+        assert_relative_eq!(35.5, mda_data.air_temp_deg.unwrap(), epsilon = 1e-4);
+        assert_eq!(mda_data.air_temp_unit, Some(TemperatureUnit::Fahrenheit));
+        // without unit letter, the value is taken as celsius
+        assert_relative_eq!(64.4, mda_data.water_temp_deg.unwrap());
+        assert_eq!(mda_data.water_temp_unit, None);
+        assert_relative_eq!(20.6, mda_data.dew_point.unwrap(), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_parse_mda_without_wind() {
+        // Airmar style station without wind sensor, ending after the dew point
+        let s = parse_nmea_sentence("$WIMDA,29.9331,I,1.0136,B,21.3,C,,,55.0,,12.0,C*76").unwrap();
+        let mda_data = parse_mda(s).unwrap();
+        assert_relative_eq!(1.0136, mda_data.pressure_bar.unwrap());
+        assert_relative_eq!(12.0, mda_data.dew_point.unwrap());
+        assert_eq!(mda_data.dew_point_unit, Some(TemperatureUnit::Celsius));
+        assert!(mda_data.wind_direction_true.is_none());
+        assert!(mda_data.wind_speed_ms.is_none());
+
+        // truncated wind fields
+        let s = parse_nmea_sentence("$WIMDA,29.9331,I,1.0136,B,21.3,C,,,55.0,,12.0,C,116.4,T*0E")
+            .unwrap();
+        let mda_data = parse_mda(s).unwrap();
+        assert_relative_eq!(116.4, mda_data.wind_direction_true.unwrap());
+        assert!(mda_data.wind_direction_magnetic.is_none());
+        assert!(mda_data.wind_speed_knots.is_none());
+    }
+
+    #[test]
+    fn test_parse_mda_extra_fields() {
+        // Maretron style sentence with vendor fields after the wind fields
+        let s = parse_nmea_sentence(
+            "$WIMDA,29.9331,I,1.0136,B,21.3,C,,,55.0,,12.0,C,116.4,T,107.7,M,1.2,N,0.6,M,A,1.0*04",
+        )
+        .unwrap();
+        let mda_data = parse_mda(s).unwrap();
+        assert_relative_eq!(55.0, mda_data.rel_humidity.unwrap());
+        assert_relative_eq!(107.7, mda_data.wind_direction_magnetic.unwrap());
+        assert_relative_eq!(0.6, mda_data.wind_speed_ms.unwrap());
     }
 }
@@ -1,12 +1,13 @@
+use core::ops::{Div, Mul, Sub};
+
 use nom::{
     character::complete::{char, one_of},
-    combinator::opt,
-    number::complete::double,
-    sequence::preceded,
+    combinator::{eof, map, opt},
+    sequence::{preceded, terminated},
     IResult,
 };
 
-use crate::{parse::NmeaSentence, Error, SentenceType};
+use crate::{parse::NmeaSentence, sentences::utils::float_number, Error, Float, SentenceType};
 
 /// MTW - Mean Temperature of Water
 ///
@@ -18,22 +19,66 @@ use crate::{parse::NmeaSentence, Error, SentenceType};
 /// $--MTW,x.x,C*hh<CR><LF>
 /// ```
 /// 1:  Temperature, degrees
-/// 2:  Unit of Measurement, (only) Celsius
+/// 2:  Unit of Measurement, Celsius by the standard
 /// 3:  Mandatory NMEA checksum
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct MtwData {
-    pub temperature: Option<f64>,
+    /// Temperature in degrees Celsius, converted from [`unit`](Self::unit)
+    pub temperature: Option<Float>,
+    /// Unit of the sentence, `None` if missing, see [`parse_mtw_lenient()`]
+    pub unit: Option<TemperatureUnit>,
 }
 
+accessors!(MtwData {
+    temperature, with_temperature: Option<Float>;
+    unit, with_unit: Option<TemperatureUnit>;
+});
+
+/// Temperature unit letter, `C` by the standard, but some low-cost sensors
+/// send `F`.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum MtwUnit {
+pub enum TemperatureUnit {
     Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    pub(crate) fn from_char(unit: char) -> Self {
+        match unit {
+            'F' => TemperatureUnit::Fahrenheit,
+            _ => TemperatureUnit::Celsius,
+        }
+    }
+
+    /// Converts a temperature in this unit to degrees Celsius.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::sentences::mtw::TemperatureUnit;
+    ///
+    /// assert_eq!(TemperatureUnit::Fahrenheit.to_celsius(212.0_f32), 100.0);
+    /// assert_eq!(TemperatureUnit::Celsius.to_celsius(17.9_f32), 17.9);
+    /// ```
+    pub fn to_celsius<T>(self, degrees: T) -> T
+    where
+        T: From<f32> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+    {
+        match self {
+            TemperatureUnit::Celsius => degrees,
+            TemperatureUnit::Fahrenheit => (degrees - T::from(32.)) * T::from(5.) / T::from(9.),
+        }
+    }
 }
 
+/// Former name of [`TemperatureUnit`].
+pub type MtwUnit = TemperatureUnit;
+
 /// # Parse MTW message
 ///
 /// Information from mtw:
@@ -47,7 +92,7 @@ pub enum MtwUnit {
 ///```
 ///
 /// 1:  17.9         Temperature, degrees
-/// 2:  C            Unit of Measurement, (only) Celsius
+/// 2:  C            Unit of Measurement, Celsius (`C`) or Fahrenheit (`F`)
 /// 3:  *16          Mandatory NMEA checksum
 pub fn parse_mtw(sentence: NmeaSentence) -> Result<MtwData, Error> {
     if sentence.message_id != SentenceType::MTW {
@@ -56,23 +101,59 @@ pub fn parse_mtw(sentence: NmeaSentence) -> Result<MtwData, Error> {
             found: sentence.message_id,
         })
     } else {
-        Ok(do_parse_mtw(sentence.data)?.1)
+        Ok(do_parse_mtw(sentence.data, false)?.1)
     }
 }
 
-fn do_parse_mtw(i: &str) -> IResult<&str, MtwData> {
-    let (i, temperature_value) = opt(double)(i)?;
-    preceded(char(','), one_of("C"))(i)?;
+/// Parses a MTW sentence like [`parse_mtw()`], also accepting a missing unit
+/// letter, which is then taken as Celsius.
+///
+/// [`Nmea`](crate::Nmea) uses it with
+/// [`Quirks::lenient_mtw`](crate::Quirks::lenient_mtw).
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{parse_nmea_sentence, sentences::mtw::parse_mtw_lenient};
+///
+/// let mtw = parse_mtw_lenient(parse_nmea_sentence("$INMTW,17.9,*58").unwrap()).unwrap();
+/// assert_eq!(mtw.temperature, Some(17.9));
+/// assert_eq!(mtw.unit, None);
+/// ```
+pub fn parse_mtw_lenient(sentence: NmeaSentence) -> Result<MtwData, Error> {
+    if sentence.message_id != SentenceType::MTW {
+        Err(Error::WrongSentenceHeader {
+            expected: SentenceType::MTW,
+            found: sentence.message_id,
+        })
+    } else {
+        Ok(do_parse_mtw(sentence.data, true)?.1)
+    }
+}
+
+fn do_parse_mtw(i: &str, lenient: bool) -> IResult<&str, MtwData> {
+    let (i, temperature_value) = opt(float_number)(i)?;
+    let (i, unit) = if lenient {
+        let unit = opt(preceded(char(','), opt(one_of("CF"))));
+        map(terminated(unit, eof), Option::flatten)(i)?
+    } else {
+        map(preceded(char(','), one_of("CF")), Some)(i)?
+    };
+    let unit = unit.map(TemperatureUnit::from_char);
     Ok((
         i,
         MtwData {
-            temperature: temperature_value,
+            temperature: temperature_value
+                .map(|degrees| unit.unwrap_or(TemperatureUnit::Celsius).to_celsius(degrees)),
+            unit,
         },
     ))
 }
 
 #[cfg(test)]
 mod tests {
+    use approx::assert_relative_eq;
+
     use super::*;
     use crate::parse::parse_nmea_sentence;
 
@@ -83,6 +164,25 @@ mod tests {
         assert_eq!(s.checksum, 0x1B);
         let mtw_data = parse_mtw(s).unwrap();
         assert_eq!(Some(17.9), mtw_data.temperature);
+        assert_eq!(Some(TemperatureUnit::Celsius), mtw_data.unit);
+    }
+
+    #[test]
+    fn test_parse_mtw_units() {
+        let s = parse_nmea_sentence("$INMTW,64.4,F*17").unwrap();
+        let mtw_data = parse_mtw(s).unwrap();
+        assert_eq!(mtw_data.unit, Some(TemperatureUnit::Fahrenheit));
+        assert_relative_eq!(18.0, mtw_data.temperature.unwrap(), epsilon = 1e-5);
+
+        for line in ["$INMTW,17.9,*58", "$INMTW,17.9*74"] {
+            let s = parse_nmea_sentence(line).unwrap();
+            assert!(parse_mtw(s).is_err());
+            let mtw_data = parse_mtw_lenient(s).unwrap();
+            assert_eq!(mtw_data.unit, None);
+            assert_eq!(mtw_data.temperature, Some(17.9));
+        }
+        let s = parse_nmea_sentence("$INMTW,17.9,x*20").unwrap();
+        assert!(parse_mtw_lenient(s).is_err());
     }
 
     #[test]
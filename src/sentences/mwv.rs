@@ -6,7 +6,11 @@ use nom::{
     IResult,
 };
 
-use crate::{parse::NmeaSentence, Error, SentenceType};
+use crate::{
+    parse::NmeaSentence,
+    wind::{self, TrueWind},
+    Error, SentenceType,
+};
 
 /// MWV - Wind Speed and Angle
 ///
@@ -19,7 +23,8 @@ use crate::{parse::NmeaSentence, Error, SentenceType};
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct MwvData {
     pub wind_direction: Option<f32>,
     pub reference: Option<MwvReference>,
@@ -28,11 +33,62 @@ pub struct MwvData {
     pub data_valid: bool,
 }
 
+accessors!(MwvData {
+    wind_direction, with_wind_direction: Option<f32>;
+    reference, with_reference: Option<MwvReference>;
+    wind_speed, with_wind_speed: Option<f32>;
+    wind_speed_units, with_wind_speed_units: Option<MwvWindSpeedUnits>;
+    data_valid, with_data_valid: bool;
+});
+
+impl MwvData {
+    /// Returns the wind angle relative to the bow in `(-180, 180]` degrees,
+    /// positive to starboard.
+    pub fn signed_angle(&self) -> Option<f32> {
+        self.wind_direction.map(wind::signed_angle)
+    }
+
+    /// Returns the true wind, computed with [`wind::true_wind()`] for
+    /// relative (apparent) wind.
+    ///
+    /// `boat_speed` must be in the [wind speed units](Self::wind_speed_units)
+    /// and `heading` in degrees True. Returns `None` for invalid data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_nmea_sentence, sentences::parse_mwv};
+    ///
+    /// let mwv = parse_mwv(parse_nmea_sentence("$WIMWV,270.0,R,10.0,N,A*17").unwrap()).unwrap();
+    /// assert_eq!(mwv.signed_angle(), Some(-90.));
+    /// let wind = mwv.true_wind(0., 90.).unwrap();
+    /// assert_eq!(wind.speed, 10.);
+    /// ```
+    pub fn true_wind(&self, boat_speed: f32, heading: f32) -> Option<TrueWind> {
+        if !self.data_valid {
+            return None;
+        }
+        let angle = self.signed_angle()?;
+        let speed = self.wind_speed?;
+        match self.reference? {
+            MwvReference::Relative => Some(wind::true_wind(angle, speed, boat_speed, heading)),
+            MwvReference::Theoretical => Some(TrueWind {
+                angle,
+                direction: crate::angle::normalize(heading + angle),
+                speed,
+            }),
+        }
+    }
+}
+
+/// Reference of the wind angle and speed
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MwvReference {
+    /// Apparent wind, relative to the moving vessel
     Relative,
+    /// True wind, computed by the instrument
     Theoretical,
 }
 
@@ -134,4 +190,26 @@ mod tests {
         );
         assert!(wimwv_data.data_valid);
     }
+
+    #[test]
+    fn test_mwv_true_wind() {
+        let mut mwv = MwvData {
+            wind_direction: Some(315.),
+            reference: Some(MwvReference::Theoretical),
+            wind_speed: Some(12.),
+            wind_speed_units: Some(MwvWindSpeedUnits::Knots),
+            data_valid: true,
+        };
+        assert_eq!(mwv.signed_angle(), Some(-45.));
+        let wind = mwv.true_wind(6., 100.).unwrap();
+        assert_eq!((wind.angle, wind.direction, wind.speed), (-45., 55., 12.));
+
+        mwv.reference = Some(MwvReference::Relative);
+        let wind = mwv.true_wind(6., 100.).unwrap();
+        assert!(wind.speed < 12.);
+        assert!(wind.angle < -45.);
+
+        mwv.data_valid = false;
+        assert!(mwv.true_wind(6., 100.).is_none());
+    }
 }
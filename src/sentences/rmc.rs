@@ -6,20 +6,24 @@ use nom::{
     IResult,
 };
 
+#[cfg(feature = "exact-coordinates")]
+use super::exact::{parse_exact_position, ExactPosition};
 use crate::{
     parse::NmeaSentence,
     sentences::utils::{parse_date, parse_hms, parse_lat_lon},
-    Error, SentenceType,
+    Error, Float, SentenceType,
 };
 
 use super::{faa_mode::parse_faa_mode, utils::parse_magnetic_variation, FaaMode};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RmcStatusOfFix {
     Autonomous,
     Differential,
+    /// The default, as nothing is known about the fix
+    #[default]
     Invalid,
 }
 
@@ -78,22 +82,40 @@ pub enum RmcNavigationStatus {
 ///     `M` = Manual input mode, `N` = not valid, `S` = Simulator, `V` = Valid
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
 pub struct RmcData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_time: Option<NaiveTime>,
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_date: Option<NaiveDate>,
     pub status_of_fix: RmcStatusOfFix,
-    pub lat: Option<f64>,
-    pub lon: Option<f64>,
+    pub lat: Option<Float>,
+    pub lon: Option<Float>,
     pub speed_over_ground: Option<f32>,
     pub true_course: Option<f32>,
     pub magnetic_variation: Option<f32>,
     pub faa_mode: Option<FaaMode>,
     pub nav_status: Option<RmcNavigationStatus>,
+    /// The position exactly as written, see [`ExactPosition`]
+    #[cfg(feature = "exact-coordinates")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "exact-coordinates")))]
+    pub exact_position: Option<ExactPosition>,
 }
 
+accessors!(RmcData {
+    fix_time, with_fix_time: Option<NaiveTime>;
+    fix_date, with_fix_date: Option<NaiveDate>;
+    status_of_fix, with_status_of_fix: RmcStatusOfFix;
+    lat, with_lat: Option<Float>;
+    lon, with_lon: Option<Float>;
+    speed_over_ground, with_speed_over_ground: Option<f32>;
+    true_course, with_true_course: Option<f32>;
+    magnetic_variation, with_magnetic_variation: Option<f32>;
+    faa_mode, with_faa_mode: Option<FaaMode>;
+    nav_status, with_nav_status: Option<RmcNavigationStatus>;
+});
+
 fn do_parse_rmc(i: &str) -> IResult<&str, RmcData> {
     // 1.  UTC of position fix, `hh` is hours, `mm` is minutes, `ss.ss` is seconds.
     let (i, fix_time) = opt(parse_hms)(i)?;
@@ -111,6 +133,8 @@ fn do_parse_rmc(i: &str) -> IResult<&str, RmcData> {
     // 4.  `N` or `S`
     // 5.  Longitude, `ddd` is degrees. `mm.mm` is minutes.
     // 6.  `E` or `W`
+    #[cfg(feature = "exact-coordinates")]
+    let exact_position = parse_exact_position(i);
     let (i, lat_lon) = parse_lat_lon(i)?;
     let (i, _) = char(',')(i)?;
     // 7.  Speed over ground, knots
@@ -150,6 +174,8 @@ fn do_parse_rmc(i: &str) -> IResult<&str, RmcData> {
             magnetic_variation,
             faa_mode: faa_mode.flatten(),
             nav_status: nav_status.flatten(),
+            #[cfg(feature = "exact-coordinates")]
+            exact_position,
         },
     ))
 }
@@ -262,6 +288,8 @@ mod tests {
             magnetic_variation,
             faa_mode,
             nav_status,
+            #[cfg(feature = "exact-coordinates")]
+                exact_position: _,
         } = parse_rmc(s).unwrap();
 
         assert_eq!(
@@ -308,7 +336,9 @@ mod tests {
                 true_course: None,
                 magnetic_variation: None,
                 faa_mode: Some(FaaMode::DataNotValid),
-                nav_status: None
+                nav_status: None,
+                #[cfg(feature = "exact-coordinates")]
+                exact_position: None,
             },
             rmc
         );
@@ -328,6 +358,8 @@ mod tests {
             magnetic_variation,
             faa_mode,
             nav_status,
+            #[cfg(feature = "exact-coordinates")]
+                exact_position: _,
         } = parse_nmea_sentence(gpsd_example)
             .map(parse_rmc)
             .unwrap()
@@ -365,6 +397,8 @@ mod tests {
             magnetic_variation,
             faa_mode,
             nav_status,
+            #[cfg(feature = "exact-coordinates")]
+                exact_position: _,
         } = parse_nmea_sentence(rmc_v41)
             .map(parse_rmc)
             .unwrap()
@@ -8,8 +8,10 @@ use crate::{parse::NmeaSentence, Error, SentenceType};
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum PgrmzFixType {
+    /// The default, as nothing is known about the fix
+    #[default]
     NoFix,
     TwoDimensional,
     ThreeDimensional,
@@ -33,13 +35,19 @@ pub enum PgrmzFixType {
 /// Example: `$PGRMZ,2282,f,3*21`
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct PgrmzData {
     /// Current altitude in feet
     pub altitude: u32,
     pub fix_type: PgrmzFixType,
 }
 
+accessors!(PgrmzData {
+    altitude, with_altitude: u32;
+    fix_type, with_fix_type: PgrmzFixType;
+});
+
 fn do_parse_pgrmz(i: &str) -> IResult<&str, PgrmzData> {
     let (i, altitude) = number::<u32>(i)?;
     let (i, _) = char(',')(i)?;
@@ -86,6 +94,7 @@ mod tests {
         let s = parse_nmea_sentence("$PGRMZ,2282,f,3*21").unwrap();
         assert_eq!(s.checksum, s.calc_checksum());
         assert_eq!(s.checksum, 0x21);
+        assert_eq!(s.manufacturer().as_deref(), Some("GRM"));
 
         let data = parse_pgrmz(s).unwrap();
         assert_eq!(data.altitude, 2282);
@@ -95,6 +104,7 @@ mod tests {
     #[test]
     fn test_wrong_talker_id() {
         let s = parse_nmea_sentence("$XXRMZ,2282,f,3*21").unwrap();
+        assert_eq!(s.manufacturer(), None);
         assert!(matches!(
             parse_pgrmz(s),
             Err(Error::UnknownTalkerId {
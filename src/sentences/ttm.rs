@@ -1,4 +1,4 @@
-use chrono::NaiveTime;
+use chrono::{Duration, NaiveTime};
 use nom::{
     bytes::complete::take_until,
     character::complete::{char, one_of},
@@ -7,8 +7,18 @@ use nom::{
     IResult,
 };
 
-use super::utils::{parse_float_num, parse_hms, parse_number_in_range};
-use crate::{Error, NmeaSentence, SentenceType};
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use super::{
+    rmc::RmcData,
+    utils::{duration_from_millis, parse_float_num, parse_hms, parse_number_in_range},
+};
+use crate::{Error, Float, NmeaSentence, SentenceType};
+
+/// Mean radius of the Earth in meters
+const EARTH_RADIUS: Float = 6_371_008.8;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
@@ -26,6 +36,17 @@ pub struct TtmAngle {
     reference: TtmReference,
 }
 
+impl TtmAngle {
+    /// Angle in degrees
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    pub fn reference(&self) -> TtmReference {
+        self.reference
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +56,17 @@ pub enum TtmDistanceUnit {
     StatuteMile,
 }
 
+impl TtmDistanceUnit {
+    /// Returns the length of the unit in meters.
+    pub fn meters(self) -> f32 {
+        match self {
+            TtmDistanceUnit::Kilometer => 1000.,
+            TtmDistanceUnit::NauticalMile => 1852.,
+            TtmDistanceUnit::StatuteMile => 1609.344,
+        }
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -90,7 +122,8 @@ pub enum TtmTypeOfAcquisition {
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
+#[non_exhaustive]
 pub struct TtmData {
     /// Target number
     pub target_number: Option<u8>,
@@ -121,6 +154,92 @@ pub struct TtmData {
     pub type_of_acquisition: Option<TtmTypeOfAcquisition>,
 }
 
+accessors!(TtmData {
+    target_number, with_target_number: Option<u8>;
+    target_distance, with_target_distance: Option<f32>;
+    bearing_from_own_ship, with_bearing_from_own_ship: Option<TtmAngle>;
+    target_speed, with_target_speed: Option<f32>;
+    target_course, with_target_course: Option<TtmAngle>;
+    distance_of_cpa, with_distance_of_cpa: Option<f32>;
+    time_to_cpa, with_time_to_cpa: Option<f32>;
+    speed_or_distance_unit, with_speed_or_distance_unit: Option<TtmDistanceUnit>;
+    target_status, with_target_status: Option<TtmStatus>;
+    is_target_reference, with_is_target_reference: bool;
+    time_of_data, with_time_of_data: Option<NaiveTime>;
+    type_of_acquisition, with_type_of_acquisition: Option<TtmTypeOfAcquisition>;
+} ref {
+    target_name, with_target_name: Option<heapless::String<32>>;
+});
+
+impl TtmData {
+    /// Returns the target distance in meters, if the unit is known.
+    pub fn target_distance_m(&self) -> Option<f32> {
+        Some(self.target_distance? * self.speed_or_distance_unit?.meters())
+    }
+
+    /// Returns the distance of the closest point of approach in meters, if
+    /// the unit is known.
+    pub fn distance_of_cpa_m(&self) -> Option<f32> {
+        Some(self.distance_of_cpa? * self.speed_or_distance_unit?.meters())
+    }
+
+    /// Returns the target speed in meters per second, if the unit is known.
+    ///
+    /// The speed unit is the distance unit per hour, e.g. knots for
+    /// [`TtmDistanceUnit::NauticalMile`].
+    pub fn target_speed_ms(&self) -> Option<f32> {
+        Some(self.target_speed? * self.speed_or_distance_unit?.meters() / 3600.)
+    }
+
+    /// Returns the time to the closest point of approach, which is negative
+    /// when the distance is increasing.
+    pub fn time_to_cpa_duration(&self) -> Option<Duration> {
+        duration_from_millis(f64::from(self.time_to_cpa?) * 60_000.)
+    }
+
+    /// Returns the position of own ship at the closest point of approach,
+    /// latitude and longitude in degrees, by dead reckoning along the course
+    /// and speed of own ship from an RMC sentence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{parse_str, ParseResult};
+    ///
+    /// # #[cfg(all(feature = "RMC", feature = "TTM"))]
+    /// # {
+    /// let Ok(ParseResult::RMC(rmc)) =
+    ///     parse_str("$GPRMC,225446,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*68")
+    /// else {
+    ///     panic!()
+    /// };
+    /// let Ok(ParseResult::TTM(ttm)) =
+    ///     parse_str("$RATTM,01,0.2,190.8,T,12.1,109.7,T,0.1,0.5,N,TGT01,T,,100021.00,A*79")
+    /// else {
+    ///     panic!()
+    /// };
+    /// let (lat, lon) = ttm.cpa_position(&rmc).unwrap();
+    /// assert!(lat > rmc.lat.unwrap() && lon > rmc.lon.unwrap());
+    /// # }
+    /// ```
+    pub fn cpa_position(&self, own_ship: &RmcData) -> Option<(Float, Float)> {
+        let seconds = self.time_to_cpa_duration()?.num_milliseconds() as Float / 1000.;
+        let speed = Float::from(own_ship.speed_over_ground?) * 1852. / 3600.;
+        let course = Float::from(own_ship.true_course?).to_radians();
+        let lat = own_ship.lat?.to_radians();
+        let lon = own_ship.lon?.to_radians();
+
+        // the destination on a sphere
+        let delta = speed * seconds / EARTH_RADIUS;
+        let cpa_lat = (lat.sin() * delta.cos() + lat.cos() * delta.sin() * course.cos()).asin();
+        let cpa_lon = lon
+            + (course.sin() * delta.sin() * lat.cos())
+                .atan2(delta.cos() - lat.sin() * cpa_lat.sin());
+        let cpa_lon = (cpa_lon.to_degrees() + 540.) % 360. - 180.;
+        Some((cpa_lat.to_degrees(), cpa_lon))
+    }
+}
+
 /// # Parse TTM message
 pub fn parse_ttm(sentence: NmeaSentence) -> Result<TtmData, Error> {
     if sentence.message_id != SentenceType::TTM {
@@ -248,7 +367,7 @@ mod tests {
     use approx::assert_relative_eq;
 
     use super::*;
-    use crate::parse::parse_nmea_sentence;
+    use crate::{parse::parse_nmea_sentence, sentences::rmc::RmcStatusOfFix};
 
     #[test]
     fn test_parse_ttm_full() {
@@ -316,4 +435,67 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_ttm_units() {
+        let mut data = parse_ttm(NmeaSentence {
+            talker_id: "RA",
+            message_id: SentenceType::TTM,
+            data: "00,0.5,187.5,T,12.0,17.6,T,0.1,1.2,N,TGT00,T,,100023.00,A",
+            checksum: 0,
+        })
+        .unwrap();
+        assert_relative_eq!(data.target_distance_m().unwrap(), 926.);
+        assert_relative_eq!(data.distance_of_cpa_m().unwrap(), 185.2, epsilon = 1e-3);
+        assert_relative_eq!(data.target_speed_ms().unwrap(), 6.1733, epsilon = 1e-4);
+        assert_eq!(data.time_to_cpa_duration(), Some(Duration::seconds(72)));
+
+        // the distance is increasing
+        data.time_to_cpa = Some(-0.5);
+        assert_eq!(data.time_to_cpa_duration(), Some(Duration::seconds(-30)));
+        data.time_to_cpa = Some(f32::MIN);
+        assert_eq!(data.time_to_cpa_duration(), None);
+        data.time_to_cpa = Some(f32::NAN);
+        assert_eq!(data.time_to_cpa_duration(), None);
+
+        data.speed_or_distance_unit = None;
+        assert_eq!(data.target_distance_m(), None);
+    }
+
+    #[test]
+    fn test_ttm_cpa_position() {
+        let data = parse_ttm(NmeaSentence {
+            talker_id: "RA",
+            message_id: SentenceType::TTM,
+            data: "00,0.5,187.5,T,12.0,17.6,T,0.1,60.0,N,TGT00,T,,100023.00,A",
+            checksum: 0,
+        })
+        .unwrap();
+        let mut own_ship = RmcData {
+            fix_time: None,
+            fix_date: None,
+            status_of_fix: RmcStatusOfFix::Autonomous,
+            lat: Some(10.),
+            lon: Some(179.9),
+            speed_over_ground: Some(6.),
+            true_course: Some(0.),
+            magnetic_variation: None,
+            faa_mode: None,
+            nav_status: None,
+            #[cfg(feature = "exact-coordinates")]
+            exact_position: None,
+        };
+        // an hour due north at 6 knots: 6 minutes of latitude
+        let (lat, lon) = data.cpa_position(&own_ship).unwrap();
+        assert_relative_eq!(lat, 10.1, epsilon = 1e-3);
+        assert_relative_eq!(lon, 179.9, epsilon = 1e-4);
+
+        // across the antimeridian
+        own_ship.true_course = Some(90.);
+        let (_, lon) = data.cpa_position(&own_ship).unwrap();
+        assert!(lon < -179.9, "{}", lon);
+
+        own_ship.true_course = None;
+        assert_eq!(data.cpa_position(&own_ship), None);
+    }
 }
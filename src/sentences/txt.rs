@@ -65,7 +65,8 @@ fn do_parse_txt(i: &str) -> IResult<&str, TxtData0<'_>> {
 /// TXT - Text
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct TxtData {
     pub count: u8,
     pub seq: u8,
@@ -74,6 +75,13 @@ pub struct TxtData {
     pub text: ArrayString<TEXT_PARAMETER_MAX_LEN>,
 }
 
+accessors!(TxtData {
+    count, with_count: u8;
+    seq, with_seq: u8;
+    text_ident, with_text_ident: u8;
+    text, with_text: ArrayString<TEXT_PARAMETER_MAX_LEN>;
+});
+
 struct TxtData0<'a> {
     pub count: u8,
     pub seq: u8,
@@ -16,7 +16,7 @@ use nom::{
 #[allow(unused_imports)]
 use num_traits::float::FloatCore;
 
-use crate::Error;
+use crate::{sentences::nom_parse_failure, Error, Float};
 
 pub fn parse_hms(i: &str) -> IResult<&str, NaiveTime> {
     map_res(
@@ -26,6 +26,9 @@ pub fn parse_hms(i: &str) -> IResult<&str, NaiveTime> {
             map_parser(take_until(","), double),
         )),
         |(hour, minutes, sec)| -> core::result::Result<NaiveTime, &'static str> {
+            if !sec.is_finite() {
+                return Err("Invalid time: second is not finite");
+            }
             if sec.is_sign_negative() {
                 return Err("Invalid time: second is negative");
             }
@@ -38,17 +41,29 @@ pub fn parse_hms(i: &str) -> IResult<&str, NaiveTime> {
             if sec >= 60. {
                 return Err("Invalid time: sec >= 60");
             }
-            NaiveTime::from_hms_nano_opt(
-                hour,
-                minutes,
-                sec.trunc() as u32,
-                (sec.fract() * 1_000_000_000f64).round() as u32,
-            )
-            .ok_or("Invalid time")
+            let (sec, nano) = split_seconds(sec);
+            NaiveTime::from_hms_nano_opt(hour, minutes, sec, nano).ok_or("Invalid time")
         },
     )(i)
 }
 
+/// The number of nanoseconds in a second.
+const NANOSECS_PER_SECOND: u64 = 1_000_000_000;
+
+/// Splits seconds in `[0, 60)` into whole seconds and nanoseconds.
+///
+/// The nanoseconds are rounded, but never up to the next minute: chrono
+/// takes a second of 60, or of a billion nanoseconds, as a leap second.
+pub(crate) fn split_seconds(seconds: f64) -> (u32, u32) {
+    let nanos =
+        ((seconds * NANOSECS_PER_SECOND as f64).round() as u64).min(60 * NANOSECS_PER_SECOND - 1);
+    // both below `u32::MAX`
+    (
+        (nanos / NANOSECS_PER_SECOND) as u32,
+        (nanos % NANOSECS_PER_SECOND) as u32,
+    )
+}
+
 /// The number of milliseconds in a second.
 const MILLISECS_PER_SECOND: u32 = 1000;
 /// The number of milliseconds in a minute.
@@ -81,34 +96,85 @@ pub fn parse_duration_hms(i: &str) -> IResult<&str, Duration> {
                 return Err("Invalid time: seconds >= 60");
             }
 
-            // We don't have to use checked operations as above checks limits number of milliseconds
-            // to value within i64 bounds.
-            Ok(Duration::milliseconds(
-                i64::from(hours) * i64::from(MILLISECS_PER_HOUR)
-                    + i64::from(minutes) * i64::from(MILLISECS_PER_MINUTE)
-                    + (seconds.trunc() as i64) * i64::from(MILLISECS_PER_SECOND)
-                    + (seconds.fract() * 1_000f32).round() as i64,
-            ))
+            // the checks above keep the milliseconds within a day
+            checked_millis(hours, minutes, seconds)
+                .map(Duration::milliseconds)
+                .ok_or("Invalid time: out of range")
         },
     )(i)
 }
 
-pub fn do_parse_lat_lon(i: &str) -> IResult<&str, (f64, f64)> {
+/// Returns the milliseconds of hours, minutes and seconds, the seconds
+/// rounded to the millisecond, `None` on overflow or if not finite.
+pub(crate) fn checked_millis(hours: u8, minutes: u8, seconds: f32) -> Option<i64> {
+    if !seconds.is_finite() {
+        return None;
+    }
+    let whole_seconds = seconds.trunc();
+    // a float beyond `i64` would saturate
+    if whole_seconds.abs() >= i64::MAX as f32 {
+        return None;
+    }
+    i64::from(hours)
+        .checked_mul(i64::from(MILLISECS_PER_HOUR))?
+        .checked_add(i64::from(minutes).checked_mul(i64::from(MILLISECS_PER_MINUTE))?)?
+        .checked_add((whole_seconds as i64).checked_mul(i64::from(MILLISECS_PER_SECOND))?)?
+        .checked_add((seconds.fract() * MILLISECS_PER_SECOND as f32).round() as i64)
+}
+
+/// Returns a [`Duration`] of milliseconds, `None` if not finite or out of the
+/// range of [`Duration`].
+pub(crate) fn duration_from_millis(millis: f64) -> Option<Duration> {
+    // `Duration` holds up to `i64::MAX` milliseconds, but not `i64::MIN`
+    const MAX_MILLIS: f64 = 9.2e18;
+    let millis = millis.round();
+    (millis.is_finite() && millis.abs() <= MAX_MILLIS)
+        .then(|| Duration::milliseconds(millis as i64))
+}
+
+/// Returns the offset in seconds east of UTC of a local zone in hours and
+/// minutes, `None` without either or beyond a day.
+pub(crate) fn zone_offset_seconds(hours: Option<i8>, minutes: Option<i8>) -> Option<i32> {
+    if hours.is_none() && minutes.is_none() {
+        return None;
+    }
+    let hours = i32::from(hours.unwrap_or(0));
+    let minutes = i32::from(minutes.unwrap_or(0));
+    let seconds = hours
+        .checked_mul(60)?
+        .checked_add(minutes)?
+        .checked_mul(60)?;
+    (seconds.abs() < 86_400).then_some(seconds)
+}
+
+/// Parses a [`Float`].
+pub(crate) fn float_number(i: &str) -> IResult<&str, Float> {
+    #[cfg(not(feature = "f32-only"))]
+    {
+        double(i)
+    }
+    #[cfg(feature = "f32-only")]
+    {
+        float(i)
+    }
+}
+
+pub fn do_parse_lat_lon(i: &str) -> IResult<&str, (Float, Float)> {
     let (i, lat_deg) = map_res(take(2usize), parse_num::<u8>)(i)?;
-    let (i, lat_min) = double(i)?;
+    let (i, lat_min) = float_number(i)?;
     let (i, _) = char(',')(i)?;
     let (i, lat_dir) = one_of("NS")(i)?;
     let (i, _) = char(',')(i)?;
     let (i, lon_deg) = map_res(take(3usize), parse_num::<u8>)(i)?;
-    let (i, lon_min) = double(i)?;
+    let (i, lon_min) = float_number(i)?;
     let (i, _) = char(',')(i)?;
     let (i, lon_dir) = one_of("EW")(i)?;
 
-    let mut lat = f64::from(lat_deg) + lat_min / 60.;
+    let mut lat = Float::from(lat_deg) + lat_min / 60.;
     if lat_dir == 'S' {
         lat = -lat;
     }
-    let mut lon = f64::from(lon_deg) + lon_min / 60.;
+    let mut lon = Float::from(lon_deg) + lon_min / 60.;
     if lon_dir == 'W' {
         lon = -lon;
     }
@@ -135,7 +201,7 @@ pub fn do_parse_magnetic_variation(i: &str) -> IResult<&str, f32> {
     Ok((i, variation_deg))
 }
 
-pub(crate) fn parse_lat_lon(i: &str) -> IResult<&str, Option<(f64, f64)>> {
+pub(crate) fn parse_lat_lon(i: &str) -> IResult<&str, Option<(Float, Float)>> {
     alt((map(tag(",,,"), |_| None), map(do_parse_lat_lon, Some)))(i)
 }
 
@@ -213,13 +279,38 @@ where
 /// If `&str` length > `MAX_LEN` it returns a [`Error::ParameterLength`] error.
 pub(crate) fn array_string<const MAX_LEN: usize>(
     string: &str,
-) -> Result<ArrayString<MAX_LEN>, Error> {
+) -> Result<ArrayString<MAX_LEN>, Error<'_>> {
     ArrayString::from(string).map_err(|_| Error::ParameterLength {
         max_length: MAX_LEN,
         parameter_length: string.len(),
     })
 }
 
+/// Parses the optional fields at the end of a sentence, like the FAA mode
+/// added by NMEA 2.3, after the last mandatory field.
+///
+/// Missing and empty fields are both `None`, and whitespace around the
+/// fields is ignored, so pre-2.3 sentences, trailing commas and sloppy
+/// emitters are accepted. More than `N` non-empty fields are an error.
+pub(crate) fn parse_optional_tail<const N: usize>(i: &str) -> IResult<&str, [Option<&str>; N]> {
+    let mut fields = [None; N];
+    let tail = i.trim();
+    if tail.is_empty() {
+        return Ok(("", fields));
+    }
+    let Some(tail) = tail.strip_prefix(',') else {
+        return Err(nom_parse_failure(i));
+    };
+    for (index, field) in tail.split(',').map(str::trim).enumerate() {
+        match fields.get_mut(index) {
+            Some(slot) => *slot = Some(field).filter(|field| !field.is_empty()),
+            None if field.is_empty() => {}
+            None => return Err(nom_parse_failure(i)),
+        }
+    }
+    Ok(("", fields))
+}
+
 pub(crate) fn parse_until_end(input: &str) -> IResult<&str, &str> {
     all_consuming(terminated(take_while(|_| true), eof))(input)
 }
@@ -231,6 +322,22 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_parse_optional_tail() {
+        assert_eq!(parse_optional_tail::<2>(""), Ok(("", [None, None])));
+        assert_eq!(parse_optional_tail::<2>(","), Ok(("", [None, None])));
+        assert_eq!(
+            parse_optional_tail::<2>(" ,A "),
+            Ok(("", [Some("A"), None]))
+        );
+        assert_eq!(
+            parse_optional_tail::<2>(",A,V,,"),
+            Ok(("", [Some("A"), Some("V")]))
+        );
+        assert!(parse_optional_tail::<2>(",A,V,X").is_err());
+        assert!(parse_optional_tail::<1>("A").is_err());
+    }
+
     #[test]
     fn test_do_parse_lat_lon() {
         let (_, lat_lon) = do_parse_lat_lon("4807.038,N,01131.324,E").unwrap();
@@ -253,6 +360,48 @@ mod tests {
         assert_eq!(time.nanosecond(), 500_000_000);
     }
 
+    #[test]
+    fn test_parse_hms_boundaries() {
+        use chrono::Timelike;
+        let (_, time) = parse_hms("235959.9999999,").unwrap();
+        assert_eq!((time.hour(), time.minute(), time.second()), (23, 59, 59));
+        assert_eq!(time.nanosecond(), 999_999_900);
+        // rounded to the last nanosecond, not to a leap second
+        let (_, time) = parse_hms("235959.9999999999,").unwrap();
+        assert_eq!((time.second(), time.nanosecond()), (59, 999_999_999));
+        let (_, time) = parse_hms("120030.9999999999,").unwrap();
+        assert_eq!((time.second(), time.nanosecond()), (31, 0));
+        let (_, time) = parse_hms("120059.9999,").unwrap();
+        assert_eq!((time.second(), time.nanosecond()), (59, 999_900_000));
+
+        assert!(parse_hms("120060,").is_err());
+        assert!(parse_hms("240000,").is_err());
+        assert!(parse_hms("1200nan,").is_err());
+        assert!(parse_hms("1200inf,").is_err());
+        assert!(parse_hms("1200-0.5,").is_err());
+
+        assert_eq!(split_seconds(0.), (0, 0));
+        assert_eq!(split_seconds(59.99999999999), (59, 999_999_999));
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        assert_eq!(checked_millis(23, 59, 59.5), Some(86_399_500));
+        assert_eq!(checked_millis(u8::MAX, u8::MAX, 0.), Some(933_300_000));
+        assert_eq!(checked_millis(0, 0, f32::MAX), None);
+        assert_eq!(checked_millis(0, 0, f32::NAN), None);
+
+        assert_eq!(duration_from_millis(-1.4), Some(Duration::milliseconds(-1)));
+        assert_eq!(duration_from_millis(f64::from(f32::MIN)), None);
+        assert_eq!(duration_from_millis(f64::INFINITY), None);
+
+        assert_eq!(zone_offset_seconds(Some(13), Some(59)), Some(50_340));
+        assert_eq!(zone_offset_seconds(Some(-13), Some(-59)), Some(-50_340));
+        assert_eq!(zone_offset_seconds(None, Some(-30)), Some(-1800));
+        assert_eq!(zone_offset_seconds(None, None), None);
+        assert_eq!(zone_offset_seconds(Some(i8::MIN), Some(i8::MIN)), None);
+    }
+
     #[test]
     fn test_parse_duration_hms() {
         let (_, time) = parse_duration_hms("125619,").unwrap();
@@ -271,6 +420,14 @@ mod tests {
             time.num_nanoseconds().unwrap(),
             (12 * 60 * 60 + 56 * 60 + 19) * 1_000_000_000 + 500_000_000
         );
+
+        // rounded to the next day
+        let (_, time) = parse_duration_hms("235959.9999,").unwrap();
+        assert_eq!(time.num_milliseconds(), 86_400_000);
+        let (_, time) = parse_duration_hms("000000.0004,").unwrap();
+        assert_eq!(time.num_milliseconds(), 0);
+        assert!(parse_duration_hms("0000nan,").is_err());
+        assert!(parse_duration_hms("0000-1,").is_err());
     }
 
     #[test]
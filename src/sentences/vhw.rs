@@ -5,7 +5,7 @@ use nom::{
     IResult,
 };
 
-use crate::{Error, NmeaSentence, SentenceType};
+use crate::{Error, Float, NmeaSentence, SentenceType};
 
 use super::utils::parse_float_num;
 
@@ -33,18 +33,26 @@ use super::utils::parse_float_num;
 /// > It’s not clear which is correct.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Default)]
+#[non_exhaustive]
 pub struct VhwData {
     /// Heading degrees, True
-    pub heading_true: Option<f64>,
+    pub heading_true: Option<Float>,
     /// Heading degrees, Magnetic
-    pub heading_magnetic: Option<f64>,
+    pub heading_magnetic: Option<Float>,
     /// Speed of vessel relative to the water, knots
-    pub relative_speed_knots: Option<f64>,
+    pub relative_speed_knots: Option<Float>,
     /// Speed of vessel relative to the water, km/hr
-    pub relative_speed_kmph: Option<f64>,
+    pub relative_speed_kmph: Option<Float>,
 }
 
+accessors!(VhwData {
+    heading_true, with_heading_true: Option<Float>;
+    heading_magnetic, with_heading_magnetic: Option<Float>;
+    relative_speed_knots, with_relative_speed_knots: Option<Float>;
+    relative_speed_kmph, with_relative_speed_kmph: Option<Float>;
+});
+
 /// # Parse VHW message
 ///
 /// ```text
@@ -75,8 +83,8 @@ pub fn parse_vhw(sentence: NmeaSentence) -> Result<VhwData, Error> {
 
 /// Parses a float value
 /// and returns `None` if the float value can be parsed but the next field does not match the specified character.
-fn do_parse_float_with_char(c: char, i: &str) -> IResult<&str, Option<f64>> {
-    let (i, value) = opt(map_res(take_until(","), parse_float_num::<f64>))(i)?;
+fn do_parse_float_with_char(c: char, i: &str) -> IResult<&str, Option<Float>> {
+    let (i, value) = opt(map_res(take_until(","), parse_float_num::<Float>))(i)?;
     let (i, _) = char(',')(i)?;
     let (i, tag) = opt(char(c))(i)?;
     Ok((i, tag.and(value)))
@@ -0,0 +1,139 @@
+use nom::{
+    bytes::complete::take_until,
+    character::complete::char,
+    combinator::{map_res, opt},
+    sequence::preceded,
+    IResult,
+};
+
+use crate::{Error, Float, NmeaSentence, SentenceType};
+
+use super::utils::parse_float_num;
+
+/// VLW - Distance Traveled through Water
+///
+/// <https://gpsd.gitlab.io/gpsd/NMEA.html#_vlw_distance_traveled_through_water>
+///
+/// ```text
+///        1   2 3   4 5   6 7   8 9
+///        |   | |   | |   | |   | |
+/// $--VLW,x.x,N,x.x,N,x.x,N,x.x,N*hh<CR><LF>
+/// ```
+/// 1. Total cumulative water distance, nautical miles
+/// 2. N = Nautical miles
+/// 3. Water distance since reset, nautical miles
+/// 4. N = Nautical miles
+/// 5. Total cumulative ground distance, nautical miles (NMEA 4 and later)
+/// 6. N = Nautical miles
+/// 7. Ground distance since reset, nautical miles (NMEA 4 and later)
+/// 8. N = Nautical miles
+/// 9. Checksum
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[non_exhaustive]
+pub struct VlwData {
+    /// Total cumulative water distance, nautical miles
+    pub total_water_distance: Option<Float>,
+    /// Water distance since reset, nautical miles
+    pub trip_water_distance: Option<Float>,
+    /// Total cumulative ground distance, nautical miles
+    pub total_ground_distance: Option<Float>,
+    /// Ground distance since reset, nautical miles
+    pub trip_ground_distance: Option<Float>,
+}
+
+accessors!(VlwData {
+    total_water_distance, with_total_water_distance: Option<Float>;
+    trip_water_distance, with_trip_water_distance: Option<Float>;
+    total_ground_distance, with_total_ground_distance: Option<Float>;
+    trip_ground_distance, with_trip_ground_distance: Option<Float>;
+});
+
+/// # Parse VLW message
+///
+/// ```text
+/// $IIVLW,2.8,N,0.8,N*4F
+/// ```
+/// 1. 2.8 Total water distance, nautical miles
+/// 2. N
+/// 3. 0.8 Water distance since reset, nautical miles
+/// 4. N
+///
+/// The ground distances are `None` before NMEA 4.
+pub fn parse_vlw(sentence: NmeaSentence) -> Result<VlwData, Error> {
+    if sentence.message_id == SentenceType::VLW {
+        Ok(do_parse_vlw(sentence.data)?.1)
+    } else {
+        Err(Error::WrongSentenceHeader {
+            expected: SentenceType::VLW,
+            found: sentence.message_id,
+        })
+    }
+}
+
+/// Parses a distance and its `N` unit, `None` if the unit is missing.
+fn do_parse_distance(i: &str) -> IResult<&str, Option<Float>> {
+    let (i, value) = opt(map_res(take_until(","), parse_float_num::<Float>))(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, unit) = opt(char('N'))(i)?;
+    Ok((i, unit.and(value)))
+}
+
+fn do_parse_vlw(i: &str) -> IResult<&str, VlwData> {
+    let (i, total_water_distance) = do_parse_distance(i)?;
+    let (i, _) = char(',')(i)?;
+    let (i, trip_water_distance) = do_parse_distance(i)?;
+    let (i, total_ground_distance) = opt(preceded(char(','), do_parse_distance))(i)?;
+    let (i, trip_ground_distance) = opt(preceded(char(','), do_parse_distance))(i)?;
+
+    Ok((
+        i,
+        VlwData {
+            total_water_distance,
+            trip_water_distance,
+            total_ground_distance: total_ground_distance.flatten(),
+            trip_ground_distance: trip_ground_distance.flatten(),
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+    use crate::parse::parse_nmea_sentence;
+
+    #[test]
+    fn test_parse_vlw() {
+        let s = parse_nmea_sentence("$IIVLW,2.8,N,0.8,N*4F").unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        let vlw = parse_vlw(s).unwrap();
+        assert_relative_eq!(vlw.total_water_distance.unwrap(), 2.8);
+        assert_relative_eq!(vlw.trip_water_distance.unwrap(), 0.8);
+        assert_eq!(vlw.total_ground_distance, None);
+        assert_eq!(vlw.trip_ground_distance, None);
+
+        let s = parse_nmea_sentence("$IIVLW,1234.5,N,12.3,N,1240.1,N,12.9,N*40").unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        let vlw = parse_vlw(s).unwrap();
+        assert_relative_eq!(vlw.total_ground_distance.unwrap(), 1240.1);
+        assert_relative_eq!(vlw.trip_ground_distance.unwrap(), 12.9);
+
+        let s = parse_nmea_sentence("$IIVLW,,,,,,,,*4D").unwrap();
+        assert_eq!(parse_vlw(s).unwrap(), VlwData::default());
+    }
+
+    #[test]
+    fn test_parse_vlw_invalid() {
+        let s = parse_nmea_sentence("$IIVLW,2.8,N*27").unwrap();
+        assert!(parse_vlw(s).is_err());
+
+        let s = parse_nmea_sentence("$GPVHW,100.5,T,105.5,M,10.5,N,19.4,K*4F").unwrap();
+        assert!(matches!(
+            parse_vlw(s),
+            Err(Error::WrongSentenceHeader { .. })
+        ));
+    }
+}
@@ -1,5 +1,12 @@
-use nom::{character::complete::char, combinator::opt, number::complete::float, IResult};
+use nom::{
+    character::complete::{anychar, char},
+    combinator::{map_res, opt},
+    number::complete::float,
+    sequence::preceded,
+    IResult,
+};
 
+use super::{faa_mode::parse_faa_mode, FaaMode};
 use crate::{parse::NmeaSentence, Error, SentenceType};
 
 /// VTG - Track made good and Ground speed
@@ -19,37 +26,63 @@ use crate::{parse::NmeaSentence, Error, SentenceType};
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[non_exhaustive]
 pub struct VtgData {
+    /// Course over ground, degrees True
     pub true_course: Option<f32>,
+    /// Course over ground, degrees Magnetic
+    pub magnetic_course: Option<f32>,
+    /// Speed over ground in knots, derived from the km/h field if the knots
+    /// field is empty
     pub speed_over_ground: Option<f32>,
+    /// Speed over ground in km/h, as sent
+    pub speed_over_ground_kmh: Option<f32>,
+    /// FAA mode indicator (NMEA 2.3 and later)
+    pub faa_mode: Option<FaaMode>,
 }
 
+accessors!(VtgData {
+    true_course, with_true_course: Option<f32>;
+    magnetic_course, with_magnetic_course: Option<f32>;
+    speed_over_ground, with_speed_over_ground: Option<f32>;
+    speed_over_ground_kmh, with_speed_over_ground_kmh: Option<f32>;
+    faa_mode, with_faa_mode: Option<FaaMode>;
+});
+
 fn do_parse_vtg(i: &str) -> IResult<&str, VtgData> {
     let (i, true_course) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
     let (i, _) = opt(char('T'))(i)?;
     let (i, _) = char(',')(i)?;
-    let (i, _magn_course) = opt(float)(i)?;
+    let (i, magnetic_course) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
     let (i, _) = opt(char('M'))(i)?;
     let (i, _) = char(',')(i)?;
     let (i, knots_ground_speed) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
     let (i, _) = opt(char('N'))(i)?;
+    let (i, _) = char(',')(i)?;
     let (i, kph_ground_speed) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
     let (i, _) = opt(char('K'))(i)?;
+    let (i, faa_mode) = opt(preceded(
+        char(','),
+        map_res(anychar, |c| parse_faa_mode(c).ok_or("unknown FAA mode")),
+    ))(i)?;
 
     Ok((
         i,
         VtgData {
             true_course,
+            magnetic_course,
             speed_over_ground: match (knots_ground_speed, kph_ground_speed) {
                 (Some(val), _) => Some(val),
                 (_, Some(val)) => Some(val / 1.852),
                 (None, None) => None,
             },
+            speed_over_ground_kmh: kph_ground_speed,
+            faa_mode,
         },
     ))
 }
@@ -100,10 +133,12 @@ pub fn parse_vtg(sentence: NmeaSentence) -> Result<VtgData, Error> {
 
 #[cfg(test)]
 mod tests {
+    use approx::assert_relative_eq;
+
     use super::*;
     use crate::{parse::parse_nmea_sentence, Error};
 
-    fn run_parse_vtg(line: &str) -> Result<VtgData, Error> {
+    fn run_parse_vtg(line: &str) -> Result<VtgData, Error<'_>> {
         let s = parse_nmea_sentence(line).expect("VTG sentence initial parse failed");
         assert_eq!(s.checksum, s.calc_checksum());
         parse_vtg(s)
@@ -114,23 +149,41 @@ mod tests {
         assert_eq!(
             VtgData {
                 true_course: None,
+                magnetic_course: None,
                 speed_over_ground: None,
+                speed_over_ground_kmh: None,
+                faa_mode: Some(FaaMode::DataNotValid),
             },
             run_parse_vtg("$GPVTG,,T,,M,,N,,K,N*2C").unwrap()
         );
         assert_eq!(
             VtgData {
                 true_course: Some(360.),
+                magnetic_course: Some(348.7),
                 speed_over_ground: Some(0.),
+                speed_over_ground_kmh: Some(0.),
+                faa_mode: None,
             },
             run_parse_vtg("$GPVTG,360.0,T,348.7,M,000.0,N,000.0,K*43").unwrap()
         );
         assert_eq!(
             VtgData {
                 true_course: Some(54.7),
+                magnetic_course: Some(34.4),
                 speed_over_ground: Some(5.5),
+                speed_over_ground_kmh: Some(10.2),
+                faa_mode: None,
             },
             run_parse_vtg("$GPVTG,054.7,T,034.4,M,005.5,N,010.2,K*48").unwrap()
         );
     }
+
+    #[test]
+    fn test_parse_vtg_kmh_only() {
+        let vtg = run_parse_vtg("$GPVTG,071.9,T,,M,,N,0018.52,K,D*27").unwrap();
+        assert_eq!(vtg.magnetic_course, None);
+        assert_relative_eq!(vtg.speed_over_ground.unwrap(), 10.0);
+        assert_eq!(vtg.speed_over_ground_kmh, Some(18.52));
+        assert_eq!(vtg.faa_mode, Some(FaaMode::Differential));
+    }
 }
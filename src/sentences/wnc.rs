@@ -29,9 +29,10 @@ use crate::{
 /// 4. K = Kilometers
 /// 5. Waypoint ID, Destination
 /// 6. Waypoint ID, Origin
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[non_exhaustive]
 pub struct WncData {
     /// Distance, Nautical Miles
     pub distance_nautical_miles: Option<f32>,
@@ -45,7 +46,14 @@ pub struct WncData {
     pub waypoint_id_origin: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
 }
 
-pub fn do_parse_wnc(i: &str) -> Result<WncData, Error> {
+accessors!(WncData {
+    distance_nautical_miles, with_distance_nautical_miles: Option<f32>;
+    distance_kilometers, with_distance_kilometers: Option<f32>;
+    waypoint_id_destination, with_waypoint_id_destination: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+    waypoint_id_origin, with_waypoint_id_origin: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+});
+
+pub fn do_parse_wnc(i: &str) -> Result<WncData, Error<'_>> {
     let (i, distance_nautical_miles) = opt(float)(i)?;
     let (i, _) = char(',')(i)?;
     let (i, _) = opt(char('N'))(i)?;
@@ -89,7 +97,7 @@ mod tests {
     use crate::{parse::parse_nmea_sentence, Error};
     use approx::assert_relative_eq;
 
-    fn run_parse_wnc(line: &str) -> Result<WncData, Error> {
+    fn run_parse_wnc(line: &str) -> Result<WncData, Error<'_>> {
         let s = parse_nmea_sentence(line).expect("WNC sentence initial parse failed");
         assert_eq!(s.checksum, s.calc_checksum());
         parse_wnc(s)
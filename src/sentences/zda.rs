@@ -6,7 +6,11 @@ use nom::{
     IResult,
 };
 
-use crate::{parse::NmeaSentence, sentences::utils::parse_hms, Error, SentenceType};
+use crate::{
+    parse::NmeaSentence,
+    sentences::utils::{parse_hms, zone_offset_seconds},
+    Error, SentenceType,
+};
 
 use super::utils::{parse_num, parse_number_in_range};
 
@@ -29,7 +33,8 @@ use super::utils::{parse_num, parse_number_in_range};
 /// 7. Checksum
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct ZdaData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub utc_time: Option<NaiveTime>,
@@ -40,6 +45,15 @@ pub struct ZdaData {
     pub local_zone_minutes: Option<i8>,
 }
 
+accessors!(ZdaData {
+    utc_time, with_utc_time: Option<NaiveTime>;
+    day, with_day: Option<u8>;
+    month, with_month: Option<u8>;
+    year, with_year: Option<u16>;
+    local_zone_hours, with_local_zone_hours: Option<i8>;
+    local_zone_minutes, with_local_zone_minutes: Option<i8>;
+});
+
 impl ZdaData {
     /// Get UTC date by `day`, `month` and `year` fields.
     /// Returns `None` if any field is `None`.
@@ -63,14 +77,8 @@ impl ZdaData {
     /// Get `chrono::FixedOffset` by `local_zone_hours` and `local_zone_minutes` fields.
     /// Return `Some` if either `local_zone_hours` or `local_zone_minutes` is `Some`.
     pub fn offset(&self) -> Option<FixedOffset> {
-        let hours = self.local_zone_hours.map(i32::from);
-        let minutes = self.local_zone_minutes.map(i32::from);
-        match (hours, minutes) {
-            (Some(h), Some(m)) => FixedOffset::east_opt(((h * 60) + m) * 60),
-            (Some(h), None) => FixedOffset::east_opt(h * 60 * 60),
-            (None, Some(m)) => FixedOffset::east_opt(m * 60),
-            (None, None) => None,
-        }
+        zone_offset_seconds(self.local_zone_hours, self.local_zone_minutes)
+            .and_then(FixedOffset::east_opt)
     }
 
     /// Caluculate local datetime
@@ -230,6 +238,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_zda_boundaries() {
+        let s = parse_nmea_sentence("$GPZDA,235959.9999999999,31,12,2023,-13,59*46").unwrap();
+        assert_eq!(s.checksum, s.calc_checksum());
+        let zda_data = parse_zda(s).unwrap();
+        assert_eq!(
+            zda_data.utc_time,
+            NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999)
+        );
+        assert_eq!(
+            (zda_data.local_zone_hours, zda_data.local_zone_minutes),
+            (Some(-13), Some(-59))
+        );
+        assert_eq!(
+            zda_data.offset(),
+            FixedOffset::west_opt(13 * 3600 + 59 * 60)
+        );
+        assert!(zda_data.local_date_time().is_some());
+    }
+
     #[test]
     fn test_parse_zda_datetime() {
         let s = parse_nmea_sentence("$GPZDA,160012.71,11,03,2004,-1,00*7D").unwrap();
@@ -24,7 +24,8 @@ use crate::{
 /// 4. Checksum
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct ZfoData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_time: Option<NaiveTime>,
@@ -38,7 +39,13 @@ pub struct ZfoData {
     pub waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
 }
 
-fn do_parse_zfo(i: &str) -> Result<ZfoData, Error> {
+accessors!(ZfoData {
+    fix_time, with_fix_time: Option<NaiveTime>;
+    fix_duration, with_fix_duration: Option<Duration>;
+    waypoint_id, with_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+});
+
+fn do_parse_zfo(i: &str) -> Result<ZfoData, Error<'_>> {
     // 1. UTC Time or observation
     let (i, fix_time) = opt(parse_hms)(i)?;
     let (i, _) = char(',')(i)?;
@@ -79,7 +86,7 @@ mod tests {
     use super::*;
     use crate::{parse::parse_nmea_sentence, Error};
 
-    fn run_parse_zfo(line: &str) -> Result<ZfoData, Error> {
+    fn run_parse_zfo(line: &str) -> Result<ZfoData, Error<'_>> {
         let s = parse_nmea_sentence(line).expect("ZFO sentence initial parse failed");
         assert_eq!(s.checksum, s.calc_checksum());
         parse_zfo(s)
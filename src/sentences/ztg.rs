@@ -26,7 +26,8 @@ use super::utils::array_string;
 /// 4. Checksum
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Default)]
+#[non_exhaustive]
 pub struct ZtgData {
     #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
     pub fix_time: Option<NaiveTime>,
@@ -40,7 +41,13 @@ pub struct ZtgData {
     pub waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>,
 }
 
-fn do_parse_ztg(i: &str) -> Result<ZtgData, Error> {
+accessors!(ZtgData {
+    fix_time, with_fix_time: Option<NaiveTime>;
+    fix_duration, with_fix_duration: Option<Duration>;
+    waypoint_id, with_waypoint_id: Option<ArrayString<TEXT_PARAMETER_MAX_LEN>>;
+});
+
+fn do_parse_ztg(i: &str) -> Result<ZtgData, Error<'_>> {
     // 1. UTC Time or observation
     let (i, fix_time) = opt(parse_hms)(i)?;
     let (i, _) = char(',')(i)?;
@@ -81,7 +88,7 @@ mod tests {
     use super::*;
     use crate::{parse::parse_nmea_sentence, Error};
 
-    fn run_parse_ztg(line: &str) -> Result<ZtgData, Error> {
+    fn run_parse_ztg(line: &str) -> Result<ZtgData, Error<'_>> {
         let s = parse_nmea_sentence(line).expect("ZTG sentence initial parse failed");
         assert_eq!(s.checksum, s.calc_checksum());
         parse_ztg(s)
@@ -0,0 +1,158 @@
+//! Thread-safe wrapper around the [`Nmea`] parser.
+
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+use heapless::Vec;
+
+use crate::{sentences::FixType, Error, Fix, Nmea, Satellite, SentenceType};
+
+/// An [`Nmea`] parser that can be shared between threads.
+///
+/// Cloning a `SharedNmea` is cheap and yields a handle to the same parser.
+/// The write lock is only taken for the duration of a single
+/// [`parse()`](SharedNmea::parse) call and readers get owned snapshots, so no
+/// lock is ever held across parses or while the caller uses the data.
+///
+/// A panic while holding the lock does not poison the parser for other
+/// handles: the state is plain data and stays usable.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::SharedNmea;
+///
+/// let nmea = SharedNmea::default();
+///
+/// let reader = nmea.clone();
+/// let ui = std::thread::spawn(move || reader.read_fix());
+///
+/// # #[cfg(feature = "GGA")]
+/// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+///     .unwrap();
+///
+/// let _fix = ui.join().unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SharedNmea {
+    inner: Arc<RwLock<Nmea>>,
+}
+
+impl SharedNmea {
+    /// Wraps an existing parser, e.g. one made by [`Nmea::create_for_navigation()`].
+    pub fn new(nmea: Nmea) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(nmea)),
+        }
+    }
+
+    /// Parses a sentence, see [`Nmea::parse()`].
+    pub fn parse<'a>(&self, sentence: &'a str) -> Result<SentenceType, Error<'a>> {
+        self.write().parse(sentence)
+    }
+
+    /// Parses a sentence for navigation, see [`Nmea::parse_for_fix()`].
+    pub fn parse_for_fix<'a>(&self, sentence: &'a str) -> Result<FixType, Error<'a>> {
+        self.write().parse_for_fix(sentence)
+    }
+
+    /// Returns a snapshot of the current fix.
+    ///
+    /// Blocks only while a sentence is being parsed.
+    pub fn read_fix(&self) -> Fix {
+        self.read().fix()
+    }
+
+    /// Returns a snapshot of the satellites in view, see [`Nmea::satellites()`].
+    ///
+    /// Blocks only while a sentence is being parsed.
+    pub fn read_satellites(&self) -> Vec<Satellite, 58> {
+        self.read().satellites()
+    }
+
+    /// Returns a snapshot of the current fix, or `None` if a sentence is
+    /// being parsed right now.
+    pub fn try_read_fix(&self) -> Option<Fix> {
+        self.try_read().map(|nmea| nmea.fix())
+    }
+
+    /// Returns a snapshot of the satellites in view, or `None` if a sentence
+    /// is being parsed right now.
+    pub fn try_read_satellites(&self) -> Option<Vec<Satellite, 58>> {
+        self.try_read().map(|nmea| nmea.satellites())
+    }
+
+    /// Runs `f` with read access to the whole parser state.
+    ///
+    /// Keep `f` short, parsing is blocked until it returns.
+    pub fn with<R>(&self, f: impl FnOnce(&Nmea) -> R) -> R {
+        f(&self.read())
+    }
+
+    /// Returns a copy of the whole parser state.
+    pub fn snapshot(&self) -> Nmea {
+        self.read().clone()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Nmea> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn try_read(&self) -> Option<RwLockReadGuard<'_, Nmea>> {
+        match self.inner.try_read() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Nmea> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl From<Nmea> for SharedNmea {
+    fn from(nmea: Nmea) -> Self {
+        Self::new(nmea)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "GGA", feature = "GSV"))]
+    fn test_shared_between_threads() {
+        let nmea = SharedNmea::default();
+        assert_eq!(nmea.read_fix(), Fix::default());
+
+        let writer = nmea.clone();
+        std::thread::spawn(move || {
+            writer
+                .parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+                .unwrap();
+            writer
+                .parse("$GPGSV,3,1,11,10,63,137,17,07,61,098,15,05,59,290,20,08,54,157,30*70")
+                .unwrap();
+        })
+        .join()
+        .unwrap();
+
+        let fix = nmea.read_fix();
+        assert_eq!(fix.fix_satellites, Some(8));
+        assert_eq!(fix.altitude_msl, Some(61.7));
+        assert_eq!(nmea.read_satellites().len(), 4);
+        assert_eq!(nmea.try_read_fix(), Some(fix));
+        assert_eq!(nmea.with(|nmea| nmea.hdop()), Some(1.03));
+    }
+
+    #[test]
+    fn test_try_read_while_parsing() {
+        let nmea = SharedNmea::default();
+        let guard = nmea.write();
+        assert!(nmea.try_read_fix().is_none());
+        assert!(nmea.try_read_satellites().is_none());
+        drop(guard);
+        assert!(nmea.try_read_fix().is_some());
+    }
+}
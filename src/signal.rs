@@ -0,0 +1,271 @@
+//! Per-band satellite counts from the Signal IDs of GSV sentences.
+//!
+//! NMEA 4.10+ receivers send one GSV group per constellation and signal,
+//! each with the number of satellites tracked on that signal. This makes it
+//! possible to check that a dual-band receiver and antenna actually track
+//! the second band (e.g. L5/E5a).
+//!
+//! The satellites of [`Nmea::satellites()`] are those of the first Signal
+//! ID received for each constellation, the SNRs of all signals are kept
+//! apart, see [`Nmea::signal_snrs()`] and [`Nmea::best_snr()`].
+//!
+//! The counts are read with [`Nmea::signal_counts()`] and
+//! [`Nmea::total_signal_counts()`], and the total is part of the
+//! [`Fix`](crate::Fix) returned by [`Nmea::fix()`].
+
+use heapless::Vec;
+
+use crate::{
+    sentences::{FrequencyBand, GnssType, GsvData},
+    Nmea,
+};
+
+/// Number of satellite signals whose SNR is kept.
+pub const SIGNAL_SNRS_LEN: usize = 64;
+
+/// Number of satellites tracked per [`FrequencyBand`].
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{sentences::GnssType, Nmea};
+///
+/// let mut nmea = Nmea::default();
+/// # #[cfg(feature = "GSV")]
+/// # {
+/// nmea.parse("$GPGSV,1,1,03,10,63,137,17,07,61,098,15,05,59,290,20,1*55").unwrap();
+/// nmea.parse("$GPGSV,1,1,02,10,63,137,37,05,59,290,35,8*6E").unwrap();
+///
+/// let counts = nmea.signal_counts(GnssType::Gps);
+/// assert_eq!(counts.l1_count(), 3);
+/// assert_eq!(counts.l5_count(), 2);
+/// assert!(counts.is_multi_frequency());
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SignalCounts {
+    counts: [u16; FrequencyBand::COUNT],
+}
+
+impl SignalCounts {
+    /// Returns the number of satellites tracked on the band.
+    pub fn count(&self, band: FrequencyBand) -> u16 {
+        self.counts[band as usize]
+    }
+
+    pub fn l1_count(&self) -> u16 {
+        self.count(FrequencyBand::L1)
+    }
+
+    pub fn l2_count(&self) -> u16 {
+        self.count(FrequencyBand::L2)
+    }
+
+    pub fn l5_count(&self) -> u16 {
+        self.count(FrequencyBand::L5)
+    }
+
+    pub fn e5b_count(&self) -> u16 {
+        self.count(FrequencyBand::E5b)
+    }
+
+    pub fn l6_count(&self) -> u16 {
+        self.count(FrequencyBand::L6)
+    }
+
+    pub fn s_count(&self) -> u16 {
+        self.count(FrequencyBand::S)
+    }
+
+    /// Returns `true` if satellites are tracked on more than one band.
+    pub fn is_multi_frequency(&self) -> bool {
+        self.counts.iter().filter(|&&count| count > 0).count() > 1
+    }
+}
+
+/// Number of satellites in view per constellation and Signal ID.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SignalTable {
+    sats_in_view: [[u16; 16]; GnssType::COUNT],
+}
+
+impl SignalTable {
+    pub(crate) fn update(&mut self, gnss_type: GnssType, signal_id: u8, sats_in_view: u16) {
+        if let Some(count) = self.sats_in_view[gnss_type as usize].get_mut(usize::from(signal_id)) {
+            *count = sats_in_view;
+        }
+    }
+
+    fn counts(&self, gnss_type: GnssType) -> SignalCounts {
+        let mut counts = SignalCounts::default();
+        for (signal_id, &sats_in_view) in self.sats_in_view[gnss_type as usize].iter().enumerate() {
+            // several signals of the same band are usually tracked together
+            if let Some(band) = FrequencyBand::from_signal_id(gnss_type, signal_id as u8) {
+                let count = &mut counts.counts[band as usize];
+                *count = (*count).max(sats_in_view);
+            }
+        }
+        counts
+    }
+}
+
+/// The SNR of a satellite on a signal.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+struct SignalSnr {
+    gnss_type: GnssType,
+    prn: u32,
+    signal_id: u8,
+    snr: Option<i16>,
+}
+
+/// SNRs of the satellites per Signal ID, from the last group of each
+/// signal. Only the first [`SIGNAL_SNRS_LEN`] signals are recorded.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SignalSnrs {
+    snrs: Vec<SignalSnr, SIGNAL_SNRS_LEN>,
+}
+
+impl SignalSnrs {
+    pub(crate) fn update(&mut self, data: &GsvData) {
+        let Some(signal_id) = data.signal_id else {
+            return;
+        };
+        if data.sentence_num == 1 {
+            // a new group replaces the satellites of the signal
+            self.snrs
+                .retain(|snr| snr.gnss_type != data.gnss_type || snr.signal_id != signal_id);
+        }
+        for satellite in data.sats_info.iter().flatten() {
+            let new = SignalSnr {
+                gnss_type: satellite.gnss_type,
+                prn: satellite.prn,
+                signal_id,
+                snr: satellite.snr,
+            };
+            let known = self.snrs.iter_mut().find(|snr| {
+                snr.gnss_type == new.gnss_type && snr.prn == new.prn && snr.signal_id == signal_id
+            });
+            match known {
+                Some(snr) => *snr = new,
+                None => {
+                    let _ = self.snrs.push(new);
+                }
+            }
+        }
+    }
+}
+
+impl Nmea {
+    /// Returns the Signal IDs of a satellite and its SNR on each of them,
+    /// from GSV sentences with a Signal ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{sentences::GnssType, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "GSV")]
+    /// # {
+    /// // E1 and E5b
+    /// nmea.parse("$GAGSV,1,1,02,02,45,120,38,07,30,250,35,7*7F").unwrap();
+    /// nmea.parse("$GAGSV,1,1,02,02,45,120,42,07,30,250,,2*71").unwrap();
+    ///
+    /// let snrs: Vec<_> = nmea.signal_snrs(GnssType::Galileo, 2).collect();
+    /// assert_eq!(snrs, [(7, Some(38.)), (2, Some(42.))]);
+    /// assert_eq!(nmea.best_snr(GnssType::Galileo, 2), Some(42.));
+    /// assert_eq!(nmea.best_snr(GnssType::Galileo, 7), Some(35.));
+    /// # }
+    /// ```
+    pub fn signal_snrs(
+        &self,
+        gnss_type: GnssType,
+        prn: u32,
+    ) -> impl Iterator<Item = (u8, Option<f32>)> + '_ {
+        self.signal_snrs
+            .snrs
+            .iter()
+            .filter(move |snr| snr.gnss_type == gnss_type && snr.prn == prn)
+            .map(|snr| (snr.signal_id, snr.snr.map(f32::from)))
+    }
+
+    /// Returns the SNR of a satellite on a signal.
+    pub fn signal_snr(&self, gnss_type: GnssType, prn: u32, signal_id: u8) -> Option<f32> {
+        self.signal_snrs(gnss_type, prn)
+            .find(|&(id, _)| id == signal_id)
+            .and_then(|(_, snr)| snr)
+    }
+
+    /// Returns the best SNR of a satellite over its signals, or its SNR in
+    /// [`satellites()`](Self::satellites) without Signal IDs.
+    pub fn best_snr(&self, gnss_type: GnssType, prn: u32) -> Option<f32> {
+        let best = self
+            .signal_snrs(gnss_type, prn)
+            .filter_map(|(_, snr)| snr)
+            .reduce(f32::max);
+        best.or_else(|| {
+            self.satellites()
+                .iter()
+                .find(|sat| sat.gnss_type() == gnss_type && sat.prn() == prn)
+                .and_then(|sat| sat.snr())
+        })
+    }
+
+    /// Returns the number of satellites of a constellation tracked per
+    /// band, from GSV sentences with a Signal ID.
+    pub fn signal_counts(&self, gnss_type: GnssType) -> SignalCounts {
+        self.signal_table.counts(gnss_type)
+    }
+
+    /// Returns the number of satellites of all constellations tracked per
+    /// band.
+    pub fn total_signal_counts(&self) -> SignalCounts {
+        let mut total = SignalCounts::default();
+        for gnss_type in GnssType::ALL_TYPES {
+            let counts = self.signal_counts(gnss_type);
+            for (total, count) in total.counts.iter_mut().zip(counts.counts) {
+                *total += count;
+            }
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_counts() {
+        let mut table = SignalTable::default();
+        table.update(GnssType::Galileo, 7, 8);
+        table.update(GnssType::Galileo, 1, 5);
+        table.update(GnssType::Beidou, 1, 10);
+        // B2I and B2b are both E5b
+        table.update(GnssType::Beidou, 0xB, 6);
+        table.update(GnssType::Beidou, 6, 4);
+        // all signals
+        table.update(GnssType::Beidou, 0, 12);
+
+        let galileo = table.counts(GnssType::Galileo);
+        assert_eq!(galileo.l1_count(), 8);
+        assert_eq!(galileo.l5_count(), 5);
+        assert!(galileo.is_multi_frequency());
+
+        let beidou = table.counts(GnssType::Beidou);
+        assert_eq!(beidou.l1_count(), 10);
+        assert_eq!(beidou.e5b_count(), 6);
+        assert_eq!(beidou.l5_count(), 0);
+
+        assert_eq!(table.counts(GnssType::Gps), SignalCounts::default());
+        assert!(!table.counts(GnssType::Gps).is_multi_frequency());
+    }
+}
@@ -0,0 +1,145 @@
+//! Visitor-style parsing, without moving the sentence data through
+//! [`ParseResult`](crate::ParseResult).
+
+use crate::{
+    parse::{checked_sentence, Dispatch},
+    sentences::*,
+    Error, SentenceType,
+};
+
+/// Receives the data of the sentences parsed by [`parse_str_ref()`].
+///
+/// There is one method per sentence type, which does nothing by default, so
+/// a sink only implements the types it is interested in.
+#[allow(unused_variables)]
+pub trait SentenceSink {
+    fn aam(&mut self, data: &AamData) {}
+    fn alm(&mut self, data: &AlmData) {}
+    fn apa(&mut self, data: &ApaData) {}
+    fn bod(&mut self, data: &BodData) {}
+    fn bwc(&mut self, data: &BwcData) {}
+    fn bww(&mut self, data: &BwwData) {}
+    fn dbk(&mut self, data: &DbkData) {}
+    fn dbs(&mut self, data: &DbsData) {}
+    fn dbt(&mut self, data: &DbtData) {}
+    fn dpt(&mut self, data: &DptData) {}
+    fn dtm(&mut self, data: &DtmData) {}
+    fn gbs(&mut self, data: &GbsData) {}
+    fn gga(&mut self, data: &GgaData) {}
+    fn gll(&mut self, data: &GllData) {}
+    fn gns(&mut self, data: &GnsData) {}
+    fn gsa(&mut self, data: &GsaData) {}
+    fn gst(&mut self, data: &GstData) {}
+    fn gsv(&mut self, data: &GsvData) {}
+    fn hdt(&mut self, data: &HdtData) {}
+    fn mda(&mut self, data: &MdaData) {}
+    fn mtw(&mut self, data: &MtwData) {}
+    fn mwv(&mut self, data: &MwvData) {}
+    fn pgrmz(&mut self, data: &PgrmzData) {}
+    fn rmc(&mut self, data: &RmcData) {}
+    fn ttm(&mut self, data: &TtmData) {}
+    fn txt(&mut self, data: &TxtData) {}
+    fn vhw(&mut self, data: &VhwData) {}
+    fn vlw(&mut self, data: &VlwData) {}
+    fn vtg(&mut self, data: &VtgData) {}
+    fn wnc(&mut self, data: &WncData) {}
+    fn zda(&mut self, data: &ZdaData) {}
+    fn zfo(&mut self, data: &ZfoData) {}
+    fn ztg(&mut self, data: &ZtgData) {}
+    /// Called for valid sentences which the crate does not implement.
+    fn unsupported(&mut self, sentence_type: SentenceType) {}
+}
+
+/// Parses a sentence like [`parse_str()`](crate::parse_str), but passes a
+/// reference to its data to the method of the sink for its type, and
+/// returns the type.
+///
+/// The data stays on the stack of this function, which saves copying large
+/// sentences like GSV into a [`ParseResult`](crate::ParseResult) on
+/// embedded targets.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{parse_str_ref, sentences::GsvData, SentenceSink};
+///
+/// #[derive(Default)]
+/// struct SatsInView(Option<u16>);
+///
+/// impl SentenceSink for SatsInView {
+///     fn gsv(&mut self, data: &GsvData) {
+///         self.0 = Some(data.sats_in_view);
+///     }
+/// }
+///
+/// let mut sink = SatsInView::default();
+/// # #[cfg(feature = "GSV")]
+/// # {
+/// parse_str_ref("$GPGSV,1,1,01,01,,,42*7F", &mut sink).unwrap();
+/// assert_eq!(sink.0, Some(1));
+/// # }
+/// ```
+pub fn parse_str_ref<'a>(
+    sentence_input: &'a str,
+    sink: &mut dyn SentenceSink,
+) -> Result<SentenceType, Error<'a>> {
+    let nmea_sentence = checked_sentence(sentence_input)?;
+    let sentence_type = nmea_sentence.message_id;
+    match SentenceType::DISPATCH[sentence_type as usize] {
+        Dispatch::Parser(_, visit) => visit(nmea_sentence, sink)?,
+        Dispatch::Disabled => return Err(Error::DisabledSentence),
+        Dispatch::Unsupported => sink.unsupported(sentence_type),
+    }
+    Ok(sentence_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Types(heapless::Vec<SentenceType, 4>);
+
+    impl SentenceSink for Types {
+        fn gga(&mut self, _data: &GgaData) {
+            self.0.push(SentenceType::GGA).unwrap();
+        }
+
+        fn unsupported(&mut self, sentence_type: SentenceType) {
+            self.0.push(sentence_type).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_str_ref() {
+        let mut sink = Types::default();
+        let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+        if cfg!(feature = "GGA") {
+            assert_eq!(parse_str_ref(gga, &mut sink), Ok(SentenceType::GGA));
+        } else {
+            assert_eq!(parse_str_ref(gga, &mut sink), Err(Error::DisabledSentence));
+        }
+        // a sentence the sink is not interested in
+        if cfg!(feature = "HDT") {
+            assert_eq!(
+                parse_str_ref("$GPHDT,274.07,T*03", &mut sink),
+                Ok(SentenceType::HDT)
+            );
+        }
+        assert_eq!(
+            parse_str_ref("$HEROT,-0.3,A*05", &mut sink),
+            Ok(SentenceType::ROT)
+        );
+        assert!(matches!(
+            parse_str_ref("$GPHDT,274.07,T*00", &mut sink),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+
+        let expected: &[SentenceType] = if cfg!(feature = "GGA") {
+            &[SentenceType::GGA, SentenceType::ROT]
+        } else {
+            &[SentenceType::ROT]
+        };
+        assert_eq!(sink.0, expected);
+    }
+}
@@ -0,0 +1,290 @@
+//! Binary snapshots of the navigation state, to hot-start a [`Nmea`] after
+//! the device slept between fixes.
+//!
+//! A snapshot holds the last fix, its time and DOPs, the leap second state
+//! and the odometer distances, but not the satellites in view nor the
+//! configuration. It is
+//! encoded with [`postcard`] after a version byte. Fields are only ever
+//! appended in later versions, so a snapshot can be restored both by older
+//! and by newer versions of this crate.
+//!
+//! Since version 3 the coordinates are stored as `f64` with and without the
+//! `f32-only` feature. The snapshots of versions 1 and 2 store them as the
+//! [`Float`] of the build which wrote them, and can only be restored by a
+//! build with the same `f32-only` setting.
+
+use chrono::{NaiveDate, NaiveTime};
+use heapless::Vec;
+
+use crate::{odometer::Anchor, sentences::FixType, Float, LeapSecondInfo, Nmea, Odometer};
+
+/// Version of the snapshots written by this crate.
+pub const SNAPSHOT_VERSION: u8 = 3;
+
+/// First version storing the coordinates as `f64`.
+const F64_COORDINATES_VERSION: u8 = 3;
+
+/// Errors of [`Nmea::snapshot()`] and [`Nmea::restore()`].
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The snapshot does not fit into the buffer.
+    BufferFull,
+    /// The snapshot is truncated or corrupted.
+    Invalid,
+    /// The snapshot has an unknown version.
+    UnknownVersion(u8),
+}
+
+impl core::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SnapshotError::BufferFull => write!(f, "The snapshot does not fit into the buffer"),
+            SnapshotError::Invalid => write!(f, "The snapshot is truncated or corrupted"),
+            SnapshotError::UnknownVersion(version) => {
+                write!(f, "Unknown snapshot version {}", version)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {}
+
+/// The state in a version 1 snapshot, new versions append their fields.
+///
+/// The coordinates are `F`, the [`Float`] of the build before version 3 and
+/// `f64` since.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SnapshotV1<F> {
+    fix_time: Option<NaiveTime>,
+    fix_date: Option<NaiveDate>,
+    fix_type: Option<FixType>,
+    latitude: Option<F>,
+    longitude: Option<F>,
+    altitude: Option<f32>,
+    speed_over_ground: Option<f32>,
+    true_course: Option<f32>,
+    num_of_fix_satellites: Option<u32>,
+    hdop: Option<f32>,
+    vdop: Option<f32>,
+    pdop: Option<f32>,
+    geoid_separation: Option<f32>,
+    age_of_differential: Option<f32>,
+    dgps_station_id: Option<u16>,
+    last_fix_time: Option<NaiveTime>,
+    leap_seconds: LeapSecondInfo,
+}
+
+impl<F> SnapshotV1<F> {
+    fn map_coordinates<T>(self, coordinate: fn(F) -> T) -> SnapshotV1<T> {
+        SnapshotV1 {
+            fix_time: self.fix_time,
+            fix_date: self.fix_date,
+            fix_type: self.fix_type,
+            latitude: self.latitude.map(coordinate),
+            longitude: self.longitude.map(coordinate),
+            altitude: self.altitude,
+            speed_over_ground: self.speed_over_ground,
+            true_course: self.true_course,
+            num_of_fix_satellites: self.num_of_fix_satellites,
+            hdop: self.hdop,
+            vdop: self.vdop,
+            pdop: self.pdop,
+            geoid_separation: self.geoid_separation,
+            age_of_differential: self.age_of_differential,
+            dgps_station_id: self.dgps_station_id,
+            last_fix_time: self.last_fix_time,
+            leap_seconds: self.leap_seconds,
+        }
+    }
+}
+
+/// The fields appended by version 2 snapshots.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SnapshotV2 {
+    odometer: Odometer,
+    odometer_anchor: Option<Anchor>,
+}
+
+impl Nmea {
+    /// Writes the navigation state into a binary snapshot of at most `N`
+    /// bytes, see the [module documentation](crate::snapshot).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "GGA")]
+    /// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///     .unwrap();
+    /// let snapshot = nmea.snapshot::<128>().unwrap();
+    ///
+    /// // after waking up
+    /// let mut restored = Nmea::default();
+    /// restored.restore(&snapshot).unwrap();
+    /// assert_eq!(restored.latitude(), nmea.latitude());
+    /// ```
+    pub fn snapshot<const N: usize>(&self) -> Result<Vec<u8, N>, SnapshotError> {
+        let state_v2 = SnapshotV2 {
+            odometer: self.odometer.odometer,
+            odometer_anchor: self.odometer.anchor,
+        };
+        let mut buffer = [0; N];
+        let (version, data) = buffer.split_first_mut().ok_or(SnapshotError::BufferFull)?;
+        *version = SNAPSHOT_VERSION;
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let state = self
+            .snapshot_v1()
+            .map_coordinates(|coordinate| coordinate as f64);
+        let len = postcard::to_slice(&state, data)
+            .map_err(|_| SnapshotError::BufferFull)?
+            .len();
+        let len = len
+            + postcard::to_slice(&state_v2, &mut data[len..])
+                .map_err(|_| SnapshotError::BufferFull)?
+                .len();
+        Ok(Vec::from_slice(&buffer[..=len]).expect("the snapshot fits into N bytes"))
+    }
+
+    fn snapshot_v1(&self) -> SnapshotV1<Float> {
+        SnapshotV1 {
+            fix_time: self.fix_time,
+            fix_date: self.fix_date,
+            fix_type: self.fix_type,
+            latitude: self.latitude,
+            longitude: self.longitude,
+            altitude: self.altitude,
+            speed_over_ground: self.speed_over_ground,
+            true_course: self.true_course,
+            num_of_fix_satellites: self.num_of_fix_satellites,
+            hdop: self.hdop,
+            vdop: self.vdop,
+            pdop: self.pdop,
+            geoid_separation: self.geoid_separation,
+            age_of_differential: self.age_of_differential,
+            dgps_station_id: self.dgps_station_id,
+            last_fix_time: self.last_fix_time,
+            leap_seconds: self.leap_seconds,
+        }
+    }
+
+    /// Restores the navigation state of a snapshot written by
+    /// [`snapshot()`](Self::snapshot), keeping the satellites in view and the
+    /// configuration.
+    ///
+    /// Fields of newer snapshot versions are ignored. The state is left
+    /// unchanged on errors.
+    pub fn restore(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        let (&version, data) = snapshot.split_first().ok_or(SnapshotError::Invalid)?;
+        if version == 0 {
+            return Err(SnapshotError::UnknownVersion(version));
+        }
+        // later versions only append fields
+        let (state, data) = if version >= F64_COORDINATES_VERSION {
+            let (state, data) = postcard::take_from_bytes::<SnapshotV1<f64>>(data)
+                .map_err(|_| SnapshotError::Invalid)?;
+            #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+            let state = state.map_coordinates(|coordinate| coordinate as Float);
+            (state, data)
+        } else {
+            postcard::take_from_bytes::<SnapshotV1<Float>>(data)
+                .map_err(|_| SnapshotError::Invalid)?
+        };
+        let state_v2 = if version >= 2 {
+            let (state_v2, _newer_fields) = postcard::take_from_bytes::<SnapshotV2>(data)
+                .map_err(|_| SnapshotError::Invalid)?;
+            Some(state_v2)
+        } else {
+            None
+        };
+        self.fix_time = state.fix_time;
+        self.fix_date = state.fix_date;
+        self.fix_type = state.fix_type;
+        self.latitude = state.latitude;
+        self.longitude = state.longitude;
+        self.altitude = state.altitude;
+        self.speed_over_ground = state.speed_over_ground;
+        self.true_course = state.true_course;
+        self.num_of_fix_satellites = state.num_of_fix_satellites;
+        self.hdop = state.hdop;
+        self.vdop = state.vdop;
+        self.pdop = state.pdop;
+        self.geoid_separation = state.geoid_separation;
+        self.age_of_differential = state.age_of_differential;
+        self.dgps_station_id = state.dgps_station_id;
+        self.last_fix_time = state.last_fix_time;
+        self.leap_seconds = state.leap_seconds;
+        if let Some(state) = state_v2 {
+            self.odometer.odometer = state.odometer;
+            self.odometer.anchor = state.odometer_anchor;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut nmea = Nmea::default();
+        nmea.fix_time = NaiveTime::from_hms_opt(9, 27, 50);
+        nmea.fix_type = Some(FixType::Rtk);
+        nmea.latitude = Some(53.361336);
+        nmea.longitude = Some(-6.50562);
+        nmea.hdop = Some(1.03);
+        nmea.leap_seconds.offset = Some(18);
+        nmea.odometer.odometer.total = 12_345.6;
+        let snapshot = nmea.snapshot::<128>().unwrap();
+        assert_eq!(snapshot[0], SNAPSHOT_VERSION);
+
+        let mut restored = Nmea::default();
+        restored.restore(&snapshot).unwrap();
+        assert_eq!(restored.fix_time, nmea.fix_time);
+        assert_eq!(restored.fix_type, nmea.fix_type);
+        assert_eq!(restored.latitude, nmea.latitude);
+        assert_eq!(restored.longitude, nmea.longitude);
+        assert_eq!(restored.hdop, nmea.hdop);
+        assert_eq!(restored.leap_seconds, nmea.leap_seconds);
+        assert_eq!(restored.odometer(), nmea.odometer());
+
+        // a newer version with appended fields
+        let mut newer = snapshot.clone();
+        newer[0] = SNAPSHOT_VERSION + 1;
+        newer.extend_from_slice(&[1, 2, 3]).unwrap();
+        let mut restored = Nmea::default();
+        restored.restore(&newer).unwrap();
+        assert_eq!(restored.latitude, nmea.latitude);
+
+        // a version 1 snapshot, without the odometer
+        let mut v1 = [1; 128];
+        let len = postcard::to_slice(&nmea.snapshot_v1(), &mut v1[1..])
+            .unwrap()
+            .len();
+        let mut restored = Nmea::default();
+        restored.restore(&v1[..=len]).unwrap();
+        assert_eq!(restored.latitude, nmea.latitude);
+        assert_eq!(restored.odometer().total, 0.);
+
+        // the coordinates are `f64` since version 3
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let latitude = nmea.latitude.map(|latitude| latitude as f64);
+        let (state, _) = postcard::take_from_bytes::<SnapshotV1<f64>>(&snapshot[1..]).unwrap();
+        assert_eq!(state.latitude, latitude);
+
+        assert_eq!(nmea.snapshot::<8>().unwrap_err(), SnapshotError::BufferFull);
+        assert_eq!(
+            restored.restore(&snapshot[..snapshot.len() / 2]),
+            Err(SnapshotError::Invalid)
+        );
+        assert_eq!(
+            restored.restore(&[0]),
+            Err(SnapshotError::UnknownVersion(0))
+        );
+        assert_eq!(restored.restore(&[]), Err(SnapshotError::Invalid));
+    }
+}
@@ -0,0 +1,228 @@
+//! Per-satellite SNR history for antenna and signal diagnostics.
+
+use heapless::Vec;
+
+use crate::{sentences::GnssType, Satellite};
+
+/// Number of SNR samples kept per satellite.
+pub const SNR_HISTORY_LEN: usize = 16;
+
+/// Number of satellites tracked by [`SnrHistory`].
+pub const SNR_HISTORY_SATELLITES: usize = 58;
+
+/// The last [`SNR_HISTORY_LEN`] SNR samples of one satellite.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SatelliteSnr {
+    gnss_type: GnssType,
+    prn: u32,
+    samples: Vec<f32, SNR_HISTORY_LEN>,
+    /// Index of the oldest sample once `samples` is full
+    next: usize,
+    last_update: u32,
+}
+
+impl SatelliteSnr {
+    fn new(gnss_type: GnssType, prn: u32) -> Self {
+        Self {
+            gnss_type,
+            prn,
+            samples: Vec::new(),
+            next: 0,
+            last_update: 0,
+        }
+    }
+
+    fn push(&mut self, snr: f32) {
+        if self.samples.push(snr).is_err() {
+            self.samples[self.next] = snr;
+            self.next = (self.next + 1) % SNR_HISTORY_LEN;
+        }
+    }
+
+    #[inline]
+    pub fn gnss_type(&self) -> GnssType {
+        self.gnss_type
+    }
+
+    #[inline]
+    pub fn prn(&self) -> u32 {
+        self.prn
+    }
+
+    /// Returns the SNR samples in dB, in no particular order.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Returns the mean SNR in dB.
+    pub fn mean_snr(&self) -> Option<f32> {
+        mean(self.samples.iter().copied())
+    }
+
+    /// Returns the lowest SNR in dB.
+    pub fn min_snr(&self) -> Option<f32> {
+        self.samples.iter().copied().reduce(f32::min)
+    }
+
+    /// Returns the highest SNR in dB.
+    pub fn max_snr(&self) -> Option<f32> {
+        self.samples.iter().copied().reduce(f32::max)
+    }
+}
+
+/// SNR history of the satellites in view, updated from GSV sentences.
+///
+/// Every report of a satellite with an SNR in a GSV sentence adds one
+/// sample. When more than [`SNR_HISTORY_SATELLITES`] satellites have been
+/// seen, the one that was reported least recently is forgotten.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnrHistory {
+    satellites: Vec<SatelliteSnr, SNR_HISTORY_SATELLITES>,
+    updates: u32,
+}
+
+impl SnrHistory {
+    /// Returns the history of a satellite.
+    pub fn get(&self, gnss_type: GnssType, prn: u32) -> Option<&SatelliteSnr> {
+        self.satellites
+            .iter()
+            .find(|sat| sat.gnss_type == gnss_type && sat.prn == prn)
+    }
+
+    /// Returns the histories of all tracked satellites.
+    pub fn iter(&self) -> impl Iterator<Item = &SatelliteSnr> {
+        self.satellites.iter()
+    }
+
+    /// Returns statistics over all tracked satellites.
+    pub fn summary(&self) -> SignalQualitySummary {
+        SignalQualitySummary {
+            satellites: self.satellites.len(),
+            mean_snr: mean(self.satellites.iter().filter_map(SatelliteSnr::mean_snr)),
+            min_snr: self
+                .satellites
+                .iter()
+                .filter_map(SatelliteSnr::min_snr)
+                .reduce(f32::min),
+            max_snr: self
+                .satellites
+                .iter()
+                .filter_map(SatelliteSnr::max_snr)
+                .reduce(f32::max),
+        }
+    }
+
+    pub(crate) fn update(&mut self, satellite: &Satellite) {
+        let Some(snr) = satellite.snr() else {
+            return;
+        };
+        self.updates = self.updates.wrapping_add(1);
+        let position = self
+            .satellites
+            .iter()
+            .position(|sat| sat.gnss_type == satellite.gnss_type && sat.prn == satellite.prn);
+        let index = match position {
+            Some(index) => index,
+            None => {
+                let new = SatelliteSnr::new(satellite.gnss_type, satellite.prn);
+                match self.satellites.push(new) {
+                    Ok(()) => self.satellites.len() - 1,
+                    Err(new) => {
+                        let oldest = self
+                            .satellites
+                            .iter()
+                            .enumerate()
+                            .max_by_key(|(_, sat)| self.updates.wrapping_sub(sat.last_update))
+                            .map(|(index, _)| index);
+                        // the history is full, so there is an oldest entry
+                        let index = oldest.unwrap_or_default();
+                        self.satellites[index] = new;
+                        index
+                    }
+                }
+            }
+        };
+        let sat = &mut self.satellites[index];
+        sat.push(snr);
+        sat.last_update = self.updates;
+    }
+}
+
+/// Signal quality statistics, see [`SnrHistory::summary()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignalQualitySummary {
+    /// Number of satellites with an SNR history
+    pub satellites: usize,
+    /// Mean of the per-satellite mean SNRs in dB
+    pub mean_snr: Option<f32>,
+    /// Lowest SNR sample in dB
+    pub min_snr: Option<f32>,
+    /// Highest SNR sample in dB
+    pub max_snr: Option<f32>,
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0., 0u32), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    fn satellite(prn: u32, snr: Option<i16>) -> Satellite {
+        Satellite {
+            gnss_type: GnssType::Gps,
+            prn,
+            elevation: None,
+            azimuth: None,
+            snr,
+            #[cfg(feature = "sat-rates")]
+            rates: None,
+        }
+    }
+
+    #[test]
+    fn test_snr_history() {
+        let mut history = SnrHistory::default();
+        for snr in 0..20 {
+            history.update(&satellite(1, Some(snr)));
+        }
+        history.update(&satellite(2, Some(40)));
+        history.update(&satellite(3, None));
+
+        let sat = history.get(GnssType::Gps, 1).unwrap();
+        assert_eq!(sat.samples().len(), SNR_HISTORY_LEN);
+        assert_eq!(sat.min_snr(), Some(4.));
+        assert_eq!(sat.max_snr(), Some(19.));
+        assert_relative_eq!(sat.mean_snr().unwrap(), 11.5);
+        assert!(history.get(GnssType::Gps, 3).is_none());
+
+        let summary = history.summary();
+        assert_eq!(summary.satellites, 2);
+        assert_relative_eq!(summary.mean_snr.unwrap(), 25.75);
+        assert_eq!(summary.min_snr, Some(4.));
+        assert_eq!(summary.max_snr, Some(40.));
+    }
+
+    #[test]
+    fn test_evicts_least_recent() {
+        let mut history = SnrHistory::default();
+        for prn in 0..SNR_HISTORY_SATELLITES as u32 {
+            history.update(&satellite(prn, Some(30)));
+        }
+        history.update(&satellite(0, Some(30)));
+        history.update(&satellite(100, Some(30)));
+        assert!(history.get(GnssType::Gps, 0).is_some());
+        assert!(history.get(GnssType::Gps, 1).is_none());
+        assert!(history.get(GnssType::Gps, 100).is_some());
+    }
+}
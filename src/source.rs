@@ -0,0 +1,469 @@
+//! Labelling sentences by their source in installations with several
+//! instances of the same kind of device, e.g. two GPS receivers or two
+//! engines on one bus.
+//!
+//! NMEA 0183 identifies instances in two ways: by talker ID, and by the
+//! `STN` (Multiple Data ID) sentence, which applies its data ID to the
+//! sentences that follow it until the next `STN`. A [`SourceMap`] maps both
+//! to user defined labels, and a [`SourceMux`] keeps one [`Nmea`] per label.
+//!
+//! The mux also counts the sentences and checksum errors of each source, so
+//! that the port of a multiplexer with a bad connection stands out in the
+//! [`SourceMux::link_report()`].
+
+use core::time::Duration;
+
+use heapless::Vec;
+
+use crate::{
+    clock::ClockRef,
+    parse::{checked_sentence, parse_nmea_sentence},
+    Clock, Error, Nmea, SentenceType,
+};
+
+/// Default number of mappings and sources.
+pub const SOURCE_MAP_LEN: usize = 8;
+
+/// Mapping from talker IDs and `STN` data IDs to source labels.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::source::SourceMap;
+///
+/// let mut sources = SourceMap::<4>::new();
+/// sources.map_station(1, "port").unwrap();
+/// sources.map_station(2, "starboard").unwrap();
+/// sources.map_talker("GP", "gps").unwrap();
+///
+/// assert_eq!(sources.source("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76"), Some("gps"));
+/// assert_eq!(sources.source("$GPSTN,02*70"), Some("starboard"));
+/// // the data ID applies until the next STN sentence
+/// assert_eq!(sources.source("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76"), Some("starboard"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap<const N: usize = SOURCE_MAP_LEN> {
+    talkers: Vec<(&'static str, &'static str), N>,
+    stations: Vec<(u8, &'static str), N>,
+    station: Option<u8>,
+}
+
+impl<const N: usize> SourceMap<N> {
+    pub const fn new() -> Self {
+        Self {
+            talkers: Vec::new(),
+            stations: Vec::new(),
+            station: None,
+        }
+    }
+
+    /// Maps a talker ID, e.g. `GP` or the `--` placeholder, to a label.
+    ///
+    /// Returns the label back if there are already `N` talker mappings.
+    pub fn map_talker(
+        &mut self,
+        talker_id: &'static str,
+        label: &'static str,
+    ) -> Result<(), &'static str> {
+        match self.talkers.iter_mut().find(|(id, _)| *id == talker_id) {
+            Some(mapping) => {
+                mapping.1 = label;
+                Ok(())
+            }
+            None => self
+                .talkers
+                .push((talker_id, label))
+                .map_err(|(_, label)| label),
+        }
+    }
+
+    /// Maps an `STN` data ID (00 to 99) to a label.
+    ///
+    /// Returns the label back if there are already `N` data ID mappings.
+    pub fn map_station(&mut self, data_id: u8, label: &'static str) -> Result<(), &'static str> {
+        match self.stations.iter_mut().find(|(id, _)| *id == data_id) {
+            Some(mapping) => {
+                mapping.1 = label;
+                Ok(())
+            }
+            None => self
+                .stations
+                .push((data_id, label))
+                .map_err(|(_, label)| label),
+        }
+    }
+
+    /// Returns the label of the sentence's source.
+    ///
+    /// The data ID of the last `STN` sentence takes precedence over the
+    /// talker ID, as long as it is mapped. `STN` sentences update the
+    /// current data ID and return its label. Returns `None` for unmapped
+    /// sources and malformed sentences.
+    pub fn source(&mut self, sentence: &str) -> Option<&'static str> {
+        let nmea_sentence = parse_nmea_sentence(sentence).ok()?;
+        if nmea_sentence.message_id == SentenceType::STN {
+            self.station = nmea_sentence.data.parse().ok();
+        }
+        self.station
+            .and_then(|station| {
+                self.stations
+                    .iter()
+                    .find(|(id, _)| *id == station)
+                    .map(|(_, label)| *label)
+            })
+            .or_else(|| {
+                self.talkers
+                    .iter()
+                    .find(|(id, _)| *id == nmea_sentence.talker_id)
+                    .map(|(_, label)| *label)
+            })
+    }
+
+    /// Forgets the data ID of the last `STN` sentence.
+    pub fn reset_station(&mut self) {
+        self.station = None;
+    }
+}
+
+/// Counters of the sentences of a source, see [`SourceMux::link_report()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LinkStats {
+    /// Sentences with a valid checksum, supported or not
+    pub sentences: u32,
+    /// Sentences with a wrong checksum
+    pub checksum_errors: u32,
+    /// Sentences with other errors, e.g. invalid fields
+    pub other_errors: u32,
+    /// Time of the first sentence, with a clock
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub first: Option<Duration>,
+    /// Time of the last sentence, with a clock
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub last: Option<Duration>,
+}
+
+impl LinkStats {
+    /// Returns the number of sentences, with errors or not.
+    pub fn total(&self) -> u32 {
+        self.sentences
+            .saturating_add(self.checksum_errors)
+            .saturating_add(self.other_errors)
+    }
+
+    /// Returns the share of the sentences with a wrong checksum, `None`
+    /// without sentences.
+    pub fn checksum_error_rate(&self) -> Option<f32> {
+        match self.total() {
+            0 => None,
+            total => Some(self.checksum_errors as f32 / total as f32),
+        }
+    }
+
+    /// Returns the sentences per second between the first and the last
+    /// sentence, `None` without clock or with a single sentence.
+    pub fn sentence_rate(&self) -> Option<f32> {
+        let elapsed = self.last?.checked_sub(self.first?)?.as_secs_f32();
+        (elapsed > 0.).then(|| (self.total() - 1) as f32 / elapsed)
+    }
+
+    fn record<T>(&mut self, result: &Result<T, Error>, now: Option<Duration>) {
+        let counter = match result {
+            Ok(_) | Err(Error::Unsupported(_) | Error::DisabledSentence) => &mut self.sentences,
+            Err(Error::ChecksumMismatch { .. }) => &mut self.checksum_errors,
+            Err(_) => &mut self.other_errors,
+        };
+        *counter = counter.saturating_add(1);
+        if now.is_some() {
+            self.first = self.first.or(now);
+            self.last = now;
+        }
+    }
+}
+
+/// The statistics of a source compared with the others, see
+/// [`SourceMux::link_report()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkReport {
+    pub label: &'static str,
+    pub stats: LinkStats,
+    /// Checksum error rate divided by the one of all sources, `None` if there
+    /// are no errors at all
+    pub relative_error_rate: Option<f32>,
+}
+
+#[derive(Debug, Clone)]
+struct Receiver {
+    label: &'static str,
+    nmea: Nmea,
+    stats: LinkStats,
+}
+
+/// Routes sentences to one [`Nmea`] per source label.
+///
+/// Sentences of unmapped sources, and of more than `N` distinct labels, are
+/// ignored. Sentences with a wrong checksum are still attributed to their
+/// source by their talker ID, or by the last `STN` sentence.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::source::{SourceMap, SourceMux};
+///
+/// let mut sources = SourceMap::new();
+/// sources.map_station(1, "port").unwrap();
+/// sources.map_station(2, "starboard").unwrap();
+/// let mut mux = SourceMux::<2>::new(sources);
+///
+/// # #[cfg(feature = "GGA")]
+/// # {
+/// mux.parse("$GPSTN,01*73").unwrap();
+/// mux.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+///     .unwrap();
+/// assert_eq!(mux.receiver("port").unwrap().fix_satellites(), Some(8));
+/// assert!(mux.receiver("starboard").is_none());
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SourceMux<const N: usize = SOURCE_MAP_LEN> {
+    map: SourceMap<N>,
+    receivers: Vec<Receiver, N>,
+    clock: Option<ClockRef>,
+    /// Sentences of no mapped source, malformed ones included
+    unattributed: u32,
+}
+
+impl<'a, const N: usize> SourceMux<N> {
+    pub fn new(map: SourceMap<N>) -> Self {
+        Self {
+            map,
+            receivers: Vec::new(),
+            clock: None,
+            unattributed: 0,
+        }
+    }
+
+    /// Sets the clock of the sentence rates, which is also set on the
+    /// [`Nmea`] of each source.
+    pub fn set_clock(&mut self, clock: &'static (dyn Clock + Sync)) {
+        self.clock = Some(ClockRef(clock));
+        for receiver in self.receivers.iter_mut() {
+            receiver.nmea.set_clock(clock);
+        }
+    }
+
+    /// Parses a sentence with the [`Nmea`] of its source and returns the
+    /// source label and the sentence type.
+    ///
+    /// Returns `Ok(None)` for ignored sentences and for `STN` sentences,
+    /// which only switch the current source.
+    pub fn parse(
+        &mut self,
+        sentence: &'a str,
+    ) -> Result<Option<(&'static str, SentenceType)>, Error<'a>> {
+        let Some(label) = self.map.source(sentence) else {
+            self.unattributed = self.unattributed.saturating_add(1);
+            return Ok(None);
+        };
+        let now = self.clock.map(|clock| clock.0.now());
+        let index = match self.receivers.iter().position(|r| r.label == label) {
+            Some(index) => index,
+            None => {
+                let mut nmea = Nmea::default();
+                if let Some(clock) = self.clock {
+                    nmea.set_clock(clock.0);
+                }
+                let receiver = Receiver {
+                    label,
+                    nmea,
+                    stats: LinkStats::default(),
+                };
+                if self.receivers.push(receiver).is_err() {
+                    return Ok(None);
+                }
+                self.receivers.len() - 1
+            }
+        };
+        let receiver = &mut self.receivers[index];
+        let result = if parse_nmea_sentence(sentence)?.message_id == SentenceType::STN {
+            checked_sentence(sentence).map(|_| None)
+        } else {
+            receiver.nmea.parse(sentence).map(Some)
+        };
+        receiver.stats.record(&result, now);
+        Ok(result?.map(|sentence_type| (label, sentence_type)))
+    }
+
+    /// Returns the state of a source.
+    pub fn receiver(&self, label: &str) -> Option<&Nmea> {
+        self.receivers
+            .iter()
+            .find(|receiver| receiver.label == label)
+            .map(|receiver| &receiver.nmea)
+    }
+
+    /// Returns the labels and states of all sources seen so far.
+    pub fn receivers(&self) -> impl Iterator<Item = (&'static str, &Nmea)> {
+        self.receivers
+            .iter()
+            .map(|receiver| (receiver.label, &receiver.nmea))
+    }
+
+    /// Returns the counters of a source.
+    pub fn link_stats(&self, label: &str) -> Option<LinkStats> {
+        self.receivers
+            .iter()
+            .find(|receiver| receiver.label == label)
+            .map(|receiver| receiver.stats)
+    }
+
+    /// Returns the number of sentences of no mapped source, or too
+    /// malformed to find their source.
+    pub fn unattributed_sentences(&self) -> u32 {
+        self.unattributed
+    }
+
+    /// Compares the checksum error rates of the sources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::source::{SourceMap, SourceMux};
+    ///
+    /// let mut sources = SourceMap::new();
+    /// sources.map_talker("GP", "gps").unwrap();
+    /// sources.map_talker("HE", "compass").unwrap();
+    /// let mut mux = SourceMux::<2>::new(sources);
+    ///
+    /// # #[cfg(feature = "HDT")]
+    /// # {
+    /// for _ in 0..3 {
+    ///     mux.parse("$GPHDT,274.07,T*03").unwrap();
+    ///     mux.parse("$HEHDT,274.07,T*19").unwrap();
+    /// }
+    /// // a corrupted byte
+    /// assert!(mux.parse("$HEHDT,274.97,T*19").is_err());
+    ///
+    /// let worst = mux.worst_link().unwrap();
+    /// assert_eq!(worst.label, "compass");
+    /// assert_eq!(worst.stats.checksum_errors, 1);
+    /// // a quarter of the sentences of the compass against a seventh overall
+    /// assert!((worst.relative_error_rate.unwrap() - 1.75).abs() < 1e-3);
+    /// # }
+    /// ```
+    pub fn link_report(&self) -> impl Iterator<Item = LinkReport> + '_ {
+        let (errors, total) = self.receivers.iter().fold((0, 0), |(errors, total), r| {
+            (
+                errors + u64::from(r.stats.checksum_errors),
+                total + u64::from(r.stats.total()),
+            )
+        });
+        let overall = (errors > 0).then(|| errors as f32 / total as f32);
+        self.receivers.iter().map(move |receiver| LinkReport {
+            label: receiver.label,
+            stats: receiver.stats,
+            relative_error_rate: overall
+                .zip(receiver.stats.checksum_error_rate())
+                .map(|(overall, rate)| rate / overall),
+        })
+    }
+
+    /// Returns the source with the highest checksum error rate, `None` if
+    /// there are no errors.
+    pub fn worst_link(&self) -> Option<LinkReport> {
+        self.link_report()
+            .filter(|report| report.stats.checksum_errors > 0)
+            .max_by(|a, b| {
+                let rate = |report: &LinkReport| report.stats.checksum_error_rate().unwrap_or(0.);
+                rate(a).total_cmp(&rate(b))
+            })
+    }
+
+    /// Returns the source map, e.g. to add mappings.
+    pub fn map_mut(&mut self) -> &mut SourceMap<N> {
+        &mut self.map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_map() {
+        let mut sources = SourceMap::<2>::new();
+        sources.map_talker("--", "unknown").unwrap();
+        sources.map_talker("II", "instruments").unwrap();
+        assert_eq!(sources.map_talker("GP", "gps"), Err("gps"));
+        // remapping an existing talker does not need space
+        sources.map_talker("II", "integrated").unwrap();
+        sources.map_station(1, "port").unwrap();
+
+        assert_eq!(sources.source("$--HDT,274.07,T*14"), Some("unknown"));
+        assert_eq!(sources.source("$IIHDT,274.07,T*14"), Some("integrated"));
+        assert_eq!(sources.source("$GPHDT,274.07,T*03"), None);
+        assert_eq!(sources.source("not a sentence"), None);
+        assert_eq!(sources.source("$IISTN,01*64"), Some("port"));
+        assert_eq!(sources.source("$--HDT,274.07,T*14"), Some("port"));
+        sources.reset_station();
+        assert_eq!(sources.source("$--HDT,274.07,T*14"), Some("unknown"));
+    }
+
+    #[test]
+    #[cfg(feature = "HDT")]
+    fn test_link_stats() {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        static NOW_MS: AtomicU64 = AtomicU64::new(0);
+        fn now() -> Duration {
+            Duration::from_millis(NOW_MS.load(Ordering::SeqCst))
+        }
+
+        let mut sources = SourceMap::<2>::new();
+        sources.map_station(1, "port").unwrap();
+        sources.map_station(2, "starboard").unwrap();
+        let mut mux = SourceMux::new(sources);
+        mux.set_clock(&(now as fn() -> Duration));
+
+        assert_eq!(mux.parse("$IIHDT,274.07,T*14"), Ok(None));
+        assert_eq!(mux.unattributed_sentences(), 1);
+        assert_eq!(mux.parse("$IISTN,01*64"), Ok(None));
+        for _ in 0..4 {
+            NOW_MS.fetch_add(250, Ordering::SeqCst);
+            mux.parse("$IIHDT,274.07,T*14").unwrap();
+        }
+        let port = mux.link_stats("port").unwrap();
+        assert_eq!((port.sentences, port.checksum_errors), (5, 0));
+        assert_eq!(port.checksum_error_rate(), Some(0.));
+        assert_eq!(port.sentence_rate(), Some(4.));
+        assert_eq!(mux.link_stats("starboard"), None);
+
+        // a corrupted STN still switches to and counts on its station
+        assert!(mux.parse("$IISTN,02*00").is_err());
+        assert!(mux.parse("$IIHDT,274.07,T*00").is_err());
+        assert!(matches!(
+            mux.parse("$IIHDT,27x.07,T*58"),
+            Err(Error::ParsingError(_))
+        ));
+        mux.parse("$IIHDT,274.07,T*14").unwrap();
+        let starboard = mux.link_stats("starboard").unwrap();
+        assert_eq!(
+            (
+                starboard.sentences,
+                starboard.checksum_errors,
+                starboard.other_errors
+            ),
+            (1, 2, 1)
+        );
+        assert_eq!(starboard.sentence_rate(), None);
+
+        let report: Vec<LinkReport, 2> = mux.link_report().collect();
+        assert_eq!(report[0].relative_error_rate, Some(0.));
+        // 2 errors of 4 sentences against 2 of 9 overall
+        assert!((report[1].relative_error_rate.unwrap() - 2.25).abs() < 1e-3);
+        assert_eq!(mux.worst_link().unwrap().label, "starboard");
+    }
+}
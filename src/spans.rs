@@ -0,0 +1,135 @@
+//! The positions of the fields in the parsed sentence, for audit trails.
+
+use core::ops::Range;
+
+use heapless::Vec;
+
+use crate::{parse::checked_sentence, parse_framed, Error, ParseResult};
+
+/// Number of fields with a span, including the address field.
+pub const FIELD_SPANS_LEN: usize = 32;
+
+/// The byte ranges of the fields of a sentence in the parsed string, see
+/// [`parse_str_with_spans()`].
+///
+/// Field 0 is the address, e.g. `GPGGA`, and the data fields are numbered
+/// from 1, like in the documentation of the sentences. The ranges are
+/// offsets into the whole input, TAG block and leading whitespace included.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FieldSpans {
+    /// Start and end of the fields, only the first ones are recorded
+    fields: Vec<(u16, u16), FIELD_SPANS_LEN>,
+    checksum: (u16, u16),
+    /// Number of fields, including those without a span
+    count: usize,
+}
+
+impl FieldSpans {
+    /// Returns the range of a field, 0 being the address.
+    pub fn field(&self, index: usize) -> Option<Range<usize>> {
+        self.fields
+            .get(index)
+            .map(|&(start, end)| start.into()..end.into())
+    }
+
+    /// Returns the text of a field in the parsed string.
+    pub fn field_str<'a>(&self, sentence: &'a str, index: usize) -> Option<&'a str> {
+        sentence.get(self.field(index)?)
+    }
+
+    /// Returns the number of data fields, some of which may have no span if
+    /// there are more than [`FIELD_SPANS_LEN`] fields.
+    pub fn field_count(&self) -> usize {
+        self.count - 1
+    }
+
+    /// Returns the range of the two checksum digits.
+    pub fn checksum(&self) -> Range<usize> {
+        self.checksum.0.into()..self.checksum.1.into()
+    }
+
+    /// Returns the ranges of the fields with a span, starting with the
+    /// address.
+    pub fn iter(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.fields
+            .iter()
+            .map(|&(start, end)| start.into()..end.into())
+    }
+}
+
+/// Parses a sentence like [`parse_str()`](crate::parse_str) and returns the
+/// positions of its fields, to trace each value to the characters it was
+/// parsed from.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::parse_str_with_spans;
+///
+/// let sentence = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+/// # #[cfg(feature = "GGA")]
+/// # {
+/// let (_gga, spans) = parse_str_with_spans(sentence).unwrap();
+/// // the latitude is field 2
+/// assert_eq!(spans.field(2), Some(18..27));
+/// assert_eq!(spans.field_str(sentence, 2), Some("5321.6802"));
+/// assert_eq!(spans.field_count(), 14);
+/// # }
+/// ```
+pub fn parse_str_with_spans(sentence: &str) -> Result<(ParseResult, FieldSpans), Error<'_>> {
+    let nmea_sentence = checked_sentence(sentence)?;
+    let data_start = nmea_sentence.data.as_ptr() as usize - sentence.as_ptr() as usize;
+    // the framing limits the sentence, but not the TAG block
+    let offset = |offset: usize| u16::try_from(offset).unwrap_or(u16::MAX);
+    let mut spans = FieldSpans {
+        // `$`, the talker ID and the sentence type before the `,`
+        fields: Vec::from_slice(&[(offset(data_start - 6), offset(data_start - 1))])
+            .unwrap_or_default(),
+        count: 1,
+        ..FieldSpans::default()
+    };
+    let mut start = data_start;
+    for field in nmea_sentence.data.split(',') {
+        // only the first fields are recorded
+        let _ = spans
+            .fields
+            .push((offset(start), offset(start + field.len())));
+        spans.count += 1;
+        start += field.len() + 1;
+    }
+    // after the `*`
+    spans.checksum = (offset(start), offset(start + 2));
+    Ok((parse_framed(nmea_sentence)?, spans))
+}
+
+#[cfg(all(test, feature = "HDT", feature = "GST"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_spans() {
+        let sentence = "\\c:1622040996*5C\\$GPHDT,274.07,T*03\r\n";
+        let (_, spans) = parse_str_with_spans(sentence).unwrap();
+        assert_eq!(spans.field_str(sentence, 0), Some("GPHDT"));
+        assert_eq!(spans.field(1), Some(24..30));
+        assert_eq!(spans.field_str(sentence, 1), Some("274.07"));
+        assert_eq!(spans.field_str(sentence, 2), Some("T"));
+        assert_eq!(spans.field(3), None);
+        assert_eq!(spans.field_count(), 2);
+        assert_eq!(&sentence[spans.checksum()], "03");
+        assert_eq!(spans.iter().count(), 3);
+
+        // empty fields have empty spans
+        let sentence = "$GPGST,,,,,,,,*57";
+        let (_, spans) = parse_str_with_spans(sentence).unwrap();
+        assert_eq!(spans.field_count(), 8);
+        assert_eq!(spans.field(8), Some(14..14));
+
+        assert!(matches!(
+            parse_str_with_spans("$GPHDT,274.07,T*00"),
+            Err(Error::ChecksumMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,688 @@
+//! Splitting a byte stream into NMEA sentences.
+//!
+//! Receivers deliver sentences as a continuous stream of bytes, often in
+//! arbitrary chunks. [`SentenceAccumulator`] collects these bytes and yields
+//! complete sentences (without the `\r\n` line ending) that can be given to
+//! [`parse_str()`](crate::parse_str) or [`Nmea::parse()`](crate::Nmea::parse).
+//!
+//! Binary RTCM3, u-blox UBX and SiRF frames interleaved with the sentences,
+//! as sent by some receivers on the same UART, are recognized and skipped,
+//! see
+//! [`SentenceAccumulator::push_event()`] to be notified about them and
+//! [`SentenceAccumulator::on_rtcm_byte()`] to forward RTCM3 corrections.
+//!
+//! For interrupt driven UARTs, see the [`isr`] module. With the `std`
+//! feature, the `serial` module reads serial ports and the `net` module UDP
+//! and TCP sources.
+
+use heapless::Vec;
+
+use crate::{Error, SENTENCE_MAX_LEN};
+
+pub mod isr;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod net;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod serial;
+
+/// Maximum length of a line read by the `serial` and `net` readers, a
+/// sentence with its TAG block and line ending.
+#[cfg(feature = "std")]
+const LINE_MAX_LEN: usize = 4 * SENTENCE_MAX_LEN;
+
+/// Binary protocols recognized in the byte stream.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryProtocol {
+    /// RTCM 3 differential corrections, starting with `0xD3`
+    Rtcm3,
+    /// u-blox UBX, starting with `0xB5 0x62`
+    Ubx,
+    /// SiRF binary, starting with `0xA0 0xA2` and ending with `0xB0 0xB3`
+    Sirf,
+}
+
+impl BinaryProtocol {
+    /// Number of bytes up to and including the length field.
+    const fn header_len(self) -> u32 {
+        match self {
+            BinaryProtocol::Rtcm3 => 3,
+            BinaryProtocol::Ubx => 6,
+            BinaryProtocol::Sirf => 4,
+        }
+    }
+}
+
+/// Something completed by a byte, see [`SentenceAccumulator::push_event()`].
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamEvent<'a> {
+    /// A sentence, without the line ending
+    Sentence(&'a str),
+    /// A skipped binary frame of `len` bytes, including its header and
+    /// checksum
+    BinaryFrame {
+        protocol: BinaryProtocol,
+        len: usize,
+    },
+}
+
+/// Collects bytes until a complete sentence is received.
+///
+/// Bytes before the first `$` (or `!` for encapsulated sentences) are
+/// skipped, a new `$` always starts a new sentence and a sentence is
+/// complete on `\r` or `\n`. Sentences longer than `N` bytes or containing
+/// non-ASCII bytes are dropped.
+///
+/// A TAG block, like `\s:gw1,c:1622040996*18\`, is kept in front of its
+/// sentence for [`Nmea::tag_block()`](crate::Nmea::tag_block), so `N` has to
+/// hold both when the source sends TAG blocks.
+///
+/// A `0xD3` byte starts an RTCM3 frame, `0xB5 0x62` a UBX frame and
+/// `0xA0 0xA2` a SiRF frame. Their length is taken from the frame header and
+/// their bytes are skipped, so `$` and line endings in the binary payload do
+/// not produce bogus sentences. A frame with an invalid header or SiRF end
+/// sequence is dropped and the accumulator resyncs on the next `$`.
+///
+/// In [`lenient()`](Self::lenient) mode, control characters inside a
+/// sentence, like the NUL padding some receivers emit after a brownout, are
+/// dropped instead of breaking the sentence.
+///
+/// Each byte costs a constant amount of work, no more than one comparison
+/// chain and one copy into the internal buffer.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{stream::SentenceAccumulator, Nmea};
+///
+/// let mut nmea = Nmea::default();
+/// let mut accumulator = SentenceAccumulator::<82>::new();
+///
+/// let chunks: [&[u8]; 2] = [
+///     b"garbage\r\n$GPGGA,092750.000,5321.6802,N,00630.3372,W,",
+///     b"1,8,1.03,61.7,M,55.2,M,,*76\r\n$GPGSV,3,1,",
+/// ];
+/// for chunk in chunks {
+///     for &byte in chunk {
+///         if let Some(sentence) = accumulator.push(byte) {
+/// #           #[cfg(feature = "GGA")]
+///             nmea.parse(sentence).unwrap();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SentenceAccumulator<const N: usize = SENTENCE_MAX_LEN> {
+    buffer: Vec<u8, N>,
+    state: State,
+    rtcm_frames: u32,
+    ubx_frames: u32,
+    sirf_frames: u32,
+    skipped_binary_bytes: u32,
+    on_rtcm_byte: Option<fn(u8)>,
+    lenient: bool,
+    /// Whether control characters were dropped from the current sentence
+    sanitizing: bool,
+    sanitized_bytes: u32,
+    sanitized_sentences: u32,
+}
+
+/// RTCM3 frame preamble
+const RTCM3_PREAMBLE: u8 = 0xD3;
+/// UBX sync characters
+const UBX_SYNC: [u8; 2] = [0xB5, 0x62];
+/// SiRF start and end sequences
+const SIRF_START: [u8; 2] = [0xA0, 0xA2];
+const SIRF_END: [u8; 2] = [0xB0, 0xB3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum State {
+    /// Waiting for the start of a sentence or frame
+    #[default]
+    Idle,
+    /// TAG block, up to its closing `\`
+    TagBlock,
+    /// Closed TAG block, waiting for the start of its sentence
+    TagBlockEnd,
+    Sentence,
+    /// Binary frame with `received` bytes so far, `length` is the total
+    /// length once the header is complete
+    Binary {
+        protocol: BinaryProtocol,
+        received: u32,
+        length: u32,
+    },
+}
+
+/// What a byte completed.
+enum Step {
+    Nothing,
+    Sentence,
+    BinaryFrame(BinaryProtocol, u32),
+    /// The sentence was dropped because it is longer than the buffer
+    TooLong,
+    /// The sentence was dropped because of a non-ASCII byte
+    NotAscii,
+}
+
+impl<const N: usize> SentenceAccumulator<N> {
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            state: State::Idle,
+            rtcm_frames: 0,
+            ubx_frames: 0,
+            sirf_frames: 0,
+            skipped_binary_bytes: 0,
+            on_rtcm_byte: None,
+            lenient: false,
+            sanitizing: false,
+            sanitized_bytes: 0,
+            sanitized_sentences: 0,
+        }
+    }
+
+    /// Drops control characters other than `\r` and `\n` inside sentences
+    /// instead of passing them on, which makes the sentence fail to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::stream::SentenceAccumulator;
+    ///
+    /// let mut accumulator = SentenceAccumulator::<82>::new().lenient();
+    /// let sentence = b"$GPHDT,274.\x00\x0007,T*03\r\n"
+    ///     .iter()
+    ///     .find_map(|&byte| accumulator.push(byte).map(String::from));
+    /// assert_eq!(sentence.as_deref(), Some("$GPHDT,274.07,T*03"));
+    /// assert_eq!(accumulator.sanitized_bytes(), 2);
+    /// assert_eq!(accumulator.sanitized_sentences(), 1);
+    /// ```
+    pub const fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Sets a callback receiving every byte of the RTCM3 frames in the
+    /// stream, e.g. to pass the corrections on to another port.
+    pub const fn on_rtcm_byte(mut self, callback: fn(u8)) -> Self {
+        self.on_rtcm_byte = Some(callback);
+        self
+    }
+
+    /// Returns the number of RTCM3 frames skipped so far.
+    pub fn rtcm_frames(&self) -> u32 {
+        self.rtcm_frames
+    }
+
+    /// Returns the number of UBX frames skipped so far.
+    pub fn ubx_frames(&self) -> u32 {
+        self.ubx_frames
+    }
+
+    /// Returns the number of SiRF frames skipped so far.
+    pub fn sirf_frames(&self) -> u32 {
+        self.sirf_frames
+    }
+
+    /// Returns the number of bytes skipped as part of binary frames,
+    /// including frames dropped because of an invalid header.
+    pub fn skipped_binary_bytes(&self) -> u32 {
+        self.skipped_binary_bytes
+    }
+
+    /// Returns the number of control characters dropped in
+    /// [`lenient()`](Self::lenient) mode.
+    pub fn sanitized_bytes(&self) -> u32 {
+        self.sanitized_bytes
+    }
+
+    /// Returns the number of sentences completed after dropping control
+    /// characters in [`lenient()`](Self::lenient) mode.
+    pub fn sanitized_sentences(&self) -> u32 {
+        self.sanitized_sentences
+    }
+
+    /// Adds a byte and returns the sentence it completes, if any.
+    pub fn push(&mut self, byte: u8) -> Option<&str> {
+        if self.push_byte(byte) {
+            self.sentence()
+        } else {
+            None
+        }
+    }
+
+    /// Adds a byte and returns the sentence or binary frame it completes,
+    /// if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::stream::{BinaryProtocol, SentenceAccumulator, StreamEvent};
+    ///
+    /// let mut accumulator = SentenceAccumulator::<82>::new();
+    /// // UBX-ACK-ACK
+    /// let bytes = b"\xb5\x62\x05\x01\x02\x00\x06\x01\x0f\x38";
+    /// let events: Vec<_> = bytes
+    ///     .iter()
+    ///     .filter_map(|&byte| accumulator.push_event(byte).map(|event| format!("{:?}", event)))
+    ///     .collect();
+    /// assert_eq!(events, ["BinaryFrame { protocol: Ubx, len: 10 }"]);
+    /// ```
+    pub fn push_event(&mut self, byte: u8) -> Option<StreamEvent<'_>> {
+        match self.step(byte) {
+            Step::Nothing | Step::TooLong | Step::NotAscii => None,
+            Step::Sentence => self.sentence().map(StreamEvent::Sentence),
+            Step::BinaryFrame(protocol, len) => Some(StreamEvent::BinaryFrame {
+                protocol,
+                len: len as usize,
+            }),
+        }
+    }
+
+    /// Drops the partially received sentence or frame.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.state = State::Idle;
+    }
+
+    /// Adds a byte and returns `true` when it completes a sentence, which
+    /// is then available from [`sentence()`](Self::sentence) until the
+    /// next sentence starts.
+    pub(crate) fn push_byte(&mut self, byte: u8) -> bool {
+        matches!(self.step(byte), Step::Sentence)
+    }
+
+    fn step(&mut self, byte: u8) -> Step {
+        if let State::Binary {
+            protocol,
+            received,
+            length,
+        } = self.state
+        {
+            return self.step_binary(protocol, received, length, byte);
+        }
+        match byte {
+            b'$' | b'!' if self.state == State::TagBlockEnd => {
+                self.state = State::Sentence;
+                return self.push_or_reset(byte);
+            }
+            b'$' | b'!' => return self.start(State::Sentence, byte),
+            b'\\' if self.state == State::TagBlock => {
+                self.state = State::TagBlockEnd;
+                return self.push_or_reset(byte);
+            }
+            b'\\' => return self.start(State::TagBlock, byte),
+            RTCM3_PREAMBLE => self.start_binary(BinaryProtocol::Rtcm3, byte),
+            _ if byte == UBX_SYNC[0] => self.start_binary(BinaryProtocol::Ubx, byte),
+            _ if byte == SIRF_START[0] => self.start_binary(BinaryProtocol::Sirf, byte),
+            // a TAG block must be directly followed by its sentence
+            _ if self.state == State::TagBlockEnd => self.reset(),
+            b'\r' | b'\n' if self.state == State::TagBlock => self.reset(),
+            _ if self.state == State::Idle => {}
+            b'\r' | b'\n' => {
+                self.state = State::Idle;
+                if self.sanitizing {
+                    self.sanitized_sentences = self.sanitized_sentences.wrapping_add(1);
+                }
+                return Step::Sentence;
+            }
+            _ if self.lenient && byte.is_ascii_control() => {
+                self.sanitized_bytes = self.sanitized_bytes.wrapping_add(1);
+                self.sanitizing = true;
+            }
+            _ if !byte.is_ascii() => {
+                self.reset();
+                return Step::NotAscii;
+            }
+            _ => return self.push_or_reset(byte),
+        }
+        Step::Nothing
+    }
+
+    /// Starts a sentence, or the TAG block in front of it, with its first
+    /// byte.
+    fn start(&mut self, state: State, byte: u8) -> Step {
+        self.buffer.clear();
+        self.sanitizing = false;
+        self.state = state;
+        // `N` is at least 1 for any useful accumulator
+        self.push_or_reset(byte)
+    }
+
+    /// Adds a byte to the sentence, which is dropped when it is too long.
+    fn push_or_reset(&mut self, byte: u8) -> Step {
+        if self.buffer.push(byte).is_err() {
+            self.reset();
+            return Step::TooLong;
+        }
+        Step::Nothing
+    }
+
+    fn start_binary(&mut self, protocol: BinaryProtocol, byte: u8) {
+        self.reset();
+        self.state = State::Binary {
+            protocol,
+            received: 1,
+            length: 0,
+        };
+        self.skip_binary_byte(protocol, byte);
+    }
+
+    fn step_binary(
+        &mut self,
+        protocol: BinaryProtocol,
+        received: u32,
+        length: u32,
+        byte: u8,
+    ) -> Step {
+        let received = received + 1;
+        let length = match (protocol, received) {
+            // the 6 bits after the RTCM3 preamble are reserved and always zero
+            (BinaryProtocol::Rtcm3, 2) if byte & 0xFC != 0 => return self.abort_binary(byte),
+            (BinaryProtocol::Rtcm3, 2) => u32::from(byte) << 8,
+            // payload and the 3 bytes of CRC-24Q
+            (BinaryProtocol::Rtcm3, 3) => (length | u32::from(byte)) + 6,
+            (BinaryProtocol::Ubx, 2) if byte != UBX_SYNC[1] => return self.abort_binary(byte),
+            // little endian length after class and ID
+            (BinaryProtocol::Ubx, 5) => u32::from(byte),
+            // payload and the 2 bytes of checksum
+            (BinaryProtocol::Ubx, 6) => (length | u32::from(byte) << 8) + 8,
+            (BinaryProtocol::Sirf, 2) if byte != SIRF_START[1] => return self.abort_binary(byte),
+            // 15 bit big endian length, payloads are shorter than 2^11
+            (BinaryProtocol::Sirf, 3) if byte > 0x07 => return self.abort_binary(byte),
+            (BinaryProtocol::Sirf, 3) => u32::from(byte) << 8,
+            // payload, 2 bytes of checksum and the end sequence
+            (BinaryProtocol::Sirf, 4) => (length | u32::from(byte)) + 8,
+            (BinaryProtocol::Sirf, _)
+                if (received + 1 == length && byte != SIRF_END[0])
+                    || (received == length && byte != SIRF_END[1]) =>
+            {
+                return self.abort_binary(byte)
+            }
+            _ => length,
+        };
+        self.skip_binary_byte(protocol, byte);
+        if received >= protocol.header_len() && received == length {
+            self.state = State::Idle;
+            let frames = match protocol {
+                BinaryProtocol::Rtcm3 => &mut self.rtcm_frames,
+                BinaryProtocol::Ubx => &mut self.ubx_frames,
+                BinaryProtocol::Sirf => &mut self.sirf_frames,
+            };
+            *frames = frames.wrapping_add(1);
+            return Step::BinaryFrame(protocol, length);
+        }
+        self.state = State::Binary {
+            protocol,
+            received,
+            length,
+        };
+        Step::Nothing
+    }
+
+    /// Drops a frame with an invalid header or end sequence, the byte may
+    /// start something else.
+    fn abort_binary(&mut self, byte: u8) -> Step {
+        self.state = State::Idle;
+        self.step(byte)
+    }
+
+    fn skip_binary_byte(&mut self, protocol: BinaryProtocol, byte: u8) {
+        self.skipped_binary_bytes = self.skipped_binary_bytes.wrapping_add(1);
+        if let (BinaryProtocol::Rtcm3, Some(callback)) = (protocol, self.on_rtcm_byte) {
+            callback(byte);
+        }
+    }
+
+    /// Returns the last completed sentence.
+    pub(crate) fn sentence(&self) -> Option<&str> {
+        if self.state != State::Idle || self.buffer.is_empty() {
+            return None;
+        }
+        // only ASCII bytes are stored
+        core::str::from_utf8(&self.buffer).ok()
+    }
+}
+
+/// Splits a buffer holding any number of sentences, e.g. the result of one
+/// read from a serial port, into sentences (without the line ending).
+///
+/// The framing is that of [`SentenceAccumulator`], except that a last
+/// sentence without line ending is returned as well.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::stream::sentences;
+///
+/// let buffer = b"$GPHDT,274.07,T*03\r\njunk$GPHDT,274.07,T*03";
+/// assert_eq!(sentences(buffer).count(), 2);
+/// ```
+pub fn sentences(bytes: &[u8]) -> Sentences<'_> {
+    Sentences {
+        bytes,
+        position: 0,
+        accumulator: SentenceAccumulator::new(),
+    }
+}
+
+/// Iterator over the sentences of a buffer, see [`sentences()`].
+#[derive(Debug, Clone)]
+pub struct Sentences<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    accumulator: SentenceAccumulator,
+}
+
+impl<'a> Sentences<'a> {
+    /// Returns the next sentence, or the error of the next sentence dropped
+    /// because it is too long or not ASCII.
+    pub(crate) fn next_framed(&mut self) -> Option<Result<&'a str, Error<'a>>> {
+        while let Some(&byte) = self.bytes.get(self.position) {
+            self.position += 1;
+            match self.accumulator.step(byte) {
+                Step::Sentence => {
+                    // the buffered bytes are the ones right before the line ending
+                    let end = self.position - 1;
+                    let start = end - self.accumulator.buffer.len();
+                    return core::str::from_utf8(&self.bytes[start..end]).ok().map(Ok);
+                }
+                Step::TooLong => {
+                    // the full buffer and the byte which did not fit
+                    let start = self.position - 1 - SENTENCE_MAX_LEN;
+                    let len = self.bytes[start..]
+                        .iter()
+                        .position(|&byte| byte == b'\r' || byte == b'\n')
+                        .unwrap_or(self.bytes.len() - start);
+                    return Some(Err(Error::SentenceLength(len)));
+                }
+                Step::NotAscii => return Some(Err(Error::ASCII)),
+                Step::Nothing | Step::BinaryFrame(..) => {}
+            }
+        }
+        if self.accumulator.state != State::Sentence {
+            return None;
+        }
+        let start = self.bytes.len() - self.accumulator.buffer.len();
+        self.accumulator.reset();
+        core::str::from_utf8(&self.bytes[start..]).ok().map(Ok)
+    }
+}
+
+impl<'a> Iterator for Sentences<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        loop {
+            if let Ok(sentence) = self.next_framed()? {
+                return Some(sentence);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect<const N: usize>(
+        accumulator: &mut SentenceAccumulator<N>,
+        bytes: &[u8],
+    ) -> std::vec::Vec<std::string::String> {
+        bytes
+            .iter()
+            .filter_map(|&byte| accumulator.push(byte).map(Into::into))
+            .collect()
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let mut accumulator = SentenceAccumulator::<SENTENCE_MAX_LEN>::new();
+        assert_eq!(
+            collect(
+                &mut accumulator,
+                b"\xff\x00junk$GPGGA,1*00\r\n$GPRMC,2*00\n\n!AIVDM,3*00\r$GPGS"
+            ),
+            ["$GPGGA,1*00", "$GPRMC,2*00", "!AIVDM,3*00"]
+        );
+        assert_eq!(collect(&mut accumulator, b"V,4*00\r\n"), ["$GPGSV,4*00"]);
+    }
+
+    #[test]
+    fn test_tag_blocks() {
+        let mut accumulator = SentenceAccumulator::<SENTENCE_MAX_LEN>::new();
+        assert_eq!(
+            collect(
+                &mut accumulator,
+                b"junk\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03\r\n\
+                \\s:gw1\r\n$GPHDT,274.07,T*03\r\n\\s:gw1*00\\ $GPHDT,274.07,T*03\r\n"
+            ),
+            [
+                "\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03",
+                "$GPHDT,274.07,T*03",
+                "$GPHDT,274.07,T*03"
+            ]
+        );
+        // the TAG block does not fit
+        let mut accumulator = SentenceAccumulator::<24>::new();
+        assert!(collect(
+            &mut accumulator,
+            b"\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03\r\n"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_sentences() {
+        let buffer = b"$GPG$GPGGA,1*00\r\n\r\n\xd3\x00\x01$\x00\x00\x00$GPRMC,2*00\n$GPGSV,3*00";
+        let split: std::vec::Vec<_> = sentences(buffer).collect();
+        assert_eq!(split, ["$GPGGA,1*00", "$GPRMC,2*00", "$GPGSV,3*00"]);
+        assert_eq!(sentences(b"junk\r\n").next(), None);
+    }
+
+    #[test]
+    fn test_resync() {
+        let mut accumulator = SentenceAccumulator::<8>::new();
+        // a new start character drops the partial sentence
+        assert_eq!(collect(&mut accumulator, b"$GPG$GPGGA\r\n"), ["$GPGGA"]);
+        // too long
+        assert!(collect(&mut accumulator, b"$GPGGA,123\r\n").is_empty());
+        // non-ASCII
+        assert!(collect(&mut accumulator, b"$GP\xc3\xa9\r\n").is_empty());
+        assert_eq!(collect(&mut accumulator, b"$GPRMC\r\n"), ["$GPRMC"]);
+    }
+
+    #[test]
+    fn test_lenient() {
+        let stream = b"$GPGGA,\x001*00\r\n$GPRMC,2\x7f*00\r\n$GPGSV,3*00\r\n";
+        let mut strict = SentenceAccumulator::<SENTENCE_MAX_LEN>::new();
+        assert_eq!(
+            collect(&mut strict, stream),
+            ["$GPGGA,\x001*00", "$GPRMC,2\x7f*00", "$GPGSV,3*00"]
+        );
+        assert_eq!(strict.sanitized_bytes(), 0);
+
+        let mut lenient = SentenceAccumulator::<SENTENCE_MAX_LEN>::new().lenient();
+        assert_eq!(
+            collect(&mut lenient, stream),
+            ["$GPGGA,1*00", "$GPRMC,2*00", "$GPGSV,3*00"]
+        );
+        assert_eq!(lenient.sanitized_bytes(), 2);
+        assert_eq!(lenient.sanitized_sentences(), 2);
+    }
+
+    #[test]
+    fn test_skip_rtcm3() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static FORWARDED: AtomicUsize = AtomicUsize::new(0);
+        fn forward(_: u8) {
+            FORWARDED.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let mut accumulator = SentenceAccumulator::<SENTENCE_MAX_LEN>::new().on_rtcm_byte(forward);
+        // 1005 message frame with `$` and `\n` in the payload
+        let mut stream = std::vec::Vec::from(*b"$GPGGA,1*00\r\n\xd3\x00\x05$\n$\r\n\x01\x02\x03");
+        stream.extend_from_slice(b"$GPRMC,2*00\r\n");
+        assert_eq!(
+            collect(&mut accumulator, &stream),
+            ["$GPGGA,1*00", "$GPRMC,2*00"]
+        );
+        assert_eq!(accumulator.rtcm_frames(), 1);
+        assert_eq!(FORWARDED.load(Ordering::SeqCst), 11);
+
+        // an invalid header is ignored
+        assert_eq!(
+            collect(&mut accumulator, b"\xd3\xff$GPGSV,3*00\r\n"),
+            ["$GPGSV,3*00"]
+        );
+        assert_eq!(accumulator.rtcm_frames(), 1);
+    }
+
+    #[test]
+    fn test_skip_ubx() {
+        let mut accumulator = SentenceAccumulator::<SENTENCE_MAX_LEN>::new();
+        // UBX-NAV-EOE with `$` and `\n` in the payload
+        let mut stream = std::vec::Vec::from(*b"\xb5\x62\x01\x61\x04\x00$\n\r$\xc9\x94");
+        stream.extend_from_slice(b"$GPRMC,2*00\r\n\xb5$GPGSV,3*00\r\n");
+        let events: std::vec::Vec<_> = stream
+            .iter()
+            .filter_map(|&byte| match accumulator.push_event(byte)? {
+                StreamEvent::Sentence(sentence) => Some(sentence.into()),
+                StreamEvent::BinaryFrame { protocol, len } => {
+                    Some(std::format!("{:?} {}", protocol, len))
+                }
+            })
+            .collect();
+        assert_eq!(events, ["Ubx 12", "$GPRMC,2*00", "$GPGSV,3*00"]);
+        assert_eq!(accumulator.ubx_frames(), 1);
+        assert_eq!(accumulator.rtcm_frames(), 0);
+    }
+
+    #[test]
+    fn test_skip_sirf() {
+        let mut accumulator = SentenceAccumulator::<SENTENCE_MAX_LEN>::new();
+        // message with `$` and `\n` in the payload, then a frame with a
+        // broken end sequence
+        let mut stream = std::vec::Vec::from(*b"\xa0\xa2\x00\x03$\n$\x00\x4e\xb0\xb3");
+        stream.extend_from_slice(b"$GPRMC,2*00\r\n\xa0\xa2\x00\x01\x02\x00\x02\xb0$GPGSV,3*00\r\n");
+        assert_eq!(
+            collect(&mut accumulator, &stream),
+            ["$GPRMC,2*00", "$GPGSV,3*00"]
+        );
+        assert_eq!(accumulator.sirf_frames(), 1);
+        assert_eq!(accumulator.skipped_binary_bytes(), 11 + 8);
+
+        // invalid length
+        assert_eq!(
+            collect(&mut accumulator, b"\xa0\xa2\x80$GPGGA,1*00\r\n"),
+            ["$GPGGA,1*00"]
+        );
+        assert_eq!(accumulator.sirf_frames(), 1);
+    }
+}
@@ -0,0 +1,208 @@
+//! Lock-free byte hand-off from an interrupt handler to the parser.
+//!
+//! An [`IsrRing`] is split into an [`IsrFeeder`], which is moved into the
+//! UART interrupt handler and only appends raw bytes, and a [`Poller`],
+//! which runs in thread context and drains the bytes, frames them with a
+//! [`SentenceAccumulator`] and parses complete sentences.
+//!
+//! The ring is a single-producer single-consumer queue, so neither side
+//! ever blocks or disables interrupts. When the ring is full the feeder
+//! drops the byte instead of applying back-pressure; the damaged sentence
+//! then fails its checksum or length check and the accumulator resyncs on
+//! the next `$`.
+//!
+//! # Worst-case costs
+//!
+//! - [`IsrFeeder::feed()`]: constant, one atomic load, one atomic store and
+//!   a byte copy. Safe to call from any interrupt priority as long as there
+//!   is a single feeder.
+//! - [`Poller::poll()`]: constant per drained byte (see
+//!   [`SentenceAccumulator`]), at most `N - 1` bytes per call.
+//! - [`Poller::poll_parse()`]: the above plus one parse, which is linear in
+//!   the sentence length and bounded by [`SENTENCE_MAX_LEN`].
+//!
+//! On targets without atomic compare-and-swap (e.g. `thumbv6m`) enable
+//! `heapless/portable-atomic`.
+//!
+//! # Examples
+//!
+//! ```
+//! use nmea::stream::isr::IsrRing;
+//!
+//! let mut ring = IsrRing::<256>::new();
+//! let (mut feeder, mut poller) = ring.split();
+//!
+//! // in the UART interrupt handler
+//! for &byte in b"$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76\r\n" {
+//!     feeder.feed(byte);
+//! }
+//!
+//! // in the main loop
+//! # #[cfg(feature = "GGA")]
+//! while let Some(result) = poller.poll_parse() {
+//!     println!("{:?}", result);
+//! }
+//! ```
+
+use heapless::spsc::{Consumer, Producer, Queue};
+
+use crate::{parse_str, Error, ParseResult, SENTENCE_MAX_LEN};
+
+use super::SentenceAccumulator;
+
+/// Backing storage for an [`IsrFeeder`] and [`Poller`] pair.
+///
+/// Holds up to `N - 1` bytes. Usually placed in a `static`, e.g. with the
+/// `static_cell` crate, so the feeder can be moved into the interrupt
+/// handler.
+#[derive(Default)]
+pub struct IsrRing<const N: usize> {
+    queue: Queue<u8, N>,
+}
+
+impl<const N: usize> IsrRing<N> {
+    pub const fn new() -> Self {
+        Self {
+            queue: Queue::new(),
+        }
+    }
+
+    /// Splits the ring into its producer and consumer halves.
+    pub fn split(&mut self) -> (IsrFeeder<'_, N>, Poller<'_, N>) {
+        let (producer, consumer) = self.queue.split();
+        (
+            IsrFeeder {
+                producer,
+                dropped: 0,
+            },
+            Poller {
+                consumer,
+                accumulator: SentenceAccumulator::new(),
+            },
+        )
+    }
+}
+
+/// Producer half of an [`IsrRing`], used from the interrupt handler.
+pub struct IsrFeeder<'a, const N: usize> {
+    producer: Producer<'a, u8, N>,
+    dropped: u32,
+}
+
+impl<const N: usize> IsrFeeder<'_, N> {
+    /// Appends a byte, returns `false` if the ring is full and the byte was
+    /// dropped.
+    #[inline]
+    pub fn feed(&mut self, byte: u8) -> bool {
+        match self.producer.enqueue(byte) {
+            Ok(()) => true,
+            Err(_) => {
+                self.dropped = self.dropped.wrapping_add(1);
+                false
+            }
+        }
+    }
+
+    /// Appends as many bytes as fit and returns their number, the
+    /// remaining bytes are counted as dropped.
+    pub fn feed_slice(&mut self, bytes: &[u8]) -> usize {
+        let fed = bytes
+            .iter()
+            .take_while(|&&byte| self.producer.enqueue(byte).is_ok())
+            .count();
+        self.dropped = self.dropped.wrapping_add((bytes.len() - fed) as u32);
+        fed
+    }
+
+    /// Returns the number of bytes dropped because the ring was full.
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+}
+
+/// Consumer half of an [`IsrRing`], used from thread context.
+pub struct Poller<'a, const N: usize, const M: usize = SENTENCE_MAX_LEN> {
+    consumer: Consumer<'a, u8, N>,
+    accumulator: SentenceAccumulator<M>,
+}
+
+impl<const N: usize, const M: usize> Poller<'_, N, M> {
+    /// Drops control characters inside sentences, see
+    /// [`SentenceAccumulator::lenient()`].
+    pub fn lenient(mut self) -> Self {
+        self.accumulator = self.accumulator.lenient();
+        self
+    }
+
+    /// Returns the accumulator, e.g. for its statistics.
+    pub fn accumulator(&self) -> &SentenceAccumulator<M> {
+        &self.accumulator
+    }
+
+    /// Drains bytes until a sentence is complete and returns it, or returns
+    /// `None` when the ring is empty.
+    pub fn poll(&mut self) -> Option<&str> {
+        while let Some(byte) = self.consumer.dequeue() {
+            if self.accumulator.push_byte(byte) {
+                return self.accumulator.sentence();
+            }
+        }
+        None
+    }
+
+    /// Like [`poll()`](Self::poll), but also parses the sentence.
+    pub fn poll_parse(&mut self) -> Option<Result<ParseResult, Error<'_>>> {
+        self.poll().map(parse_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_and_poll() {
+        let mut ring = IsrRing::<16>::new();
+        let (mut feeder, mut poller) = ring.split();
+        assert_eq!(poller.poll(), None);
+
+        assert_eq!(feeder.feed_slice(b"xx$GPA\r\n$GPB"), 12);
+        assert_eq!(poller.poll(), Some("$GPA"));
+        assert_eq!(poller.poll(), None);
+        assert_eq!(feeder.feed_slice(b"\r\n"), 2);
+        assert_eq!(poller.poll(), Some("$GPB"));
+        // drains the trailing `\n`
+        assert_eq!(poller.poll(), None);
+
+        // capacity is N - 1
+        assert_eq!(feeder.feed_slice(&[b'x'; 20]), 15);
+        assert!(!feeder.feed(b'x'));
+        assert_eq!(feeder.dropped(), 6);
+        assert_eq!(poller.poll(), None);
+        assert!(feeder.feed(b'x'));
+    }
+
+    #[test]
+    fn test_feed_slice_dropped() {
+        let mut ring = IsrRing::<8>::new();
+        let (mut feeder, _poller) = ring.split();
+        let bytes = b"$GPA,1,2,3\r\n";
+        let fed = feeder.feed_slice(bytes);
+        assert_eq!(fed, 7);
+        assert_eq!(feeder.dropped() as usize, bytes.len() - fed);
+        assert_eq!(feeder.feed_slice(b"$GPB"), 0);
+        assert_eq!(feeder.dropped() as usize, bytes.len() - fed + 4);
+    }
+
+    #[test]
+    #[cfg(feature = "GGA")]
+    fn test_poll_parse() {
+        let mut ring = IsrRing::<128>::new();
+        let (mut feeder, mut poller) = ring.split();
+        feeder.feed_slice(
+            b"$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76\r\n",
+        );
+        assert!(matches!(poller.poll_parse(), Some(Ok(ParseResult::GGA(_)))));
+        assert!(poller.poll_parse().is_none());
+    }
+}
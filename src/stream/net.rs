@@ -0,0 +1,270 @@
+//! Receiving sentences from the network.
+//!
+//! Marine gateways broadcast sentences over UDP, usually on port
+//! [`NMEA_UDP_PORT`], or serve them over TCP. [`UdpReceiver`] splits the
+//! received datagrams into sentences and [`TcpReceiver`] reads a stream of
+//! lines. TAG blocks are kept in front of their sentences, see
+//! [`split_tag_block()`](crate::split_tag_block).
+//!
+//! Both are blocking, use the timeouts of the socket to poll them.
+
+use std::{
+    io::{self, BufRead, BufReader, Read},
+    net::{TcpStream, ToSocketAddrs, UdpSocket},
+    vec,
+    vec::Vec,
+};
+
+use crate::{Error, Nmea, SentenceType};
+
+use super::LINE_MAX_LEN;
+
+/// The IEC 61162-450 port on which gateways broadcast sentences.
+pub const NMEA_UDP_PORT: u16 = 10110;
+
+/// Largest UDP payload.
+const DATAGRAM_MAX_LEN: usize = 65_507;
+
+/// Splits a datagram holding any number of sentences into sentences,
+/// without their line ending.
+///
+/// Empty lines and lines that are not ASCII are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::stream::net::datagram_sentences;
+///
+/// let datagram = b"\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03\r\n$GPHDT,274.07,T*03\r\n";
+/// let sentences: Vec<_> = datagram_sentences(datagram).collect();
+/// assert_eq!(
+///     sentences,
+///     ["\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03", "$GPHDT,274.07,T*03"]
+/// );
+/// ```
+pub fn datagram_sentences(datagram: &[u8]) -> impl Iterator<Item = &str> {
+    datagram
+        .split(|&byte| byte == b'\n' || byte == b'\r')
+        .filter(|line| line.is_ascii())
+        .filter_map(|line| core::str::from_utf8(line).ok())
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+}
+
+/// Receives sentences broadcast over UDP.
+///
+/// # Examples
+///
+/// ```no_run
+/// use nmea::{
+///     stream::net::{UdpReceiver, NMEA_UDP_PORT},
+///     Nmea,
+/// };
+///
+/// let mut receiver = UdpReceiver::bind(("0.0.0.0", NMEA_UDP_PORT))?;
+/// let mut nmea = Nmea::default();
+/// loop {
+///     receiver.feed(&mut nmea)?;
+///     println!("{:?}", nmea.latitude());
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct UdpReceiver {
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+}
+
+impl UdpReceiver {
+    /// Binds a socket to receive the datagrams sent to an address.
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        UdpSocket::bind(address).map(Self::from_socket)
+    }
+
+    /// Receives the datagrams of a socket, e.g. one which joined a
+    /// multicast group.
+    pub fn from_socket(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            buffer: vec![0; DATAGRAM_MAX_LEN],
+        }
+    }
+
+    /// Returns the socket, e.g. to set its read timeout.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Waits for a datagram and returns its sentences.
+    pub fn recv(&mut self) -> io::Result<impl Iterator<Item = &str>> {
+        let len = self.socket.recv(&mut self.buffer)?;
+        Ok(datagram_sentences(&self.buffer[..len]))
+    }
+
+    /// Waits for a datagram and parses its sentences.
+    ///
+    /// Returns the number of sentences parsed, the others are skipped.
+    pub fn feed(&mut self, nmea: &mut Nmea) -> io::Result<usize> {
+        Ok(self
+            .recv()?
+            .filter(|sentence| nmea.parse(sentence).is_ok())
+            .count())
+    }
+}
+
+/// Reads sentences from a TCP stream, or any other reader of lines.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::{stream::net::TcpReceiver, Nmea};
+///
+/// let mut receiver = TcpReceiver::new(&b"$GPHDT,274.07,T*03\r\n$GPHDT,274.07,T*03\r\n"[..]);
+/// let mut nmea = Nmea::default();
+/// let mut parsed = 0;
+/// while let Some(result) = receiver.feed(&mut nmea)? {
+/// #   #[cfg(feature = "HDT")]
+///     result.unwrap();
+///     parsed += 1;
+/// }
+/// assert_eq!(parsed, 2);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TcpReceiver<R = TcpStream> {
+    reader: BufReader<R>,
+    line: Vec<u8>,
+}
+
+impl TcpReceiver {
+    /// Connects to a server of sentences.
+    pub fn connect(address: impl ToSocketAddrs) -> io::Result<Self> {
+        TcpStream::connect(address).map(Self::new)
+    }
+}
+
+impl<R: Read> TcpReceiver<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            line: Vec::new(),
+        }
+    }
+
+    /// Returns the reader, e.g. to set the read timeout of a stream.
+    pub fn get_ref(&self) -> &R {
+        self.reader.get_ref()
+    }
+
+    /// Reads the next sentence, without its line ending.
+    ///
+    /// Empty lines, lines that are not ASCII and lines longer than a
+    /// sentence with a TAG block are skipped. Returns `None` at the end of
+    /// the stream.
+    pub fn read_sentence(&mut self) -> io::Result<Option<&str>> {
+        loop {
+            self.line.clear();
+            let read = (&mut self.reader)
+                .take(LINE_MAX_LEN as u64)
+                .read_until(b'\n', &mut self.line)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            if read == LINE_MAX_LEN && !self.line.ends_with(b"\n") {
+                self.skip_line()?;
+                continue;
+            }
+            if self.line.is_ascii()
+                && core::str::from_utf8(&self.line).is_ok_and(|line| !line.trim().is_empty())
+            {
+                break;
+            }
+        }
+        Ok(Some(
+            core::str::from_utf8(&self.line).unwrap_or_default().trim(),
+        ))
+    }
+
+    /// Skips the rest of an over-long line.
+    fn skip_line(&mut self) -> io::Result<()> {
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                return Ok(());
+            }
+            match buffer.iter().position(|&byte| byte == b'\n') {
+                Some(end) => {
+                    self.reader.consume(end + 1);
+                    return Ok(());
+                }
+                None => {
+                    let len = buffer.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    /// Reads the next sentence and parses it.
+    ///
+    /// Returns `None` at the end of the stream.
+    pub fn feed(&mut self, nmea: &mut Nmea) -> io::Result<Option<Result<SentenceType, Error<'_>>>> {
+        Ok(self.read_sentence()?.map(|sentence| nmea.parse(sentence)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tcp_receiver() {
+        let stream = b"\r\n$GPHDT,274.07,T*03\n\n\\c:1622040996,n:7*13\\$GPHDT,274.07,T*03\r\n$GPH";
+        let mut receiver = TcpReceiver::new(&stream[..]);
+        assert_eq!(
+            receiver.read_sentence().unwrap(),
+            Some("$GPHDT,274.07,T*03")
+        );
+        assert_eq!(
+            receiver.read_sentence().unwrap(),
+            Some("\\c:1622040996,n:7*13\\$GPHDT,274.07,T*03")
+        );
+        assert_eq!(receiver.read_sentence().unwrap(), Some("$GPH"));
+        assert_eq!(receiver.read_sentence().unwrap(), None);
+    }
+
+    #[test]
+    fn test_tcp_receiver_invalid_lines() {
+        let mut stream = vec![b'$'; 10 * LINE_MAX_LEN];
+        stream.extend_from_slice(b"\r\n$GPHDT,\xff274.07,T*03\r\n$GPHDT,274.07,T*03\r\n");
+        let mut receiver = TcpReceiver::new(&stream[..]);
+        assert_eq!(
+            receiver.read_sentence().unwrap(),
+            Some("$GPHDT,274.07,T*03")
+        );
+        assert_eq!(receiver.read_sentence().unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "HDT")]
+    fn test_udp_receiver() {
+        let mut receiver = UdpReceiver::bind("127.0.0.1:0").unwrap();
+        let address = receiver.socket().local_addr().unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+        sender
+            .send_to(
+                b"$GPHDT,274.07,T*00\r\n\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03\r\n",
+                address,
+            )
+            .unwrap();
+
+        let mut nmea = Nmea::default();
+        // the first sentence has a wrong checksum
+        assert_eq!(receiver.feed(&mut nmea).unwrap(), 1);
+        assert_eq!(nmea.heading, Some(274.07));
+        assert_eq!(
+            nmea.tag_block().and_then(|tag_block| tag_block.unix_time),
+            Some(1_622_040_996)
+        );
+    }
+}
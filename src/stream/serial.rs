@@ -0,0 +1,266 @@
+//! Reading sentences from a serial port.
+//!
+//! [`SerialReader`] frames the bytes of any [`Read`], e.g. a port of the
+//! `serialport` crate, with a [`SentenceAccumulator`], so sentences split
+//! over several reads are reassembled and garbage, like the partial sentence
+//! at the start of a read or binary frames, is skipped. TAG blocks are kept
+//! in front of their sentences.
+//!
+//! Serial ports usually have a read timeout. A timeout, like a
+//! [`WouldBlock`](io::ErrorKind::WouldBlock) of a non-blocking port, only
+//! means that no complete sentence is available yet: the reader keeps the
+//! partial sentence for the next call. [`SerialReader::read_sentence()`]
+//! returns `None` then, while the iterator yields the timeout as an error
+//! item and only ends with the stream.
+
+use std::io::{self, ErrorKind, Read};
+
+use crate::{parse_str, Error, Nmea, OwnedError, ParseResult};
+
+use super::{SentenceAccumulator, LINE_MAX_LEN};
+
+/// Size of the buffer of a single read.
+const READ_BUFFER_LEN: usize = 256;
+
+/// Reads sentences from a serial port, see the [module documentation](self).
+///
+/// It is an iterator over the parse results of the sentences, see
+/// [`next()`](Self::next).
+///
+/// # Examples
+///
+/// ```
+/// use nmea::stream::serial::SerialReader;
+///
+/// // e.g. `serialport::new("/dev/ttyUSB0", 9600).open()?`
+/// let port = &b"\x00$GPHDT,274.07,T*03\r\n$GPHDT,274"[..];
+/// let mut reader = SerialReader::new(port);
+/// # #[cfg(feature = "HDT")]
+/// # {
+/// for result in &mut reader {
+///     println!("{:?}", result?);
+/// }
+/// # }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct SerialReader<R> {
+    reader: R,
+    buffer: [u8; READ_BUFFER_LEN],
+    /// The bytes of the buffer still to be framed
+    position: usize,
+    len: usize,
+    accumulator: SentenceAccumulator<LINE_MAX_LEN>,
+}
+
+impl<R: Read> SerialReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: [0; READ_BUFFER_LEN],
+            position: 0,
+            len: 0,
+            accumulator: SentenceAccumulator::new(),
+        }
+    }
+
+    /// Returns the reader, e.g. to change the timeout of the port.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the reader and drops the partially received sentence.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads until a sentence is complete and returns it, without its line
+    /// ending.
+    ///
+    /// Returns `None` when the read times out or at the end of the stream.
+    pub fn read_sentence(&mut self) -> io::Result<Option<&str>> {
+        match self.next_sentence() {
+            Err(error) if is_timeout(&error) => Ok(None),
+            result => result,
+        }
+    }
+
+    /// Reads until a sentence is complete, returning the timeouts as errors.
+    fn next_sentence(&mut self) -> io::Result<Option<&str>> {
+        loop {
+            while self.position < self.len {
+                let byte = self.buffer[self.position];
+                self.position += 1;
+                if self.accumulator.push_byte(byte) {
+                    return Ok(self.accumulator.sentence());
+                }
+            }
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => return Ok(None),
+                Ok(len) => {
+                    self.position = 0;
+                    self.len = len;
+                }
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Parses the sentences available until the read times out.
+    ///
+    /// Returns the number of sentences parsed, the others are skipped. The
+    /// bytes of the binary frames in between are counted in
+    /// [`Nmea::skipped_binary_bytes()`].
+    pub fn feed(&mut self, nmea: &mut Nmea) -> io::Result<usize> {
+        let mut parsed = 0;
+        let mut skipped = self.accumulator.skipped_binary_bytes();
+        loop {
+            let end = match self.read_sentence() {
+                Ok(Some(sentence)) => {
+                    if nmea.parse(sentence).is_ok() {
+                        parsed += 1;
+                    }
+                    None
+                }
+                Ok(None) => Some(Ok(parsed)),
+                Err(error) => Some(Err(error)),
+            };
+            let total = self.accumulator.skipped_binary_bytes();
+            nmea.count_skipped_binary_bytes(total.wrapping_sub(skipped));
+            skipped = total;
+            if let Some(end) = end {
+                return end;
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for SerialReader<R> {
+    type Item = io::Result<Result<ParseResult, OwnedError>>;
+
+    /// Returns the parse result of the next sentence, or `None` at the end
+    /// of the stream.
+    ///
+    /// A read timeout is returned as an error of kind
+    /// [`TimedOut`](ErrorKind::TimedOut) or
+    /// [`WouldBlock`](ErrorKind::WouldBlock). Iterating again resumes
+    /// reading.
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_sentence() {
+            Ok(sentence) => {
+                sentence.map(|sentence| Ok(parse_str(sentence).map_err(Error::into_owned)))
+            }
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(error.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns chunks of bytes, with a timeout after each one.
+    struct TimingOutPort<'a> {
+        chunks: &'a [&'a [u8]],
+        timed_out: bool,
+    }
+
+    impl Read for TimingOutPort<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if !core::mem::replace(&mut self.timed_out, true) {
+                if let Some((chunk, chunks)) = self.chunks.split_first() {
+                    self.chunks = chunks;
+                    buf[..chunk.len()].copy_from_slice(chunk);
+                    return Ok(chunk.len());
+                }
+            }
+            self.timed_out = false;
+            Err(ErrorKind::TimedOut.into())
+        }
+    }
+
+    #[test]
+    fn test_read_sentence() {
+        let chunks: [&[u8]; 3] = [
+            b"T*03\r\n\xff$GPHDT,274",
+            b".07,T*03\r\n$GPHDT,274.07,T*03\r\n$GP",
+            b"HDT,274.07,T*03\r\n",
+        ];
+        let mut reader = SerialReader::new(TimingOutPort {
+            chunks: &chunks,
+            timed_out: false,
+        });
+        let mut sentences = std::vec::Vec::new();
+        for _ in 0..6 {
+            sentences.push(
+                reader
+                    .read_sentence()
+                    .unwrap()
+                    .map(std::string::String::from),
+            );
+        }
+        assert_eq!(
+            sentences,
+            [
+                None,
+                Some("$GPHDT,274.07,T*03".into()),
+                Some("$GPHDT,274.07,T*03".into()),
+                None,
+                Some("$GPHDT,274.07,T*03".into()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "HDT")]
+    fn test_tag_block() {
+        let port = &b"\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03\r\n"[..];
+        let mut nmea = Nmea::default();
+        assert_eq!(SerialReader::new(port).feed(&mut nmea).unwrap(), 1);
+        assert_eq!(nmea.tag_block().unwrap().unix_time, Some(1_622_040_996));
+    }
+
+    #[test]
+    #[cfg(feature = "HDT")]
+    fn test_skipped_binary_bytes() {
+        let port = &b"\xa0\xa2\x00\x01\x02\x00\x02\xb0\xb3$GPHDT,274.07,T*03\r\n\xa0\xa2"[..];
+        let mut nmea = Nmea::default();
+        assert_eq!(SerialReader::new(port).feed(&mut nmea).unwrap(), 1);
+        assert_eq!(nmea.skipped_binary_bytes(), 11);
+    }
+
+    #[test]
+    #[cfg(feature = "HDT")]
+    fn test_parse_results() {
+        let port = &b"$GPHDT,274.07,T*03\r\n$GPHDT,274.07,T*00\r\n"[..];
+        let results: std::vec::Vec<_> = SerialReader::new(port).map(Result::unwrap).collect();
+        assert!(matches!(results[0], Ok(ParseResult::HDT(_))));
+        assert!(matches!(
+            results[1],
+            Err(OwnedError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "HDT")]
+    fn test_iterator_timeout() {
+        let chunks: [&[u8]; 2] = [b"$GPHDT,274", b".07,T*03\r\n"];
+        let mut reader = SerialReader::new(TimingOutPort {
+            chunks: &chunks,
+            timed_out: false,
+        });
+        let error = reader.next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::TimedOut);
+        assert!(matches!(
+            reader.next().unwrap().unwrap(),
+            Ok(ParseResult::HDT(_))
+        ));
+        assert!(reader.next().unwrap().is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! TAG blocks of IEC 61162-450 and NMEA 4.0.
+//!
+//! Network multiplexers, e.g. of AIS feeds, prefix sentences with a TAG
+//! block like `\s:gw1,c:1622040996*18\$GPRMC,...`. The framing of this
+//! crate keeps TAG blocks in front of their sentences, the parsers skip
+//! them, so prefixed sentences are parsed like the others, and
+//! [`split_tag_block()`] and [`Nmea::tag_block()`] return their fields.
+
+use arrayvec::ArrayString;
+use nom::error::ErrorKind;
+
+use crate::{parse::checksum, Error, Nmea};
+
+/// Maximum length of the source and destination identifiers of a
+/// [`TagBlock`].
+pub const TAG_ID_MAX_LEN: usize = 15;
+
+/// The fields of a TAG block. Unknown fields are ignored.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagBlock {
+    /// Source identifier, `s:`
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub source: Option<ArrayString<TAG_ID_MAX_LEN>>,
+    /// Destination identifier, `d:`
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub destination: Option<ArrayString<TAG_ID_MAX_LEN>>,
+    /// UNIX time, `c:`, in seconds, or milliseconds for some multiplexers
+    pub unix_time: Option<u64>,
+    /// Line count, `n:`
+    pub line_count: Option<u32>,
+    /// Relative time, `r:`
+    pub relative_time: Option<u64>,
+}
+
+/// Splits the TAG block from the sentence which follows it.
+///
+/// Returns `None` for sentences without TAG block.
+///
+/// # Errors
+///
+/// - [`Error::ChecksumMismatch`] when the checksum of the TAG block does not
+///   match its fields.
+/// - [`Error::ParsingError`] when the TAG block is not terminated or has
+///   invalid fields.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::split_tag_block;
+///
+/// let (tag_block, sentence) =
+///     split_tag_block("\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03").unwrap();
+/// let tag_block = tag_block.unwrap();
+/// assert_eq!(tag_block.source.as_deref(), Some("gw1"));
+/// assert_eq!(tag_block.unix_time, Some(1_622_040_996));
+/// assert_eq!(sentence, "$GPHDT,274.07,T*03");
+/// ```
+pub fn split_tag_block(input: &str) -> Result<(Option<TagBlock>, &str), Error<'_>> {
+    let Some((block, sentence)) = strip_tag_block(input)? else {
+        return Ok((None, input));
+    };
+    Ok((Some(parse_tag_block(block)?), sentence))
+}
+
+/// Returns the TAG block, without its delimiters, and the sentence.
+fn strip_tag_block(input: &str) -> Result<Option<(&str, &str)>, Error<'_>> {
+    let Some(rest) = input.trim_start().strip_prefix('\\') else {
+        return Ok(None);
+    };
+    match rest.split_once('\\') {
+        Some(split) => Ok(Some(split)),
+        None => Err(parsing_error(input)),
+    }
+}
+
+/// Returns the sentence after a TAG block, or the input if it has none.
+pub(crate) fn skip_tag_block(input: &str) -> Result<&str, Error<'_>> {
+    Ok(strip_tag_block(input)?.map_or(input, |(_, sentence)| sentence))
+}
+
+fn parse_tag_block(block: &str) -> Result<TagBlock, Error<'_>> {
+    let (fields, found) = block.rsplit_once('*').ok_or_else(|| parsing_error(block))?;
+    let found = u8::from_str_radix(found, 16).map_err(|_| parsing_error(block))?;
+    let calculated = checksum(fields.as_bytes().iter());
+    if calculated != found {
+        return Err(Error::ChecksumMismatch { calculated, found });
+    }
+
+    let mut tag_block = TagBlock::default();
+    for field in fields.split(',') {
+        let (code, value) = field.split_once(':').ok_or_else(|| parsing_error(field))?;
+        let id = || ArrayString::from(value).map_err(|_| parsing_error(field));
+        let number = || value.parse().map_err(|_| parsing_error(field));
+        match code {
+            "s" => tag_block.source = Some(id()?),
+            "d" => tag_block.destination = Some(id()?),
+            "c" => tag_block.unix_time = Some(number()?),
+            "n" => tag_block.line_count = Some(value.parse().map_err(|_| parsing_error(field))?),
+            "r" => tag_block.relative_time = Some(number()?),
+            _ => {}
+        }
+    }
+    Ok(tag_block)
+}
+
+fn parsing_error(input: &str) -> Error<'_> {
+    Error::ParsingError(nom::Err::Error(nom::error::Error::new(
+        input,
+        ErrorKind::Verify,
+    )))
+}
+
+impl Nmea {
+    /// Returns the TAG block of the last sentence passed to
+    /// [`parse()`](Self::parse) or [`parse_for_fix()`](Self::parse_for_fix).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "HDT")]
+    /// # {
+    /// nmea.parse("\\s:rcvr2,n:42,c:1622040996*60\\$GPHDT,274.07,T*03").unwrap();
+    /// assert_eq!(nmea.heading, Some(274.07));
+    /// assert_eq!(nmea.tag_block().unwrap().line_count, Some(42));
+    ///
+    /// nmea.parse("$GPHDT,274.07,T*03").unwrap();
+    /// assert_eq!(nmea.tag_block(), None);
+    /// # }
+    /// ```
+    pub fn tag_block(&self) -> Option<&TagBlock> {
+        self.tag_block.as_ref()
+    }
+
+    pub(crate) fn read_tag_block<'a>(&mut self, sentence: &'a str) -> Result<(), Error<'a>> {
+        self.tag_block = split_tag_block(sentence)?.0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_tag_block() {
+        let (tag_block, sentence) =
+            split_tag_block("\\c:1622040996,n:7*13\\$GPHDT,274.07,T*03\r\n").unwrap();
+        assert_eq!(
+            tag_block,
+            Some(TagBlock {
+                unix_time: Some(1_622_040_996),
+                line_count: Some(7),
+                ..TagBlock::default()
+            })
+        );
+        assert_eq!(sentence, "$GPHDT,274.07,T*03\r\n");
+
+        assert_eq!(
+            split_tag_block("$GPHDT,274.07,T*03"),
+            Ok((None, "$GPHDT,274.07,T*03"))
+        );
+        assert_eq!(
+            split_tag_block("\\c:1622040996,n:7*14\\$GPHDT,274.07,T*03"),
+            Err(Error::ChecksumMismatch {
+                calculated: 0x13,
+                found: 0x14
+            })
+        );
+        assert!(matches!(
+            split_tag_block("\\c:1622040996,n:7*13$GPHDT,274.07,T*03"),
+            Err(Error::ParsingError(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "HDT")]
+    fn test_parse_str_with_tag_block() {
+        let result = crate::parse_str("\\s:gw1,c:1622040996*18\\$GPHDT,274.07,T*03").unwrap();
+        assert!(matches!(result, crate::ParseResult::HDT(_)));
+    }
+}
@@ -0,0 +1,228 @@
+//! Time scales of the GNSS constellations, for conversions of fix times to
+//! GPS, Galileo, GLONASS and BeiDou time.
+//!
+//! The time scales differ from GPS time by whole seconds, see
+//! [`TimeSystem`], and by a bias of some nanoseconds, which some receivers
+//! report in TXT or proprietary sentences, see [`TimeSystemOffsets`].
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::float::FloatCore;
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::{LeapSecondInfo, Nmea};
+
+/// BDT - GPS offset in seconds, fixed since the BeiDou epoch.
+const BDT_GPS_OFFSET: i64 = -14;
+
+/// GLONASS time - UTC offset in seconds, the time of Moscow.
+const GLONASS_UTC_OFFSET: i64 = 3 * 3600;
+
+/// A time scale, see [`Nmea::fix_time_in()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeSystem {
+    Utc,
+    /// UTC plus the leap seconds since 1980
+    Gps,
+    /// Aligned with GPS time
+    Galileo,
+    /// UTC(SU) plus 3 hours, with the leap seconds of UTC
+    Glonass,
+    /// GPS time minus 14 seconds
+    BeiDou,
+}
+
+/// Biases of the time scales against GPS time, beyond their whole seconds
+/// of [`TimeSystem`].
+///
+/// Each offset is GPS time minus the time of the system in nanoseconds,
+/// e.g. the GGTO for Galileo, `None` until reported.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TimeSystemOffsets {
+    pub galileo: Option<f32>,
+    pub glonass: Option<f32>,
+    pub beidou: Option<f32>,
+}
+
+impl TimeSystemOffsets {
+    /// Returns the offset of a system, zero for UTC and GPS and if
+    /// unknown.
+    pub fn offset(&self, system: TimeSystem) -> f32 {
+        match system {
+            TimeSystem::Utc | TimeSystem::Gps => None,
+            TimeSystem::Galileo => self.galileo,
+            TimeSystem::Glonass => self.glonass,
+            TimeSystem::BeiDou => self.beidou,
+        }
+        .unwrap_or(0.)
+    }
+
+    /// Converts a UTC time to the time of a system, with the leap seconds
+    /// of `leap_seconds` and the offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{Duration, NaiveDate};
+    /// use nmea::{LeapSecondInfo, TimeSystem, TimeSystemOffsets};
+    ///
+    /// let offsets = TimeSystemOffsets {
+    ///     beidou: Some(-20.),
+    ///     ..TimeSystemOffsets::default()
+    /// };
+    /// let utc = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    /// let bdt = offsets.convert(utc, &LeapSecondInfo::default(), TimeSystem::BeiDou);
+    /// assert_eq!(bdt, utc + Duration::seconds(4) + Duration::nanoseconds(20));
+    /// ```
+    pub fn convert(
+        &self,
+        utc: NaiveDateTime,
+        leap_seconds: &LeapSecondInfo,
+        system: TimeSystem,
+    ) -> NaiveDateTime {
+        let nominal = match system {
+            TimeSystem::Utc => return utc,
+            TimeSystem::Gps | TimeSystem::Galileo => leap_seconds.gps_time(utc),
+            TimeSystem::Glonass => utc + Duration::seconds(GLONASS_UTC_OFFSET),
+            TimeSystem::BeiDou => leap_seconds.gps_time(utc) + Duration::seconds(BDT_GPS_OFFSET),
+        };
+        // float to integer casts saturate, and `NaN` is zero
+        nominal - Duration::nanoseconds(self.offset(system).round() as i64)
+    }
+
+    /// Reads the offsets from TXT sentences with `GPS-GAL`, `GGTO`,
+    /// `GPS-GLO` or `GPS-BDS` followed by the number of nanoseconds, e.g.
+    /// `GPS-GLO: -12.5 ns`.
+    pub(crate) fn update_from_text(&mut self, text: &str) {
+        const LABELS: [(&[u8], TimeSystem); 4] = [
+            (b"GPS-GAL", TimeSystem::Galileo),
+            (b"GGTO", TimeSystem::Galileo),
+            (b"GPS-GLO", TimeSystem::Glonass),
+            (b"GPS-BDS", TimeSystem::BeiDou),
+        ];
+        for (label, system) in LABELS {
+            let Some(start) = text
+                .as_bytes()
+                .windows(label.len())
+                .position(|window| window.eq_ignore_ascii_case(label))
+            else {
+                continue;
+            };
+            let rest = &text[start + label.len()..];
+            let Some(offset) = leading_number(rest) else {
+                continue;
+            };
+            let slot = match system {
+                TimeSystem::Galileo => &mut self.galileo,
+                TimeSystem::Glonass => &mut self.glonass,
+                _ => &mut self.beidou,
+            };
+            *slot = Some(offset);
+        }
+    }
+}
+
+/// Returns the first signed decimal number of `text`, skipping the
+/// separators before it, `None` beyond a second.
+fn leading_number(text: &str) -> Option<f32> {
+    let text = text.trim_start_matches([' ', ':', '=']);
+    let end = text
+        .char_indices()
+        .find(|(index, c)| !(c.is_ascii_digit() || *c == '.' || (*index == 0 && *c == '-')))
+        .map_or(text.len(), |(index, _)| index);
+    let offset: f32 = text[..end].parse().ok()?;
+    (offset.abs() < 1e9).then_some(offset)
+}
+
+impl Nmea {
+    /// Returns the biases of the time scales.
+    pub fn time_system_offsets(&self) -> TimeSystemOffsets {
+        self.time_offsets
+    }
+
+    /// Sets the biases of the time scales, e.g. from a proprietary
+    /// sentence.
+    pub fn set_time_system_offsets(&mut self, offsets: TimeSystemOffsets) {
+        self.time_offsets = offsets;
+    }
+
+    /// Returns the fix date and time in the time scale of a system, see
+    /// [`TimeSystemOffsets::convert()`].
+    pub fn fix_time_in(&self, system: TimeSystem) -> Option<NaiveDateTime> {
+        Some(
+            self.time_offsets
+                .convert(self.fix_datetime()?, &self.leap_seconds, system),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_update_from_text() {
+        let mut offsets = TimeSystemOffsets::default();
+        offsets.update_from_text("ANTSTATUS=OK");
+        assert_eq!(offsets, TimeSystemOffsets::default());
+        offsets.update_from_text("TIME GPS-GLO: -12.5 ns GPS-BDS=3ns");
+        assert_eq!(offsets.glonass, Some(-12.5));
+        assert_eq!(offsets.beidou, Some(3.));
+        offsets.update_from_text("ggto 1.5");
+        assert_eq!(offsets.galileo, Some(1.5));
+        offsets.update_from_text("GPS-GAL: n/a");
+        assert_eq!(offsets.galileo, Some(1.5));
+        offsets.update_from_text("GPS-GAL: 2000000000");
+        assert_eq!(offsets.galileo, Some(1.5));
+    }
+
+    #[test]
+    fn test_convert() {
+        let utc = NaiveDate::from_ymd_opt(2024, 3, 1)
+            .unwrap()
+            .and_hms_opt(23, 0, 0)
+            .unwrap();
+        let leap_seconds = LeapSecondInfo::default();
+        let offsets = TimeSystemOffsets {
+            glonass: Some(100.),
+            ..TimeSystemOffsets::default()
+        };
+        let convert = |system| offsets.convert(utc, &leap_seconds, system);
+        assert_eq!(convert(TimeSystem::Utc), utc);
+        assert_eq!(convert(TimeSystem::Gps), utc + Duration::seconds(18));
+        assert_eq!(convert(TimeSystem::Galileo), convert(TimeSystem::Gps));
+        assert_eq!(convert(TimeSystem::BeiDou), utc + Duration::seconds(4));
+        // the next day in Moscow
+        assert_eq!(
+            convert(TimeSystem::Glonass),
+            NaiveDate::from_ymd_opt(2024, 3, 2)
+                .unwrap()
+                .and_hms_nano_opt(1, 59, 59, 999_999_900)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "RMC", feature = "TXT"))]
+    fn test_fix_time_in() {
+        let mut nmea = Nmea::default();
+        nmea.parse("$GPTXT,01,01,02,GPS-BDS 5.0*7A").unwrap();
+        assert_eq!(nmea.fix_time_in(TimeSystem::BeiDou), None);
+        nmea.parse("$GPRMC,225446.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*46")
+            .unwrap();
+        let utc = nmea.fix_datetime().unwrap();
+        assert_eq!(
+            nmea.fix_time_in(TimeSystem::BeiDou),
+            Some(utc + Duration::seconds(4) - Duration::nanoseconds(5))
+        );
+        nmea.reset();
+        assert_eq!(nmea.time_system_offsets().beidou, Some(5.));
+    }
+}
@@ -0,0 +1,156 @@
+//! Parsing time per sentence type, measured with a user [`Clock`], to check
+//! on the target itself that parsing fits the budget of the main loop.
+//!
+//! [`Nmea`](crate::Nmea) records the timings of all sentences when a clock
+//! is set, see [`Nmea::parse_timings()`](crate::Nmea::parse_timings). For a
+//! cycle count, use a clock which returns the cycle counter as nanoseconds.
+
+use core::time::Duration;
+
+use crate::{parse_str, sentence_table::SentenceTable, Clock, Error, ParseResult, SentenceType};
+
+/// Number of sentence types with a separate timing.
+pub const PARSE_TIMING_SENTENCES: usize = 16;
+
+/// Parsing time statistics of one sentence type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseTiming {
+    /// Number of sentences measured
+    pub count: u32,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub total: Duration,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub min: Duration,
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub max: Duration,
+}
+
+impl ParseTiming {
+    fn new(elapsed: Duration) -> Self {
+        Self {
+            count: 1,
+            total: elapsed,
+            min: elapsed,
+            max: elapsed,
+        }
+    }
+
+    fn update(&mut self, elapsed: Duration) {
+        self.count = self.count.saturating_add(1);
+        self.total = self.total.saturating_add(elapsed);
+        self.min = self.min.min(elapsed);
+        self.max = self.max.max(elapsed);
+    }
+
+    /// Returns the mean parsing time.
+    pub fn mean(&self) -> Duration {
+        self.total / self.count.max(1)
+    }
+}
+
+/// Parsing times of the first [`PARSE_TIMING_SENTENCES`] sentence types.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseTimings {
+    sentences: SentenceTable<ParseTiming, PARSE_TIMING_SENTENCES>,
+}
+
+impl ParseTimings {
+    /// Returns the timing of a sentence type.
+    pub fn get(&self, sentence_type: SentenceType) -> Option<&ParseTiming> {
+        self.sentences.get(sentence_type)
+    }
+
+    /// Returns the timings of all measured sentence types.
+    pub fn iter(&self) -> impl Iterator<Item = (SentenceType, &ParseTiming)> {
+        self.sentences.iter()
+    }
+
+    /// Returns the longest parsing time of any sentence.
+    pub fn max(&self) -> Option<Duration> {
+        self.sentences.iter().map(|(_, timing)| timing.max).max()
+    }
+
+    /// Adds the parsing time of one sentence.
+    pub fn record(&mut self, sentence_type: SentenceType, elapsed: Duration) {
+        self.sentences.update_or_insert(
+            sentence_type,
+            |timing| timing.update(elapsed),
+            || ParseTiming::new(elapsed),
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.sentences.clear();
+    }
+}
+
+/// Parses a sentence like [`parse_str()`] and records its parsing time.
+///
+/// Only valid sentences are recorded.
+///
+/// # Examples
+///
+/// ```
+/// use core::time::Duration;
+/// use nmea::{timing::{parse_str_timed, ParseTimings}, SentenceType};
+///
+/// fn cycles() -> Duration {
+///     // read the cycle counter here
+///     Duration::from_nanos(0)
+/// }
+///
+/// let mut timings = ParseTimings::default();
+/// # #[cfg(feature = "HDT")]
+/// # {
+/// parse_str_timed("$GPHDT,274.07,T*03", &cycles, &mut timings).unwrap();
+/// assert_eq!(timings.get(SentenceType::HDT).unwrap().count, 1);
+/// # }
+/// ```
+pub fn parse_str_timed<'a>(
+    sentence: &'a str,
+    clock: &dyn Clock,
+    timings: &mut ParseTimings,
+) -> Result<ParseResult, Error<'a>> {
+    timed(clock, timings, || parse_str(sentence))
+}
+
+/// Records the time of a successful parse.
+pub(crate) fn timed<'a>(
+    clock: &dyn Clock,
+    timings: &mut ParseTimings,
+    parse: impl FnOnce() -> Result<ParseResult, Error<'a>>,
+) -> Result<ParseResult, Error<'a>> {
+    let start = clock.now();
+    let parse_result = parse()?;
+    let elapsed = clock.now().saturating_sub(start);
+    timings.record((&parse_result).into(), elapsed);
+    Ok(parse_result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timings() {
+        let mut timings = ParseTimings::default();
+        let us = Duration::from_micros;
+        timings.record(SentenceType::GGA, us(30));
+        timings.record(SentenceType::GSV, us(80));
+        timings.record(SentenceType::GGA, us(10));
+        timings.record(SentenceType::GGA, us(20));
+
+        let gga = timings.get(SentenceType::GGA).unwrap();
+        assert_eq!(gga.count, 3);
+        assert_eq!(gga.min, us(10));
+        assert_eq!(gga.max, us(30));
+        assert_eq!(gga.mean(), us(20));
+        assert_eq!(timings.max(), Some(us(80)));
+        assert_eq!(timings.iter().count(), 2);
+        assert!(timings.get(SentenceType::RMC).is_none());
+    }
+}
@@ -0,0 +1,137 @@
+//! Counting of the sentences [`Nmea`] does not use, with the reason, to
+//! turn silently dropped data into configuration feedback.
+
+use core::fmt;
+
+use crate::{parse::NmeaSentence, sentence_table::SentenceTable, Error, Nmea, SentenceType};
+
+/// Number of sentence types with a separate unsupported count.
+pub const UNSUPPORTED_LEN: usize = 16;
+
+/// Why [`Nmea`] does not use a sentence type.
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedReason {
+    /// The sentence is implemented, but the cargo feature is not enabled.
+    FeatureDisabled(&'static str),
+    /// The crate does not implement the sentence.
+    NotImplemented,
+    /// The sentence is parsed by [`parse_str()`](crate::parse_str), but not
+    /// merged into the state of [`Nmea`].
+    NotMerged,
+}
+
+impl UnsupportedReason {
+    fn of(sentence_type: SentenceType) -> Self {
+        let metadata = sentence_type.metadata();
+        match metadata.feature {
+            Some(feature) if !metadata.parsed => UnsupportedReason::FeatureDisabled(feature),
+            Some(_) => UnsupportedReason::NotMerged,
+            None => UnsupportedReason::NotImplemented,
+        }
+    }
+}
+
+impl fmt::Display for UnsupportedReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsupportedReason::FeatureDisabled(feature) => {
+                write!(f, "enable the `{}` cargo feature", feature)
+            }
+            UnsupportedReason::NotImplemented => write!(f, "not implemented"),
+            UnsupportedReason::NotMerged => write!(f, "use `parse_str()` for this sentence"),
+        }
+    }
+}
+
+/// A sentence type received but not used, see [`Nmea::unsupported_summary()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedSentence {
+    pub sentence_type: SentenceType,
+    /// Number of sentences received
+    pub count: u32,
+    pub reason: UnsupportedReason,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct UnsupportedTable {
+    sentences: SentenceTable<u32, UNSUPPORTED_LEN>,
+}
+
+impl UnsupportedTable {
+    fn record(&mut self, sentence_type: SentenceType) {
+        self.sentences.update_or_insert(
+            sentence_type,
+            |count| *count = count.saturating_add(1),
+            || 1,
+        );
+    }
+}
+
+impl Nmea {
+    /// Returns the known sentence types which were received but not used,
+    /// and why, for the first [`UNSUPPORTED_LEN`] types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{Nmea, SentenceType, UnsupportedReason};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// // ROT - Rate of Turn
+    /// assert!(nmea.parse("$HEROT,-0.3,A*05").is_err());
+    ///
+    /// let unsupported = nmea.unsupported_summary().next().unwrap();
+    /// assert_eq!(unsupported.sentence_type, SentenceType::ROT);
+    /// assert_eq!(unsupported.count, 1);
+    /// assert_eq!(unsupported.reason, UnsupportedReason::NotImplemented);
+    /// ```
+    pub fn unsupported_summary(&self) -> impl Iterator<Item = UnsupportedSentence> + '_ {
+        self.unsupported
+            .sentences
+            .iter()
+            .map(|(sentence_type, &count)| UnsupportedSentence {
+                sentence_type,
+                count,
+                reason: UnsupportedReason::of(sentence_type),
+            })
+    }
+
+    /// Counts the sentence of an unsupported or disabled sentence error.
+    pub(crate) fn record_unsupported(&mut self, sentence: &NmeaSentence, error: &Error) {
+        if matches!(error, Error::Unsupported(_) | Error::DisabledSentence) {
+            self.unsupported.record(sentence.message_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_reason() {
+        assert_eq!(
+            UnsupportedReason::of(SentenceType::ROT),
+            UnsupportedReason::NotImplemented
+        );
+        let reason = UnsupportedReason::of(SentenceType::DBK);
+        if cfg!(feature = "DBK") {
+            assert_eq!(reason, UnsupportedReason::NotMerged);
+        } else {
+            assert_eq!(reason, UnsupportedReason::FeatureDisabled("DBK"));
+        }
+
+        let mut table = UnsupportedTable::default();
+        table.record(SentenceType::ROT);
+        table.record(SentenceType::DBK);
+        table.record(SentenceType::ROT);
+        assert_eq!(table.sentences.get(SentenceType::ROT), Some(&2));
+        assert_eq!(table.sentences.get(SentenceType::DBK), Some(&1));
+    }
+}
@@ -0,0 +1,119 @@
+//! When each sentence type last contributed data to [`Nmea`], for
+//! diagnostics like "GSA: 2 s ago, GSV: 31 s ago".
+
+use core::time::Duration;
+
+use crate::{sentence_table::SentenceTable, Nmea, SentenceType};
+use chrono::NaiveTime;
+
+/// Number of sentence types whose last update is recorded.
+pub const UPDATES_LEN: usize = 16;
+
+/// The last time a sentence type contributed data, see
+/// [`Nmea::last_update()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastUpdate {
+    /// Fix time of the epoch the sentence belonged to
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub fix_time: Option<NaiveTime>,
+    /// Time of the [`Clock`](crate::Clock), if one is set
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    pub clock: Option<Duration>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct UpdateTable {
+    updates: SentenceTable<LastUpdate, UPDATES_LEN>,
+}
+
+impl UpdateTable {
+    fn record(&mut self, sentence_type: SentenceType, update: LastUpdate) {
+        // only the first types are recorded
+        let _ = self.updates.insert(sentence_type, update);
+    }
+
+    fn get(&self, sentence_type: SentenceType) -> Option<LastUpdate> {
+        self.updates.get(sentence_type).copied()
+    }
+}
+
+impl Nmea {
+    /// Returns when a sentence type last contributed data, for the first
+    /// [`UPDATES_LEN`] types merged by [`parse()`](Self::parse) or
+    /// [`parse_for_fix()`](Self::parse_for_fix).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveTime;
+    /// use nmea::{Nmea, SentenceType};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(all(feature = "GGA", feature = "GSA"))]
+    /// # {
+    /// nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+    ///     .unwrap();
+    /// nmea.parse("$GPGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1,1.4*3E")
+    ///     .unwrap();
+    ///
+    /// let gsa = nmea.last_update(SentenceType::GSA).unwrap();
+    /// assert_eq!(gsa.fix_time, NaiveTime::from_hms_opt(9, 27, 50));
+    /// assert_eq!(nmea.last_update(SentenceType::RMC), None);
+    /// # }
+    /// ```
+    pub fn last_update(&self, sentence_type: SentenceType) -> Option<LastUpdate> {
+        self.updates.get(sentence_type)
+    }
+
+    /// Returns the time since a sentence type last contributed data,
+    /// measured with the [`Clock`](crate::Clock).
+    pub fn update_age(&self, sentence_type: SentenceType) -> Option<Duration> {
+        let clock = self.last_update(sentence_type)?.clock?;
+        Some(self.now()?.saturating_sub(clock))
+    }
+
+    pub(crate) fn record_update(&mut self, sentence_type: SentenceType) {
+        let update = LastUpdate {
+            fix_time: self.fix_time,
+            clock: self.now(),
+        };
+        self.updates.record(sentence_type, update);
+    }
+}
+
+#[cfg(all(test, feature = "GSA", feature = "GSV"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_age() {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        static NOW_MS: AtomicU64 = AtomicU64::new(0);
+        fn now() -> Duration {
+            Duration::from_millis(NOW_MS.load(Ordering::SeqCst))
+        }
+
+        let mut nmea = Nmea::default();
+        nmea.set_clock(&(now as fn() -> Duration));
+        nmea.parse("$GPGSV,1,1,01,01,,,42*7F").unwrap();
+        NOW_MS.store(29_000, Ordering::SeqCst);
+        nmea.parse("$GPGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1,1.4*3E")
+            .unwrap();
+        NOW_MS.store(31_000, Ordering::SeqCst);
+
+        assert_eq!(
+            nmea.update_age(SentenceType::GSA),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            nmea.update_age(SentenceType::GSV),
+            Some(Duration::from_secs(31))
+        );
+        assert_eq!(nmea.update_age(SentenceType::GGA), None);
+    }
+}
@@ -0,0 +1,226 @@
+//! UTM and MGRS grid coordinates of the positions.
+//!
+//! [`Utm::from_lat_lon()`] projects a WGS 84 position with the series of
+//! the USGS Professional Paper 1395, accurate to the millimeter within the
+//! zones, and [`Utm::mgrs()`] formats it as a Military Grid Reference
+//! System string. The zones follow the exceptions around Norway and
+//! Svalbard. The polar regions, covered by UPS instead of UTM, are not
+//! supported.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use core::fmt::Write;
+
+use arrayvec::ArrayString;
+
+use crate::Fix;
+
+/// Maximum length of the MGRS strings, e.g. `33UXP0500444996`.
+pub const MGRS_MAX_LEN: usize = 15;
+
+/// Scale factor at the central meridian
+const K0: f64 = 0.9996;
+/// WGS 84 semi-major axis in meters
+const A: f64 = 6_378_137.;
+/// WGS 84 flattening
+const F: f64 = 1. / 298.257_223_563;
+
+/// Latitude bands from 80° South, 8° each but the 12° of `X`.
+const BANDS: &[u8; 20] = b"CDEFGHJKLMNPQRSTUVWX";
+/// Row letters of the 100 km squares of MGRS.
+const ROWS: &[u8; 20] = b"ABCDEFGHJKLMNPQRSTUV";
+/// Column letters of the 100 km squares of MGRS, by zone modulo 3.
+const COLUMNS: [&[u8; 8]; 3] = [b"STUVWXYZ", b"ABCDEFGH", b"JKLMNPQR"];
+
+/// A position in the Universal Transverse Mercator grid.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::utm::Utm;
+///
+/// let utm = Utm::from_lat_lon(53.361336, -6.505620).unwrap();
+/// assert_eq!((utm.zone, utm.band), (29, 'U'));
+/// assert_eq!(utm.mgrs(3).as_str(), "29UPV659153");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Utm {
+    /// Zone from 1 to 60
+    pub zone: u8,
+    /// Latitude band letter, from `C` to `X`, `N` and after in the Northern
+    /// hemisphere
+    pub band: char,
+    /// Easting in meters, 500 km at the central meridian of the zone
+    pub easting: f64,
+    /// Northing in meters from the equator, plus 10000 km in the Southern
+    /// hemisphere
+    pub northing: f64,
+}
+
+impl Utm {
+    /// Projects a position in degrees, `None` outside of the latitudes from
+    /// 80° South to 84° North.
+    pub fn from_lat_lon(latitude: f64, longitude: f64) -> Option<Self> {
+        if !(-80.0..=84.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return None;
+        }
+        let zone = zone(latitude, longitude);
+        let band_index = (((latitude + 80.) / 8.) as usize).min(BANDS.len() - 1);
+        let band = char::from(BANDS[band_index]);
+
+        let central_meridian = f64::from(zone) * 6. - 183.;
+        let mut lon = longitude - central_meridian;
+        // the zone 1 touches the antimeridian from the East
+        if lon < -180. {
+            lon += 360.;
+        } else if lon > 180. {
+            lon -= 360.;
+        }
+        let (phi, lambda) = (latitude.to_radians(), lon.to_radians());
+
+        let e2 = F * (2. - F);
+        let (e4, e6) = (e2 * e2, e2 * e2 * e2);
+        let ep2 = e2 / (1. - e2);
+        let (sin, cos) = phi.sin_cos();
+        let n = A / (1. - e2 * sin * sin).sqrt();
+        let t = (sin / cos).powi(2);
+        let c = ep2 * cos * cos;
+        let a = lambda * cos;
+        let m = A
+            * ((1. - e2 / 4. - 3. * e4 / 64. - 5. * e6 / 256.) * phi
+                - (3. * e2 / 8. + 3. * e4 / 32. + 45. * e6 / 1024.) * (2. * phi).sin()
+                + (15. * e4 / 256. + 45. * e6 / 1024.) * (4. * phi).sin()
+                - (35. * e6 / 3072.) * (6. * phi).sin());
+
+        let easting = K0
+            * n
+            * (a + (1. - t + c) * a.powi(3) / 6.
+                + (5. - 18. * t + t * t + 72. * c - 58. * ep2) * a.powi(5) / 120.)
+            + 500_000.;
+        let mut northing = K0
+            * (m + n
+                * (sin / cos)
+                * (a * a / 2.
+                    + (5. - t + 9. * c + 4. * c * c) * a.powi(4) / 24.
+                    + (61. - 58. * t + t * t + 600. * c - 330. * ep2) * a.powi(6) / 720.));
+        if latitude < 0. {
+            northing += 10_000_000.;
+        }
+        Some(Utm {
+            zone,
+            band,
+            easting,
+            northing,
+        })
+    }
+
+    /// Returns the MGRS reference with `digits` digits per axis, from 0 for
+    /// the 100 km square to 5 for 1 m, the coordinates being truncated.
+    pub fn mgrs(&self, digits: u8) -> ArrayString<MGRS_MAX_LEN> {
+        let digits = digits.min(5);
+        let column = ((self.easting / 100_000.) as usize).clamp(1, 8) - 1;
+        let row = (self.northing / 100_000.) as usize
+            // the rows of the even zones are shifted by 5 letters
+            + if self.zone % 2 == 0 { 5 } else { 0 };
+        let divisor = 10_u32.pow(u32::from(5 - digits));
+        let easting = (self.easting % 100_000.) as u32 / divisor;
+        let northing = (self.northing % 100_000.) as u32 / divisor;
+
+        let mut mgrs = ArrayString::new();
+        // at most 15 characters, which fit
+        let _ = write!(
+            mgrs,
+            "{}{}{}{}",
+            self.zone,
+            self.band,
+            char::from(COLUMNS[usize::from(self.zone % 3)][column]),
+            char::from(ROWS[row % ROWS.len()]),
+        );
+        if digits > 0 {
+            let width = usize::from(digits);
+            let _ = write!(mgrs, "{:02$}{:02$}", easting, northing, width);
+        }
+        mgrs
+    }
+}
+
+/// Returns the UTM zone of a position, with the exceptions of Norway and
+/// Svalbard.
+fn zone(latitude: f64, longitude: f64) -> u8 {
+    if (56.0..64.0).contains(&latitude) && (3.0..12.0).contains(&longitude) {
+        return 32;
+    }
+    if (72.0..=84.0).contains(&latitude) && (0.0..42.0).contains(&longitude) {
+        return match longitude {
+            longitude if longitude < 9. => 31,
+            longitude if longitude < 21. => 33,
+            longitude if longitude < 33. => 35,
+            _ => 37,
+        };
+    }
+    ((((longitude + 180.) / 6.) as u8) % 60) + 1
+}
+
+impl Fix {
+    /// Returns the UTM coordinates of the position, see [`Utm`].
+    pub fn utm(&self) -> Option<Utm> {
+        let (latitude, longitude) = (self.latitude?, self.longitude?);
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let (latitude, longitude) = (latitude as f64, longitude as f64);
+        Utm::from_lat_lon(latitude, longitude)
+    }
+
+    /// Returns the MGRS reference of the position with `digits` digits per
+    /// axis, see [`Utm::mgrs()`].
+    pub fn mgrs(&self, digits: u8) -> Option<ArrayString<MGRS_MAX_LEN>> {
+        Some(self.utm()?.mgrs(digits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_utm() {
+        let utm = Utm::from_lat_lon(0., 3.).unwrap();
+        assert_eq!((utm.zone, utm.band), (31, 'N'));
+        assert_relative_eq!(utm.easting, 500_000., epsilon = 1e-6);
+        assert_relative_eq!(utm.northing, 0., epsilon = 1e-6);
+        assert_eq!(utm.mgrs(5).as_str(), "31NEA0000000000");
+        assert_eq!(utm.mgrs(0).as_str(), "31NEA");
+
+        let utm = Utm::from_lat_lon(-33.856784, 151.215297).unwrap();
+        assert_eq!((utm.zone, utm.band), (56, 'H'));
+        assert_relative_eq!(utm.easting, 334_900.26, epsilon = 0.01);
+        assert_relative_eq!(utm.northing, 6_252_290.52, epsilon = 0.01);
+
+        // Norway and Svalbard
+        assert_eq!(Utm::from_lat_lon(60., 5.).unwrap().zone, 32);
+        assert_eq!(Utm::from_lat_lon(78., 15.).unwrap().zone, 33);
+        assert_eq!(Utm::from_lat_lon(0., 180.).unwrap().zone, 1);
+        assert_eq!(Utm::from_lat_lon(84., 0.).unwrap().band, 'X');
+        assert_eq!(Utm::from_lat_lon(85., 0.), None);
+
+        // the Washington Monument
+        let utm = Utm::from_lat_lon(38.8895, -77.0352).unwrap();
+        assert_eq!(utm.mgrs(5).as_str(), "18SUJ2348606483");
+    }
+
+    #[test]
+    fn test_fix_mgrs() {
+        let fix = Fix {
+            latitude: Some(53.361336),
+            longitude: Some(-6.50562),
+            ..Fix::default()
+        };
+        assert_eq!(fix.mgrs(4).unwrap().as_str(), "29UPV65981536");
+        assert_eq!(Fix::default().utm(), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! Velocity vector in the local East, North, Up frame, see
+//! [`Nmea::velocity()`].
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float as _;
+
+use chrono::{NaiveDateTime, NaiveTime};
+
+use crate::{geo, Nmea};
+
+/// Velocity in meters per second in the local East, North, Up frame.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Velocity {
+    /// Speed to the East
+    pub east: f32,
+    /// Speed to the North
+    pub north: f32,
+    /// Climb rate, `None` without the altitudes of two fixes
+    pub up: Option<f32>,
+}
+
+impl Velocity {
+    /// Returns the horizontal speed in meters per second.
+    pub fn horizontal_speed(&self) -> f32 {
+        self.east.hypot(self.north)
+    }
+}
+
+/// The last altitude and the climb rate since the previous one.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) struct ClimbState {
+    #[cfg_attr(feature = "defmt-03", defmt(Debug2Format))]
+    last: Option<(NaiveTime, Option<NaiveDateTime>, f32)>,
+    /// Climb rate in meters per second at the time of the last altitude
+    rate: Option<f32>,
+}
+
+impl Nmea {
+    /// Returns the velocity of the fix in meters per second, from the speed
+    /// and the course over ground.
+    ///
+    /// The climb rate is the change of the altitude since the previous fix
+    /// with an altitude, `None` if the current fix has no altitude yet.
+    /// Returns `None` without speed, or without course while moving.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::Nmea;
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "VTG")]
+    /// # {
+    /// nmea.parse("$GPVTG,090.0,T,,M,001.0,N,,K*46").unwrap();
+    /// let velocity = nmea.velocity().unwrap();
+    /// assert!((velocity.east - 0.514).abs() < 0.001);
+    /// assert!(velocity.north.abs() < 0.001);
+    /// assert_eq!(velocity.up, None);
+    /// # }
+    /// ```
+    pub fn velocity(&self) -> Option<Velocity> {
+        let speed = self.speed_over_ground? * geo::METERS_PER_SECOND_PER_KNOT;
+        let (east, north) = match self.true_course {
+            Some(course) => {
+                let (sin, cos) = course.to_radians().sin_cos();
+                (speed * sin, speed * cos)
+            }
+            None if speed == 0. => (0., 0.),
+            None => return None,
+        };
+        Some(Velocity {
+            east,
+            north,
+            up: self.climb_rate(),
+        })
+    }
+
+    /// Returns the climb rate in meters per second, see
+    /// [`velocity()`](Self::velocity).
+    pub fn climb_rate(&self) -> Option<f32> {
+        match (self.climb.last, self.fix_time) {
+            (Some((time, _, _)), Some(fix_time)) if time == fix_time => self.climb.rate,
+            _ => None,
+        }
+    }
+
+    /// Updates the climb rate with the altitude of a GGA or GNS sentence
+    /// which was just stored.
+    pub(crate) fn update_climb(&mut self) {
+        if !self.fix_type.is_some_and(|fix_type| fix_type.is_valid()) {
+            return;
+        }
+        let (Some(fix_time), Some(altitude)) = (self.fix_time, self.altitude) else {
+            return;
+        };
+        let current = (fix_time, self.fix_datetime, altitude);
+        if let Some((time, datetime, last)) = self.climb.last {
+            let elapsed = geo::elapsed((time, datetime), (fix_time, self.fix_datetime));
+            if elapsed == 0. {
+                return;
+            }
+            self.climb.rate = (elapsed > 0.).then(|| (altitude - last) / elapsed);
+        }
+        self.climb.last = Some(current);
+    }
+}
+
+#[cfg(all(test, feature = "GGA", feature = "RMC"))]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_velocity() {
+        let mut nmea = Nmea::default();
+        assert_eq!(nmea.velocity(), None);
+        nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76")
+            .unwrap();
+        // South West at 10 knots
+        nmea.parse("$GPRMC,092752.000,A,5321.6802,N,00630.3372,W,10.0,225.0,310324,,,A*4D")
+            .unwrap();
+        let velocity = nmea.velocity().unwrap();
+        assert_relative_eq!(velocity.east, -3.637, epsilon = 0.001);
+        assert_relative_eq!(velocity.north, -3.637, epsilon = 0.001);
+        assert_relative_eq!(velocity.horizontal_speed(), 5.144, epsilon = 0.001);
+        // the climb rate of the previous fix
+        assert_eq!(velocity.up, None);
+
+        // 3 m higher in 2 s
+        nmea.parse("$GPGGA,092752.000,5321.6802,N,00630.3372,W,1,8,1.03,64.7,M,55.2,M,,*71")
+            .unwrap();
+        assert_relative_eq!(nmea.velocity().unwrap().up.unwrap(), 1.5, epsilon = 0.001);
+        assert_relative_eq!(nmea.climb_rate().unwrap(), 1.5, epsilon = 0.001);
+    }
+}
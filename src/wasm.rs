@@ -0,0 +1,63 @@
+//! JavaScript bindings for `wasm32-unknown-unknown` builds.
+//!
+//! Parse results are converted to plain JavaScript objects with
+//! `serde-wasm-bindgen`, using the same layout as the `serde` derives.
+//! `chrono` is used without its `wasmbind` and `clock` features, as the
+//! parser never reads the system clock.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{Error, Nmea};
+
+/// A stateful [`Nmea`] parser usable from JavaScript.
+///
+/// ```js
+/// import { WasmNmea } from "nmea";
+///
+/// const nmea = new WasmNmea();
+/// const sentenceType = nmea.parse("$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76");
+/// console.log(sentenceType, nmea.fix().latitude);
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Default)]
+pub struct WasmNmea {
+    inner: Nmea,
+}
+
+#[wasm_bindgen]
+impl WasmNmea {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmNmea {
+        WasmNmea::default()
+    }
+
+    /// Parses a sentence and stores its data like [`Nmea::parse()`], and
+    /// returns the serialized [`SentenceType`](crate::SentenceType).
+    ///
+    /// Use [`parseStr()`](parse_str) to get the data of a single sentence.
+    pub fn parse(&mut self, sentence: &str) -> Result<JsValue, JsError> {
+        let sentence_type = self.inner.parse(sentence).map_err(js_error)?;
+        Ok(serde_wasm_bindgen::to_value(&sentence_type)?)
+    }
+
+    /// Returns the serialized [`Fix`](crate::Fix).
+    pub fn fix(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.inner.fix())?)
+    }
+
+    /// Returns the serialized satellites in view.
+    pub fn satellites(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.inner.satellites())?)
+    }
+}
+
+/// Parses a single sentence without keeping state, see [`parse_str()`](crate::parse_str).
+#[wasm_bindgen(js_name = parseStr)]
+pub fn parse_str(sentence: &str) -> Result<JsValue, JsError> {
+    let parse_result = crate::parse_str(sentence).map_err(js_error)?;
+    Ok(serde_wasm_bindgen::to_value(&parse_result)?)
+}
+
+fn js_error(err: Error<'_>) -> JsError {
+    JsError::new(&err.to_string())
+}
@@ -0,0 +1,446 @@
+//! Weather and water data of the MTW, MWV, MDA, DBK, DBS, DBT and DPT
+//! sentences, tagged with the talker which sent them.
+//!
+//! Installations often receive the same quantity from several talkers, e.g.
+//! the wind of the masthead unit (`WI`) repeated by the instrument
+//! multiplexer (`II`), late or rounded. The [talker
+//! priority](Nmea::set_weather_talker_priority) ranks them: in an epoch, a
+//! value is only replaced by a talker of the same or a higher priority.
+//! Talkers missing from the priority come after the others. The values of
+//! the previous epochs can be replaced by any talker, so the data keeps
+//! flowing when the preferred instrument stops.
+//!
+//! The epochs are the cycles of the [clock](crate::CycleSource::Clock), and
+//! follow the fix times as well: a value keeps its priority until the fix
+//! time changed twice since it was received, as it may come before the fix
+//! of its epoch.
+
+use chrono::NaiveTime;
+use heapless::Vec;
+
+use crate::{
+    epoch::FixEpochs,
+    sentences::{
+        mwv::{MwvReference, MwvWindSpeedUnits},
+        Depth, DepthReference, MdaData, MtwData, MwvData,
+    },
+    Nmea, TalkerId,
+};
+
+/// Maximum number of talkers of the weather talker priority.
+pub const WEATHER_TALKERS_LEN: usize = 4;
+
+/// A value and the talker of its sentence.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sourced<T> {
+    pub value: T,
+    /// `None` for data stored without its sentence, with
+    /// [`Nmea::parse_result_merge()`]
+    pub talker: Option<TalkerId>,
+}
+
+/// Wind angle and speed of an MWV sentence.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wind {
+    /// Angle relative to the bow in `(-180, 180]` degrees, positive to
+    /// starboard
+    pub angle: f32,
+    /// Speed in knots, converted from the unit of the sentence, taken as
+    /// knots if missing
+    pub speed_knots: f32,
+}
+
+/// Depth of the water and the point it is measured from.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaterDepth {
+    pub depth: Depth,
+    pub reference: DepthReference,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Reading<T> {
+    sourced: Sourced<T>,
+    /// Rank of the talker in the priority, `None` for values of the
+    /// previous clock cycles
+    rank: Option<usize>,
+    /// The epoch of the fix times the value was received in
+    epoch: u32,
+}
+
+/// Replaces the reading unless it is from this epoch and a talker of a
+/// higher priority.
+fn update<T>(
+    reading: &mut Option<Reading<T>>,
+    value: Option<T>,
+    talker: Option<TalkerId>,
+    rank: usize,
+    epochs: FixEpochs,
+) {
+    let Some(value) = value else {
+        return;
+    };
+    let replace = match reading {
+        Some(Reading {
+            rank: Some(current),
+            epoch,
+            ..
+        }) if epochs.is_recent(*epoch) => rank <= *current,
+        _ => true,
+    };
+    if replace {
+        *reading = Some(Reading {
+            sourced: Sourced { value, talker },
+            rank: Some(rank),
+            epoch: epochs.current(),
+        });
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct WeatherState {
+    priority: Vec<TalkerId, WEATHER_TALKERS_LEN>,
+    /// The talker of the sentence being stored
+    pub(crate) talker: Option<TalkerId>,
+    water_temperature: Option<Reading<f32>>,
+    water_depth: Option<Reading<WaterDepth>>,
+    air_temperature: Option<Reading<f32>>,
+    pressure: Option<Reading<f32>>,
+    relative_humidity: Option<Reading<f32>>,
+    relative_wind: Option<Reading<Wind>>,
+    theoretical_wind: Option<Reading<Wind>>,
+    epochs: FixEpochs,
+}
+
+impl WeatherState {
+    /// The state of a new epoch, keeping the values but not their rank.
+    pub(crate) fn restarted(&self) -> Self {
+        fn aged<T: Copy>(reading: Option<Reading<T>>) -> Option<Reading<T>> {
+            reading.map(|reading| Reading {
+                rank: None,
+                ..reading
+            })
+        }
+        Self {
+            priority: self.priority.clone(),
+            talker: None,
+            water_temperature: aged(self.water_temperature),
+            water_depth: aged(self.water_depth),
+            air_temperature: aged(self.air_temperature),
+            pressure: aged(self.pressure),
+            relative_humidity: aged(self.relative_humidity),
+            relative_wind: aged(self.relative_wind),
+            theoretical_wind: aged(self.theoretical_wind),
+            epochs: self.epochs,
+        }
+    }
+
+    /// The state without values, with the same priority.
+    pub(crate) fn cleared(&self) -> Self {
+        Self {
+            priority: self.priority.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the talker of the sentence being stored, its rank and the
+    /// epochs at the fix time.
+    fn source(&mut self, fix_time: Option<NaiveTime>) -> (Option<TalkerId>, usize, FixEpochs) {
+        self.epochs.advance(fix_time);
+        let rank = self
+            .talker
+            .and_then(|talker| self.priority.iter().position(|&id| id == talker))
+            .unwrap_or(self.priority.len());
+        (self.talker, rank, self.epochs)
+    }
+}
+
+impl Nmea {
+    /// Sets the talkers of the weather and water data, from the most to the
+    /// least preferred, see the [module documentation](crate::weather).
+    /// Invalid talker IDs are ignored, and only the first
+    /// [`WEATHER_TALKERS_LEN`] are kept.
+    ///
+    /// The default is no priority: the last value received is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{sentences::mwv::MwvReference, Nmea};
+    ///
+    /// let mut nmea = Nmea::default();
+    /// // trust the masthead unit over the multiplexer
+    /// nmea.set_weather_talker_priority(&["WI", "II"]);
+    /// # #[cfg(feature = "MWV")]
+    /// # {
+    /// nmea.parse("$WIMWV,041.1,R,01.0,N,A*16").unwrap();
+    /// nmea.parse("$IIMWV,041.0,R,01.0,N,A*09").unwrap();
+    /// let wind = nmea.wind(MwvReference::Relative).unwrap();
+    /// assert_eq!(wind.value.angle, 41.1);
+    /// assert_eq!(wind.talker.unwrap().as_str(), "WI");
+    /// # }
+    /// ```
+    pub fn set_weather_talker_priority(&mut self, priority: &[&str]) {
+        let mut talkers = Vec::new();
+        for talker in priority.iter().filter_map(|talker| TalkerId::new(talker)) {
+            if !talkers.contains(&talker) && talkers.push(talker).is_err() {
+                break;
+            }
+        }
+        self.weather = WeatherState {
+            priority: talkers,
+            ..self.weather.restarted()
+        };
+    }
+
+    /// Returns the talkers of the weather and water data, from the most to
+    /// the least preferred.
+    pub fn weather_talker_priority(&self) -> &[TalkerId] {
+        &self.weather.priority
+    }
+
+    /// Returns the water temperature in degrees Celsius, of MTW or MDA.
+    pub fn water_temperature(&self) -> Option<Sourced<f32>> {
+        self.weather
+            .water_temperature
+            .map(|reading| reading.sourced)
+    }
+
+    /// Returns the water depth of the last DBK, DBS, DBT or DPT sentence,
+    /// with the point it is measured from.
+    ///
+    /// The units of DBK, DBS and DBT are reconciled with
+    /// [`Depth::reconcile()`]. The depths are kept as sent, without
+    /// applying the offset of the transducer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nmea::{
+    ///     sentences::{DepthReference, DepthUnit},
+    ///     Nmea,
+    /// };
+    ///
+    /// let mut nmea = Nmea::default();
+    /// # #[cfg(feature = "DBT")]
+    /// # {
+    /// nmea.parse("$SDDBT,7.8,f,2.4,M,1.3,F*0D").unwrap();
+    /// let water_depth = nmea.water_depth().unwrap().value;
+    /// assert_eq!(water_depth.depth.meters, 2.4);
+    /// assert_eq!(water_depth.depth.source_unit, DepthUnit::Meters);
+    /// assert_eq!(water_depth.reference, DepthReference::Transducer);
+    /// # }
+    /// ```
+    pub fn water_depth(&self) -> Option<Sourced<WaterDepth>> {
+        self.weather.water_depth.map(|reading| reading.sourced)
+    }
+
+    /// Returns the air temperature in degrees Celsius, of MDA.
+    pub fn air_temperature(&self) -> Option<Sourced<f32>> {
+        self.weather.air_temperature.map(|reading| reading.sourced)
+    }
+
+    /// Returns the barometric pressure in bars, of MDA.
+    pub fn barometric_pressure(&self) -> Option<Sourced<f32>> {
+        self.weather.pressure.map(|reading| reading.sourced)
+    }
+
+    /// Returns the relative humidity in percent, of MDA.
+    pub fn relative_humidity(&self) -> Option<Sourced<f32>> {
+        self.weather
+            .relative_humidity
+            .map(|reading| reading.sourced)
+    }
+
+    /// Returns the last valid wind of MWV with the reference.
+    pub fn wind(&self, reference: MwvReference) -> Option<Sourced<Wind>> {
+        match reference {
+            MwvReference::Relative => self.weather.relative_wind,
+            MwvReference::Theoretical => self.weather.theoretical_wind,
+        }
+        .map(|reading| reading.sourced)
+    }
+
+    pub(crate) fn merge_mtw_data(&mut self, mtw: MtwData) {
+        let (talker, rank, epochs) = self.weather.source(self.fix_time);
+        #[allow(clippy::unnecessary_cast)] // `Float` is `f32` with `f32-only`
+        let temperature = mtw.temperature.map(|temperature| temperature as f32);
+        update(
+            &mut self.weather.water_temperature,
+            temperature,
+            talker,
+            rank,
+            epochs,
+        );
+    }
+
+    pub(crate) fn merge_water_depth(&mut self, depth: Option<Depth>, reference: DepthReference) {
+        let (talker, rank, epochs) = self.weather.source(self.fix_time);
+        let water_depth = depth.map(|depth| WaterDepth { depth, reference });
+        update(
+            &mut self.weather.water_depth,
+            water_depth,
+            talker,
+            rank,
+            epochs,
+        );
+    }
+
+    pub(crate) fn merge_mwv_data(&mut self, mwv: MwvData) {
+        if !mwv.data_valid {
+            return;
+        }
+        let (talker, rank, epochs) = self.weather.source(self.fix_time);
+        let wind = mwv
+            .signed_angle()
+            .zip(mwv.wind_speed)
+            .map(|(angle, speed)| Wind {
+                angle,
+                speed_knots: match mwv.wind_speed_units {
+                    Some(MwvWindSpeedUnits::KilometersPerHour) => speed / 1.852,
+                    Some(MwvWindSpeedUnits::MetersPerSecond) => speed * 3600. / 1852.,
+                    Some(MwvWindSpeedUnits::MilesPerHour) => speed * 1609.344 / 1852.,
+                    Some(MwvWindSpeedUnits::Knots) | None => speed,
+                },
+            });
+        let reading = match mwv.reference {
+            Some(MwvReference::Relative) => &mut self.weather.relative_wind,
+            Some(MwvReference::Theoretical) => &mut self.weather.theoretical_wind,
+            None => return,
+        };
+        update(reading, wind, talker, rank, epochs);
+    }
+
+    pub(crate) fn merge_mda_data(&mut self, mda: MdaData) {
+        let (talker, rank, epochs) = self.weather.source(self.fix_time);
+        let weather = &mut self.weather;
+        update(
+            &mut weather.water_temperature,
+            mda.water_temp_deg,
+            talker,
+            rank,
+            epochs,
+        );
+        update(
+            &mut weather.air_temperature,
+            mda.air_temp_deg,
+            talker,
+            rank,
+            epochs,
+        );
+        update(
+            &mut weather.pressure,
+            mda.pressure_bar,
+            talker,
+            rank,
+            epochs,
+        );
+        update(
+            &mut weather.relative_humidity,
+            mda.rel_humidity,
+            talker,
+            rank,
+            epochs,
+        );
+    }
+}
+
+#[cfg(all(test, feature = "MWV"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "MTW", feature = "MWV", feature = "MDA"))]
+    fn test_weather_talker_priority() {
+        let mut nmea = Nmea::default();
+        nmea.set_weather_talker_priority(&["WI", "II", "X", "WI"]);
+        assert_eq!(
+            nmea.weather_talker_priority(),
+            [TalkerId::new("WI").unwrap(), TalkerId::new("II").unwrap()]
+        );
+
+        nmea.parse("$IIMTW,17.5,C*10").unwrap();
+        assert_eq!(nmea.water_temperature().unwrap().value, 17.5);
+        // the MDA of the weather station replaces the multiplexer
+        nmea.parse("$WIMDA,29.7544,I,1.0076,B,35.5,C,18.0,C,42.1,,20.6,C*2E")
+            .unwrap();
+        let water_temperature = nmea.water_temperature().unwrap();
+        assert_eq!(water_temperature.value, 18.0);
+        assert_eq!(water_temperature.talker, TalkerId::new("WI"));
+        assert_eq!(nmea.barometric_pressure().unwrap().value, 1.0076);
+        // but not the other way around, nor unranked talkers
+        nmea.parse("$IIMTW,17.5,C*10").unwrap();
+        nmea.parse("$HCMTW,17.0,C*1E").unwrap();
+        assert_eq!(nmea.water_temperature().unwrap().value, 18.0);
+
+        // invalid wind is ignored, speeds are converted to knots
+        nmea.parse("$IIMWV,100.0,T,10.0,M,A*08").unwrap();
+        nmea.parse("$WIMWV,200.0,T,5.0,M,V*36").unwrap();
+        let wind = nmea.wind(MwvReference::Theoretical).unwrap();
+        assert_eq!(wind.talker, TalkerId::new("II"));
+        approx::assert_relative_eq!(wind.value.speed_knots, 19.438444);
+        assert!(nmea.wind(MwvReference::Relative).is_none());
+
+        // in a new epoch, any talker replaces the values
+        nmea.tick();
+        nmea.parse("$IIMTW,17.5,C*10").unwrap();
+        let water_temperature = nmea.water_temperature().unwrap();
+        assert_eq!(water_temperature.value, 17.5);
+        assert_eq!(water_temperature.talker, TalkerId::new("II"));
+        assert_eq!(nmea.air_temperature().unwrap().value, 35.5);
+
+        nmea.reset();
+        assert!(nmea.water_temperature().is_none());
+        assert_eq!(nmea.weather_talker_priority().len(), 2);
+    }
+
+    #[test]
+    #[cfg(all(feature = "MWV", feature = "RMC"))]
+    fn test_weather_fallback() {
+        let mut nmea = Nmea::default();
+        nmea.set_weather_talker_priority(&["WI", "II"]);
+        nmea.parse("$WIMWV,041.1,R,01.0,N,A*16").unwrap();
+        // the masthead unit stops, the multiplexer takes over in the second
+        // epoch
+        let rmc = [
+            "$GPRMC,225446.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*46",
+            "$GPRMC,225447.33,A,4916.45,N,12311.12,W,000.5,054.7,191194,020.3,E*47",
+        ];
+        let mut talkers = std::vec::Vec::new();
+        for rmc in rmc {
+            nmea.parse(rmc).unwrap();
+            nmea.parse("$IIMWV,041.0,R,01.0,N,A*09").unwrap();
+            let wind = nmea.wind(MwvReference::Relative).unwrap();
+            talkers.push(wind.talker.unwrap());
+        }
+        assert_eq!(
+            talkers,
+            [TalkerId::new("WI").unwrap(), TalkerId::new("II").unwrap()]
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "DBK", feature = "DBS"))]
+    fn test_water_depth_reference() {
+        let mut nmea = Nmea::default();
+        nmea.parse("$SDDBK,1330.5,f,0405.5,M,0221.6,F*2E").unwrap();
+        let water_depth = nmea.water_depth().unwrap();
+        assert_eq!(water_depth.talker, Some(TalkerId::new("SD").unwrap()));
+        assert_eq!(water_depth.value.depth.meters, 405.5);
+        assert_eq!(water_depth.value.reference, DepthReference::Keel);
+
+        nmea.parse("$SDDBS,3.2,f,1.0,M,0.5,F*04").unwrap();
+        let water_depth = nmea.water_depth().unwrap().value;
+        assert_eq!(water_depth.depth.meters, 1.0);
+        assert_eq!(water_depth.reference, DepthReference::Surface);
+    }
+}
@@ -0,0 +1,110 @@
+//! Wind helpers, e.g. for the apparent wind of [`MwvData`](crate::sentences::MwvData).
+//!
+//! Angles are in degrees, wind directions are where the wind comes from.
+
+#[cfg(not(feature = "std"))]
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::angle;
+
+/// True wind computed from the apparent wind, see [`true_wind()`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt-03", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrueWind {
+    /// True wind angle relative to the bow in `(-180, 180]` degrees,
+    /// positive to starboard
+    pub angle: f32,
+    /// True wind direction in `[0, 360)` degrees True
+    pub direction: f32,
+    /// True wind speed, in the unit of the apparent wind and boat speeds
+    pub speed: f32,
+}
+
+/// Returns an angle relative to the bow in `(-180, 180]` degrees, positive
+/// to starboard.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::wind::signed_angle;
+///
+/// assert_eq!(signed_angle(41.1), 41.1);
+/// assert_eq!(signed_angle(270.), -90.);
+/// ```
+pub fn signed_angle(degrees: f32) -> f32 {
+    angle::difference(0., degrees)
+}
+
+/// Computes the true wind from the apparent wind measured on board.
+///
+/// `apparent_angle` is relative to the bow, `boat_speed` must be in the unit
+/// of `apparent_speed` and `heading` in degrees True. With the speed through
+/// water, the result is the wind over the water. With the speed over ground,
+/// it is the wind over the ground, as long as the course over ground is
+/// close to the heading.
+///
+/// # Examples
+///
+/// ```
+/// use nmea::wind::true_wind;
+///
+/// // 10 knots on the nose while motoring at 10 knots: no wind
+/// let wind = true_wind(0., 10., 10., 90.);
+/// assert_eq!(wind.speed, 0.);
+///
+/// // 10 knots abeam while drifting: the apparent wind is the true wind
+/// let wind = true_wind(-90., 10., 0., 90.);
+/// assert_eq!((wind.angle, wind.direction, wind.speed), (-90., 0., 10.));
+/// ```
+pub fn true_wind(
+    apparent_angle: f32,
+    apparent_speed: f32,
+    boat_speed: f32,
+    heading: f32,
+) -> TrueWind {
+    let (sin, cos) = apparent_angle.to_radians().sin_cos();
+    // the motion of the boat adds a head wind of its speed
+    let ahead = apparent_speed * cos - boat_speed;
+    let abeam = apparent_speed * sin;
+    let speed = ahead.hypot(abeam);
+    let angle = if speed == 0. {
+        0.
+    } else {
+        signed_angle(abeam.atan2(ahead).to_degrees())
+    };
+    TrueWind {
+        angle,
+        direction: angle::normalize(heading + angle),
+        speed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_true_wind() {
+        // beam reach at 6 knots in 12 knots of true wind from abeam
+        let apparent_speed = 12_f32.hypot(6.);
+        let apparent_angle = 12_f32.atan2(6.).to_degrees();
+        let wind = true_wind(apparent_angle, apparent_speed, 6., 350.);
+        assert_relative_eq!(wind.angle, 90., epsilon = 1e-3);
+        assert_relative_eq!(wind.direction, 80., epsilon = 1e-3);
+        assert_relative_eq!(wind.speed, 12., epsilon = 1e-4);
+
+        // the same on port
+        let wind = true_wind(-apparent_angle, apparent_speed, 6., 10.);
+        assert_relative_eq!(wind.angle, -90., epsilon = 1e-3);
+        assert_relative_eq!(wind.direction, 280., epsilon = 1e-3);
+
+        // running downwind faster than the wind
+        let wind = true_wind(0., 2., 6., 0.);
+        assert_relative_eq!(wind.angle, 180.);
+        assert_relative_eq!(wind.speed, 4.);
+    }
+}
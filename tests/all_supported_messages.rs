@@ -43,6 +43,8 @@ fn test_all_supported_messages() {
         (SentenceType::TXT, "$GNTXT,01,01,02,u-blox AG - www.u-blox.com*4E"),
         // VHW
         (SentenceType::VHW, "$GPVHW,100.5,T,105.5,M,10.5,N,19.4,K*4F"),
+        // VLW
+        (SentenceType::VLW, "$IIVLW,2.8,N,0.8,N*4F"),
         // VTG
         (SentenceType::VTG, "$GPVTG,360.0,T,348.7,M,000.0,N,000.0,K*43"),
         // WNC
@@ -55,8 +57,12 @@ fn test_all_supported_messages() {
         (SentenceType::ZTG, "$GPZTG,145832.12,042359.17,WPT*24"),
         // DPT (Depth of Water)
         (SentenceType::DPT, "$SDDPT,17.9,0.5*6D"),
+        // DTM
+        (SentenceType::DTM, "$GPDTM,999,,0.08,N,0.07,E,-47.7,W84*1B"),
         // DBS
         (SentenceType::DBS, "$SDDBS,12.3,f,3.75,M,2.05,F*37"),
+        // DBT
+        (SentenceType::DBT, "$SDDBT,7.8,f,2.4,M,1.3,F*0D"),
     ]
     .into_iter()
     .collect::<HashMap<_, _>>();
@@ -6,7 +6,11 @@ use std::{
 };
 
 use helpers::format_satellites;
-use nmea::{parse_str, Nmea};
+use nmea::{
+    parse_str,
+    sentences::{FixType, GnssType},
+    Nmea, SatId,
+};
 
 mod helpers;
 
@@ -182,6 +186,80 @@ fn test_parse_all_logs() {
     }
 }
 
+/// Parses a log with [`Nmea::parse()`] and [`Nmea::parse_for_fix()`], all
+/// its lines are valid sentences or comments starting with `#`.
+fn parse_log(name: &str) -> [Nmea; 2] {
+    let log = fs::read_to_string(Path::new("tests").join("data").join(name)).unwrap();
+    let mut nmea1 = Nmea::default();
+    let mut nmea2 = Nmea::default();
+    for line in log.lines().filter(|line| !line.starts_with('#')) {
+        nmea1
+            .parse(line)
+            .unwrap_or_else(|err| panic!("{:?}: {}", line, err));
+        nmea2
+            .parse_for_fix(line)
+            .unwrap_or_else(|err| panic!("{:?}: {}", line, err));
+    }
+    [nmea1, nmea2]
+}
+
+#[test]
+fn test_parse_synthetic_navic_log() {
+    // synthetic GPS and NavIC epoch with the System IDs and six GNS modes of
+    // NMEA 4.11
+    for nmea in parse_log("navic_synthetic.log") {
+        assert_eq!(nmea.fix_type(), Some(FixType::DGps));
+        assert_eq!(nmea.fix_satellites_of(GnssType::Gps), 8);
+        assert_eq!(nmea.fix_satellites_of(GnssType::NavIC), 6);
+        assert!(nmea
+            .fix_sat_ids()
+            .any(|id| id == SatId::new(GnssType::NavIC, 9)));
+
+        let satellites = nmea.satellites();
+        assert_eq!(satellites.len(), 14);
+        let navic = satellites
+            .iter()
+            .filter(|sat| sat.gnss_type() == GnssType::NavIC);
+        assert_eq!(navic.count(), 6);
+    }
+}
+
+#[test]
+fn test_parse_synthetic_beidou_log() {
+    // synthetic epoch of a BeiDou only receiver
+    for nmea in parse_log("beidou_synthetic.log") {
+        assert_eq!(nmea.fix_type(), Some(FixType::Gps));
+        assert_eq!(nmea.fix_satellites(), Some(9));
+        assert_eq!(nmea.fix_satellites_of(GnssType::Beidou), 9);
+        assert_eq!(nmea.fix_satellites_of(GnssType::Gps), 0);
+
+        let satellites = nmea.satellites();
+        assert_eq!(satellites.len(), 10);
+        assert!(satellites
+            .iter()
+            .all(|sat| sat.gnss_type() == GnssType::Beidou));
+        assert_eq!(satellites.last().unwrap().prn(), 40);
+    }
+}
+
+#[test]
+fn test_gsa_of_several_talkers() {
+    // one GSA per talker, as sent by GPS and BeiDou receivers
+    let mut nmea = Nmea::default();
+    nmea.parse("$GPGSA,A,3,23,31,22,16,03,07,,,,,,,1.8,1.1,1.4*3E")
+        .unwrap();
+    nmea.parse("$BDGSA,A,3,214,,,,,,,,,,,,1.8,1.1,1.4*18")
+        .unwrap();
+    assert_eq!(nmea.fix_satellites_of(GnssType::Gps), 6);
+    assert_eq!(nmea.fix_satellites_of(GnssType::Beidou), 1);
+
+    // the next GPS fix only replaces the GPS satellites
+    nmea.parse("$GPGSA,A,3,19,28,14,18,27,22,31,39,,,,,1.7,1.0,1.3*34")
+        .unwrap();
+    assert_eq!(nmea.fix_satellites_of(GnssType::Gps), 7);
+    assert_eq!(nmea.fix_satellites_of(GnssType::Beidou), 1);
+}
+
 fn err_to_string<E: Error>(e: E) -> String {
     e.to_string()
 }
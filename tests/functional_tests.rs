@@ -13,6 +13,37 @@ fn test_invalid_datetime() {
     assert!(matches!(res, Err(Error::ParsingError(_))));
 }
 
+#[test]
+fn test_surrounding_whitespace() {
+    let gga = "$GPGGA,092750.000,5321.6802,N,00630.3372,W,1,8,1.03,61.7,M,55.2,M,,*76";
+    for sentence in [
+        format!("{gga}\r\n"),
+        format!("{gga}\n"),
+        format!("  {gga} \r\n"),
+        format!("\n{gga}"),
+    ] {
+        assert!(
+            matches!(parse_str(&sentence), Ok(ParseResult::GGA(_))),
+            "{:?}",
+            sentence
+        );
+    }
+
+    // the line ending does not count towards the maximum length
+    let long = format!("$GPTXT,01,01,02,{}*", "x".repeat(SENTENCE_MAX_LEN - 19));
+    let long = format!("{long}{:02X}\r\n", checksum(&long[1..long.len() - 1]));
+    assert_eq!(long.trim_end().len(), SENTENCE_MAX_LEN);
+    assert!(!matches!(parse_str(&long), Err(Error::SentenceLength(_))));
+    assert!(matches!(
+        parse_str(&format!("{long}x")),
+        Err(Error::SentenceLength(_))
+    ));
+}
+
+fn checksum(data: &str) -> u8 {
+    data.bytes().fold(0, |c, x| c ^ x)
+}
+
 #[test]
 fn test_gga_north_west() {
     use chrono::Timelike;
@@ -27,7 +58,8 @@ fn test_gga_north_west() {
     assert_eq!(nmea.fix_type().unwrap(), FixType::Gps);
     assert_eq!(nmea.fix_satellites().unwrap(), 8);
     assert_eq!(nmea.hdop().unwrap(), 1.03);
-    assert_relative_eq!(nmea.geoid_altitude().unwrap(), (61.7 + 55.2));
+    assert_relative_eq!(nmea.altitude_ellipsoidal().unwrap(), (61.7 + 55.2));
+    assert_eq!(nmea.geoid_separation().unwrap(), 55.2);
 }
 
 #[test]
@@ -152,6 +184,8 @@ fn test_gsv_real_data() {
         "$GLGSV,3,1,10,74,40,078,43,66,23,275,31,82,10,347,36,73,15,015,38*6B",
         "$GLGSV,3,2,10,75,19,135,36,65,76,333,31,88,32,233,33,81,40,302,38*6A",
         "$GLGSV,3,3,10,72,40,075,43,87,00,000,*6F",
+        // the last sentence of another group, whose satellites are not
+        // mixed with those of the complete group
         //             26         31         32
         "$GPGSV,4,4,15,26,02,112,,31,45,071,,32,01,066,*4C",
     ];
@@ -170,9 +204,8 @@ fn test_gsv_real_data() {
             "{Gps 22 Some(82.0) Some(181.0) Some(36.0)}",
             "{Gps 23 Some(34.0) Some(232.0) Some(42.0)}",
             "{Gps 25 Some(11.0) Some(45.0) Some(33.0)}",
-            "{Gps 26 Some(2.0) Some(112.0) None}",
-            "{Gps 31 Some(45.0) Some(71.0) None}",
-            "{Gps 32 Some(1.0) Some(66.0) None}",
+            "{Gps 31 Some(45.0) Some(92.0) Some(38.0)}",
+            "{Gps 32 Some(14.0) Some(61.0) Some(39.0)}",
             "{Glonass 65 Some(76.0) Some(333.0) Some(31.0)}",
             "{Glonass 66 Some(23.0) Some(275.0) Some(31.0)}",
             "{Glonass 72 Some(40.0) Some(75.0) Some(43.0)}",
@@ -260,7 +293,7 @@ fn test_nmea_parse() {
 
     assert_eq!(nmea.latitude().unwrap(), 53. + 21.6802 / 60.);
     assert_eq!(nmea.longitude().unwrap(), -(6. + 30.3372 / 60.));
-    assert_eq!(nmea.altitude().unwrap(), 61.7);
+    assert_eq!(nmea.altitude_msl().unwrap(), 61.7);
     pretty_assertions::assert_eq!(
         vec![
             "{Gps 2 Some(39.0) Some(223.0) Some(19.0)}",
@@ -0,0 +1,91 @@
+//! Conformance of the parsers to the examples of the gpsd NMEA documentation
+//! in `tests/data/gpsd_examples.txt`, for the enabled sentence features.
+
+use std::{fs, path::Path};
+
+use nmea::{
+    metadata::REGISTRY, parse_nmea_sentence, parse_str, supported_sentences, Error, SentenceType,
+};
+
+fn examples() -> Vec<(SentenceType, String)> {
+    let table =
+        fs::read_to_string(Path::new("tests").join("data").join("gpsd_examples.txt")).unwrap();
+    table
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (sentence_type, sentence) = line
+                .split_once(' ')
+                .unwrap_or_else(|| panic!("invalid example line {:?}", line));
+            (
+                SentenceType::try_from(sentence_type).unwrap(),
+                sentence.trim().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_gpsd_examples() {
+    for (sentence_type, sentence) in examples() {
+        let result = parse_str(&sentence);
+        if sentence_type.metadata().parsed {
+            let parsed = result.unwrap_or_else(|err| panic!("{:?}: {}", sentence, err));
+            assert_eq!(SentenceType::from(&parsed), sentence_type, "{:?}", sentence);
+        } else {
+            assert_eq!(result, Err(Error::DisabledSentence), "{:?}", sentence);
+        }
+    }
+}
+
+#[test]
+fn test_gpsd_examples_field_counts() {
+    for (sentence_type, sentence) in examples() {
+        let Some((min, max)) = sentence_type.metadata().fields else {
+            continue;
+        };
+        let sentence = parse_nmea_sentence(&sentence).unwrap();
+        // some emitters pad the sentences with empty fields
+        let field_count = sentence.data.trim_end_matches(',').split(',').count();
+        assert!(
+            (usize::from(min)..=usize::from(max)).contains(&field_count),
+            "{} fields of {:?} outside of the registry range {:?}",
+            field_count,
+            sentence.data,
+            (min, max)
+        );
+    }
+}
+
+#[test]
+fn test_gpsd_examples_cover_all_features() {
+    let examples = examples();
+    for meta in REGISTRY.iter().filter(|meta| meta.feature.is_some()) {
+        assert!(
+            examples
+                .iter()
+                .any(|(sentence_type, _)| *sentence_type == meta.sentence_type),
+            "no gpsd example for {}",
+            meta.sentence_type
+        );
+    }
+}
+
+#[test]
+fn test_supported_sentences_match_registry() {
+    for meta in REGISTRY.iter() {
+        assert_eq!(
+            meta.sentence_type.is_parsable(),
+            meta.parsed,
+            "{}",
+            meta.sentence_type
+        );
+    }
+    let parsed: Vec<_> = REGISTRY
+        .iter()
+        .filter(|meta| meta.parsed)
+        .map(|meta| meta.sentence_type)
+        .collect();
+    assert_eq!(supported_sentences(), parsed);
+}
@@ -0,0 +1,191 @@
+//! Robustness of the parsers to corrupted sentences.
+//!
+//! The sentences of the test data are mutated one character or one field at
+//! a time and their checksum is recomputed, so the mutated sentences reach
+//! the sentence parsers. The parsers must never panic and must only fail with
+//! parsing errors.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use nmea::{parse_bytes, parse_str, Error, Nmea};
+
+/// Sentences of each type and talker taken from every log.
+const SENTENCES_PER_TYPE: usize = 4;
+
+/// Characters replacing each character of the sentences.
+const REPLACEMENTS: &[char] = &[',', '.', '-', '0', '9', 'A', 'Z', 'x', ' '];
+
+/// Fields replacing each field of the sentences.
+const FIELD_REPLACEMENTS: &[&str] = &[
+    "",
+    "0",
+    "-1",
+    "999999999999",
+    "1e9",
+    ".",
+    "--",
+    "A",
+    "NaN",
+    "235960.00",
+    "999999",
+    "ZZZZZZZZZZ",
+];
+
+fn corpus() -> Vec<String> {
+    let data = Path::new("tests").join("data");
+    let mut sentences = BTreeMap::<String, Vec<String>>::new();
+    for log in [
+        "nmea1.log",
+        "nmea2.log",
+        "nmea_with_sat_info.log",
+        "beidou_synthetic.log",
+        "navic_synthetic.log",
+    ] {
+        let content = fs::read_to_string(data.join(log)).unwrap();
+        for line in content.lines().map(str::trim) {
+            let (Some(header), Some(_)) = (line.get(..6), line.find('*')) else {
+                continue;
+            };
+            let examples = sentences.entry(header.to_string()).or_default();
+            if examples.len() < SENTENCES_PER_TYPE && !examples.iter().any(|s| s == line) {
+                examples.push(line.to_string());
+            }
+        }
+    }
+    let examples = fs::read_to_string(data.join("gpsd_examples.txt")).unwrap();
+    let gpsd_examples = examples
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter_map(|line| line.split_once(' '))
+        .map(|(_, sentence)| sentence.trim().to_string());
+    sentences
+        .into_values()
+        .flatten()
+        .chain(gpsd_examples)
+        .collect()
+}
+
+/// Returns the sentence with the body between `$` and `*` and its checksum.
+fn with_checksum(body: &str) -> String {
+    let checksum = body.bytes().fold(0, |checksum, byte| checksum ^ byte);
+    format!("${}*{:02X}", body, checksum)
+}
+
+fn body(sentence: &str) -> &str {
+    let end = sentence.rfind('*').unwrap();
+    &sentence[1..end]
+}
+
+/// The single character and single field mutations of a sentence.
+fn mutations(sentence: &str) -> Vec<String> {
+    let body = body(sentence);
+    let mut mutations = Vec::new();
+    for (index, original) in body.char_indices() {
+        // the address field is kept, so the sentence parsers are reached
+        if index < 5 {
+            continue;
+        }
+        let (before, after) = (&body[..index], &body[index + original.len_utf8()..]);
+        mutations.push(with_checksum(&format!("{}{}", before, after)));
+        for &replacement in REPLACEMENTS.iter().filter(|&&c| c != original) {
+            mutations.push(with_checksum(&format!(
+                "{}{}{}",
+                before, replacement, after
+            )));
+        }
+    }
+    let fields: Vec<_> = body.split(',').collect();
+    for index in 1..fields.len() {
+        for replacement in FIELD_REPLACEMENTS {
+            let mut mutated = fields.clone();
+            mutated[index] = replacement;
+            mutations.push(with_checksum(&mutated.join(",")));
+        }
+        let mut removed = fields.clone();
+        removed.remove(index);
+        mutations.push(with_checksum(&removed.join(",")));
+        let mut repeated = fields.clone();
+        repeated.insert(index, fields[index]);
+        mutations.push(with_checksum(&repeated.join(",")));
+    }
+    mutations
+}
+
+/// Fails for errors which are not caused by the fields of the sentence.
+fn check_error(sentence: &str, error: Error) {
+    assert!(
+        !matches!(
+            error,
+            Error::ChecksumMismatch { .. } | Error::Utf8Decoding | Error::ASCII
+        ),
+        "{:?}: unexpected error {:?}",
+        sentence,
+        error
+    );
+    // the errors can be displayed and kept
+    let _ = error.to_string();
+    let _ = error.into_owned();
+}
+
+#[test]
+fn test_mutated_sentences_do_not_panic() {
+    let corpus = corpus();
+    assert!(corpus.len() > 50);
+    let mut nmea = Nmea::default();
+    let mut parsed = 0;
+    let mut failed = 0;
+    for sentence in &corpus {
+        for mutated in mutations(sentence) {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                match parse_str(&mutated) {
+                    Ok(_) => parsed += 1,
+                    Err(error) => {
+                        check_error(&mutated, error);
+                        failed += 1;
+                    }
+                }
+                // the byte framing gives the same results, compared as text
+                // for the NaN values
+                assert_eq!(
+                    format!("{:?}", parse_bytes(mutated.as_bytes())),
+                    format!("{:?}", parse_str(&mutated))
+                );
+                // the merge into the state must not panic either
+                let _ = nmea.parse(&mutated);
+                let _ = nmea.parse_for_fix(&mutated);
+            }));
+            if let Err(panic) = result {
+                panic::resume_unwind(Box::new(format!(
+                    "{:?} (mutated from {:?}) panicked: {:?}",
+                    mutated,
+                    sentence,
+                    panic
+                        .downcast_ref::<String>()
+                        .map(String::as_str)
+                        .or_else(|| panic.downcast_ref::<&str>().copied())
+                )));
+            }
+        }
+    }
+    // some mutations are still valid sentences, others are rejected
+    assert!(parsed > 0 && failed > 0, "{} {}", parsed, failed);
+}
+
+#[test]
+fn test_mutations() {
+    let mutations = mutations("$GPHDT,274.07,T*03");
+    assert!(mutations.contains(&"$GPHDT,274.0,T*34".to_string()));
+    assert!(mutations.contains(&"$GPHDT,,T*1B".to_string()));
+    assert!(mutations.contains(&"$GPHDT,274.07,T,T*7B".to_string()));
+    assert!(mutations
+        .iter()
+        .all(|sentence| parse_str(sentence).map_or_else(
+            |error| !matches!(error, Error::ChecksumMismatch { .. }),
+            |_| true
+        )));
+}